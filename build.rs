@@ -15,6 +15,10 @@ fn main() {
     // We need at minimum version 1.20.0.
     assert!(version >= version_parse("1.20.0"));
 
+    if version >= version_parse("1.36.0") {
+        println!("cargo:rustc-cfg=has_maybe_uninit");
+    }
+
     if version >= version_parse("1.26.0") {
         println!("cargo:rustc-cfg=has_i128");
     }
@@ -22,6 +22,7 @@ fn main() {
 
     if version >= version_parse("1.27.0") {
         println!("cargo:rustc-cfg=has_full_range_inclusive");
+        println!("cargo:rustc-cfg=has_simd");
     }
 
     if version >= version_parse("1.26.0") {
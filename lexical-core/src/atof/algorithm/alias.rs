@@ -4,10 +4,11 @@ use lib::{iter, slice};
 use float::*;
 use util::*;
 use super::bigcomp::ToBigInt;
-use super::correct::FloatErrors;
+use super::correct::DigitIter;
+use super::lemire::EiselLemire;
 
 pub type SliceIter<'a, T> = slice::Iter<'a, T>;
-pub type ChainedSliceIter<'a, T> = iter::Chain<SliceIter<'a, T>, SliceIter<'a, T>>;
+pub type ChainedDigitIter<'a, C> = iter::Chain<DigitIter<'a, C>, DigitIter<'a, C>>;
 
 // TRAITS
 
@@ -16,7 +17,8 @@ macro_rules! def_float_type {
         /// Trait to simplify type signatures for atof.
         pub(super) trait FloatType:
             $(FloatRounding<$t> +)*
-            StablePower
+            StablePower +
+            EiselLemire
         {
             type Mantissa: Mantissa;
             type ExtendedFloat: ExtendedFloatType<Self>;
@@ -40,19 +42,6 @@ impl FloatType for f64 {
     type ExtendedFloat = ExtendedFloat<Self::Mantissa>;
 }
 
-/// Trait for a useable mantissa.
-pub(super) trait MantissaType:
-    Mantissa +
-    FloatErrors
-{}
-
-impl MantissaType for u64 {
-}
-
-#[cfg(has_i128)]
-impl MantissaType for u128 {
-}
-
 /// Trait for extended-float types.
 pub(super) trait ExtendedFloatType<F: FloatType>:
     ToBigInt<F::Mantissa> +
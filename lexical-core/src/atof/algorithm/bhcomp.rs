@@ -3,6 +3,7 @@
 //! Compares the actual significant digits of the mantissa to the
 //! theoretical digits from `b+h`, scaled into the proper range.
 
+use lib::cmp;
 use util::*;
 use super::alias::*;
 use super::bigcomp;
@@ -14,9 +15,10 @@ use super::math::*;
 ///
 /// This invokes the comparison with `b+h`.
 #[inline]
-pub(super) fn small_atof<F>(slc: FloatSlice, radix: u32, max_digits: usize, exponent: i32, f: F, kind: RoundingKind)
+pub(super) fn small_atof<F, C>(slc: FloatSlice<C>, radix: u32, max_digits: usize, exponent: i32, f: F, kind: RoundingKind)
     -> F
-    where F: FloatType
+    where F: FloatType,
+          C: CodeUnit
 {
     // Get the significant digits and radix exponent for the real digits.
     let mut real_digits = parse_mantissa(slc, radix, max_digits);
@@ -65,15 +67,62 @@ pub(super) fn small_atof<F>(slc: FloatSlice, radix: u32, max_digits: usize, expo
     bigcomp::round_to_native(f, real_digits.compare(&theor_digits), kind)
 }
 
+/// Compare the real digits to a finite, positive 64-bit float.
+///
+/// Used to round to floats narrower than the 64-bit float, when the
+/// 64-bit float is exactly halfway between two narrower floats.
+pub(super) fn compare<C: CodeUnit>(slc: FloatSlice<C>, radix: u32, f: f64)
+    -> cmp::Ordering
+{
+    // Get the significant digits and radix exponent for the real digits.
+    // The 64-bit float has a finite representation in the same number
+    // of digits as any 64-bit float, so the remaining digits only
+    // determine if the real value is above it.
+    let max_digits = unwrap_or_max(max_digits::<f64>(radix));
+    let count = max_digits.min(slc.mantissa_digits());
+    let real_exp = slc.scientific_exponent() + 1 - count.as_i32();
+    let mut real_digits = parse_mantissa(slc, radix, max_digits);
+
+    // Get the significant digits and the binary exponent for the float.
+    let mut theor_digits = Bigint::from_u64(f.mantissa());
+    let theor_exp = f.exponent();
+
+    // Scale the real digits and the float digits to the same order,
+    // with all exponents relative to `theor_digits`, like `small_atof`.
+    let (binary_exp, halfradix_exp, radix_exp) = if real_exp >= 0 {
+        real_digits.imul_power(radix, real_exp.as_u32());
+        (theor_exp, 0, 0)
+    } else if radix.is_even() {
+        (theor_exp - real_exp, -real_exp, 0)
+    } else {
+        (theor_exp, 0, -real_exp)
+    };
+
+    if halfradix_exp != 0 {
+        theor_digits.imul_power(radix / 2, halfradix_exp.as_u32());
+    }
+    if radix_exp != 0 {
+        theor_digits.imul_power(radix, radix_exp.as_u32());
+    }
+    if binary_exp > 0 {
+        theor_digits.imul_power(2, binary_exp.as_u32());
+    } else if binary_exp < 0 {
+        real_digits.imul_power(2, (-binary_exp).as_u32());
+    }
+
+    real_digits.compare(&theor_digits)
+}
+
 /// Calculate the exact value of the float.
 ///
 /// Notes:
 ///     The digits iterator must not have any trailing zeros (true for
 ///     `FloatSlice`).
 ///     sci_exponent and digits.size_hint() must not overflow i32.
-pub(super) fn atof<'a, F>(slc: FloatSlice, radix: u32, f: F, kind: RoundingKind)
+pub(super) fn atof<'a, F, C>(slc: FloatSlice<C>, radix: u32, f: F, kind: RoundingKind)
     -> F
-    where F: FloatType
+    where F: FloatType,
+          C: CodeUnit
 {
     // We have a finite conversions number of digits for base10.
     // In order for a float in radix `b` with a finite number of digits
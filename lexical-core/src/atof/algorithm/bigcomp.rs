@@ -239,7 +239,7 @@ pub(super) fn make_ratio<F: Float>(radix: u32, sci_exponent: i32, f: F, kind: Ro
     // Scale the denominator so it has the number of bits
     // in the radix as the number of leading zeros.
     let wlz = integral_binary_factor(radix).as_usize();
-    let nlz = den.leading_zeros().wrapping_sub(wlz) & (u32::BITS - 1);
+    let nlz = den.leading_zeros().wrapping_sub(wlz) & (<u32 as Integer>::BITS - 1);
     small::ishl_bits(den.data_mut(), nlz);
     den.exp -= nlz.as_i32();
 
@@ -257,7 +257,7 @@ pub(super) fn make_ratio<F: Float>(radix: u32, sci_exponent: i32, f: F, kind: Ro
         // denominator will be normalized.
         // We need to add one to the quotient,since we're calculating the
         // ceiling of the divmod.
-        let (q, r) = shift.ceil_divmod(Limb::BITS);
+        let (q, r) = shift.ceil_divmod(<Limb as Integer>::BITS);
         // Since we're using a power from the denominator to the
         // numerator, we to invert r, not add u32::BITS.
         let r = -r;
@@ -265,7 +265,7 @@ pub(super) fn make_ratio<F: Float>(radix: u32, sci_exponent: i32, f: F, kind: Ro
         num.exp -= r;
         if !q.is_zero() {
             den.pad_zero_digits(q);
-            den.exp -= Limb::BITS.as_i32() * q.as_i32();
+            den.exp -= <Limb as Integer>::BITS.as_i32() * q.as_i32();
         }
     }
 
@@ -278,17 +278,18 @@ pub(super) fn make_ratio<F: Float>(radix: u32, sci_exponent: i32, f: F, kind: Ro
 /// * `radix`       - Radix for the number parsing.
 /// * `num`         - Numerator for the fraction.
 /// * `denm`        - Denominator for the fraction.
-pub(super) fn compare_digits<'a, Iter>(mut digits: Iter, radix: u32, mut num: Bigint, den: Bigint)
+pub(super) fn compare_digits<'a, Iter, C>(mut digits: Iter, radix: u32, mut num: Bigint, den: Bigint)
     -> cmp::Ordering
-    where Iter: Iterator<Item=&'a u8>
+    where Iter: Iterator<Item=&'a C>,
+          C: CodeUnit + 'a
 {
     // Iterate until we get a difference in the generated digits.
     // If we run out,return Equal.
     let radix = as_limb(radix);
     while !num.data.is_empty() {
         let actual = match digits.next() {
-            Some(&v) => v,
-            None    => return cmp::Ordering::Less,
+            Some(&v) => v.to_byte(),
+            None     => return cmp::Ordering::Less,
         };
         let expected = digit_to_char(num.quorem(&den));
         num.imul_small(radix);
@@ -317,9 +318,10 @@ pub(super) fn compare_digits<'a, Iter>(mut digits: Iter, radix: u32, mut num: Bi
 /// * `sci_exponent`    - Exponent of basen string in scientific notation.
 /// * `f`               - Sub-halfway (`b`) float.
 #[inline]
-pub(super) fn atof<F>(slc: FloatSlice, radix: u32, f: F, kind: RoundingKind)
+pub(super) fn atof<F, C>(slc: FloatSlice<C>, radix: u32, f: F, kind: RoundingKind)
     -> F
-    where F: FloatType
+    where F: FloatType,
+          C: CodeUnit
 {
     // This works when we're doing, like, round-even.
     let (num, den) = make_ratio(radix, slc.scientific_exponent(), f, kind);
@@ -20,6 +20,9 @@ cfg_if! {
 if #[cfg(feature = "radix")] {
     use lib::Vec;
     type DataType = Vec<Limb>;
+
+    #[cfg(has_i128)]
+    type QuadDataType = Vec<Limb>;
 } else {
     // Maximum denominator is 767 mantissa digits + 324 exponent,
     // or 1091 digits, or approximately 3600 bits (round up to 4k).
@@ -30,6 +33,15 @@ if #[cfg(feature = "radix")] {
 
     #[cfg(limb_width_64)]
     type DataType = stackvector::StackVec<[Limb; 64]>;
+
+    // Maximum quad denominator is 11564 mantissa digits + 4966 exponent,
+    // or 16530 digits, or approximately 38400 bits, and the numerator
+    // is shifted 130 bits above it (round up to 64k).
+    #[cfg(all(has_i128, limb_width_32))]
+    type QuadDataType = stackvector::StackVec<[Limb; 2048]>;
+
+    #[cfg(all(has_i128, limb_width_64))]
+    type QuadDataType = stackvector::StackVec<[Limb; 1024]>;
 }}  // cfg_if
 
 // BIGINT
@@ -70,18 +82,77 @@ impl SmallOps for Bigint {
 impl LargeOps for Bigint {
 }
 
+// QUAD BIGINT
+
+/// Storage for a big integer type large enough for quad floats.
+#[cfg(has_i128)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct QuadBigint {
+    /// Internal storage for the Bigint, in little-endian order.
+    data: QuadDataType,
+}
+
+#[cfg(has_i128)]
+impl Default for QuadBigint {
+    fn default() -> Self {
+        let mut bigint = QuadBigint { data: QuadDataType::default() };
+        bigint.data.reserve(20);
+        bigint
+    }
+}
+
+#[cfg(has_i128)]
+impl SharedOps for QuadBigint {
+    type StorageType = QuadDataType;
+
+    #[inline]
+    fn data<'a>(&'a self) -> &'a Self::StorageType {
+        &self.data
+    }
+
+    #[inline]
+    fn data_mut<'a>(&'a mut self) -> &'a mut Self::StorageType {
+        &mut self.data
+    }
+}
+
+#[cfg(has_i128)]
+impl SmallOps for QuadBigint {
+}
+
+#[cfg(has_i128)]
+impl LargeOps for QuadBigint {
+}
+
 // PARSE MANTISSA
 
 /// Parse the full mantissa into a big integer.
 ///
 /// Max digits is the maximum number of digits plus one.
-pub(super) fn parse_mantissa(slc: FloatSlice, radix: u32, max_digits: usize)
+pub(super) fn parse_mantissa<C: CodeUnit>(slc: FloatSlice<C>, radix: u32, max_digits: usize)
     -> Bigint
 {
-    let small_powers = Bigint::small_powers(radix);
+    parse_mantissa_impl(slc, radix, max_digits)
+}
+
+/// Parse the full mantissa into a big integer large enough for quad floats.
+#[cfg(has_i128)]
+pub(super) fn parse_quad_mantissa<C: CodeUnit>(slc: FloatSlice<C>, radix: u32, max_digits: usize)
+    -> QuadBigint
+{
+    parse_mantissa_impl(slc, radix, max_digits)
+}
+
+/// Parse the full mantissa into a generic big integer.
+fn parse_mantissa_impl<T, C>(slc: FloatSlice<C>, radix: u32, max_digits: usize)
+    -> T
+    where T: SmallOps,
+          C: CodeUnit
+{
+    let small_powers = T::small_powers(radix);
     let count = slc.mantissa_digits();
     let bits = count / integral_binary_factor(radix).as_usize();
-    let bytes = bits / Limb::BITS;
+    let bytes = bits / <Limb as Integer>::BITS;
 
     // Main loop
     let step = small_powers.len() - 2;
@@ -90,8 +161,8 @@ pub(super) fn parse_mantissa(slc: FloatSlice, radix: u32, max_digits: usize)
     let mut counter = 0;
     let mut value: Limb = 0;
     let mut i: usize = 0;
-    let mut result = Bigint::default();
-    result.data.reserve(bytes);
+    let mut result = T::default();
+    result.data_mut().reserve(bytes);
 
     let mut iter = slc.mantissa_iter();
     while let Some(&digit) = iter.next() {
@@ -177,6 +248,27 @@ fn max_digits_f64(radix: u32) -> Option<usize> {
     }
 }
 
+/// Implied method to calculate the number of digits from a 128-bit float.
+fn max_digits_f128(radix: u32) -> Option<usize> {
+    match radix {
+        6  => Some(10159),
+        10 => Some(11565),
+        12 => Some(11927),
+        14 => Some(12194),
+        18 => Some(12568),
+        20 => Some(12706),
+        22 => Some(12823),
+        24 => Some(12924),
+        26 => Some(13012),
+        28 => Some(13089),
+        30 => Some(13158),
+        34 => Some(13277),
+        36 => Some(13328),
+        // Powers of two and odd numbers should be unreachable
+        _  => None,
+    }
+}
+
 /// Calculate the maximum number of digits possible in the mantissa.
 ///
 /// Returns the maximum number of digits plus one.
@@ -199,6 +291,10 @@ fn max_digits_f64(radix: u32) -> Option<usize> {
 ///     emin = -1022
 ///     p2 = 53
 ///
+/// For f128, this follows as:
+///     emin = -16382
+///     p2 = 113
+///
 /// In Python:
 ///     `-emin + p2 + math.floor((emin+1)*math.log(2, b) - math.log(1-2**(-p2), b))`
 ///
@@ -210,6 +306,7 @@ pub(super) fn max_digits<F>(radix: u32)
     match F::BITS {
         32 => max_digits_f32(radix),
         64 => max_digits_f64(radix),
+        128 => max_digits_f128(radix),
         _  => unreachable!(),
     }
 }
@@ -300,9 +397,10 @@ pub(super) fn use_bigcomp(radix: u32, count: usize)
 
 /// Calculate the mantissa for a big integer with a positive exponent.
 #[inline]
-pub(super) fn large_atof<F>(slc: FloatSlice, radix: u32, max_digits: usize, exponent: i32, kind: RoundingKind)
+pub(super) fn large_atof<F, C>(slc: FloatSlice<C>, radix: u32, max_digits: usize, exponent: i32, kind: RoundingKind)
     -> F
-    where F: FloatType
+    where F: FloatType,
+          C: CodeUnit
 {
     // Simple, we just need to multiply by the power of the radix.
     // Now, we can calculate the mantissa and the exponent from this.
@@ -319,3 +417,203 @@ pub(super) fn large_atof<F>(slc: FloatSlice, radix: u32, max_digits: usize, expo
     into_float(fp)
 }
 
+
+// DECIMAL DIGITS
+
+/// Write the exact decimal digits of `mant * 2^exp` to the buffer.
+///
+/// Returns the number of digits written, without leading zeros, and
+/// the position of the decimal point relative to the first digit,
+/// such that the value is `0.d1d2...dn * 10^point`. The buffer must
+/// be large enough to hold every digit in the exact representation,
+/// which is at most 767 digits for a 64-bit float, rounded up to a
+/// multiple of 9.
+pub(crate) fn decimal_digits(mant: u64, exp: i32, digits: &mut [u8])
+    -> (usize, i32)
+{
+    debug_assert!(mant != 0, "Mantissa cannot be zero.");
+    decimal_digits_impl(Bigint::from_u64(mant), exp, digits)
+}
+
+/// Write the exact decimal digits of a 128-bit mantissa to the buffer.
+///
+/// Identical to `decimal_digits`, but the exact representation of a
+/// quad float has at most 11563 digits.
+#[cfg(has_i128)]
+pub(crate) fn quad_decimal_digits(mant: u128, exp: i32, digits: &mut [u8])
+    -> (usize, i32)
+{
+    debug_assert!(mant != 0, "Mantissa cannot be zero.");
+    decimal_digits_impl(QuadBigint::from_u128(mant), exp, digits)
+}
+
+/// Write the exact decimal digits of `bigint * 2^exp` to the buffer.
+fn decimal_digits_impl<T>(mut bigint: T, exp: i32, digits: &mut [u8])
+    -> (usize, i32)
+    where T: SmallOps
+{
+    // Scale the value to a big integer, such that `mant * 2^exp`
+    // is `bigint * 10^scale`, since `2^-n` is `5^n * 10^-n`.
+    let scale = if exp >= 0 {
+        bigint.imul_pow2(exp.as_u32());
+        0
+    } else {
+        bigint.imul_pow5(exp.wrapping_neg().as_u32());
+        exp
+    };
+
+    // Extract 9 digits at a time, from the least-significant digits,
+    // since 10^9 fits in a limb on every platform.
+    let mut index = digits.len();
+    while !bigint.is_zero() {
+        let mut chunk = bigint.idiv_small(1000000000);
+        for _ in 0..9 {
+            index -= 1;
+            index_mut!(digits[index] = digit_to_char(chunk % 10));
+            chunk /= 10;
+        }
+    }
+
+    // Skip the leading zeros, and move the digits to the front.
+    while index!(digits[index]) == b'0' {
+        index += 1;
+    }
+    let count = digits.len() - index;
+    for i in 0..count {
+        let c = index!(digits[index + i]);
+        index_mut!(digits[i] = c);
+    }
+
+    (count, count.as_i32() + scale)
+}
+
+// RADIX DIGITS
+
+/// Write the shortest digits of `mant * 2^exp` in a radix that round-trip.
+///
+/// Uses the free-format algorithm of Steele and White, with the
+/// improvements of Burger and Dybvig, generating digits until the
+/// digits uniquely identify the float within the rounding boundaries,
+/// halfway to the adjacent floats. The boundaries round to the float
+/// if the mantissa is even, with round-nearest, tie-even. The lower
+/// boundary is closer if the mantissa is the hidden bit of a normal
+/// float, since the previous float has a smaller exponent.
+///
+/// Returns the number of digits written and the position of the
+/// point relative to the first digit, such that the value is
+/// `0.d1d2...dn * radix^point`.
+pub(crate) fn radix_digits(mant: u64, exp: i32, is_lower_closer: bool, radix: u32, digits: &mut [u8])
+    -> (usize, i32)
+{
+    debug_assert!(mant != 0, "Mantissa cannot be zero.");
+    let r = Bigint::from_u64(mant);
+    radix_digits_impl(r, mant.as_f64(), mant & 1 == 0, exp, is_lower_closer, radix, digits)
+}
+
+/// Write the shortest digits of a 128-bit mantissa in a radix that round-trip.
+///
+/// Identical to `radix_digits`, but for quad floats.
+#[cfg(has_i128)]
+pub(crate) fn quad_radix_digits(mant: u128, exp: i32, is_lower_closer: bool, radix: u32, digits: &mut [u8])
+    -> (usize, i32)
+{
+    debug_assert!(mant != 0, "Mantissa cannot be zero.");
+    let r = QuadBigint::from_u128(mant);
+    radix_digits_impl(r, mant.as_f64(), mant & 1 == 0, exp, is_lower_closer, radix, digits)
+}
+
+/// Write the shortest digits of `r * 2^exp` in a radix that round-trip.
+///
+/// The mantissa is passed as a big integer, with its approximate
+/// value and whether it is even.
+fn radix_digits_impl<T>(mut r: T, mant: f64, is_even: bool, exp: i32, is_lower_closer: bool, radix: u32, digits: &mut [u8])
+    -> (usize, i32)
+    where T: LargeOps
+{
+    debug_assert_radix!(radix);
+
+    // Scale the value and the distances to the upper and lower
+    // boundaries to big integers, such that the value is `r / s`,
+    // and the boundaries are `(r + mp) / s` and `(r - mm) / s`.
+    let shift = if is_lower_closer { 2 } else { 1 };
+    r.ishl(shift);
+    let mut s = T::from_u64(1 << shift);
+    let mut mp = T::from_u64(1 << (shift - 1));
+    let mut mm = T::from_u64(1);
+    if exp >= 0 {
+        r.imul_pow2(exp.as_u32());
+        mp.imul_pow2(exp.as_u32());
+        mm.imul_pow2(exp.as_u32());
+    } else {
+        s.imul_pow2(exp.wrapping_neg().as_u32());
+    }
+
+    // Estimate the position of the point, which is either exact or one
+    // too small, and scale the value to the range `[1/radix, 1)`.
+    let log_radix = radix.as_f64().ln();
+    let estimate = mant.ln() / log_radix + exp.as_f64() * (2.0f64.ln() / log_radix);
+    let mut point = (estimate - 1e-10).ceil().as_i32();
+    if point >= 0 {
+        s.imul_power(radix, point.as_u32());
+    } else {
+        let n = point.wrapping_neg().as_u32();
+        r.imul_power(radix, n);
+        mp.imul_power(radix, n);
+        mm.imul_power(radix, n);
+    }
+
+    let is_high = |r: &T, mp: &T, s: &T| {
+        let mut high = r.clone();
+        high.iadd_large(mp);
+        match is_even {
+            true  => high.greater_equal(s),
+            false => high.greater(s),
+        }
+    };
+    if is_high(&r, &mp, &s) {
+        s.imul_small(as_limb(radix));
+        point += 1;
+    }
+
+    // Shift the denominator so it has the number of bits in the radix
+    // as leading zeros, so a single limb estimates each digit.
+    let bits = <Limb as Integer>::BITS;
+    let wlz = integral_binary_factor(radix).as_usize();
+    let nlz = s.leading_zeros().wrapping_sub(wlz) & (bits - 1);
+    r.ishl(nlz);
+    s.ishl(nlz);
+    mp.ishl(nlz);
+    mm.ishl(nlz);
+
+    // Generate digits until the value is within the boundaries.
+    let mut count = 0;
+    loop {
+        r.imul_small(as_limb(radix));
+        mp.imul_small(as_limb(radix));
+        mm.imul_small(as_limb(radix));
+        let mut digit = r.quorem(&s);
+        let low = match is_even {
+            true  => r.less_equal(&mm),
+            false => r.less(&mm),
+        };
+        let high = is_high(&r, &mp, &s);
+        if low && high {
+            // Both are valid, round to the nearest digit, or to even.
+            let twice = r.shl(1);
+            if twice.greater(&s) {
+                digit += 1;
+            } else if twice.equal(&s) {
+                digit += digit % 2;
+            }
+        } else if high {
+            digit += 1;
+        }
+        index_mut!(digits[count] = digit_to_char(digit));
+        count += 1;
+        if low || high {
+            break;
+        }
+    }
+
+    (count, point)
+}
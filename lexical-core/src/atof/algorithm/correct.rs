@@ -8,7 +8,7 @@
 // Fix a compiler bug that thinks `ExactExponent` isn't used.
 #![allow(unused_imports)]
 
-use lib::ptr;
+use lib::{cmp, ptr};
 
 use atoi;
 use float::*;
@@ -17,6 +17,7 @@ use super::alias::*;
 use super::cached::ModeratePathCache;
 use super::bhcomp;
 use super::exponent::*;
+use super::lemire::*;
 use super::small_powers::get_small_powers_64;
 
 // SHARED
@@ -35,15 +36,53 @@ macro_rules! rtrim_0 {
 // In this case, the mantissa can be represented by an integer,
 // which allows any value to be exactly reconstructed.
 
+// DIGITS
+
+/// Get the number of digits in a slice, ignoring any digit or group separators.
+///
+/// Digits are always alphanumeric, while separators never are.
+#[inline]
+fn count_digits<C: CodeUnit>(bytes: &[C], separators: bool) -> usize {
+    match separators {
+        false => bytes.len(),
+        true  => bytes.iter().filter(|&&c| c.to_byte().is_ascii_alphanumeric()).count(),
+    }
+}
+
+/// Iterator over digits, skipping any digit or group separators.
+#[derive(Clone, Debug)]
+pub(super) struct DigitIter<'a, C: 'a> {
+    /// Iterator over the raw code units.
+    iter: SliceIter<'a, C>,
+    /// If separators must be skipped.
+    separators: bool,
+}
+
+impl<'a, C: CodeUnit> Iterator for DigitIter<'a, C> {
+    type Item = &'a C;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a C> {
+        match self.separators {
+            false => self.iter.next(),
+            true  => self.iter.by_ref().find(|&&c| c.to_byte().is_ascii_alphanumeric()),
+        }
+    }
+}
+
 // FLOAT SLICE
 
 /// Substrings and information from parsing the float.
+///
+/// If digit separators or digit grouping are enabled, the substrings
+/// may contain separators, and all offsets are byte offsets into the
+/// substrings.
 #[derive(Debug)]
-pub(super) struct FloatSlice<'a> {
+pub(crate) struct FloatSlice<'a, C: 'a> {
     /// Substring for the integer component of the mantissa.
-    integer: &'a [u8],
+    integer: &'a [C],
     /// Substring for the fraction component of the mantissa.
-    fraction: &'a [u8],
+    fraction: &'a [C],
     /// Offset to where the digits start in either integer or fraction.
     digits_start: usize,
     /// Offset to where the digits end in the fraction.
@@ -52,12 +91,14 @@ pub(super) struct FloatSlice<'a> {
     truncated: usize,
     /// Raw exponent for the float.
     raw_exponent: i32,
+    /// If the substrings may contain digit or group separators.
+    separators: bool,
 }
 
-impl<'a> FloatSlice<'a> {
+impl<'a, C: CodeUnit> FloatSlice<'a, C> {
     /// Create uninitialized slice.
     #[inline]
-    pub(super) fn uninitialized() -> FloatSlice<'a> {
+    pub(super) fn uninitialized() -> FloatSlice<'a, C> {
         FloatSlice {
             integer: &[],
             fraction: &[],
@@ -65,42 +106,55 @@ impl<'a> FloatSlice<'a> {
             digits_end: explicit_uninitialized(),
             truncated: explicit_uninitialized(),
             raw_exponent: explicit_uninitialized(),
+            separators: false,
         }
     }
 
-    /// Get the length of the integer substring.
+    /// Get the integer substring, without leading zeros.
+    #[inline]
+    pub(crate) fn integer(&self) -> &'a [C] {
+        self.integer
+    }
+
+    /// Get the fraction substring, including leading and trailing zeros.
+    #[inline]
+    pub(crate) fn fraction(&self) -> &'a [C] {
+        self.fraction
+    }
+
+    /// Get the raw exponent for the float.
     #[inline]
-    pub(super) fn integer_len(&self) -> usize {
-        self.integer.len()
+    pub(crate) fn raw_exponent(&self) -> i32 {
+        self.raw_exponent
     }
 
     /// Get number of parsed integer digits.
     #[inline]
     pub(super) fn integer_digits(&self) -> usize {
-        self.integer_len()
+        count_digits(self.integer, self.separators)
     }
 
     /// Iterate over the integer digits.
     #[inline]
-    pub(super) fn integer_iter(&self) -> SliceIter<u8> {
-        self.integer.iter()
+    pub(super) fn integer_iter(&self) -> DigitIter<'a, C> {
+        DigitIter { iter: self.integer.iter(), separators: self.separators }
     }
 
     /// Get the length of the fraction substring.
     #[inline]
     pub(super) fn fraction_len(&self) -> usize {
-        self.digits_end
+        count_digits(&self.fraction[..self.digits_end], self.separators)
     }
 
     /// Iterate over the fraction digits.
     #[inline]
     pub(super) fn fraction_digits(&self) -> usize {
-        self.fraction_len() - self.digits_start
+        count_digits(&self.fraction[self.digits_start..self.digits_end], self.separators)
     }
 
     /// Iterate over the digits, by chaining two slices.
     #[inline]
-    pub(super) fn fraction_iter(&self) -> SliceIter<u8> {
+    pub(super) fn fraction_iter(&self) -> DigitIter<'a, C> {
         // We need to rtrim the zeros in the slice fraction.
         // These are useless and just add computational complexity later,
         // just like leading zeros in the integer.
@@ -108,7 +162,8 @@ impl<'a> FloatSlice<'a> {
         // but we should remove them before doing anything costly.
         // In practice, we only call `mantissa_iter()` once per parse,
         // so this is effectively free.
-        self.fraction[self.digits_start..self.digits_end].iter()
+        let fraction = &self.fraction[self.digits_start..self.digits_end];
+        DigitIter { iter: fraction.iter(), separators: self.separators }
     }
 
     /// Get the number of digits in the mantissa.
@@ -120,16 +175,16 @@ impl<'a> FloatSlice<'a> {
 
     /// Iterate over the mantissa digits, by chaining two slices.
     #[inline]
-    pub(super) fn mantissa_iter(&self) -> ChainedSliceIter<u8> {
+    pub(super) fn mantissa_iter(&self) -> ChainedDigitIter<'a, C> {
         self.integer_iter().chain(self.fraction_iter())
     }
 
     /// Get number of truncated digits.
     #[inline]
-    pub(super) fn truncated_digits(&self) -> usize {
+    pub(crate) fn truncated_digits(&self) -> usize {
         // If we have truncated digits, need to remove the number of
         // trailing zeros from that.
-        let trailing = self.fraction.len() - self.digits_end;
+        let trailing = count_digits(&self.fraction[self.digits_end..], self.separators);
         match self.truncated > trailing {
             true  => self.truncated - trailing,
             false => 0,
@@ -138,7 +193,7 @@ impl<'a> FloatSlice<'a> {
 
     /// Get the mantissa exponent from the raw exponent.
     #[inline]
-    pub(super) fn mantissa_exponent(&self) -> i32 {
+    pub(crate) fn mantissa_exponent(&self) -> i32 {
         mantissa_exponent(self.raw_exponent, self.fraction_len(), self.truncated_digits())
     }
 
@@ -147,7 +202,7 @@ impl<'a> FloatSlice<'a> {
     pub(super) fn scientific_exponent(&self) -> i32 {
         let fraction_start = match self.digits_start.is_zero() {
             true  => 0,
-            false => self.digits_start,
+            false => count_digits(&self.fraction[..self.digits_start], self.separators),
         };
         scientific_exponent(self.raw_exponent, self.integer_digits(), fraction_start)
     }
@@ -181,11 +236,12 @@ fn adjust_truncated_mantissa<M>(mantissa: M, radix: u32, trimmed: usize, truncat
 /// the number of parsed fraction digits, and the number of truncated
 /// digits (including those in both the integer and fraction).
 ///
-/// The float string must be non-special, non-zero, and positive.
+/// The float string must be non-special and positive.
 #[inline]
-fn parse_mantissa<'a, M>(radix: u32, mut bytes: &'a [u8])
-    -> (M, FloatSlice, &'a [u8], Option<&'a u8>)
-    where M: Mantissa
+fn parse_mantissa<'a, M, C>(radix: u32, mut bytes: &'a [C], decimal_point: u8)
+    -> (M, FloatSlice<'a, C>, &'a [C], Option<&'a C>)
+    where M: Mantissa,
+          C: CodeUnit
 {
     // Initialize our variables for the output.
     let mut mantissa: M = M::ZERO;
@@ -208,7 +264,7 @@ fn parse_mantissa<'a, M>(radix: u32, mut bytes: &'a [u8])
     slc.integer = slice_from_span(first, len);
 
     // Check for trailing digits.
-    let has_fraction = Some(&b'.') == bytes.get(0);
+    let has_fraction = Some(decimal_point) == byte_at(bytes, 0);
     if has_fraction && truncated.is_none() {
         // Has a decimal, no truncation, calculate the rest of it.
         // We know this is safe, since we know we have a fraction.
@@ -267,19 +323,139 @@ fn parse_mantissa<'a, M>(radix: u32, mut bytes: &'a [u8])
     }
 }
 
+/// Trim leading 0s and digit or group separators.
+#[inline]
+fn ltrim_0_separator<'a, C: CodeUnit>(bytes: &'a [C])
+    -> (&'a [C], usize)
+{
+    let count = bytes.iter().map(|&c| c.to_byte()).take_while(|&c| c == b'0' || !c.is_ascii_alphanumeric()).count();
+    (&index!(bytes[count..]), count)
+}
+
+/// Trim trailing 0s and digit or group separators.
+#[inline]
+fn rtrim_0_separator<'a, C: CodeUnit>(bytes: &'a [C])
+    -> (&'a [C], usize)
+{
+    let count = bytes.iter().rev().map(|&c| c.to_byte()).take_while(|&c| c == b'0' || !c.is_ascii_alphanumeric()).count();
+    (&index!(bytes[..bytes.len() - count]), count)
+}
+
+/// Parse validated digits, skipping any digit or group separators.
+///
+/// Returns the position where the mantissa was truncated, if any.
+#[inline]
+fn parse_digits_separator<'a, M, C>(mantissa: &mut M, radix: u32, bytes: &'a [C])
+    -> Option<&'a C>
+    where M: Mantissa,
+          C: CodeUnit
+{
+    for digits in bytes.split(|&c| !c.to_byte().is_ascii_alphanumeric()) {
+        let (_, truncated) = atoi::checked_positive(mantissa, as_cast(radix), digits);
+        if truncated.is_some() {
+            return truncated;
+        }
+    }
+    None
+}
+
+/// Parse the mantissa from a string with digit separators or digit grouping.
+///
+/// Identical to `parse_mantissa`, except the placement of digit and
+/// group separators is validated for each component, and valid
+/// separators are skipped.
+///
+/// The float string must be non-special and positive.
+#[inline]
+fn parse_mantissa_separator<'a, M, C>(radix: u32, mut bytes: &'a [C], options: &ParseOptions)
+    -> (M, FloatSlice<'a, C>, &'a [C], Option<&'a C>)
+    where M: Mantissa,
+          C: CodeUnit
+{
+    // Initialize our variables for the output.
+    let mut mantissa: M = M::ZERO;
+    let mut slc = FloatSlice::uninitialized();
+    slc.separators = true;
+
+    // Get the digits remaining after the truncated position.
+    let remaining = |slc: &'a [C], p: &'a C| -> &'a [C] {
+        &index!(slc[distance(slc.as_ptr(), p)..])
+    };
+
+    // Validate the integral value, and then trim the leading 0s, which
+    // may be interspersed with digit or group separators.
+    let len = atoi::integer_len(radix, bytes, options);
+    slc.integer = ltrim_0_separator(&index!(bytes[..len])).0;
+    bytes = &index!(bytes[len..]);
+    let integer_truncated = parse_digits_separator(&mut mantissa, radix, slc.integer);
+
+    // Check for trailing digits.
+    if Some(options.decimal_point()) == byte_at(bytes, 0) {
+        // Validate the fraction, and then parse it if the integer
+        // did not overflow.
+        // We know this is safe, since we know we have a fraction.
+        bytes = &index!(bytes[1..]);
+        let rules = options.digit_separator_flags().fraction_rules();
+        let len = atoi::digits_len(radix, bytes, options.separator(), rules);
+        slc.fraction = &index!(bytes[..len]);
+        bytes = &index!(bytes[len..]);
+        slc.digits_start = match mantissa.is_zero() {
+            // Can ignore the leading digits while the mantissa is 0.
+            true  => ltrim_0_separator(slc.fraction).1,
+            false => 0,
+        };
+        let fraction_truncated = match integer_truncated {
+            Some(_) => None,
+            None    => {
+                let fraction = &slc.fraction[slc.digits_start..];
+                parse_digits_separator(&mut mantissa, radix, fraction)
+            },
+        };
+
+        let trim = rtrim_0_separator(slc.fraction);
+        slc.digits_end = slc.fraction.len() - trim.1;
+        slc.truncated = match (integer_truncated, fraction_truncated) {
+            (Some(p), _) => {
+                count_digits(remaining(slc.integer, p), slc.separators)
+                    + count_digits(slc.fraction, slc.separators)
+            },
+            (None, Some(p)) => count_digits(remaining(slc.fraction, p), slc.separators),
+            (None, None)    => 0,
+        };
+        let trimmed = count_digits(&slc.fraction[slc.digits_end..], slc.separators);
+        mantissa = adjust_truncated_mantissa(mantissa, radix, trimmed, slc.truncated);
+        (mantissa, slc, bytes, integer_truncated.or(fraction_truncated))
+    } else {
+        // No decimal, return the number of truncated digits.
+        slc.digits_start = 0;
+        slc.fraction = slice_from_span(bytes.as_ptr(), 0);
+        slc.truncated = integer_truncated.map_or(0, |p| {
+            count_digits(remaining(slc.integer, p), slc.separators)
+        });
+        slc.digits_end = 0;
+        (mantissa, slc, bytes, integer_truncated)
+    }
+}
+
 /// Parse the mantissa and exponent from a string.
 ///
 /// Returns the mantissa, the exponent, the scientific-notation exponent,
 /// the number of parsed digits, and the current parser state.
 ///
-/// The float string must be non-special, non-zero, and positive.
+/// The float string must be non-special and positive.
 #[inline]
-fn parse_float<'a, M>(radix: u32, bytes: &'a [u8])
-    -> (M, FloatSlice, &'a [u8], Option<&'a u8>)
-    where M: Mantissa
+pub(crate) fn parse_float<'a, M, C>(radix: u32, bytes: &'a [C], options: &ParseOptions)
+    -> (M, FloatSlice<'a, C>, &'a [C], Option<&'a C>)
+    where M: Mantissa,
+          C: CodeUnit
 {
-    let (mantissa, mut slc, bytes, truncated) = parse_mantissa::<M>(radix, bytes);
-    let (raw_exponent, bytes) = parse_exponent(radix, bytes);
+    let (mantissa, mut slc, bytes, truncated) = match options.separator() {
+        None if !options.locale().is_grouped() => {
+            parse_mantissa::<M, C>(radix, bytes, options.decimal_point())
+        },
+        _ => parse_mantissa_separator::<M, C>(radix, bytes, options),
+    };
+    let (raw_exponent, bytes) = parse_exponent(radix, bytes, options);
     slc.raw_exponent = raw_exponent;
 
     (mantissa, slc, bytes, truncated)
@@ -444,6 +620,27 @@ fn fast_path<F>(mantissa: u64, radix: u32, exponent: i32)
     }
 }
 
+/// Convert mantissa to a narrower float using the 64-bit fast path.
+///
+/// The 64-bit fast path handles more mantissa digits and larger exponents
+/// than the narrower float, but rounds twice. The result is still correct
+/// unless the 64-bit float is exactly halfway between two narrower floats,
+/// since every halfway point is exactly representable as a 64-bit float,
+/// so the exact value and the 64-bit float cannot be on opposite sides of
+/// a halfway point. Returns None if the result may be incorrect.
+#[inline]
+fn narrow_fast_path<F>(mantissa: u64, radix: u32, exponent: i32, kind: RoundingKind)
+    -> Option<F>
+    where F: FloatRounding<u64>
+{
+    let (double, valid) = fast_path::<f64>(mantissa, radix, exponent);
+    if valid && is_nearest(kind) && !is_narrow_halfway::<F>(double) {
+        Some(narrow_f64(double, kind))
+    } else {
+        None
+    }
+}
+
 // MODERATE
 // --------
 
@@ -744,22 +941,27 @@ pub(super) fn moderate_path<F, M>(mantissa: M, radix: u32, exponent: i32, trunca
 /// Parse power-of-two radix string to native float.
 #[cfg(feature = "radix")]
 #[inline]
-fn pow2_to_native<'a, F>(radix: u32, pow2_exp: i32, bytes: &'a [u8], sign: Sign)
-    -> (F, &'a [u8])
-    where F: FloatType
+fn pow2_to_native<'a, F, C>(radix: u32, pow2_exp: i32, bytes: &'a [C], sign: Sign, options: &ParseOptions)
+    -> (F, &'a [C])
+    where F: FloatType,
+          C: CodeUnit
 {
-    let (mut mantissa, slc, bytes, truncated) = parse_float::<u64>(radix, bytes);
+    let (mut mantissa, slc, bytes, truncated) = parse_float::<u64, C>(radix, bytes, options);
 
     // We have a power of 2, can get an exact value even if the mantissa
     // was truncated. Check to see if there are any truncated digits, depending
     // on our rounding scheme.
-    let kind = global_rounding(sign);
+    let kind = internal_rounding(options.rounding(), sign);
     let mantissa_size = F::MANTISSA_SIZE + 1;
     if truncated.is_some() {
         if kind != RoundingKind::Downward {
-            // See if we need to round-up.
+            // See if we need to round-up, skipping the decimal point
+            // and any digit or group separators.
             let bytes = slice_from_range(truncated.unwrap(), bytes.as_ptr());
-            let count = bytes.iter().take_while(|&&c| c == b'0' || c == b'.').count();
+            let count = bytes.iter()
+                .map(|&c| c.to_byte())
+                .take_while(|&c| c == b'0' || !c.is_ascii_alphanumeric())
+                .count();
             let bytes = &bytes[count..];
             let is_truncated = bytes.get(0).map_or(false, |&c| char_to_digit(c).as_u32() < radix);
             if cfg!(feature = "rounding") || kind == RoundingKind::NearestTieEven {
@@ -797,72 +999,118 @@ fn pow2_to_native<'a, F>(radix: u32, pow2_exp: i32, bytes: &'a [u8], sign: Sign)
     }
 }
 
-/// Parse non-power-of-two radix string to native float.
+/// Convert the parsed mantissa and float slice to native float.
+///
+/// The float string must be non-special and positive.
 #[inline]
-fn pown_to_native<'a, F>(radix: u32, bytes: &'a [u8], lossy: bool, sign: Sign)
-    -> (F, &'a [u8])
-    where F: FloatType
+fn slice_to_native<F, C>(mantissa: u64, slc: FloatSlice<C>, radix: u32, lossy: bool, kind: RoundingKind)
+    -> F
+    where F: FloatType,
+          C: CodeUnit
 {
-    let (mantissa, slc, bytes, _) = parse_float::<u64>(radix, bytes);
     let exponent = slc.mantissa_exponent();
-    let kind = global_rounding(sign);
-
     if mantissa == 0 {
         // Literal 0, return early.
         // Value cannot be truncated, since we discard leading 0s.
-        return (F::ZERO, bytes);
+        return F::ZERO;
     } else if exponent > 0x40000000 {
         // Extremely large exponent, will always be infinity.
         // Avoid potential overflows in exponent addition.
-        return (F::INFINITY, bytes);
+        return F::INFINITY;
     } else if exponent < -0x40000000 {
         // Extremely small exponent, will always be zero.
         // Avoid potential overflows in exponent addition.
-        return (F::ZERO, bytes);
+        return F::ZERO;
     } else if slc.truncated.is_zero() {
         // Try last fast path to exact, no mantissa truncation
         let (float, valid) = fast_path::<F>(mantissa, radix, exponent);
         if valid {
-            return (float, bytes);
+            return float;
+        }
+        if F::BITS < 64 {
+            // Try the fast path for a 64-bit float, and round it.
+            if let Some(float) = narrow_fast_path::<F>(mantissa, radix, exponent, kind) {
+                return float;
+            }
+        }
+    }
+
+    // Moderate path for decimal strings (use the Eisel-Lemire algorithm).
+    // Only rounds to nearest, tie even, and only falls back to the
+    // extended-float and slow paths if the value is nearly halfway.
+    if radix == 10 && kind == RoundingKind::NearestTieEven {
+        let float = match slc.truncated {
+            0 => eisel_lemire::<F>(mantissa, exponent),
+            _ => eisel_lemire_truncated::<F>(mantissa, exponent),
+        };
+        if let Some(float) = float {
+            return float;
         }
     }
 
     // Moderate path (use an extended 80-bit representation).
+    // The extended float is within a few units in the last place of its
+    // 64-bit mantissa of the exact value, or less than 2^-8 ULPs of the
+    // native float, so rounding it gives the correct float, or for values
+    // within the error of halfway, its neighbor. Lossy parsing is therefore
+    // within 1 ULP of the correct float.
     let (fp, valid) = moderate_path::<F, _>(mantissa, radix, exponent, slc.truncated != 0, kind);
     if valid || lossy {
-        let float = fp.into_rounded_float_impl::<F>(kind);
-        return (float, bytes);
+        return fp.into_rounded_float_impl::<F>(kind);
     }
 
     // Slow path
     let b = fp.into_rounded_float_impl::<F>(RoundingKind::Downward);
     if b.is_special() {
         // We have a non-finite number, we get to leave early.
-        return (b, bytes);
+        b
     } else {
-        let float = bhcomp::atof(slc, radix, b, kind);
-        return (float, bytes);
+        bhcomp::atof(slc, radix, b, kind)
     }
 }
 
+/// Convert the parsed mantissa and float slice to a 64-bit float.
+///
+/// The radix must not be a power of two, and the float string must be
+/// non-special and positive.
+#[inline]
+pub(crate) fn slice_to_double<C: CodeUnit>(mantissa: u64, slc: FloatSlice<C>, radix: u32, kind: RoundingKind)
+    -> f64
+{
+    slice_to_native::<f64, C>(mantissa, slc, radix, false, kind)
+}
+
+/// Parse non-power-of-two radix string to native float.
+#[inline]
+fn pown_to_native<'a, F, C>(radix: u32, bytes: &'a [C], lossy: bool, sign: Sign, options: &ParseOptions)
+    -> (F, &'a [C])
+    where F: FloatType,
+          C: CodeUnit
+{
+    let (mantissa, slc, bytes, _) = parse_float::<u64, C>(radix, bytes, options);
+    let kind = internal_rounding(options.rounding(), sign);
+    (slice_to_native::<F, C>(mantissa, slc, radix, lossy, kind), bytes)
+}
+
 /// Parse native float from string.
 ///
-/// The float string must be non-special, non-zero, and positive.
+/// The float string must be non-special and positive.
 #[inline]
-fn to_native<F>(radix: u32, bytes: &[u8], lossy: bool, sign: Sign)
+fn to_native<F, C>(radix: u32, bytes: &[C], lossy: bool, sign: Sign, options: &ParseOptions)
     -> (F, usize)
-    where F: FloatType
+    where F: FloatType,
+          C: CodeUnit
 {
     #[cfg(not(feature = "radix"))] {
-        let (f, slc) = pown_to_native(radix, bytes, lossy, sign);
+        let (f, slc) = pown_to_native(radix, bytes, lossy, sign, options);
         (f, bytes.len() - slc.len())
     }
 
     #[cfg(feature = "radix")] {
         let pow2_exp = pow2_exponent(radix);
         let (f, slc) = match pow2_exp {
-            0 => pown_to_native(radix, bytes, lossy, sign),
-            _ => pow2_to_native(radix, pow2_exp, bytes, sign),
+            0 => pown_to_native(radix, bytes, lossy, sign, options),
+            _ => pow2_to_native(radix, pow2_exp, bytes, sign, options),
         };
         (f, bytes.len() - slc.len())
     }
@@ -870,34 +1118,100 @@ fn to_native<F>(radix: u32, bytes: &[u8], lossy: bool, sign: Sign)
 
 /// Parse 32-bit float from string.
 #[inline]
-pub(crate) fn atof(radix: u32, bytes: &[u8], sign: Sign)
+pub(crate) fn atof<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
     -> (f32, usize)
 {
-    to_native::<f32>(radix, bytes, false, sign)
+    to_native::<f32, C>(radix, bytes, false, sign, options)
 }
 
 /// Parse 64-bit float from string.
 #[inline]
-pub(crate) fn atod(radix: u32, bytes: &[u8], sign: Sign)
+pub(crate) fn atod<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
     -> (f64, usize)
 {
-    to_native::<f64>(radix, bytes, false, sign)
+    to_native::<f64, C>(radix, bytes, false, sign, options)
 }
 
-/// Parse 32-bit float from string.
+/// Parse 32-bit float from string, within 1 ULP of the correct float.
 #[inline]
-pub(crate) fn atof_lossy(radix: u32, bytes: &[u8], sign: Sign)
+pub(crate) fn atof_lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
     -> (f32, usize)
 {
-    to_native::<f32>(radix, bytes, true, sign)
+    to_native::<f32, C>(radix, bytes, true, sign, options)
 }
 
-/// Parse 64-bit float from string.
+/// Parse 64-bit float from string, within 1 ULP of the correct float.
 #[inline]
-pub(crate) fn atod_lossy(radix: u32, bytes: &[u8], sign: Sign)
+pub(crate) fn atod_lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
     -> (f64, usize)
 {
-    to_native::<f64>(radix, bytes, true, sign)
+    to_native::<f64, C>(radix, bytes, true, sign, options)
+}
+
+/// Determine if a 64-bit float is exactly halfway between two narrower floats.
+#[inline]
+fn is_narrow_halfway<F>(double: f64)
+    -> bool
+    where F: FloatRounding<u64>
+{
+    if double.is_special() || double.is_zero() {
+        return false;
+    }
+
+    // The halfway point is the lower float plus half its ULP, which is
+    // always exactly representable as a 64-bit float.
+    let lower: F = narrow_f64(double, RoundingKind::Downward);
+    if lower.is_special() {
+        return false;
+    }
+    let half_ulp = ExtendedFloat { mant: 1u64, exp: lower.exponent() - 1 };
+    let half_ulp: f64 = half_ulp.into_rounded_float_impl(RoundingKind::NearestTieEven);
+    double == lower.as_f64() + half_ulp
+}
+
+/// Parse a float narrower than a 32-bit float from string.
+///
+/// The 64-bit float is rounded to the narrower float, which is correct,
+/// since the narrower float has less than half the precision, unless
+/// the 64-bit float is exactly halfway between two narrower floats. In
+/// that case, the real value may be slightly above or below the halfway
+/// point, so compare the real digits to it. Directed rounding does not
+/// need this, since both roundings truncate in the same direction.
+#[inline]
+pub(crate) fn atoh<F, C>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
+    -> (F, usize)
+    where F: FloatRounding<u64>,
+          C: CodeUnit
+{
+    let (double, len) = to_native::<f64, C>(radix, bytes, false, sign, options);
+    let kind = internal_rounding(options.rounding(), sign);
+    let is_nearest = kind == RoundingKind::NearestTieEven || kind == RoundingKind::NearestTieAwayZero;
+    if is_nearest && is_narrow_halfway::<F>(double) {
+        let (_, slc, _, _) = parse_float::<u64, C>(radix, bytes, options);
+        let kind = match bhcomp::compare(slc, radix, double) {
+            cmp::Ordering::Greater => RoundingKind::Upward,
+            cmp::Ordering::Less    => RoundingKind::Downward,
+            cmp::Ordering::Equal   => kind,
+        };
+        (narrow_f64(double, kind), len)
+    } else {
+        (narrow_f64(double, kind), len)
+    }
+}
+
+/// Parse a float narrower than a 32-bit float from string.
+///
+/// Rounds the lossy 64-bit float to the narrower float, which may be
+/// incorrect near halfway points, but is within 1 ULP of the correct float.
+#[inline]
+pub(crate) fn atoh_lossy<F, C>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
+    -> (F, usize)
+    where F: FloatRounding<u64>,
+          C: CodeUnit
+{
+    let (double, len) = to_native::<f64, C>(radix, bytes, true, sign, options);
+    let kind = internal_rounding(options.rounding(), sign);
+    (narrow_f64(double, kind), len)
 }
 
 // TESTS
@@ -920,6 +1234,7 @@ mod tests {
             digits_end: 4,
             truncated: 0,
             raw_exponent: 0,
+            separators: false,
         };
         assert_eq!(slc.scientific_exponent(), 0);
 
@@ -931,8 +1246,23 @@ mod tests {
             digits_end: 5,
             truncated: 0,
             raw_exponent: 0,
+            separators: false,
         };
         assert_eq!(slc.scientific_exponent(), -1);
+
+        // Check "0.0_0_12_345", with digit separators.
+        let slc = FloatSlice {
+            integer: "".as_bytes(),
+            fraction: "0_0_12_345".as_bytes(),
+            digits_start: 4,
+            digits_end: 10,
+            truncated: 0,
+            raw_exponent: 0,
+            separators: true,
+        };
+        assert_eq!(slc.scientific_exponent(), -3);
+        assert_eq!(slc.mantissa_digits(), 5);
+        assert_eq!(slc.mantissa_exponent(), -7);
     }
 
     // PARSE MANTISSA
@@ -940,11 +1270,11 @@ mod tests {
     fn check_parse_mantissa<M>(radix: u32, s: &str, tup: (M, usize, usize, usize, usize, &str))
         where M: Mantissa
     {
-        let (value, slc, bytes, _) = parse_mantissa::<M>(radix, s.as_bytes());
+        let (value, slc, bytes, _) = parse_mantissa::<M, u8>(radix, s.as_bytes(), b'.');
         let digits: stackvector::StackVec<[u8; 1024]> = slc.mantissa_iter().cloned().collect();
         let digits = str::from_utf8(&digits).unwrap();
         assert_eq!(value, tup.0);
-        assert_eq!(slc.integer_len(), tup.1);
+        assert_eq!(slc.integer().len(), tup.1);
         assert_eq!(slc.fraction_len(), tup.2);
         assert_eq!(slc.truncated_digits(), tup.3);
         assert_eq!(distance(s.as_ptr(), bytes.as_ptr()), tup.4);
@@ -982,7 +1312,7 @@ mod tests {
     fn check_parse_float<M>(radix: u32, s: &str, tup: (M, i32, i32, usize, usize, bool, &str))
         where M: Mantissa
     {
-        let (value, slc, bytes, truncated) = parse_float::<M>(radix, s.as_bytes());
+        let (value, slc, bytes, truncated) = parse_float::<M, u8>(radix, s.as_bytes(), &ParseOptions::new());
         let digits: stackvector::StackVec<[u8; 1024]> = slc.mantissa_iter().cloned().collect();
         let digits = str::from_utf8(&digits).unwrap();
         assert_eq!(value, tup.0);
@@ -995,6 +1325,70 @@ mod tests {
         assert_eq!(digits.len(), slc.mantissa_digits());
     }
 
+    fn check_parse_float_separator<M>(radix: u32, s: &str, separator: u8, flags: DigitSeparator)
+        where M: Mantissa
+    {
+        // Parsing with digit separators should match parsing without them.
+        let mut options = ParseOptions::new();
+        options.set_digit_separator(separator, flags);
+        let stripped: String = s.chars().filter(|&c| c != separator as char).collect();
+        let (value, slc, bytes, truncated) = parse_float::<M, u8>(radix, s.as_bytes(), &options);
+        let (expected_value, expected_slc, expected_bytes, expected_truncated) = parse_float::<M, u8>(radix, stripped.as_bytes(), &ParseOptions::new());
+        let digits: stackvector::StackVec<[u8; 1024]> = slc.mantissa_iter().cloned().collect();
+        let expected_digits: stackvector::StackVec<[u8; 1024]> = expected_slc.mantissa_iter().cloned().collect();
+        assert_eq!(value, expected_value);
+        assert_eq!(slc.mantissa_exponent(), expected_slc.mantissa_exponent());
+        assert_eq!(slc.scientific_exponent(), expected_slc.scientific_exponent());
+        assert_eq!(slc.mantissa_digits(), expected_slc.mantissa_digits());
+        assert_eq!(slc.truncated_digits(), expected_slc.truncated_digits());
+        assert_eq!(bytes.len(), expected_bytes.len());
+        assert_eq!(truncated.is_some(), expected_truncated.is_some());
+        assert_eq!(&digits[..], &expected_digits[..]);
+    }
+
+    #[test]
+    fn parse_float_separator_test() {
+        let flags = DigitSeparator::ALL;
+        check_parse_float_separator::<u64>(10, "1_2.3_45", b'_', flags);
+        check_parse_float_separator::<u64>(10, "_0_0_1_2._3_4_5_0_0_e_1_0_", b'_', flags);
+        check_parse_float_separator::<u64>(10, "0.0_000_000_000_000_000_000_000_000_000_1", b'_', flags);
+        check_parse_float_separator::<u64>(10, "100_000_000_000_000_000_000", b'_', flags);
+        check_parse_float_separator::<u64>(10, "1_000_000_000_000_000_000_000.0_1", b'_', flags);
+        check_parse_float_separator::<u64>(10, "1.234_567_891_234_567_891_234_567_000", b'_', flags);
+        check_parse_float_separator::<u128>(10, "12_345.678_9e-1_0", b'_', flags);
+        check_parse_float_separator::<u128>(10, "1'234'567'891'234'567'891'234'567'891'234'567'891.5", b'\'', flags);
+    }
+
+    fn check_parse_float_locale<M>(radix: u32, s: &str, expected: &str, locale: Locale)
+        where M: Mantissa
+    {
+        // Parsing with a locale should match parsing the C locale string.
+        let mut options = ParseOptions::new();
+        options.set_locale(locale);
+        let (value, slc, bytes, truncated) = parse_float::<M, u8>(radix, s.as_bytes(), &options);
+        let (expected_value, expected_slc, expected_bytes, expected_truncated) = parse_float::<M, u8>(radix, expected.as_bytes(), &ParseOptions::new());
+        let digits: stackvector::StackVec<[u8; 1024]> = slc.mantissa_iter().cloned().collect();
+        let expected_digits: stackvector::StackVec<[u8; 1024]> = expected_slc.mantissa_iter().cloned().collect();
+        assert_eq!(value, expected_value);
+        assert_eq!(slc.mantissa_exponent(), expected_slc.mantissa_exponent());
+        assert_eq!(slc.scientific_exponent(), expected_slc.scientific_exponent());
+        assert_eq!(slc.truncated_digits(), expected_slc.truncated_digits());
+        assert_eq!(bytes.len(), expected_bytes.len());
+        assert_eq!(truncated.is_some(), expected_truncated.is_some());
+        assert_eq!(&digits[..], &expected_digits[..]);
+    }
+
+    #[test]
+    fn parse_float_locale_test() {
+        check_parse_float_locale::<u64>(10, "1.234.567,89", "1234567.89", Locale::DE_DE);
+        check_parse_float_locale::<u64>(10, "1234567,89e3", "1234567.89e3", Locale::DE_DE);
+        check_parse_float_locale::<u64>(10, "0,001.5", "0.001.5", Locale::DE_DE);
+        check_parse_float_locale::<u64>(10, "1\u{a0}000\u{a0}000,5", "1000000.5", Locale::FR_FR);
+        check_parse_float_locale::<u64>(10, "12,34,56,789.01", "123456789.01", Locale::EN_IN);
+        check_parse_float_locale::<u64>(10, "100,000,000,000,000,000,000.1", "100000000000000000000.1", Locale::EN_US);
+        check_parse_float_locale::<u64>(10, "1,23,456", "1,23,456", Locale::EN_US);
+    }
+
     #[test]
     fn parse_float_test() {
         // 64-bit
@@ -1200,6 +1594,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn narrow_fast_path_test() {
+        let kind = RoundingKind::NearestTieEven;
+
+        // Disguised fast path for a 64-bit float.
+        assert_eq!(narrow_fast_path::<f32>(12, 10, 20, kind), Some(12e20));
+        assert_eq!(narrow_fast_path::<f32>(15, 10, 24, kind), Some(1.5e25));
+        assert_eq!(narrow_fast_path::<f32>(1, 10, 37, kind), Some(1e37));
+        assert_eq!(narrow_fast_path::<f32>(1, 10, 38, kind), None);
+
+        // Mantissa has too many digits for a 32-bit float.
+        assert_eq!(narrow_fast_path::<f32>(123456789, 10, 0, kind), Some(123456792.0));
+        assert_eq!(narrow_fast_path::<f32>(123456789, 10, -15, kind), Some(1.2345679e-7));
+
+        // Halfway between two 32-bit floats, may be incorrectly rounded.
+        assert_eq!(narrow_fast_path::<f32>(16777217, 10, 0, kind), None);
+        assert_eq!(narrow_fast_path::<f32>(16777219, 10, 0, kind), None);
+
+        // Directed rounding is incorrect after rounding to nearest.
+        assert_eq!(narrow_fast_path::<f32>(123456789, 10, 0, RoundingKind::Downward), None);
+    }
+
     #[cfg(feature = "radix")]
     #[test]
     fn float_moderate_path_test() {
@@ -1249,7 +1665,7 @@ mod tests {
     }
 
     fn check_atof(radix: u32, s: &str, tup: (f32, usize)) {
-        let (value, len) = atof(radix, s.as_bytes(), Sign::Positive);
+        let (value, len) = atof(radix, s.as_bytes(), Sign::Positive, &ParseOptions::new());
         assert_f32_eq!(value, tup.0);
         assert_eq!(len, tup.1);
     }
@@ -1296,7 +1712,7 @@ mod tests {
     }
 
     fn check_atod(radix: u32, s: &str, tup: (f64, usize)) {
-        let (value, len) = atod(radix, s.as_bytes(), Sign::Positive);
+        let (value, len) = atod(radix, s.as_bytes(), Sign::Positive, &ParseOptions::new());
         assert_f64_eq!(value, tup.0);
         assert_eq!(len, tup.1);
     }
@@ -1391,7 +1807,7 @@ mod tests {
     // Lossy
 
     fn check_atof_lossy(radix: u32, s: &str, tup: (f32, usize)) {
-        let (value, len) = atof_lossy(radix, s.as_bytes(), Sign::Positive);
+        let (value, len) = atof_lossy(radix, s.as_bytes(), Sign::Positive, &ParseOptions::new());
         assert_f32_eq!(value, tup.0);
         assert_eq!(len, tup.1);
     }
@@ -1405,7 +1821,7 @@ mod tests {
     }
 
     fn check_atod_lossy(radix: u32, s: &str, tup: (f64, usize)) {
-        let (value, len) = atod_lossy(radix, s.as_bytes(), Sign::Positive);
+        let (value, len) = atod_lossy(radix, s.as_bytes(), Sign::Positive, &ParseOptions::new());
         assert_f64_eq!(value, tup.0);
         assert_eq!(len, tup.1);
     }
@@ -5,6 +5,8 @@ use util::*;
 
 /// Parse the exponential portion from a float-string, if we have an `(e|^)[+-]?\d+`.
 ///
+/// Exponents without any digits are not consumed.
+///
 /// On overflow, just return a comically large exponent, since we don't
 /// care. It will lead to infinity regardless, and doesn't affect whether
 /// the type is representable.
@@ -13,21 +15,32 @@ use util::*;
 ///
 /// The float string must be non-special, non-zero, and positive.
 #[inline]
-pub(super) fn parse_exponent<'a>(radix: u32, bytes: &'a [u8])
-    -> (i32, &'a [u8])
+pub(super) fn parse_exponent<'a, C: CodeUnit>(radix: u32, bytes: &'a [C], options: &ParseOptions)
+    -> (i32, &'a [C])
 {
     // Force a check that the distance is >= 2, so we ensure there's something
     // after the exponent. This fixes a regression discovered via proptest.
     // Safety: bytes.len() >= 2.
-    if bytes.len() >= 2 && case_insensitive_equal(index!(bytes[0]), exponent_notation_char(radix)) {
+    if bytes.len() >= 2 && case_insensitive_equal(index!(bytes[0]), options.exponent_notation_char(radix)) {
         // Use atoi_sign so we can handle overflow differently for +/- numbers.
         // We care whether the value is positive.
         // Use i32::max_value() since it's valid in 2s complement for
         // positive or negative numbers, and will trigger a short-circuit.
         // Safety: bytes.len() >= 2.
+        let original = bytes;
         let bytes = &index!(bytes[1..]);
-        let cb = atoi::unchecked::<i32>;
-        let (exponent, sign, len, truncated) = atoi::filter_sign::<i32, _>(radix, bytes, cb);
+        let (exponent, sign, len, truncated) = match options.separator() {
+            None            => {
+                let cb = atoi::unchecked::<i32, C>;
+                atoi::filter_sign::<i32, _, _>(radix, bytes, 0, cb)
+            },
+            Some(separator) => {
+                let rules = options.digit_separator_flags().exponent_rules();
+                atoi::filter_sign_separator::<i32, _, _>(radix, bytes, 0, |v, r, b, s| {
+                    atoi::unchecked_separator(v, r, b, s, separator, rules)
+                })
+            },
+        };
         let exponent = match truncated.is_some() {
             true  => match sign {
                 Sign::Negative => -i32::max_value(),
@@ -36,6 +49,16 @@ pub(super) fn parse_exponent<'a>(radix: u32, bytes: &'a [u8])
             false => exponent,
         };
 
+        // Don't consume an exponent without digits, so the caller may
+        // report it at the exponent character.
+        let sign_bytes = match index!(bytes[0]).to_byte() {
+            b'+' | b'-' => 1,
+            _           => 0,
+        };
+        if len <= sign_bytes {
+            return (0, original);
+        }
+
         // Safety: atoi always returns a value <= bytes.len().
         (exponent, &index!(bytes[len..]))
     } else {
@@ -140,7 +163,7 @@ mod test {
     use super::*;
 
     fn check_parse_exponent(radix: u32, s: &str, tup: (i32, usize)) {
-        let (value, slc) = parse_exponent(radix, s.as_bytes());
+        let (value, slc) = parse_exponent(radix, s.as_bytes(), &ParseOptions::new());
         assert_eq!(value, tup.0);
         assert_eq!(distance(s.as_ptr(), slc.as_ptr()), tup.1);
     }
@@ -155,6 +178,11 @@ mod test {
         check_parse_exponent(28, "e1h", (0, 0));
         check_parse_exponent(10, "^45", (0, 0));
 
+        // missing exponent digits
+        check_parse_exponent(10, "e", (0, 0));
+        check_parse_exponent(10, "e+", (0, 0));
+        check_parse_exponent(10, "e-a", (0, 0));
+
         // trailing characters
         check_parse_exponent(10, "e45 ", (45, 3));
         check_parse_exponent(10, "e45-", (45, 3));
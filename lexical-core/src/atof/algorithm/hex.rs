@@ -0,0 +1,283 @@
+//! Algorithm to parse C99 hexadecimal float literals, like `0x1.8p+3`.
+//!
+//! The hexadecimal digits map directly to bits in the mantissa, so the
+//! float is exactly representable as an extended-precision float, except
+//! for any digits which do not fit in the 64-bit (or 128-bit) mantissa. Any truncated,
+//! non-zero digits set the lowest (sticky) bit of the mantissa, which is
+//! always below the rounding bits of the native float, so the value is
+//! correctly rounded.
+
+use float::*;
+use util::*;
+
+/// Maximum absolute value for the binary exponent.
+///
+/// Any value past these bounds overflows to infinity or underflows to 0,
+/// and clamping prevents overflow when normalizing the extended float.
+const MAX_EXPONENT: i32 = 0x10000;
+
+/// Parse hexadecimal digits into the mantissa.
+///
+/// Leading zeros are not significant, and only the significant digits
+/// which fit in the mantissa are stored. Each fraction digit stored lowers the
+/// binary exponent, while each integer digit truncated raises it.
+///
+/// Returns the number of digits processed, and if any truncated digits
+/// were non-zero.
+#[inline]
+fn parse_digits<M, C>(bytes: &[C], fp: &mut ExtendedFloat<M>, digits: &mut usize, is_fraction: bool)
+    -> (usize, bool)
+    where M: Mantissa,
+          C: CodeUnit
+{
+    let max_digits = (M::FULL / 4).as_usize();
+    let mut truncated = false;
+    let count = bytes.iter()
+        .take_while(|&&c| char_to_digit(c) < 16)
+        .map(|&c| char_to_digit(c) as u32)
+        .fold(0, |count, digit| {
+            if *digits < max_digits {
+                if !fp.mant.is_zero() || digit != 0 {
+                    fp.mant = (fp.mant << 4) | as_cast(digit);
+                    *digits += 1;
+                }
+                if is_fraction {
+                    fp.exp = fp.exp.saturating_sub(4);
+                }
+            } else {
+                truncated |= digit != 0;
+                if !is_fraction {
+                    fp.exp = fp.exp.saturating_add(4);
+                }
+            }
+            count + 1
+        });
+
+    (count, truncated)
+}
+
+/// Parse the decimal digits of the binary exponent.
+///
+/// Returns the exponent, saturated on overflow, and the number of
+/// digits processed.
+#[inline]
+fn parse_exponent_digits<C: CodeUnit>(bytes: &[C])
+    -> (i32, usize)
+{
+    bytes.iter()
+        .map(|&c| c.to_byte())
+        .take_while(|c| c.is_ascii_digit())
+        .fold((0i32, 0), |(value, count), c| {
+            let digit = (c - b'0') as i32;
+            (value.saturating_mul(10).saturating_add(digit), count + 1)
+        })
+}
+
+/// Parse a hexadecimal float literal, after the `0x` prefix.
+///
+/// The literal has the form `[0-9A-Fa-f]*(\.[0-9A-Fa-f]*)?([pP][+-]?[0-9]*)?`,
+/// with at least one mantissa digit, and the binary exponent is optional.
+/// Digit separators are not supported.
+///
+/// Returns the value, the number of bytes processed, and an error code
+/// for any violation of the number grammar.
+pub(crate) fn atof<F, C>(bytes: &[C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions)
+    -> (F, usize, ErrorCode)
+    where F: FloatRounding<u64>,
+          C: CodeUnit
+{
+    atof_impl::<F, u64, C>(bytes, sign, grammar, options)
+}
+
+/// Parse a quad hexadecimal float literal, after the `0x` prefix.
+#[cfg(all(has_i128, feature = "correct"))]
+pub(crate) fn atoq<C: CodeUnit>(bytes: &[C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions)
+    -> (f128, usize, ErrorCode)
+{
+    atof_impl::<f128, u128, C>(bytes, sign, grammar, options)
+}
+
+/// Parse a hexadecimal float literal using the extended-float mantissa.
+#[inline]
+fn atof_impl<F, M, C>(bytes: &[C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions)
+    -> (F, usize, ErrorCode)
+    where F: FloatRounding<M>,
+          M: Mantissa,
+          C: CodeUnit
+{
+    let mut fp = ExtendedFloat { mant: M::ZERO, exp: 0 };
+    let mut digits = 0;
+
+    // Parse the integer digits.
+    let (integer_len, mut truncated) = parse_digits(bytes, &mut fp, &mut digits, false);
+    let mut index = integer_len;
+
+    // Parse the fraction digits.
+    if Some(b'.') == byte_at(bytes, index) {
+        let fraction = &index!(bytes[index+1..]);
+        let (len, is_truncated) = parse_digits(fraction, &mut fp, &mut digits, true);
+        if integer_len == 0 && len == 0 {
+            return (F::ZERO, 0, ErrorCode::EmptyMantissa);
+        } else if integer_len == 0 && grammar.contains(NumberGrammar::REQUIRED_INTEGER_DIGITS) {
+            return (F::ZERO, 0, ErrorCode::EmptyInteger);
+        } else if len == 0 && grammar.contains(NumberGrammar::REQUIRED_FRACTION_DIGITS) {
+            return (F::ZERO, index + 1, ErrorCode::EmptyFraction);
+        }
+        truncated |= is_truncated;
+        index += len + 1;
+    } else if integer_len == 0 {
+        return (F::ZERO, 0, ErrorCode::EmptyMantissa);
+    }
+
+    // Parse the binary exponent.
+    if let Some(c) = byte_at(bytes, index) {
+        if c == b'p' || c == b'P' {
+            let (sign_bytes, exponent_sign) = match byte_at(bytes, index + 1) {
+                Some(b'+') if grammar.contains(NumberGrammar::NO_POSITIVE_EXPONENT_SIGN) => {
+                    return (F::ZERO, index + 1, ErrorCode::InvalidPositiveExponentSign);
+                },
+                Some(b'+') => (1, Sign::Positive),
+                Some(b'-') => (1, Sign::Negative),
+                _ if grammar.contains(NumberGrammar::REQUIRED_EXPONENT_SIGN) => {
+                    return (F::ZERO, index + 1, ErrorCode::MissingExponentSign);
+                },
+                _ => (0, Sign::Positive),
+            };
+            index += 1 + sign_bytes;
+            let (exponent, len) = parse_exponent_digits(&index!(bytes[index..]));
+            if len == 0 && grammar.contains(NumberGrammar::REQUIRED_EXPONENT_DIGITS) {
+                return (F::ZERO, index, ErrorCode::EmptyExponent);
+            }
+            index += len;
+            fp.exp = match exponent_sign {
+                Sign::Positive => fp.exp.saturating_add(exponent),
+                Sign::Negative => fp.exp.saturating_sub(exponent),
+            };
+        }
+    }
+
+    // Export the float, using the sticky bit for correct rounding.
+    let value = match fp.mant.is_zero() {
+        true  => F::ZERO,
+        false => {
+            if truncated {
+                fp.mant |= M::ONE;
+            }
+            fp.exp = fp.exp.max(-MAX_EXPONENT).min(MAX_EXPONENT);
+            fp.into_rounded_float::<F>(options.rounding(), sign)
+        },
+    };
+
+    (value, index, ErrorCode::Success)
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_atof<F>(s: &str, tup: (F, usize, ErrorCode))
+        where F: FloatRounding<u64>
+    {
+        let options = ParseOptions::new();
+        let grammar = NumberGrammar::STANDARD;
+        assert_eq!(atof::<F, u8>(s.as_bytes(), Sign::Positive, grammar, &options), tup);
+    }
+
+    #[test]
+    fn atof_test() {
+        check_atof::<f64>("1.8p+3", (12.0, 6, ErrorCode::Success));
+        check_atof::<f64>("1.8P3", (12.0, 5, ErrorCode::Success));
+        check_atof::<f64>("1p-2", (0.25, 4, ErrorCode::Success));
+        check_atof::<f64>("A", (10.0, 1, ErrorCode::Success));
+        check_atof::<f64>("a.", (10.0, 2, ErrorCode::Success));
+        check_atof::<f64>(".8", (0.5, 2, ErrorCode::Success));
+        check_atof::<f64>("0", (0.0, 1, ErrorCode::Success));
+        check_atof::<f64>("0.000p+100", (0.0, 10, ErrorCode::Success));
+        check_atof::<f64>("00001.0000p0", (1.0, 12, ErrorCode::Success));
+        check_atof::<f64>("1.fffffffffffffp+1023", (f64::MAX, 21, ErrorCode::Success));
+        check_atof::<f64>("1p+1024", (f64::INFINITY, 7, ErrorCode::Success));
+        check_atof::<f64>("1p99999999999", (f64::INFINITY, 13, ErrorCode::Success));
+        check_atof::<f64>("1p-99999999999", (0.0, 14, ErrorCode::Success));
+        check_atof::<f32>("1.fffffep+127", (f32::MAX, 13, ErrorCode::Success));
+        check_atof::<f32>("1.8p+3", (12.0, 6, ErrorCode::Success));
+
+        // Trailing data.
+        check_atof::<f64>("1.8p+3x", (12.0, 6, ErrorCode::Success));
+        check_atof::<f64>("1g", (1.0, 1, ErrorCode::Success));
+    }
+
+    #[test]
+    fn atof_subnormal_test() {
+        check_atof::<f64>("1p-1074", (5e-324, 7, ErrorCode::Success));
+        check_atof::<f64>("0.0000000000001p-1022", (5e-324, 21, ErrorCode::Success));
+        check_atof::<f64>("0.fffffffffffffp-1022", (2.225073858507201e-308, 21, ErrorCode::Success));
+        check_atof::<f64>("1p-1075", (0.0, 7, ErrorCode::Success));
+        check_atof::<f64>("1.8p-1075", (5e-324, 9, ErrorCode::Success));
+        check_atof::<f32>("1p-149", (1e-45, 6, ErrorCode::Success));
+        check_atof::<f32>("1p-150", (0.0, 6, ErrorCode::Success));
+    }
+
+    #[test]
+    fn atof_rounding_test() {
+        // Halfway, round-nearest, tie-even.
+        check_atof::<f64>("1.00000000000008p0", (1.0, 18, ErrorCode::Success));
+        check_atof::<f64>("1.00000000000018p0", (1.0000000000000004, 18, ErrorCode::Success));
+        check_atof::<f32>("1.000001p0", (1.0, 10, ErrorCode::Success));
+        check_atof::<f32>("1.000003p0", (1.0000002, 10, ErrorCode::Success));
+
+        // Above halfway, only from the truncated digits.
+        check_atof::<f64>("1.000000000000080000000000000001p0", (1.0000000000000002, 34, ErrorCode::Success));
+        check_atof::<f64>("1000000000000080000000000000000.1p-120", (1.0000000000000002, 38, ErrorCode::Success));
+        check_atof::<f64>("1000000000000080000000000000000.0p-120", (1.0, 38, ErrorCode::Success));
+    }
+
+    #[cfg(all(has_i128, feature = "correct"))]
+    #[test]
+    fn atoq_test() {
+        let options = ParseOptions::new();
+        let grammar = NumberGrammar::STANDARD;
+        let check = |s: &str, bits: u128, len: usize| {
+            let (float, count, code) = atoq(s.as_bytes(), Sign::Positive, grammar, &options);
+            assert_eq!((float.to_bits(), count, code), (bits, len, ErrorCode::Success));
+        };
+        check("1.8p+3", 0x40028000000000000000000000000000, 6);
+        check("1.ffffffffffffffffffffffffffffp+16383", 0x7FFEFFFFFFFFFFFFFFFFFFFFFFFFFFFF, 37);
+        check("1p+16384", 0x7FFF0000000000000000000000000000, 8);
+        check("1p-16494", 1, 8);
+        check("1p-16495", 0, 8);
+
+        // Halfway, round-nearest, tie-even, and above halfway from the
+        // truncated digits.
+        check("1.00000000000000000000000000008p0", 0x3FFF0000000000000000000000000000, 33);
+        check("1.00000000000000000000000000018p0", 0x3FFF0000000000000000000000000002, 33);
+        check("1.000000000000000000000000000080000001p0", 0x3FFF0000000000000000000000000001, 40);
+    }
+
+    #[test]
+    fn atof_grammar_test() {
+        check_atof::<f64>("", (0.0, 0, ErrorCode::EmptyMantissa));
+        check_atof::<f64>(".", (0.0, 0, ErrorCode::EmptyMantissa));
+        check_atof::<f64>(".p1", (0.0, 0, ErrorCode::EmptyMantissa));
+        check_atof::<f64>("p1", (0.0, 0, ErrorCode::EmptyMantissa));
+        check_atof::<f64>("1p", (0.0, 2, ErrorCode::EmptyExponent));
+        check_atof::<f64>("1p-", (0.0, 3, ErrorCode::EmptyExponent));
+
+        let options = ParseOptions::new();
+        let grammar = NumberGrammar::REQUIRED_DIGITS | NumberGrammar::NO_POSITIVE_EXPONENT_SIGN;
+        assert_eq!(atof::<f64, u8>(b".8p1", Sign::Positive, grammar, &options), (0.0, 0, ErrorCode::EmptyInteger));
+        assert_eq!(atof::<f64, u8>(b"1.p1", Sign::Positive, grammar, &options), (0.0, 2, ErrorCode::EmptyFraction));
+        assert_eq!(atof::<f64, u8>(b"1.8p+1", Sign::Positive, grammar, &options), (0.0, 4, ErrorCode::InvalidPositiveExponentSign));
+        assert_eq!(atof::<f64, u8>(b"1.8p1", Sign::Positive, grammar, &options), (3.0, 5, ErrorCode::Success));
+
+        let grammar = NumberGrammar::REQUIRED_EXPONENT_SIGN;
+        assert_eq!(atof::<f64, u8>(b"1p1", Sign::Positive, grammar, &options), (0.0, 2, ErrorCode::MissingExponentSign));
+        assert_eq!(atof::<f64, u8>(b"1p", Sign::Positive, grammar, &options), (0.0, 2, ErrorCode::MissingExponentSign));
+
+        let grammar = NumberGrammar::default();
+        assert_eq!(atof::<f64, u8>(b"1p", Sign::Positive, grammar, &options), (1.0, 2, ErrorCode::Success));
+        assert_eq!(atof::<f64, u8>(b"1p-x", Sign::Positive, grammar, &options), (1.0, 3, ErrorCode::Success));
+    }
+}
@@ -1,6 +1,7 @@
 //! Lossy algorithms for string-to-float conversions.
 
 use atoi;
+use float::FloatRounding;
 use util::*;
 use super::exponent::parse_exponent;
 
@@ -12,8 +13,8 @@ type Wrapped = WrappedFloat<f64>;
 ///
 /// Use a float since for large numbers, this may even overflow a u64.
 #[inline]
-fn parse_integer<'a>(radix: u32, bytes: &'a [u8])
-    -> (f64, &'a [u8])
+fn parse_integer<'a, C: CodeUnit>(radix: u32, bytes: &'a [C])
+    -> (f64, &'a [C])
 {
     // Trim leading zeros, since we haven't parsed anything yet.
     let bytes = ltrim_char_slice(bytes, b'0').0;
@@ -32,12 +33,12 @@ fn parse_integer<'a>(radix: u32, bytes: &'a [u8])
 /// values for each may be too small to change the integer components
 /// representation **immediately**.
 #[inline]
-fn parse_fraction<'a>(radix: u32, bytes: &'a [u8])
-    -> (f64, &'a [u8])
+fn parse_fraction<'a, C: CodeUnit>(radix: u32, bytes: &'a [C], decimal_point: u8)
+    -> (f64, &'a [C])
 {
     // Ensure if there's a decimal, there are trailing values, so
     // invalid floats like "0." lead to an error.
-    if Some(&b'.') == bytes.get(0) {
+    if Some(decimal_point) == byte_at(bytes, 0) {
         // We know this must be true, since we just got the first value.
         let mut bytes = &index!(bytes[1..]);
         let first = bytes.as_ptr();
@@ -65,7 +66,7 @@ fn parse_fraction<'a>(radix: u32, bytes: &'a [u8])
             }
 
             // do/while condition
-            if char_to_digit(*bytes.get(0).unwrap_or(&b'\0')).as_u32() >= radix {
+            if char_to_digit(byte_at(bytes, 0).unwrap_or(b'\0')).as_u32() >= radix {
                 break;
             }
         }
@@ -80,26 +81,73 @@ fn parse_fraction<'a>(radix: u32, bytes: &'a [u8])
 ///
 /// The float string must be non-special, non-zero, and positive.
 #[inline]
-fn parse_mantissa<'a>(radix: u32, bytes: &'a [u8])
-    -> (f64, &'a [u8])
+fn parse_mantissa<'a, C: CodeUnit>(radix: u32, bytes: &'a [C], decimal_point: u8)
+    -> (f64, &'a [C])
 {
     let (integer, bytes) = parse_integer(radix, bytes);
-    let (fraction, bytes) = parse_fraction(radix, bytes);
+    let (fraction, bytes) = parse_fraction(radix, bytes, decimal_point);
 
     (integer + fraction, bytes)
 }
 
+/// Parse the mantissa from a string with digit separators or digit grouping.
+///
+/// The float string must be non-special, non-zero, and positive.
+#[inline]
+fn parse_mantissa_separator<'a, C: CodeUnit>(radix: u32, bytes: &'a [C], options: &ParseOptions)
+    -> (f64, &'a [C])
+{
+    // Parse the integer, skipping validated digit or group separators.
+    // Digits are always alphanumeric, while separators never are.
+    let len = atoi::integer_len(radix, bytes, options);
+    let mut integer = Wrapped::ZERO;
+    for digits in index!(bytes[..len]).split(|&c| !c.to_byte().is_ascii_alphanumeric()) {
+        atoi::unchecked_positive(&mut integer, as_cast(radix), digits);
+    }
+    let bytes = &index!(bytes[len..]);
+
+    // Parse the fraction, in chunks of at most 12 digits.
+    let mut fraction: f64 = 0.;
+    let bytes = if Some(options.decimal_point()) == byte_at(bytes, 0) {
+        // We know this must be true, since we just got the first value.
+        let bytes = &index!(bytes[1..]);
+        let rules = options.digit_separator_flags().fraction_rules();
+        let len = atoi::digits_len(radix, bytes, options.separator(), rules);
+        let mut count: i32 = 0;
+        for digits in index!(bytes[..len]).split(|&c| !c.to_byte().is_ascii_alphanumeric()) {
+            for chunk in digits.chunks(12) {
+                let mut value: u64 = 0;
+                atoi::unchecked_positive(&mut value, radix.as_u64(), chunk);
+                count = count.saturating_add(chunk.len().try_i32_or_max());
+                if value != 0 {
+                    fraction += f64::iterative_pow(value as f64, radix, -count);
+                }
+            }
+        }
+        &index!(bytes[len..])
+    } else {
+        bytes
+    };
+
+    (integer.into_inner() + fraction, bytes)
+}
+
 // PARSE
 
 /// Parse the mantissa and exponent from a string.
 ///
 /// The float string must be non-special, non-zero, and positive.
 #[inline]
-fn parse_float<'a>(radix: u32, bytes: &'a [u8])
-    -> (f64, i32, &'a [u8])
+fn parse_float<'a, C: CodeUnit>(radix: u32, bytes: &'a [C], options: &ParseOptions)
+    -> (f64, i32, &'a [C])
 {
-    let (mantissa, bytes) = parse_mantissa(radix, bytes);
-    let (exponent, bytes) = parse_exponent(radix, bytes);
+    let (mantissa, bytes) = match options.separator() {
+        None if !options.locale().is_grouped() => {
+            parse_mantissa(radix, bytes, options.decimal_point())
+        },
+        _ => parse_mantissa_separator(radix, bytes, options),
+    };
+    let (exponent, bytes) = parse_exponent(radix, bytes, options);
 
     (mantissa, exponent, bytes)
 }
@@ -110,10 +158,10 @@ fn parse_float<'a>(radix: u32, bytes: &'a [u8])
 ///
 /// The float string must be non-special, non-zero, and positive.
 #[inline]
-pub(crate) fn atof(radix: u32, bytes: &[u8], sign: Sign)
+pub(crate) fn atof<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
     -> (f32, usize)
 {
-    let (value, len) = atod(radix, bytes, sign);
+    let (value, len) = atod(radix, bytes, sign, options);
     (value as f32, len)
 }
 
@@ -121,10 +169,10 @@ pub(crate) fn atof(radix: u32, bytes: &[u8], sign: Sign)
 ///
 /// The float string must be non-special, non-zero, and positive.
 #[inline]
-pub(crate) fn atod(radix: u32, bytes: &[u8], _: Sign)
+pub(crate) fn atod<C: CodeUnit>(radix: u32, bytes: &[C], _: Sign, options: &ParseOptions)
     -> (f64, usize)
 {
-    let (mut value, exponent, slc) = parse_float(radix, bytes);
+    let (mut value, exponent, slc) = parse_float(radix, bytes, options);
     if exponent != 0 && value != 0.0 {
         value = value.iterative_pow(radix, exponent);
     }
@@ -132,17 +180,39 @@ pub(crate) fn atod(radix: u32, bytes: &[u8], _: Sign)
 }
 
 #[inline]
-pub(crate) fn atof_lossy(radix: u32, bytes: &[u8], sign: Sign)
+pub(crate) fn atof_lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
     -> (f32, usize)
 {
-    atof(radix, bytes, sign)
+    atof(radix, bytes, sign, options)
 }
 
 #[inline]
-pub(crate) fn atod_lossy(radix: u32, bytes: &[u8], sign: Sign)
+pub(crate) fn atod_lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
     -> (f64, usize)
 {
-    atod(radix, bytes, sign)
+    atod(radix, bytes, sign, options)
+}
+
+/// Parse a float narrower than a 32-bit float from string.
+///
+/// The float string must be non-special, non-zero, and positive.
+#[inline]
+pub(crate) fn atoh<F, C>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
+    -> (F, usize)
+    where F: FloatRounding<u64>,
+          C: CodeUnit
+{
+    let (value, len) = atod(radix, bytes, sign, options);
+    (narrow_f64(value, RoundingKind::NearestTieEven), len)
+}
+
+#[inline]
+pub(crate) fn atoh_lossy<F, C>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
+    -> (F, usize)
+    where F: FloatRounding<u64>,
+          C: CodeUnit
+{
+    atoh(radix, bytes, sign, options)
 }
 
 // TESTS
@@ -166,7 +236,7 @@ mod tests {
     }
 
     fn check_parse_fraction(radix: u32, s: &str, tup: (f64, usize)) {
-        let (value, slc) = parse_fraction(radix, s.as_bytes());
+        let (value, slc) = parse_fraction(radix, s.as_bytes(), b'.');
         assert_eq!(value, tup.0);
         assert_eq!(s.len() - slc.len(), tup.1);
     }
@@ -179,7 +249,7 @@ mod tests {
     }
 
     fn check_parse_mantissa(radix: u32, s: &str, tup: (f64, usize)) {
-        let (value, slc) = parse_mantissa(radix, s.as_bytes());
+        let (value, slc) = parse_mantissa(radix, s.as_bytes(), b'.');
         assert_eq!(value, tup.0);
         assert_eq!(s.len() - slc.len(), tup.1);
     }
@@ -192,7 +262,7 @@ mod tests {
     }
 
     fn check_parse_float(radix: u32, s: &str, tup: (f64, i32, usize)) {
-        let (value, exponent, slc) = parse_float(radix, s.as_bytes());
+        let (value, exponent, slc) = parse_float(radix, s.as_bytes(), &ParseOptions::new());
         assert_eq!(value, tup.0);
         assert_eq!(exponent, tup.1);
         assert_eq!(s.len() - slc.len(), tup.2);
@@ -207,7 +277,7 @@ mod tests {
     }
 
     fn check_atof(radix: u32, s: &str, tup: (f32, usize)) {
-        let (value, len) = atof(radix, s.as_bytes(), Sign::Positive);
+        let (value, len) = atof(radix, s.as_bytes(), Sign::Positive, &ParseOptions::new());
         assert_eq!(value, tup.0);
         assert_eq!(len, tup.1);
     }
@@ -221,7 +291,7 @@ mod tests {
     }
 
     fn check_atod(radix: u32, s: &str, tup: (f64, usize)) {
-        let (value, len) = atod(radix, s.as_bytes(), Sign::Positive);
+        let (value, len) = atod(radix, s.as_bytes(), Sign::Positive, &ParseOptions::new());
         assert_eq!(value, tup.0);
         assert_eq!(len, tup.1);
     }
@@ -239,7 +309,7 @@ mod tests {
     // correct feature. Use the same tests.
 
     fn check_atof_lossy(radix: u32, s: &str, tup: (f32, usize)) {
-        let (value, len) = atof_lossy(radix, s.as_bytes(), Sign::Positive);
+        let (value, len) = atof_lossy(radix, s.as_bytes(), Sign::Positive, &ParseOptions::new());
         assert_f32_eq!(value, tup.0);
         assert_eq!(len, tup.1);
     }
@@ -253,7 +323,7 @@ mod tests {
     }
 
     fn check_atod_lossy(radix: u32, s: &str, tup: (f64, usize)) {
-        let (value, len) = atod_lossy(radix, s.as_bytes(), Sign::Positive);
+        let (value, len) = atod_lossy(radix, s.as_bytes(), Sign::Positive, &ParseOptions::new());
         assert_f64_eq!(value, tup.0);
         assert_eq!(len, tup.1);
     }
@@ -0,0 +1,256 @@
+//! Eisel-Lemire algorithm for decimal strings.
+//!
+//! Computes the correctly-rounded float from a 64-bit decimal mantissa
+//! and a decimal exponent, using the product of the mantissa with a
+//! 128-bit approximation of the power of five. The product is exact
+//! enough to determine the rounded float, unless the value is extremely
+//! close to halfway between two floats, in which case the algorithm
+//! reports the value as ambiguous, and the caller must compare the
+//! digits to the halfway point.
+//!
+//! This implementation is based off of the Rust port of fast_float,
+//! which is in turn based off of Daniel Lemire's paper, "Number Parsing
+//! at a Gigabyte per Second":
+//!     https://arxiv.org/abs/2101.11408
+//!     https://github.com/fastfloat/fast_float
+
+use util::*;
+use super::power_of_five::*;
+
+// FLOAT
+
+/// Float with the limits for the Eisel-Lemire algorithm.
+pub(super) trait EiselLemire: Float {
+    /// Smallest decimal exponent for a non-zero value.
+    const SMALLEST_POWER_OF_TEN: i32;
+    /// Largest decimal exponent for a non-infinite value.
+    const LARGEST_POWER_OF_TEN: i32;
+    /// Smallest decimal exponent where a product may be exactly halfway.
+    const MIN_EXPONENT_ROUND_TO_EVEN: i32;
+    /// Largest decimal exponent where a product may be exactly halfway.
+    const MAX_EXPONENT_ROUND_TO_EVEN: i32;
+}
+
+impl EiselLemire for f32 {
+    const SMALLEST_POWER_OF_TEN: i32 = -65;
+    const LARGEST_POWER_OF_TEN: i32 = 38;
+    const MIN_EXPONENT_ROUND_TO_EVEN: i32 = -17;
+    const MAX_EXPONENT_ROUND_TO_EVEN: i32 = 10;
+}
+
+impl EiselLemire for f64 {
+    const SMALLEST_POWER_OF_TEN: i32 = -342;
+    const LARGEST_POWER_OF_TEN: i32 = 308;
+    const MIN_EXPONENT_ROUND_TO_EVEN: i32 = -4;
+    const MAX_EXPONENT_ROUND_TO_EVEN: i32 = 23;
+}
+
+// MULTIPLICATION
+
+/// Multiply two 64-bit integers, returning the low and high 64 bits.
+#[cfg(has_i128)]
+#[inline]
+fn full_multiplication(a: u64, b: u64) -> (u64, u64) {
+    let r = (a as u128) * (b as u128);
+    (r as u64, (r >> 64) as u64)
+}
+
+/// Multiply two 64-bit integers, returning the low and high 64 bits.
+#[cfg(not(has_i128))]
+#[inline]
+fn full_multiplication(a: u64, b: u64) -> (u64, u64) {
+    let (a_hi, a_lo) = (a >> 32, a & 0xFFFFFFFF);
+    let (b_hi, b_lo) = (b >> 32, b & 0xFFFFFFFF);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+    let cross = (lo_lo >> 32) + (hi_lo & 0xFFFFFFFF) + lo_hi;
+    let hi = hi_hi + (hi_lo >> 32) + (cross >> 32);
+    let lo = (cross << 32) | (lo_lo & 0xFFFFFFFF);
+    (lo, hi)
+}
+
+/// Compute the product of the normalized mantissa with the power of five.
+///
+/// Only multiplies by the low 64 bits of the power of five if the high
+/// bits of the product needed for `precision` bits may be inexact.
+/// Returns the low and high 64 bits of the truncated product.
+#[inline]
+fn compute_product_approx(q: i32, w: u64, precision: i32) -> (u64, u64) {
+    debug_assert!(q >= SMALLEST_POWER_OF_FIVE && q <= LARGEST_POWER_OF_FIVE);
+    debug_assert!(precision > 0 && precision < 64);
+
+    let mask = 0xFFFFFFFFFFFFFFFF >> precision;
+    let (hi5, lo5) = index!(POWER_OF_FIVE_128[(q - SMALLEST_POWER_OF_FIVE) as usize]);
+    let (mut first_lo, mut first_hi) = full_multiplication(w, hi5);
+    if first_hi & mask == mask {
+        let (_, second_hi) = full_multiplication(w, lo5);
+        first_lo = first_lo.wrapping_add(second_hi);
+        if second_hi > first_lo {
+            first_hi += 1;
+        }
+    }
+    (first_lo, first_hi)
+}
+
+/// Calculate the binary exponent of 10^q, approximately `q * log2(10)`.
+#[inline]
+fn power(q: i32) -> i32 {
+    (q.wrapping_mul(152170 + 65536) >> 16) + 63
+}
+
+// ALGORITHM
+
+/// Create a float from the biased binary exponent and the mantissa bits.
+#[inline]
+fn to_float<F: Float>(power2: i32, mantissa: u64) -> F {
+    let bits = ((power2 as u64) << F::MANTISSA_SIZE) | mantissa;
+    F::from_bits(as_cast(bits))
+}
+
+/// Compute the correctly-rounded float from the decimal mantissa and exponent.
+///
+/// Rounds to nearest, with ties to even. Returns None if the value is too
+/// close to halfway between two floats to determine the correct float.
+pub(super) fn eisel_lemire<F: EiselLemire>(mut w: u64, q: i32) -> Option<F> {
+    let mantissa_size = F::MANTISSA_SIZE;
+    let infinite_power = F::MAX_EXPONENT + F::EXPONENT_BIAS;
+    let minimum_exponent = F::MANTISSA_SIZE - F::EXPONENT_BIAS;
+
+    if w == 0 || q < F::SMALLEST_POWER_OF_TEN {
+        return Some(F::ZERO);
+    } else if q > F::LARGEST_POWER_OF_TEN {
+        return Some(F::INFINITY);
+    }
+
+    // Normalize the mantissa, and multiply by the power of five, which
+    // gives the product with at least `mantissa_size + 3` exact bits.
+    let lz = w.leading_zeros() as i32;
+    w <<= lz;
+    let (lo, hi) = compute_product_approx(q, w, mantissa_size + 3);
+    if lo == 0xFFFFFFFFFFFFFFFF && (q < -27 || q > 55) {
+        // The truncated product may be below a halfway point, while the
+        // exact product is above it. For exponents in `[-27, 55]`, the
+        // power of five is exact, or the product cannot be halfway.
+        return None;
+    }
+
+    // Shift the product to `mantissa_size + 2` bits: the hidden bit,
+    // the mantissa, and the rounding bit.
+    let upperbit = (hi >> 63) as i32;
+    let mut mantissa = hi >> (upperbit + 64 - mantissa_size - 3);
+    let mut power2 = power(q) + upperbit - lz - minimum_exponent;
+    if power2 <= 0 {
+        // Denormal float, shift the mantissa to the denormal exponent,
+        // and round.
+        if -power2 + 1 >= 64 {
+            return Some(F::ZERO);
+        }
+        mantissa >>= -power2 + 1;
+        mantissa += mantissa & 1;
+        mantissa >>= 1;
+        // Rounding up may create a normal float, with a biased exponent of 1.
+        power2 = (mantissa >= (1u64 << mantissa_size)) as i32;
+        return Some(to_float(power2, mantissa & !(1u64 << mantissa_size)));
+    }
+
+    // Check for an exact halfway product, which must round down to even.
+    // The product is only exact for small exponents, otherwise, a power
+    // of five cannot divide the mantissa.
+    if lo <= 1
+        && q >= F::MIN_EXPONENT_ROUND_TO_EVEN
+        && q <= F::MAX_EXPONENT_ROUND_TO_EVEN
+        && mantissa & 3 == 1
+        && (mantissa << (upperbit + 64 - mantissa_size - 3)) == hi
+    {
+        mantissa &= !1u64;
+    }
+
+    // Round to nearest, and handle the carry into the next exponent.
+    mantissa += mantissa & 1;
+    mantissa >>= 1;
+    if mantissa >= (2u64 << mantissa_size) {
+        mantissa = 1u64 << mantissa_size;
+        power2 += 1;
+    }
+    mantissa &= !(1u64 << mantissa_size);
+    if power2 >= infinite_power {
+        return Some(F::INFINITY);
+    }
+    Some(to_float(power2, mantissa))
+}
+
+/// Compute the correctly-rounded float from a truncated decimal mantissa.
+///
+/// The digits are between `w` and `w + 1`, so if both round to the same
+/// float, it is the correctly-rounded float.
+#[inline]
+pub(super) fn eisel_lemire_truncated<F: EiselLemire>(w: u64, q: i32) -> Option<F> {
+    let lower = eisel_lemire::<F>(w, q);
+    let upper = w.checked_add(1).and_then(|w| eisel_lemire::<F>(w, q));
+    match lower.is_some() && lower == upper {
+        true  => lower,
+        false => None,
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_multiplication_test() {
+        assert_eq!(full_multiplication(0, 0), (0, 0));
+        assert_eq!(full_multiplication(1 << 32, 1 << 32), (0, 1));
+        assert_eq!(full_multiplication(0xFFFFFFFFFFFFFFFF, 2), (0xFFFFFFFFFFFFFFFE, 1));
+        assert_eq!(full_multiplication(0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF), (1, 0xFFFFFFFFFFFFFFFE));
+    }
+
+    #[test]
+    fn power_of_five_test() {
+        assert_eq!(POWER_OF_FIVE_128.len(), (LARGEST_POWER_OF_FIVE - SMALLEST_POWER_OF_FIVE + 1) as usize);
+        let index = |q: i32| (q - SMALLEST_POWER_OF_FIVE) as usize;
+        assert_eq!(POWER_OF_FIVE_128[index(0)], (0x8000000000000000, 0));
+        assert_eq!(POWER_OF_FIVE_128[index(1)], (0xA000000000000000, 0));
+        assert_eq!(POWER_OF_FIVE_128[index(-1)], (0xCCCCCCCCCCCCCCCC, 0xCCCCCCCCCCCCCCCD));
+    }
+
+    #[test]
+    fn eisel_lemire_f32_test() {
+        assert_eq!(eisel_lemire::<f32>(1, 0), Some(1.0));
+        assert_eq!(eisel_lemire::<f32>(15, -1), Some(1.5));
+        assert_eq!(eisel_lemire::<f32>(33554431, 0), Some(33554432.0));
+        assert_eq!(eisel_lemire::<f32>(34028235, 31), Some(3.4028235e38));
+        assert_eq!(eisel_lemire::<f32>(34028236, 31), Some(f32::INFINITY));
+        assert_eq!(eisel_lemire::<f32>(1, -45), Some(1e-45));
+        assert_eq!(eisel_lemire::<f32>(1, -46), Some(0.0));
+    }
+
+    #[test]
+    fn eisel_lemire_f64_test() {
+        assert_eq!(eisel_lemire::<f64>(1, 0), Some(1.0));
+        assert_eq!(eisel_lemire::<f64>(123456789, -4), Some(12345.6789));
+        assert_eq!(eisel_lemire::<f64>(17976931348623157, 292), Some(1.7976931348623157e308));
+        assert_eq!(eisel_lemire::<f64>(17976931348623159, 292), Some(f64::INFINITY));
+        assert_eq!(eisel_lemire::<f64>(22250738585072014, -324), Some(2.2250738585072014e-308));
+        assert_eq!(eisel_lemire::<f64>(5, -324), Some(5e-324));
+        assert_eq!(eisel_lemire::<f64>(2, -324), Some(0.0));
+        assert_eq!(eisel_lemire::<f64>(1, 309), Some(f64::INFINITY));
+
+        // Exactly halfway, round down to even.
+        assert_eq!(eisel_lemire::<f64>(9007199254740993, 0), Some(9007199254740992.0));
+        assert_eq!(eisel_lemire::<f64>(9007199254740995, 0), Some(9007199254740996.0));
+    }
+
+    #[test]
+    fn eisel_lemire_truncated_test() {
+        assert_eq!(eisel_lemire_truncated::<f64>(9007199254740992, 0), Some(9007199254740992.0));
+        assert_eq!(eisel_lemire_truncated::<f64>(9007199254740993, 0), None);
+        assert_eq!(eisel_lemire_truncated::<f64>(12345678901234567890, -19), Some(1.2345678901234567));
+        assert_eq!(eisel_lemire_truncated::<f64>(0xFFFFFFFFFFFFFFFF, 0), None);
+    }
+}
@@ -12,7 +12,9 @@ mod bigint;
 mod cached;
 mod cached_float80;
 mod large_powers;
+mod lemire;
 mod math;
+mod power_of_five;
 mod small_powers;
 
 #[cfg(has_i128)]
@@ -34,8 +36,21 @@ mod small_powers_64;
 
 
 // Export algorithms.
+pub(crate) mod hex;
+
 #[cfg(feature = "correct")]
 pub(crate) mod correct;
 
-#[cfg(not(feature = "correct"))]
+#[cfg(all(has_i128, feature = "correct"))]
+pub(crate) mod quad;
+
+#[cfg(feature = "correct")]
+pub(crate) use self::bigint::decimal_digits;
+
+#[cfg(feature = "correct")]
+pub(crate) use self::bigint::radix_digits;
+
+#[cfg(all(has_i128, feature = "correct"))]
+pub(crate) use self::bigint::{quad_decimal_digits, quad_radix_digits};
+
 pub(crate) mod incorrect;
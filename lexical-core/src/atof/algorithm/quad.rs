@@ -0,0 +1,183 @@
+//! Correct algorithm to parse quad floats from strings.
+//!
+//! A quad float has too much precision for the moderate path to be
+//! useful, so any value which cannot be represented exactly by the
+//! native mantissa is calculated from the ratio of big integers. The
+//! numerator is scaled so the quotient has more bits than the float,
+//! and the remainder collapses into a sticky bit, which is enough to
+//! correctly round the quotient to the float.
+
+use float::*;
+use util::*;
+use super::bigint::*;
+use super::correct::{parse_float, FloatSlice};
+use super::math::*;
+
+// FAST
+
+/// Convert the parsed mantissa to the float if it is exact.
+///
+/// This requires the mantissa to have no truncated digits, and either
+/// the radix to be a power of 2, or the value to be an integer which
+/// fits in the native mantissa.
+#[inline]
+fn fast_path(mantissa: u128, radix: u32, exponent: i32, kind: RoundingKind)
+    -> Option<f128>
+{
+    let mut mant = mantissa;
+    let exp = if radix.is_power_of_two() {
+        // Any power of 2 only affects the exponent.
+        exponent.saturating_mul(radix.trailing_zeros().as_i32())
+    } else if exponent >= 0 {
+        for _ in 0..exponent {
+            mant = mant.checked_mul(radix.as_u128())?;
+        }
+        0
+    } else {
+        return None;
+    };
+
+    let fp = ExtendedFloat { mant, exp };
+    Some(fp.into_rounded_float_impl(kind))
+}
+
+// SLOW
+
+/// Calculate the float from the ratio of big integers.
+#[inline]
+fn slow_path<C: CodeUnit>(slc: FloatSlice<C>, radix: u32, kind: RoundingKind)
+    -> f128
+{
+    let max_digits = unwrap_or_max(max_digits::<f128>(radix));
+    let count = max_digits.min(slc.mantissa_digits());
+    let sci_exp = slc.scientific_exponent();
+    let exponent = sci_exp + 1 - count.as_i32();
+
+    // Check the value is not far out of range, since the big integers
+    // are only large enough for the range of the float. The value is
+    // in `[radix^sci_exp, radix^(sci_exp+1))`, and truncating the log2
+    // of the radix only makes either bound closer to 0.
+    let log2 = 31 - radix.leading_zeros().as_i32();
+    if sci_exp.saturating_mul(log2) >= f128::MAX_EXPONENT + f128::MANTISSA_SIZE {
+        return f128::INFINITY;
+    } else if (sci_exp + 1).saturating_mul(log2) < f128::DENORMAL_EXPONENT - 1 {
+        return f128::ZERO;
+    }
+
+    // Calculate the high 128 bits of the value, and if any lower bits
+    // are truncated.
+    let mut num = parse_quad_mantissa(slc, radix, max_digits);
+    let (mant, exp, is_truncated) = if exponent >= 0 {
+        num.imul_power(radix, exponent.as_u32());
+        let (mant, is_truncated) = num.hi128();
+        let exp = num.bit_length().as_i32() - 128;
+        (mant, exp, is_truncated)
+    } else {
+        // Remove the factors of 2 from the radix, since they only
+        // affect the binary exponent.
+        let n = exponent.wrapping_neg().as_u32();
+        let pow2 = radix.trailing_zeros();
+        let odd = radix >> pow2;
+        let mut den = QuadBigint::from_u64(1);
+        if odd != 1 {
+            den.imul_power(odd, n);
+        }
+
+        // Shift the numerator so the quotient has at least 129 bits.
+        let shift = (den.bit_length() + 130).saturating_sub(num.bit_length());
+        num.ishl(shift);
+        let rem = num.idiv_large(&den);
+        let (mant, is_truncated) = num.hi128();
+        let exp = num.bit_length().as_i32() - 128 - shift.as_i32() - (n * pow2).as_i32();
+        (mant, exp, is_truncated || !rem.is_zero())
+    };
+
+    // Collapse the truncated bits into the lowest bit, which is always
+    // below the rounding bits.
+    let fp = ExtendedFloat { mant: mant | is_truncated as u128, exp };
+    fp.into_rounded_float_impl(kind)
+}
+
+// ATOF
+
+/// Parse 128-bit float from string.
+///
+/// The float string must be non-special, non-zero, and positive.
+pub(crate) fn atoq<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions)
+    -> (f128, usize)
+{
+    let (mantissa, slc, rest, _) = parse_float::<u128, C>(radix, bytes, options);
+    let len = bytes.len() - rest.len();
+    let exponent = slc.mantissa_exponent();
+    let kind = internal_rounding(options.rounding(), sign);
+
+    let float = if mantissa == 0 {
+        // Literal 0, return early.
+        // Value cannot be truncated, since we discard leading 0s.
+        f128::ZERO
+    } else if exponent > 0x40000000 {
+        // Extremely large exponent, will always be infinity.
+        f128::INFINITY
+    } else if exponent < -0x40000000 {
+        // Extremely small exponent, will always be zero.
+        f128::ZERO
+    } else if slc.truncated_digits().is_zero() {
+        match fast_path(mantissa, radix, exponent, kind) {
+            Some(float) => float,
+            None        => slow_path(slc, radix, kind),
+        }
+    } else {
+        slow_path(slc, radix, kind)
+    };
+    (float, len)
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_atoq(radix: u32, s: &str, bits: u128) {
+        let options = ParseOptions::new();
+        let (float, len) = atoq(radix, s.as_bytes(), Sign::Positive, &options);
+        assert_eq!(float.to_bits(), bits, "{}", s);
+        assert_eq!(len, s.len());
+    }
+
+    #[test]
+    fn atoq_test() {
+        // Fast path.
+        check_atoq(10, "1", 0x3FFF0000000000000000000000000000);
+        check_atoq(10, "1.5", 0x3FFF8000000000000000000000000000);
+        check_atoq(10, "1e30", 0x406293E5939A08CE9DBD480000000000);
+        check_atoq(10, "0", 0);
+
+        // Slow path, exact and rounded.
+        check_atoq(10, "0.1", 0x3FFB999999999999999999999999999A);
+        check_atoq(10, "0.3333333333333333333333333333333333", 0x3FFD5555555555555555555555555555);
+        check_atoq(10, "1e100", 0x414B249AD2594C37CEB0B2784C4CE0BF);
+        check_atoq(10, "1e-100", 0x3EB2BFF2EE48E052FD7AB2F0FC572779);
+        check_atoq(10, "1.18973149535723176508575932662800702e4932", 0x7FFEFFFFFFFFFFFFFFFFFFFFFFFFFFFF);
+        check_atoq(10, "1.2e4932", 0x7FFF0000000000000000000000000000);
+        check_atoq(10, "6.4751751194380251109244389582276466e-4966", 1);
+        check_atoq(10, "3.2e-4966", 0);
+        check_atoq(10, "3.3e-4966", 1);
+        check_atoq(10, "1e-5000", 0);
+
+        // Halfway between 1 and the next float, rounding to even,
+        // and just above halfway.
+        check_atoq(10, "1.00000000000000000000000000000000009629649721936179265279889712924636592690508241076940976199693977832794189453125", 0x3FFF0000000000000000000000000000);
+        check_atoq(10, "1.000000000000000000000000000000000096296497219361792652798897129246365926905082410769409761996939778327941894531250000000000000001", 0x3FFF0000000000000000000000000001);
+    }
+
+    #[cfg(feature = "radix")]
+    #[test]
+    fn atoq_radix_test() {
+        check_atoq(2, "1.1", 0x3FFF8000000000000000000000000000);
+        check_atoq(16, "1.8", 0x3FFF8000000000000000000000000000);
+        check_atoq(3, "0.1", 0x3FFD5555555555555555555555555555);
+        check_atoq(6, "0.3", 0x3FFE0000000000000000000000000000);
+    }
+}
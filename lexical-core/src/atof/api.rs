@@ -2,48 +2,137 @@
 //!
 //! Uses either the imprecise or the precise algorithm.
 
+use atoi;
 use util::*;
 
-// Select the back-end
-cfg_if! {
-if #[cfg(feature = "correct")] {
-    use super::algorithm::correct as algorithm;
-} else {
-    use super::algorithm::incorrect as algorithm;
-}}  // cfg_if
+use super::algorithm::incorrect;
+
+#[cfg(feature = "correct")]
+use super::algorithm::correct;
+
+use super::algorithm::hex;
+
+#[cfg(all(has_i128, feature = "correct"))]
+use super::algorithm::quad;
 
 // TRAITS
 
 /// Trait to define parsing of a string to float.
 trait StringToFloat: Float {
     /// Serialize string to float, favoring correctness.
-    fn default(radix: u32, bytes: &[u8], sign: Sign) -> (Self, usize);
+    fn default<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> (Self, usize);
 
     /// Serialize string to float, prioritizing speed over correctness.
-    fn lossy(radix: u32, bytes: &[u8], sign: Sign) -> (Self, usize);
+    fn lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> (Self, usize);
+
+    /// Serialize hexadecimal float literal to float, after the `0x` prefix.
+    fn hex<C: CodeUnit>(bytes: &[C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions) -> (Self, usize, ErrorCode);
 }
 
 impl StringToFloat for f32 {
     #[inline]
-    fn default(radix: u32, bytes: &[u8], sign: Sign) -> (f32, usize) {
-        algorithm::atof(radix, bytes, sign)
+    fn default<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> (f32, usize) {
+        match options.algorithm() {
+            #[cfg(feature = "correct")]
+            ParseAlgorithm::Correct => correct::atof(radix, bytes, sign, options),
+            #[cfg(feature = "correct")]
+            ParseAlgorithm::Lossy => correct::atof_lossy(radix, bytes, sign, options),
+            ParseAlgorithm::Incorrect => incorrect::atof(radix, bytes, sign, options),
+        }
+    }
+
+    #[inline]
+    fn lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> (f32, usize) {
+        match options.algorithm() {
+            #[cfg(feature = "correct")]
+            ParseAlgorithm::Correct | ParseAlgorithm::Lossy => correct::atof_lossy(radix, bytes, sign, options),
+            ParseAlgorithm::Incorrect => incorrect::atof_lossy(radix, bytes, sign, options),
+        }
     }
 
     #[inline]
-    fn lossy(radix: u32, bytes: &[u8], sign: Sign) -> (f32, usize) {
-        algorithm::atof_lossy(radix, bytes, sign)
+    fn hex<C: CodeUnit>(bytes: &[C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions) -> (f32, usize, ErrorCode) {
+        hex::atof(bytes, sign, grammar, options)
     }
 }
 
 impl StringToFloat for f64 {
     #[inline]
-    fn default(radix: u32, bytes: &[u8], sign: Sign) -> (f64, usize) {
-        algorithm::atod(radix, bytes, sign)
+    fn default<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> (f64, usize) {
+        match options.algorithm() {
+            #[cfg(feature = "correct")]
+            ParseAlgorithm::Correct => correct::atod(radix, bytes, sign, options),
+            #[cfg(feature = "correct")]
+            ParseAlgorithm::Lossy => correct::atod_lossy(radix, bytes, sign, options),
+            ParseAlgorithm::Incorrect => incorrect::atod(radix, bytes, sign, options),
+        }
+    }
+
+    #[inline]
+    fn lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> (f64, usize) {
+        match options.algorithm() {
+            #[cfg(feature = "correct")]
+            ParseAlgorithm::Correct | ParseAlgorithm::Lossy => correct::atod_lossy(radix, bytes, sign, options),
+            ParseAlgorithm::Incorrect => incorrect::atod_lossy(radix, bytes, sign, options),
+        }
+    }
+
+    #[inline]
+    fn hex<C: CodeUnit>(bytes: &[C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions) -> (f64, usize, ErrorCode) {
+        hex::atof(bytes, sign, grammar, options)
+    }
+}
+
+/// Implement string-to-float for floats narrower than a 32-bit float.
+macro_rules! string_to_half {
+    ($($t:ty)*) => ($(
+        impl StringToFloat for $t {
+            #[inline]
+            fn default<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> ($t, usize) {
+                match options.algorithm() {
+                    #[cfg(feature = "correct")]
+                    ParseAlgorithm::Correct => correct::atoh(radix, bytes, sign, options),
+                    #[cfg(feature = "correct")]
+                    ParseAlgorithm::Lossy => correct::atoh_lossy(radix, bytes, sign, options),
+                    ParseAlgorithm::Incorrect => incorrect::atoh(radix, bytes, sign, options),
+                }
+            }
+
+            #[inline]
+            fn lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> ($t, usize) {
+                match options.algorithm() {
+                    #[cfg(feature = "correct")]
+                    ParseAlgorithm::Correct | ParseAlgorithm::Lossy => correct::atoh_lossy(radix, bytes, sign, options),
+                    ParseAlgorithm::Incorrect => incorrect::atoh_lossy(radix, bytes, sign, options),
+                }
+            }
+
+            #[inline]
+            fn hex<C: CodeUnit>(bytes: &[C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions) -> ($t, usize, ErrorCode) {
+                hex::atof(bytes, sign, grammar, options)
+            }
+        }
+    )*)
+}
+
+string_to_half! { f16 bf16 }
+
+#[cfg(all(has_i128, feature = "correct"))]
+impl StringToFloat for f128 {
+    #[inline]
+    fn default<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> (f128, usize) {
+        quad::atoq(radix, bytes, sign, options)
+    }
+
+    /// Quad floats are always parsed with the correct algorithm.
+    #[inline]
+    fn lossy<C: CodeUnit>(radix: u32, bytes: &[C], sign: Sign, options: &ParseOptions) -> (f128, usize) {
+        quad::atoq(radix, bytes, sign, options)
     }
 
     #[inline]
-    fn lossy(radix: u32, bytes: &[u8], sign: Sign) -> (f64, usize) {
-        algorithm::atod_lossy(radix, bytes, sign)
+    fn hex<C: CodeUnit>(bytes: &[C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions) -> (f128, usize, ErrorCode) {
+        hex::atoq(bytes, sign, grammar, options)
     }
 }
 
@@ -51,22 +140,22 @@ impl StringToFloat for f64 {
 // Utilities to filter special values.
 
 #[inline]
-fn is_nan(bytes: &[u8]) -> bool {
-    case_insensitive_starts_with_slice(bytes, get_nan_string())
+pub(super) fn is_nan<C: CodeUnit>(bytes: &[C], options: &ParseOptions) -> bool {
+    case_insensitive_starts_with_slice(bytes, options.nan_string())
 }
 
 #[inline]
-fn is_inf(bytes: &[u8]) -> bool {
-    case_insensitive_starts_with_slice(bytes, get_inf_string())
+pub(super) fn is_inf<C: CodeUnit>(bytes: &[C], options: &ParseOptions) -> bool {
+    case_insensitive_starts_with_slice(bytes, options.inf_string())
 }
 
 #[inline]
-fn is_infinity(bytes: &[u8]) -> bool {
-    case_insensitive_starts_with_slice(bytes, get_infinity_string())
+pub(super) fn is_infinity<C: CodeUnit>(bytes: &[C], options: &ParseOptions) -> bool {
+    case_insensitive_starts_with_slice(bytes, options.infinity_string())
 }
 
 #[inline]
-fn is_zero(bytes: &[u8]) -> bool {
+fn is_zero<C: CodeUnit>(bytes: &[C]) -> bool {
     // Ignore other variants of 0, we just want to most common literal ones.
     match bytes.len() {
         1 => equal_to_slice(bytes, b"0"),
@@ -75,75 +164,301 @@ fn is_zero(bytes: &[u8]) -> bool {
     }
 }
 
+// GRAMMAR
+
+/// Determine if the byte is a C float suffix.
+#[inline]
+fn is_float_suffix(c: Option<u8>) -> bool {
+    match c {
+        Some(b'f') | Some(b'F') | Some(b'l') | Some(b'L') => true,
+        _ => false,
+    }
+}
+
+/// Determine if the byte is the exponent character.
+#[inline]
+fn is_exponent_char(c: Option<u8>, exponent_char: u8) -> bool {
+    match c {
+        Some(c) => case_insensitive_equal(c, exponent_char),
+        None    => false,
+    }
+}
+
+/// Determine if the float is a hexadecimal float literal, like `0x1.8p+3`.
+#[inline]
+fn is_hex_float<C: CodeUnit>(radix: u32, bytes: &[C], grammar: NumberGrammar) -> bool {
+    let is_prefix = match byte_at(bytes, 1) {
+        Some(b'x') | Some(b'X') => Some(b'0') == byte_at(bytes, 0),
+        _                       => false,
+    };
+    radix == 10 && is_prefix && grammar.contains(NumberGrammar::HEX_FLOAT)
+}
+
+/// Determine if the float has neither integer nor fraction digits.
+///
+/// Only input which otherwise looks like a float, like `.` or `.e5`,
+/// has an empty mantissa, any other input is an invalid digit.
+#[inline]
+pub(super) fn is_empty_mantissa<C: CodeUnit>(radix: u32, bytes: &[C], options: &ParseOptions) -> bool {
+    // Fast path, the float starts with a digit.
+    if let Some(&c) = bytes.first() {
+        if char_to_digit(c).as_u32() < radix {
+            return false;
+        }
+    }
+
+    if atoi::integer_len(radix, bytes, options) != 0 {
+        return false;
+    }
+
+    let bytes = match byte_at(bytes, 0) {
+        Some(c) if c == options.decimal_point() => {
+            let fraction = &index!(bytes[1..]);
+            let rules = options.digit_separator_flags().fraction_rules();
+            if atoi::digits_len(radix, fraction, options.separator(), rules) != 0 {
+                return false;
+            }
+            fraction
+        },
+        _ => bytes,
+    };
+    bytes.is_empty() || is_exponent_char(byte_at(bytes, 0), options.exponent_notation_char(radix))
+}
+
+/// Get the number of bytes of the float valid for the number grammar.
+///
+/// Returns the index of the first byte which violates the grammar,
+/// and the error code for the violation, or the length of the buffer
+/// and success if the grammar is not violated. Required exponent digits
+/// are validated after parsing, since the standard grammar requires them.
+#[inline]
+pub(super) fn grammar_len<C: CodeUnit>(radix: u32, bytes: &[C], grammar: NumberGrammar, options: &ParseOptions)
+    -> (usize, ErrorCode)
+{
+    let decimal_point = options.decimal_point();
+
+    // Validate the integer digits.
+    let integer_len = atoi::integer_len(radix, bytes, options);
+    if integer_len == 0 && Some(decimal_point) == byte_at(bytes, 0) && grammar.contains(NumberGrammar::REQUIRED_INTEGER_DIGITS) {
+        return (0, ErrorCode::EmptyInteger);
+    } else if grammar.contains(NumberGrammar::NO_FLOAT_LEADING_ZEROS) {
+        if let Some(index) = atoi::leading_zero_index(&index!(bytes[..integer_len])) {
+            return (index, ErrorCode::InvalidLeadingZeros);
+        }
+    }
+
+    // Validate the fraction digits.
+    let mut index = integer_len;
+    if Some(decimal_point) == byte_at(bytes, index) {
+        let fraction = &index!(bytes[index+1..]);
+        let rules = options.digit_separator_flags().fraction_rules();
+        let fraction_len = atoi::digits_len(radix, fraction, options.separator(), rules);
+        if fraction_len == 0 && grammar.contains(NumberGrammar::REQUIRED_FRACTION_DIGITS) {
+            return (index + 1, ErrorCode::EmptyFraction);
+        }
+        index += fraction_len + 1;
+    }
+
+    // Validate the exponent sign.
+    if is_exponent_char(byte_at(bytes, index), options.exponent_notation_char(radix)) {
+        let index = index + 1;
+        match byte_at(bytes, index) {
+            Some(b'+') if grammar.contains(NumberGrammar::NO_POSITIVE_EXPONENT_SIGN) => {
+                return (index, ErrorCode::InvalidPositiveExponentSign);
+            },
+            Some(b'+') | Some(b'-') => (),
+            _ if grammar.contains(NumberGrammar::REQUIRED_EXPONENT_SIGN) => {
+                return (index, ErrorCode::MissingExponentSign);
+            },
+            _ => (),
+        }
+    }
+
+    (bytes.len(), ErrorCode::Success)
+}
+
+/// Parse the float, or the prefix of the float valid for the number grammar.
+#[inline]
+fn parse_grammar<'a, F: StringToFloat, C: CodeUnit>(radix: u32, bytes: &'a [C], lossy: bool, sign: Sign, grammar: NumberGrammar, options: &ParseOptions)
+    -> (F, usize, ErrorCode)
+{
+    let (len, code) = grammar_len(radix, bytes, grammar, options);
+    let valid = &index!(bytes[..len]);
+    let (value, processed) = match len {
+        0 => (F::ZERO, 0),
+        _ if lossy => F::lossy(radix, valid, sign, options),
+        _ => F::default(radix, valid, sign, options),
+    };
+
+    match code {
+        ErrorCode::Success  => (value, processed, code),
+        _                   => (value, len, code),
+    }
+}
+
+/// Get the number of bytes processed after an exponent without digits,
+/// and a trailing C float suffix.
+#[inline]
+pub(super) fn trailing_len<C: CodeUnit>(radix: u32, bytes: &[C], processed: usize, grammar: NumberGrammar, options: &ParseOptions)
+    -> (usize, ErrorCode)
+{
+    let exponent_char = options.exponent_notation_char(radix);
+    let mut processed = processed;
+
+    // The parsers do not consume exponents without digits, so an exponent
+    // character directly after the mantissa starts an empty exponent.
+    if is_exponent_char(byte_at(bytes, processed), exponent_char) {
+        let mantissa = &index!(bytes[..processed]);
+        if !mantissa.iter().any(|&c| case_insensitive_equal(c, exponent_char)) {
+            let sign_bytes = match byte_at(bytes, processed + 1) {
+                Some(b'+') | Some(b'-') => 1,
+                _                       => 0,
+            };
+            let index = processed + 1 + sign_bytes;
+            if grammar.contains(NumberGrammar::REQUIRED_EXPONENT_DIGITS) {
+                return (index, ErrorCode::EmptyExponent);
+            }
+            processed = index;
+        }
+    }
+
+    // Consume a trailing C float suffix, only valid if the float
+    // has a fraction or exponent, like `1.f` or `1e5f`.
+    if grammar.contains(NumberGrammar::FLOAT_SUFFIX) && is_float_suffix(byte_at(bytes, processed)) {
+        let is_float = index!(bytes[..processed]).iter().any(|&c| {
+            c.to_byte() == options.decimal_point() || case_insensitive_equal(c, exponent_char)
+        });
+        if is_float {
+            return (processed + 1, ErrorCode::Success);
+        }
+    }
+
+    (processed, ErrorCode::Success)
+}
+
+/// Handle an exponent without digits, and a trailing C float suffix.
+#[inline]
+fn filter_trailing<'a, F: StringToFloat, C: CodeUnit>(radix: u32, bytes: &'a [C], value: F, processed: usize, grammar: NumberGrammar, options: &ParseOptions)
+    -> (F, usize, ErrorCode)
+{
+    let (processed, code) = trailing_len(radix, bytes, processed, grammar, options);
+    (value, processed, code)
+}
+
+/// Parse a hexadecimal float literal, and a trailing C float suffix.
+#[inline]
+fn filter_hex<'a, F: StringToFloat, C: CodeUnit>(bytes: &'a [C], sign: Sign, grammar: NumberGrammar, options: &ParseOptions)
+    -> (F, usize, ErrorCode)
+{
+    // Skip the `0x` prefix, which is not part of the literal.
+    let (value, processed, code) = F::hex(&index!(bytes[2..]), sign, grammar, options);
+    let processed = processed + 2;
+
+    // Consume a trailing C float suffix, only valid if the float has a
+    // binary exponent, since `f` is otherwise a hexadecimal digit.
+    let has_exponent = index!(bytes[..processed]).iter().any(|&c| c.to_byte() == b'p' || c.to_byte() == b'P');
+    match code {
+        ErrorCode::Success if grammar.contains(NumberGrammar::FLOAT_SUFFIX) && has_exponent && is_float_suffix(byte_at(bytes, processed)) => {
+            (value, processed + 1, code)
+        },
+        _ => (value, processed, code),
+    }
+}
+
 // ATOF
 
 /// Convert string to float and handle special floating-point strings.
 /// Forcing inlining leads to much better codegen at high optimization levels.
 #[inline]
-fn filter_special<'a, F: StringToFloat>(radix: u32, bytes: &'a [u8], lossy: bool, sign: Sign)
-    -> (F, usize)
+fn filter_special<'a, F: StringToFloat, C: CodeUnit>(radix: u32, bytes: &'a [C], lossy: bool, sign: Sign, grammar: NumberGrammar, options: &ParseOptions)
+    -> (F, usize, ErrorCode)
 {
     // Special case checks
     // Check long infinity first before short infinity.
     // Short infinity short-circuits, we want to parse as many characters
     // as possible.
+    let special = !grammar.contains(NumberGrammar::NO_SPECIAL);
     if is_zero(bytes) {
-        (F::ZERO, bytes.len())
-    } else if is_infinity(bytes) {
-        let len = get_infinity_string().len();
-        (F::INFINITY, len)
-    } else if is_inf(bytes) {
-        let len = get_inf_string().len();
-        (F::INFINITY, len)
-    } else if is_nan(bytes) {
-        let len = get_nan_string().len();
-        (F::NAN, len)
-    } else if bytes.len() == 1 && index!(bytes[0]) == b'.' {
-        // We know the above statement is safe, since `bytes.len() == 1`.
-        // Handle case where we have a decimal point, but no leading or trailing
-        // digits. This should return a value of 0, but the checked parsers
-        // should reject this out-right.
-        (F::ZERO, 0)
-    } else if lossy {
-        F::lossy(radix, bytes, sign)
+        (F::ZERO, bytes.len(), ErrorCode::Success)
+    } else if special && is_infinity(bytes, options) {
+        let len = options.infinity_string().len();
+        (F::INFINITY, len, ErrorCode::Success)
+    } else if special && is_inf(bytes, options) {
+        let len = options.inf_string().len();
+        (F::INFINITY, len, ErrorCode::Success)
+    } else if special && is_nan(bytes, options) {
+        let len = options.nan_string().len();
+        (F::NAN, len, ErrorCode::Success)
+    } else if is_hex_float(radix, bytes, grammar) {
+        filter_hex::<F, C>(bytes, sign, grammar, options)
+    } else if is_empty_mantissa(radix, bytes, options) {
+        // Handle case where we have a decimal point or exponent, but no
+        // leading or trailing digits, like `.` or `.e5`. The standard
+        // grammar reports these as invalid digits.
+        match grammar == NumberGrammar::STANDARD {
+            true  => (F::ZERO, 0, ErrorCode::Success),
+            false => (F::ZERO, 0, ErrorCode::EmptyMantissa),
+        }
+    } else if grammar == NumberGrammar::STANDARD {
+        // The standard grammar does not report grammar violations, so
+        // an exponent without digits is an invalid digit.
+        let (value, processed) = match lossy {
+            true  => F::lossy(radix, bytes, sign, options),
+            false => F::default(radix, bytes, sign, options),
+        };
+        (value, processed, ErrorCode::Success)
     } else {
-        F::default(radix, bytes, sign)
+        let (value, processed, code) = parse_grammar::<F, C>(radix, bytes, lossy, sign, grammar, options);
+        match code {
+            ErrorCode::Success  => filter_trailing::<F, C>(radix, bytes, value, processed, grammar, options),
+            _                   => (value, processed, code),
+        }
     }
 }
 
 /// Handle +/- values and empty buffers.
 /// Forcing inlining leads to much better codegen at high optimization levels.
 #[inline]
-fn filter_sign<'a, F: StringToFloat>(radix: u32, bytes: &'a [u8], lossy: bool)
-    -> (F, Sign, usize)
+fn filter_sign<'a, F: StringToFloat, C: CodeUnit>(radix: u32, bytes: &'a [C], lossy: bool, options: &ParseOptions)
+    -> (F, Sign, usize, ErrorCode)
 {
     let len = bytes.len();
-    let (sign_bytes, sign) = match bytes.get(0) {
-        Some(&b'+') => (1, Sign::Positive),
-        Some(&b'-') => (1, Sign::Negative),
+    let grammar = options.grammar();
+    let (sign_bytes, sign) = match byte_at(bytes, 0) {
+        Some(b'+') => (1, Sign::Positive),
+        Some(b'-') => (1, Sign::Negative),
         _           => (0, Sign::Positive),
     };
 
-    if len > sign_bytes {
+    if sign_bytes == 1 && sign == Sign::Positive && grammar.contains(NumberGrammar::NO_POSITIVE_MANTISSA_SIGN) {
+        // Leading `+` is invalid for the number grammar.
+        (F::ZERO, sign, 0, ErrorCode::InvalidPositiveMantissaSign)
+    } else if len == 1 && byte_at(bytes, 0) == Some(options.decimal_point()) {
+        // Lone decimal point, without a sign or any digits.
+        (F::ZERO, sign, 0, ErrorCode::EmptyMantissa)
+    } else if len > sign_bytes {
         // `bytes.len() > sign_bytes`, so this range is always valid.
         let bytes = &index!(bytes[sign_bytes..]);
-        let (value, len) = filter_special::<F>(radix, bytes, lossy, sign);
-        (value, sign, len + sign_bytes)
+        let (value, len, code) = filter_special::<F, C>(radix, bytes, lossy, sign, grammar, options);
+        (value, sign, len + sign_bytes, code)
+    } else if grammar == NumberGrammar::STANDARD {
+        // Sign without any digits, an invalid digit for the standard grammar.
+        (F::ZERO, sign, 0, ErrorCode::Success)
     } else {
-        (F::ZERO, sign, 0)
+        (F::ZERO, sign, sign_bytes, ErrorCode::EmptyMantissa)
     }
 }
 
 /// Iteratively filter simple cases and then invoke parser.
 /// Forcing inlining leads to much better codegen at high optimization levels.
 #[inline]
-fn atof<F: StringToFloat>(radix: u32, bytes: &[u8], lossy: bool)
-    -> (F, usize)
+fn atof<F: StringToFloat, C: CodeUnit>(radix: u32, bytes: &[C], lossy: bool, options: &ParseOptions)
+    -> (F, usize, ErrorCode)
 {
-    let (value, sign, len) = filter_sign::<F>(radix, bytes, lossy);
+    let (value, sign, len, code) = filter_sign::<F, C>(radix, bytes, lossy, options);
     match sign {
-        Sign::Negative => (-value, len),
-        Sign::Positive => (value, len),
+        Sign::Negative => (-value, len, code),
+        Sign::Positive => (value, len, code),
     }
 }
 
@@ -151,22 +466,33 @@ fn atof<F: StringToFloat>(radix: u32, bytes: &[u8], lossy: bool)
 
 /// Expand the generic atof function for specified types.
 macro_rules! wrap {
-    ($name:ident, $f:tt, $lossy:expr) => (
-        /// Parse float and return value, subslice read, and if truncated.
+    ($name:ident, $options_name:ident, $f:tt, $lossy:expr) => (
+        /// Parse float with custom options and return value, subslice read, and error code.
         #[inline]
-        fn $name(radix: u8, bytes: &[u8])
-            -> ($f, usize, bool)
+        fn $options_name<C: CodeUnit>(radix: u8, bytes: &[C], options: &ParseOptions)
+            -> ($f, usize, ErrorCode)
         {
-            let (value, len) = atof::<$f>(radix.into(), bytes, $lossy);
-            (value, len, false)
+            atof::<$f, C>(radix.into(), bytes, $lossy, options)
+        }
+
+        /// Parse float and return value, subslice read, and error code.
+        #[inline]
+        fn $name<C: CodeUnit>(radix: u8, bytes: &[C])
+            -> ($f, usize, ErrorCode)
+        {
+            $options_name(radix, bytes, &ParseOptions::global())
         }
     )
 }
 
-wrap!(atof32_impl, f32, false);
-wrap!(atof64_impl, f64, false);
-wrap!(atof32_lossy_impl, f32, true);
-wrap!(atof64_lossy_impl, f64, true);
+wrap!(atof32_impl, atof32_options_impl, f32, false);
+wrap!(atof64_impl, atof64_options_impl, f64, false);
+wrap!(atof32_lossy_impl, atof32_lossy_options_impl, f32, true);
+wrap!(atof64_lossy_impl, atof64_lossy_options_impl, f64, true);
+wrap!(atof16_impl, atof16_options_impl, f16, false);
+wrap!(atobf16_impl, atobf16_options_impl, bf16, false);
+wrap!(atof16_lossy_impl, atof16_lossy_options_impl, f16, true);
+wrap!(atobf16_lossy_impl, atobf16_lossy_options_impl, bf16, true);
 
 // RANGE API (FFI)
 generate_from_range_api!(atof32_range, atof32_radix_range, f32, atof32_impl);
@@ -177,16 +503,78 @@ generate_try_from_range_api!(try_atof32_range, try_atof32_radix_range, f32, atof
 generate_try_from_range_api!(try_atof64_range, try_atof64_radix_range, f64, atof64_impl);
 generate_try_from_range_api!(try_atof32_lossy_range, try_atof32_lossy_radix_range, f32, atof32_lossy_impl);
 generate_try_from_range_api!(try_atof64_lossy_range, try_atof64_lossy_radix_range, f64, atof64_lossy_impl);
+generate_from_range_options_api!(atof32_range_with_options, atof32_radix_range_with_options, f32, atof32_options_impl);
+generate_from_range_options_api!(atof64_range_with_options, atof64_radix_range_with_options, f64, atof64_options_impl);
+generate_from_range_options_api!(atof32_lossy_range_with_options, atof32_lossy_radix_range_with_options, f32, atof32_lossy_options_impl);
+generate_from_range_options_api!(atof64_lossy_range_with_options, atof64_lossy_radix_range_with_options, f64, atof64_lossy_options_impl);
+generate_try_from_range_options_api!(try_atof32_range_with_options, try_atof32_radix_range_with_options, f32, atof32_options_impl);
+generate_try_from_range_options_api!(try_atof64_range_with_options, try_atof64_radix_range_with_options, f64, atof64_options_impl);
+generate_try_from_range_options_api!(try_atof32_lossy_range_with_options, try_atof32_lossy_radix_range_with_options, f32, atof32_lossy_options_impl);
+generate_try_from_range_options_api!(try_atof64_lossy_range_with_options, try_atof64_lossy_radix_range_with_options, f64, atof64_lossy_options_impl);
 
 // SLICE API
 generate_from_slice_api!(atof32_slice, atof32_radix_slice, f32, atof32_impl);
 generate_from_slice_api!(atof64_slice, atof64_radix_slice, f64, atof64_impl);
 generate_from_slice_api!(atof32_lossy_slice, atof32_lossy_radix_slice, f32, atof32_lossy_impl);
 generate_from_slice_api!(atof64_lossy_slice, atof64_lossy_radix_slice, f64, atof64_lossy_impl);
+generate_from_slice_api!(atof16_slice, atof16_radix_slice, f16, atof16_impl);
+generate_from_slice_api!(atobf16_slice, atobf16_radix_slice, bf16, atobf16_impl);
+generate_from_slice_api!(atof16_lossy_slice, atof16_lossy_radix_slice, f16, atof16_lossy_impl);
+generate_from_slice_api!(atobf16_lossy_slice, atobf16_lossy_radix_slice, bf16, atobf16_lossy_impl);
 generate_try_from_slice_api!(try_atof32_slice, try_atof32_radix_slice, f32, atof32_impl);
 generate_try_from_slice_api!(try_atof64_slice, try_atof64_radix_slice, f64, atof64_impl);
 generate_try_from_slice_api!(try_atof32_lossy_slice, try_atof32_lossy_radix_slice, f32, atof32_lossy_impl);
 generate_try_from_slice_api!(try_atof64_lossy_slice, try_atof64_lossy_radix_slice, f64, atof64_lossy_impl);
+generate_try_from_slice_api!(try_atof16_slice, try_atof16_radix_slice, f16, atof16_impl);
+generate_try_from_slice_api!(try_atobf16_slice, try_atobf16_radix_slice, bf16, atobf16_impl);
+generate_try_from_slice_api!(try_atof16_lossy_slice, try_atof16_lossy_radix_slice, f16, atof16_lossy_impl);
+generate_try_from_slice_api!(try_atobf16_lossy_slice, try_atobf16_lossy_radix_slice, bf16, atobf16_lossy_impl);
+generate_from_slice_options_api!(atof32_slice_with_options, atof32_radix_slice_with_options, f32, atof32_options_impl);
+generate_from_slice_options_api!(atof64_slice_with_options, atof64_radix_slice_with_options, f64, atof64_options_impl);
+generate_from_slice_options_api!(atof32_lossy_slice_with_options, atof32_lossy_radix_slice_with_options, f32, atof32_lossy_options_impl);
+generate_from_slice_options_api!(atof64_lossy_slice_with_options, atof64_lossy_radix_slice_with_options, f64, atof64_lossy_options_impl);
+generate_from_slice_options_api!(atof16_slice_with_options, atof16_radix_slice_with_options, f16, atof16_options_impl);
+generate_from_slice_options_api!(atobf16_slice_with_options, atobf16_radix_slice_with_options, bf16, atobf16_options_impl);
+generate_from_slice_options_api!(atof16_lossy_slice_with_options, atof16_lossy_radix_slice_with_options, f16, atof16_lossy_options_impl);
+generate_from_slice_options_api!(atobf16_lossy_slice_with_options, atobf16_lossy_radix_slice_with_options, bf16, atobf16_lossy_options_impl);
+generate_try_from_slice_options_api!(try_atof32_slice_with_options, try_atof32_radix_slice_with_options, f32, atof32_options_impl);
+generate_try_from_slice_options_api!(try_atof64_slice_with_options, try_atof64_radix_slice_with_options, f64, atof64_options_impl);
+generate_try_from_slice_options_api!(try_atof32_lossy_slice_with_options, try_atof32_lossy_radix_slice_with_options, f32, atof32_lossy_options_impl);
+generate_try_from_slice_options_api!(try_atof64_lossy_slice_with_options, try_atof64_lossy_radix_slice_with_options, f64, atof64_lossy_options_impl);
+generate_try_from_slice_options_api!(try_atof16_slice_with_options, try_atof16_radix_slice_with_options, f16, atof16_options_impl);
+generate_try_from_slice_options_api!(try_atobf16_slice_with_options, try_atobf16_radix_slice_with_options, bf16, atobf16_options_impl);
+generate_try_from_slice_options_api!(try_atof16_lossy_slice_with_options, try_atof16_lossy_radix_slice_with_options, f16, atof16_lossy_options_impl);
+generate_try_from_slice_options_api!(try_atobf16_lossy_slice_with_options, try_atobf16_lossy_radix_slice_with_options, bf16, atobf16_lossy_options_impl);
+
+// PARTIAL API
+generate_partial_from_slice_api!(atof32_partial_slice, atof32_partial_radix_slice, f32, atof32_impl);
+generate_partial_from_slice_api!(atof64_partial_slice, atof64_partial_radix_slice, f64, atof64_impl);
+generate_partial_from_slice_api!(atof32_lossy_partial_slice, atof32_lossy_partial_radix_slice, f32, atof32_lossy_impl);
+generate_partial_from_slice_api!(atof64_lossy_partial_slice, atof64_lossy_partial_radix_slice, f64, atof64_lossy_impl);
+generate_partial_from_slice_api!(atof16_partial_slice, atof16_partial_radix_slice, f16, atof16_impl);
+generate_partial_from_slice_api!(atobf16_partial_slice, atobf16_partial_radix_slice, bf16, atobf16_impl);
+generate_partial_from_slice_api!(atof16_lossy_partial_slice, atof16_lossy_partial_radix_slice, f16, atof16_lossy_impl);
+generate_partial_from_slice_api!(atobf16_lossy_partial_slice, atobf16_lossy_partial_radix_slice, bf16, atobf16_lossy_impl);
+generate_partial_from_slice_options_api!(atof32_partial_slice_with_options, atof32_partial_radix_slice_with_options, f32, atof32_options_impl);
+generate_partial_from_slice_options_api!(atof64_partial_slice_with_options, atof64_partial_radix_slice_with_options, f64, atof64_options_impl);
+generate_partial_from_slice_options_api!(atof32_lossy_partial_slice_with_options, atof32_lossy_partial_radix_slice_with_options, f32, atof32_lossy_options_impl);
+generate_partial_from_slice_options_api!(atof64_lossy_partial_slice_with_options, atof64_lossy_partial_radix_slice_with_options, f64, atof64_lossy_options_impl);
+generate_partial_from_slice_options_api!(atof16_partial_slice_with_options, atof16_partial_radix_slice_with_options, f16, atof16_options_impl);
+generate_partial_from_slice_options_api!(atobf16_partial_slice_with_options, atobf16_partial_radix_slice_with_options, bf16, atobf16_options_impl);
+generate_partial_from_slice_options_api!(atof16_lossy_partial_slice_with_options, atof16_lossy_partial_radix_slice_with_options, f16, atof16_lossy_options_impl);
+generate_partial_from_slice_options_api!(atobf16_lossy_partial_slice_with_options, atobf16_lossy_partial_radix_slice_with_options, bf16, atobf16_lossy_options_impl);
+
+// QUAD API
+cfg_if! {
+if #[cfg(all(has_i128, feature = "correct"))] {
+wrap!(atof128_impl, atof128_options_impl, f128, false);
+generate_from_slice_api!(atof128_slice, atof128_radix_slice, f128, atof128_impl);
+generate_try_from_slice_api!(try_atof128_slice, try_atof128_radix_slice, f128, atof128_impl);
+generate_from_slice_options_api!(atof128_slice_with_options, atof128_radix_slice_with_options, f128, atof128_options_impl);
+generate_try_from_slice_options_api!(try_atof128_slice_with_options, try_atof128_radix_slice_with_options, f128, atof128_options_impl);
+generate_partial_from_slice_api!(atof128_partial_slice, atof128_partial_radix_slice, f128, atof128_impl);
+generate_partial_from_slice_options_api!(atof128_partial_slice_with_options, atof128_partial_radix_slice_with_options, f128, atof128_options_impl);
+}}  // cfg_if
 
 // TESTS
 // -----
@@ -355,7 +743,7 @@ mod tests {
 
         // Add various unittests from proptests.
         assert_eq!(ErrorCode::InvalidDigit, try_atof64_slice(b"0e").error.code);
-        assert_eq!(ErrorCode::InvalidDigit, try_atof64_slice(b".").error.code);
+        assert_eq!(ErrorCode::EmptyMantissa, try_atof64_slice(b".").error.code);
         assert_eq!(ErrorCode::InvalidDigit, try_atof64_slice(b"+.").error.code);
         assert_eq!(ErrorCode::InvalidDigit, try_atof64_slice(b"-.").error.code);
         assert_eq!(ErrorCode::InvalidDigit, try_atof64_slice(b"+").error.code);
@@ -380,7 +768,7 @@ mod tests {
 
     #[test]
     fn try_atof32_base10_test() {
-        assert_eq!(invalid_digit_error(0.0, 0), try_atof32_slice(b"."));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 0), try_atof32_slice(b"."));
         assert_eq!(empty_error(0.0), try_atof32_slice(b""));
         assert_eq!(success(0.0), try_atof32_slice(b"0.0"));
         assert_eq!(invalid_digit_error(1.0, 1), try_atof32_slice(b"1a"));
@@ -391,7 +779,7 @@ mod tests {
 
     #[test]
     fn try_atof64_base10_test() {
-        assert_eq!(invalid_digit_error(0.0, 0), try_atof64_slice(b"."));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 0), try_atof64_slice(b"."));
         assert_eq!(empty_error(0.0), try_atof64_slice(b""));
         assert_eq!(success(0.0), try_atof64_slice(b"0.0"));
         assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice(b"1a"));
@@ -400,9 +788,541 @@ mod tests {
         assert_eq!(success(5.002868148396374), try_atof64_slice(b"5.002868148396374"));
     }
 
+    #[test]
+    fn atof_options_test() {
+        let mut options = ParseOptions::new();
+        options.set_nan_string(b"NULL");
+        options.set_inf_string(b"INF");
+        options.set_infinity_string(b"INFINITY");
+        options.set_exponent_char(b'p');
+
+        assert!(atof64_slice_with_options(b"null", &options).is_nan());
+        assert!(atof64_slice_with_options(b"-infinity", &options).is_infinite());
+        assert_eq!(invalid_digit_error(0.0, 0), try_atof64_slice_with_options(b"NaN", &options));
+        assert_eq!(success(1.5e10), try_atof64_slice_with_options(b"1.5p10", &options));
+        assert_eq!(invalid_digit_error(1.5, 3), try_atof64_slice_with_options(b"1.5e10", &options));
+        assert_eq!(success(1.5e10), try_atof32_slice_with_options(b"1.5P10", &options));
+
+        // Default options are unaffected.
+        assert_eq!(success(1.5e10), try_atof64_slice_with_options(b"1.5e10", &ParseOptions::new()));
+    }
+
+    #[cfg(all(feature = "correct", feature = "rounding"))]
+    #[test]
+    fn atof_rounding_options_test() {
+        let mut options = ParseOptions::new();
+        options.set_rounding(RoundingKind::TowardZero);
+        assert_eq!(success(16777216.0), try_atof32_slice_with_options(b"16777217", &options));
+        assert_eq!(success(-16777216.0), try_atof32_slice_with_options(b"-16777217", &options));
+        options.set_rounding(RoundingKind::TowardPositiveInfinity);
+        assert_eq!(success(16777218.0), try_atof32_slice_with_options(b"16777217", &options));
+        assert_eq!(success(-16777216.0), try_atof32_slice_with_options(b"-16777217", &options));
+    }
+
+    #[test]
+    fn atof_algorithm_test() {
+        let mut options = ParseOptions::new();
+        options.set_algorithm(ParseAlgorithm::Incorrect);
+        assert_eq!(success(1.5), try_atof64_slice_with_options(b"1.5", &options));
+        assert_eq!(success(-1.5), try_atof32_slice_with_options(b"-1.5", &options));
+        assert_eq!(success(f16::from_f32(1.5)), try_atof16_slice_with_options(b"1.5", &options));
+        assert_relative_eq!(atof64_slice_with_options(b"7.038531e-26", &options), 7.038531e-26, max_relative=1e-15);
+        assert_eq!(invalid_digit_error(1.5, 3), try_atof64_slice_with_options(b"1.5x", &options));
+
+        #[cfg(feature = "correct")] {
+            options.set_algorithm(ParseAlgorithm::Correct);
+            assert_eq!(success(7.038531e-26), try_atof64_slice_with_options(b"7.038531e-26", &options));
+            assert_eq!(success(1.7976931348623157e308), try_atof64_slice_with_options(b"1.7976931348623157e308", &options));
+        }
+    }
+
+    /// Multiply little-endian, base 10^9 limbs by a small factor.
+    #[cfg(feature = "correct")]
+    fn mul_limbs(limbs: &mut Vec<u64>, factor: u64) {
+        let mut carry = 0;
+        for limb in limbs.iter_mut() {
+            let product = *limb * factor + carry;
+            *limb = product % 1000000000;
+            carry = product / 1000000000;
+        }
+        while carry != 0 {
+            limbs.push(carry % 1000000000);
+            carry /= 1000000000;
+        }
+    }
+
+    /// Get the exact decimal digits and exponent of `mant * 2^exp2`.
+    #[cfg(feature = "correct")]
+    fn exact_decimal(mant: u64, exp2: i32) -> (Vec<u8>, i32) {
+        let mut limbs = vec![mant % 1000000000, mant / 1000000000 % 1000000000, mant / 1000000000 / 1000000000];
+        let (step, factor, mut count, exp10) = match exp2 >= 0 {
+            // Multiply by powers of 2.
+            true  => (29, 1 << 29, exp2, 0),
+            // Multiply by powers of 5, and divide by powers of 10.
+            false => (13, 1220703125, -exp2, exp2),
+        };
+        while count >= step {
+            mul_limbs(&mut limbs, factor);
+            count -= step;
+        }
+        let base = if exp2 >= 0 { 2u64 } else { 5u64 };
+        mul_limbs(&mut limbs, base.pow(count as u32));
+        while limbs.len() > 1 && *limbs.last().unwrap() == 0 {
+            limbs.pop();
+        }
+
+        let mut digits = limbs.last().unwrap().to_string();
+        for limb in limbs.iter().rev().skip(1) {
+            digits.push_str(&format!("{:09}", limb));
+        }
+        (digits.into_bytes(), exp10)
+    }
+
+    /// Get exact, slightly above, and slightly below halfway strings.
+    ///
+    /// The halfway point is between the float with the mantissa and
+    /// exponent and the next float. The strings differ from the halfway
+    /// point by `10^-zeros` of its last digit.
+    #[cfg(feature = "correct")]
+    fn halfway_strings(mant: u64, exp: i32, zeros: usize) -> Vec<String> {
+        let (digits, exp10) = exact_decimal(2 * mant + 1, exp - 1);
+        let mut above = digits.clone();
+        above.extend(vec![b'0'; zeros]);
+        above.push(b'1');
+        let mut below = digits.clone();
+        for digit in below.iter_mut().rev() {
+            match *digit {
+                b'0' => *digit = b'9',
+                _    => { *digit -= 1; break; },
+            }
+        }
+        below.extend(vec![b'9'; zeros + 1]);
+
+        let exact = String::from_utf8(digits).unwrap();
+        let above = String::from_utf8(above).unwrap();
+        let below = String::from_utf8(below).unwrap();
+        let shifted = exp10 - zeros as i32 - 1;
+        vec![format!("{}e{}", exact, exp10), format!("{}e{}", above, shifted), format!("{}e{}", below, shifted)]
+    }
+
+    /// Get the ULPs between the lossy and the correct 32-bit floats.
+    #[cfg(feature = "correct")]
+    fn lossy_ulps32(bytes: &[u8]) -> u32 {
+        let mut options = ParseOptions::new();
+        let correct = atof32_slice_with_options(bytes, &options).to_bits();
+        options.set_algorithm(ParseAlgorithm::Lossy);
+        let lossy = atof32_slice_with_options(bytes, &options).to_bits();
+        assert_eq!(lossy, atof32_lossy_slice(bytes).to_bits());
+        match correct > lossy {
+            true  => correct - lossy,
+            false => lossy - correct,
+        }
+    }
+
+    /// Get the ULPs between the lossy and the correct 64-bit floats.
+    #[cfg(feature = "correct")]
+    fn lossy_ulps64(bytes: &[u8]) -> u64 {
+        let mut options = ParseOptions::new();
+        let correct = atof64_slice_with_options(bytes, &options).to_bits();
+        options.set_algorithm(ParseAlgorithm::Lossy);
+        let lossy = atof64_slice_with_options(bytes, &options).to_bits();
+        assert_eq!(lossy, atof64_lossy_slice(bytes).to_bits());
+        match correct > lossy {
+            true  => correct - lossy,
+            false => lossy - correct,
+        }
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    fn exact_decimal_test() {
+        assert_eq!(exact_decimal(3, 0), (b"3".to_vec(), 0));
+        assert_eq!(exact_decimal(3, 4), (b"48".to_vec(), 0));
+        assert_eq!(exact_decimal(3, -2), (b"75".to_vec(), -2));
+        assert_eq!(exact_decimal(1, 64), (b"18446744073709551616".to_vec(), 0));
+        assert_eq!(exact_decimal(1, -20).0.len(), 14);
+        assert_eq!(halfway_strings(1 << 52, 1, 2), vec![
+            "9007199254740993e0".to_string(),
+            "9007199254740993001e-3".to_string(),
+            "9007199254740992999e-3".to_string(),
+        ]);
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    fn atof_lossy_algorithm_test() {
+        let mut options = ParseOptions::new();
+        options.set_algorithm(ParseAlgorithm::Lossy);
+        assert_eq!(success(1.5), try_atof64_slice_with_options(b"1.5", &options));
+        assert_eq!(success(7.038531e-26), try_atof64_slice_with_options(b"7.038531e-26", &options));
+        assert_eq!(success(-1.7976931348623157e308), try_atof64_slice_with_options(b"-1.7976931348623157e308", &options));
+        assert_eq!(success(5e-324), try_atof64_slice_with_options(b"5e-324", &options));
+        assert_eq!(success(1.5), try_atof32_slice_with_options(b"1.5", &options));
+        assert_eq!(invalid_digit_error(1.5, 3), try_atof64_slice_with_options(b"1.5x", &options));
+
+        // Near-halfway cases, including the halfway points to the largest
+        // float and to the smallest denormal float.
+        for s in halfway_strings(1 << 52, 0, 30).iter()
+            .chain(halfway_strings(0x1FFFFFFFFFFFFF, 971, 0).iter())
+            .chain(halfway_strings(0, -1074, 10).iter())
+            .chain(halfway_strings(1, -1074, 700).iter())
+        {
+            assert!(lossy_ulps64(s.as_bytes()) <= 1, "{}", s);
+        }
+        for s in halfway_strings(1 << 23, 0, 30).iter()
+            .chain(halfway_strings(0xFFFFFF, 104, 0).iter())
+            .chain(halfway_strings(0, -149, 10).iter())
+        {
+            assert!(lossy_ulps32(s.as_bytes()) <= 1, "{}", s);
+        }
+
+        // 16-bit floats are rounded from the lossy 64-bit float.
+        let value = atof16_slice_with_options(b"2049.0000000000000000000001", &options).to_bits();
+        assert!(value == 0x6800 || value == 0x6801);
+    }
+
+    #[test]
+    fn atof16_base10_test() {
+        assert_eq!(0x0000, atof16_slice(b"0").to_bits());
+        assert_eq!(0x3C00, atof16_slice(b"1").to_bits());
+        assert_eq!(0xBE00, atof16_slice(b"-1.5").to_bits());
+        assert_eq!(0x7BFF, atof16_slice(b"65504").to_bits());
+        assert_eq!(0x7C00, atof16_slice(b"65520").to_bits());
+        assert_eq!(0x0001, atof16_slice(b"5.960464477539063e-8").to_bits());
+        assert_eq!(0x6800, atof16_slice(b"2049").to_bits());
+        assert_eq!(0x6802, atof16_slice(b"2051").to_bits());
+        assert_eq!(0x6800, atof16_lossy_slice(b"2049").to_bits());
+        assert!(atof16_slice(b"NaN").is_nan());
+
+        assert_eq!(success(f16::from_bits(0x3E00)), try_atof16_slice(b"1.5"));
+        assert_eq!(invalid_digit_error(f16::from_bits(0x3C00), 1), try_atof16_slice(b"1a"));
+        assert_eq!(success((f16::from_bits(0x3E00), 3)), atof16_partial_slice(b"1.5,"));
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    fn atof16_halfway_test() {
+        // Halfway cases, where the 64-bit float is exactly halfway and
+        // the remaining digits determine the direction.
+        assert_eq!(0x6801, atof16_slice(b"2049.0000000000000000000001").to_bits());
+        assert_eq!(0x6800, atof16_slice(b"2048.9999999999999999999999").to_bits());
+        assert_eq!(0x7BFF, atof16_slice(b"65519.99999999999999999").to_bits());
+        assert_eq!(0x0000, atof16_slice(b"2.98023223876953125e-8").to_bits());
+        assert_eq!(0x0001, atof16_slice(b"2.98023223876953126e-8").to_bits());
+        assert_eq!(0x8001, atof16_slice(b"-2.98023223876953126e-8").to_bits());
+    }
+
+    #[test]
+    fn atobf16_base10_test() {
+        assert_eq!(0x0000, atobf16_slice(b"0").to_bits());
+        assert_eq!(0x3F80, atobf16_slice(b"1").to_bits());
+        assert_eq!(0xBFC0, atobf16_slice(b"-1.5").to_bits());
+        assert_eq!(0x4380, atobf16_slice(b"257").to_bits());
+        assert_eq!(0x4382, atobf16_slice(b"259").to_bits());
+        assert_eq!(0x7F80, atobf16_slice(b"1e39").to_bits());
+        assert_eq!(0x4380, atobf16_lossy_slice(b"257").to_bits());
+
+        assert_eq!(success(bf16::from_bits(0x3FC0)), try_atobf16_slice(b"1.5"));
+        assert_eq!(invalid_digit_error(bf16::from_bits(0x3F80), 1), try_atobf16_slice(b"1a"));
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    fn atobf16_halfway_test() {
+        assert_eq!(0x4381, atobf16_slice(b"257.00000000000000000001").to_bits());
+        assert_eq!(0x4380, atobf16_slice(b"256.99999999999999999999").to_bits());
+    }
+
+    #[cfg(all(feature = "correct", feature = "rounding"))]
+    #[test]
+    fn atof16_rounding_options_test() {
+        let mut options = ParseOptions::new();
+        options.set_rounding(RoundingKind::TowardZero);
+        assert_eq!(0x6800, atof16_slice_with_options(b"2049.5", &options).to_bits());
+        assert_eq!(0x7BFF, atof16_slice_with_options(b"65519", &options).to_bits());
+        options.set_rounding(RoundingKind::TowardPositiveInfinity);
+        assert_eq!(0x6801, atof16_slice_with_options(b"2048.001", &options).to_bits());
+        assert_eq!(0xE800, atof16_slice_with_options(b"-2049", &options).to_bits());
+        assert_eq!(0x4381, atobf16_slice_with_options(b"256.5", &options).to_bits());
+    }
+
+    #[cfg(all(has_i128, feature = "correct"))]
+    #[test]
+    fn atof128_base10_test() {
+        assert_eq!(0, atof128_slice(b"0").to_bits());
+        assert_eq!(0x3FFF0000000000000000000000000000, atof128_slice(b"1").to_bits());
+        assert_eq!(0xBFFF8000000000000000000000000000, atof128_slice(b"-1.5").to_bits());
+        assert_eq!(0x3FFB999999999999999999999999999A, atof128_slice(b"0.1").to_bits());
+        assert_eq!(0x406293E5939A08CE9DBD480000000000, atof128_slice(b"1e30").to_bits());
+        assert_eq!(0x7FFF0000000000000000000000000000, atof128_slice(b"1e5000").to_bits());
+        assert!(atof128_slice(b"NaN").is_nan());
+
+        assert_eq!(success(f128::from_f64(1.5)), try_atof128_slice(b"1.5"));
+        assert_eq!(invalid_digit_error(f128::ONE, 1), try_atof128_slice(b"1a"));
+        assert_eq!(success((f128::from_f64(1.5), 3)), atof128_partial_slice(b"1.5,"));
+
+        let mut options = ParseOptions::new();
+        options.set_grammar(NumberGrammar::STANDARD | NumberGrammar::HEX_FLOAT);
+        assert_eq!(success(f128::from_f64(1.5)), try_atof128_slice_with_options(b"0x1.8p+0", &options));
+    }
+
+    #[cfg(feature = "radix")]
+    #[test]
+    fn atof_radix_options_test() {
+        let mut options = ParseOptions::new();
+        options.set_exponent_backup_char(b'@');
+        assert_eq!(success(1234.0), try_atof32_radix_slice_with_options(36, b"YA", &options));
+        assert_eq!(success(16.0), try_atof64_radix_slice_with_options(16, b"1@1", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_radix_slice_with_options(16, b"1^1", &options));
+    }
+
+    #[test]
+    fn atof_separator_test() {
+        let mut options = ParseOptions::new();
+        options.set_digit_separator(b'_', DigitSeparator::INTERNAL);
+        assert_eq!(success(1000000.0), try_atof64_slice_with_options(b"1_000_000", &options));
+        assert_eq!(success(1000.0005), try_atof64_slice_with_options(b"1_000.000_5", &options));
+        assert_eq!(success(-1.5e10), try_atof64_slice_with_options(b"-1.5e1_0", &options));
+        assert_eq!(success(0.001), try_atof32_slice_with_options(b"0_0.0_01", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1__0", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1_.0", &options));
+        assert_eq!(invalid_digit_error(1.0, 2), try_atof64_slice_with_options(b"1._0", &options));
+        assert_eq!(invalid_digit_error(1.0, 3), try_atof64_slice_with_options(b"1.0_e1", &options));
+
+        options.set_digit_separator(b'\'', DigitSeparator::ALL);
+        assert_eq!(success(1000.0005), try_atof64_slice_with_options(b"\'1\'\'000\'.\'000\'5\'", &options));
+        assert_eq!(success(1.5e10), try_atof64_slice_with_options(b"1.5e\'1\'0\'", &options));
+        assert_eq!(success(-1.5e-10), try_atof64_slice_with_options(b"-1.5e-\'\'10", &options));
+
+        // Only allow separators in the fraction.
+        options.set_digit_separator(b'_', DigitSeparator::FRACTION_INTERNAL);
+        assert_eq!(success(1.0005), try_atof64_slice_with_options(b"1.000_5", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1_0.5", &options));
+    }
+
+    #[test]
+    fn atof_partial_test() {
+        assert_eq!(success((1.5, 3)), atof32_partial_slice(b"1.5abc"));
+        assert_eq!(success((-1.5e3, 6)), atof64_partial_slice(b"-1.5e3,2.0"));
+        assert_eq!(success((1.0, 2)), atof64_lossy_partial_slice(b"1.,"));
+        assert_eq!(success((1.0, 2)), atof64_partial_slice(b"1.e"));
+        assert_eq!(empty_error((0.0, 0)), atof32_partial_slice(b""));
+        assert_eq!(invalid_digit_error((0.0, 0), 0), atof64_partial_slice(b"e5"));
+
+        let mut options = ParseOptions::new();
+        options.set_locale(Locale::DE_DE);
+        assert_eq!(success((1234.5, 7)), atof64_partial_slice_with_options(b"1.234,5 Euro", &options));
+    }
+
+    #[test]
+    fn atof_utf16_test() {
+        let utf16 = |s: &str| s.encode_utf16().collect::<Vec<u16>>();
+        assert_eq!(success(1.5), try_atof32_slice(&utf16("1.5")));
+        assert_eq!(success(-1.5e3), try_atof64_slice(&utf16("-1.5e3")));
+        assert_eq!(success(1.5), try_atof64_lossy_slice(&utf16("1.5")));
+        assert!(try_atof64_slice(&utf16("NaN")).value.is_nan());
+        assert_eq!(success(f64::INFINITY), try_atof64_slice(&utf16("inf")));
+        assert_eq!(invalid_digit_error(12.0, 2), try_atof64_slice(&utf16("12\u{661}3")));
+        assert_eq!(invalid_digit_error(1.5, 3), try_atof64_slice(&utf16("1.5\u{ff45}3")));
+        assert_eq!(success((1.5, 3)), atof64_partial_slice(&utf16("1.5\u{20ac}")));
+        assert_eq!(success((1.0e3, 3)), atof64_partial_slice(&utf16("1e3\u{b2}")));
+
+        let mut options = ParseOptions::new();
+        options.set_locale(Locale::FR_FR);
+        assert_eq!(success(1234.5), try_atof64_slice_with_options(&utf16("1\u{a0}234,5"), &options));
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    fn atof_utf16_truncated_test() {
+        // Truncated digits above the halfway representation.
+        let halfway = format!("9007199254740993{}1e-801", "0".repeat(800));
+        let utf16: Vec<u16> = halfway.encode_utf16().collect();
+        assert_eq!(success(9007199254740994.0), try_atof64_slice(&utf16));
+    }
+
+    #[test]
+    fn atof_locale_test() {
+        let mut options = ParseOptions::new();
+        options.set_locale(Locale::DE_DE);
+        assert_eq!(success(1234567.5), try_atof64_slice_with_options(b"1.234.567,5", &options));
+        assert_eq!(success(-1.5e10), try_atof64_slice_with_options(b"-1,5e10", &options));
+        assert_eq!(success(0.5), try_atof32_slice_with_options(b",5", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1.5", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1.23,5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 0), try_atof64_slice_with_options(b",", &options));
+
+        options.set_locale(Locale::FR_FR);
+        assert_eq!(success(1234567.5), try_atof64_slice_with_options("1\u{a0}234\u{a0}567,5".as_bytes(), &options));
+
+        // The locale applies to the number grammar.
+        options.set_format(NumberFormat::Json);
+        assert_eq!(success(1234567.5), try_atof64_slice_with_options("1\u{a0}234\u{a0}567,5".as_bytes(), &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyInteger, 0), try_atof64_slice_with_options(b",5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidLeadingZeros, 0), try_atof64_slice_with_options("0\u{a0}001".as_bytes(), &options));
+    }
+
+    #[test]
+    fn atof_format_test() {
+        let mut options = ParseOptions::new();
+
+        options.set_format(NumberFormat::Json);
+        assert_eq!(success(-0.5e-10), try_atof64_slice_with_options(b"-0.5e-10", &options));
+        assert_eq!(success(100.0), try_atof64_slice_with_options(b"1E+2", &options));
+        assert_eq!(success(-0.0), try_atof64_slice_with_options(b"-0", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidPositiveMantissaSign, 0), try_atof64_slice_with_options(b"+1", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidLeadingZeros, 0), try_atof64_slice_with_options(b"01", &options));
+        assert_eq!(new_error(-0.0, ErrorCode::InvalidLeadingZeros, 1), try_atof64_slice_with_options(b"-01.5", &options));
+        assert_eq!(new_error(1.0, ErrorCode::EmptyFraction, 2), try_atof64_slice_with_options(b"1.", &options));
+        assert_eq!(new_error(1.0, ErrorCode::EmptyFraction, 2), try_atof64_slice_with_options(b"1.e5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyInteger, 0), try_atof64_slice_with_options(b".5", &options));
+        assert_eq!(invalid_digit_error(0.0, 0), try_atof64_slice_with_options(b"NaN", &options));
+        assert_eq!(invalid_digit_error(-0.0, 1), try_atof64_slice_with_options(b"-inf", &options));
+        assert_eq!(invalid_digit_error(0.0, 0), try_atof64_slice_with_options(b"_1", &options));
+
+        options.set_format(NumberFormat::Toml);
+        assert_eq!(success(1000.5), try_atof64_slice_with_options(b"+1_000.5", &options));
+        assert_eq!(success(1e10), try_atof64_slice_with_options(b"1e1_0", &options));
+        assert!(try_atof64_slice_with_options(b"-inf", &options).value.is_infinite());
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1__000", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidLeadingZeros, 0), try_atof64_slice_with_options(b"01.5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidLeadingZeros, 0), try_atof64_slice_with_options(b"0_1.5", &options));
+        assert_eq!(new_error(1.0, ErrorCode::EmptyFraction, 2), try_atof64_slice_with_options(b"1.e5", &options));
+
+        options.set_format(NumberFormat::Yaml);
+        assert_eq!(success(0.5), try_atof64_slice_with_options(b"+.5", &options));
+        assert_eq!(success(1.0), try_atof64_slice_with_options(b"1.", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1_0", &options));
+
+        options.set_format(NumberFormat::Rust);
+        assert_eq!(success(1.0), try_atof64_slice_with_options(b"1.", &options));
+        assert_eq!(success(1000.5), try_atof64_slice_with_options(b"1__000_.5_", &options));
+        assert_eq!(success(1e10), try_atof64_slice_with_options(b"1e_1_0", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyInteger, 0), try_atof64_slice_with_options(b".5", &options));
+        assert_eq!(invalid_digit_error(1.0, 2), try_atof64_slice_with_options(b"1._5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidPositiveMantissaSign, 0), try_atof64_slice_with_options(b"+1", &options));
+
+        options.set_format(NumberFormat::Python);
+        assert_eq!(success(10.5), try_atof64_slice_with_options(b"01_0.5", &options));
+        assert_eq!(success(0.5), try_atof64_slice_with_options(b".5", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1__0", &options));
+
+        options.set_format(NumberFormat::C);
+        assert_eq!(success(1.0), try_atof64_slice_with_options(b"1.f", &options));
+        assert_eq!(success(1e5), try_atof32_slice_with_options(b"1e5F", &options));
+        assert_eq!(success(0.5), try_atof64_slice_with_options(b".5L", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1f", &options));
+        assert_eq!(invalid_digit_error(1.0, 3), try_atof64_slice_with_options(b"1.fl", &options));
+
+        options.set_format(NumberFormat::JavaScript);
+        assert_eq!(success(1000.5), try_atof64_slice_with_options(b"1_000.5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidLeadingZeros, 0), try_atof64_slice_with_options(b"01.5", &options));
+        assert_eq!(invalid_digit_error(0.0, 0), try_atof64_slice_with_options(b"Infinity", &options));
+    }
+
+    #[test]
+    fn atof_grammar_test() {
+        // Standard grammar only requires exponent digits, and reports
+        // violations as invalid digits, other than a lone decimal point.
+        let mut options = ParseOptions::new();
+        assert_eq!(success(0.5), try_atof64_slice_with_options(b".5", &options));
+        assert_eq!(success(1.0), try_atof64_slice_with_options(b"1.", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 0), try_atof64_slice_with_options(b".", &options));
+        assert_eq!(invalid_digit_error(0.0, 1), try_atof64_slice_with_options(b"-.", &options));
+        assert_eq!(invalid_digit_error(0.0, 1), try_atof64_slice_with_options(b"-.e5", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1e", &options));
+        assert_eq!(invalid_digit_error(1.5, 3), try_atof64_slice_with_options(b"1.5e", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1e+", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1e-a", &options));
+        assert_eq!(invalid_digit_error(1.0, 1), try_atof64_slice_with_options(b"1ee5", &options));
+        assert_eq!(invalid_digit_error(1e5, 3), try_atof64_slice_with_options(b"1e5e", &options));
+
+        // Any other grammar reports the violations with distinct error codes.
+        options.set_grammar(NumberGrammar::REQUIRED_EXPONENT_DIGITS | NumberGrammar::FLOAT_SUFFIX);
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 1), try_atof64_slice_with_options(b"-.", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 1), try_atof64_slice_with_options(b"-.e5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 1), try_atof64_slice_with_options(b"+", &options));
+        assert_eq!(new_error(1.0, ErrorCode::EmptyExponent, 2), try_atof64_slice_with_options(b"1e", &options));
+        assert_eq!(new_error(1.5, ErrorCode::EmptyExponent, 4), try_atof64_slice_with_options(b"1.5e", &options));
+        assert_eq!(new_error(1.0, ErrorCode::EmptyExponent, 3), try_atof64_slice_with_options(b"1e+", &options));
+        assert_eq!(new_error(1.0, ErrorCode::EmptyExponent, 3), try_atof64_slice_with_options(b"1e-a", &options));
+        assert_eq!(new_error(1.0, ErrorCode::EmptyExponent, 2), try_atof64_slice_with_options(b"1ee5", &options));
+        options.set_grammar(NumberGrammar::STANDARD);
+
+        // Empty exponents are valid without required exponent digits.
+        options.set_grammar(NumberGrammar::NO_SPECIAL);
+        assert_eq!(success(1.0), try_atof64_slice_with_options(b"1e", &options));
+        assert_eq!(success(1.0), try_atof64_slice_with_options(b"1e+", &options));
+        assert_eq!(invalid_digit_error(1.0, 3), try_atof64_slice_with_options(b"1e+a", &options));
+        assert_eq!(invalid_digit_error(0.0, 0), try_atof64_slice_with_options(b"NaN", &options));
+
+        options.set_grammar(NumberGrammar::REQUIRED_DIGITS);
+        assert_eq!(success(1.5e5), try_atof64_slice_with_options(b"1.5e5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 0), try_atof64_slice_with_options(b".", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyInteger, 0), try_atof64_slice_with_options(b".5", &options));
+        assert_eq!(new_error(1.0, ErrorCode::EmptyFraction, 2), try_atof64_slice_with_options(b"1.", &options));
+        assert_eq!(new_error(1.5, ErrorCode::EmptyExponent, 4), try_atof64_slice_with_options(b"1.5e", &options));
+
+        options.set_grammar(NumberGrammar::REQUIRED_EXPONENT_DIGITS | NumberGrammar::NO_POSITIVE_EXPONENT_SIGN);
+        assert_eq!(success(1e-5), try_atof64_slice_with_options(b"1e-5", &options));
+        assert_eq!(new_error(1.0, ErrorCode::InvalidPositiveExponentSign, 2), try_atof64_slice_with_options(b"1e+5", &options));
+
+        options.set_grammar(NumberGrammar::REQUIRED_EXPONENT_SIGN);
+        assert_eq!(success(1e5), try_atof64_slice_with_options(b"1e+5", &options));
+        assert_eq!(new_error(1.0, ErrorCode::MissingExponentSign, 2), try_atof64_slice_with_options(b"1e5", &options));
+
+        options.set_grammar(NumberGrammar::NO_POSITIVE_MANTISSA_SIGN | NumberGrammar::NO_FLOAT_LEADING_ZEROS);
+        assert_eq!(success(0.5), try_atof32_slice_with_options(b"0.5", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidPositiveMantissaSign, 0), try_atof32_slice_with_options(b"+1", &options));
+        assert_eq!(new_error(-0.0, ErrorCode::InvalidLeadingZeros, 1), try_atof32_slice_with_options(b"-00.5", &options));
+
+        // Grammar errors are reported after the digit separators.
+        options.set_grammar(NumberGrammar::REQUIRED_FRACTION_DIGITS);
+        options.set_digit_separator(b'_', DigitSeparator::INTERNAL | DigitSeparator::TRAILING);
+        assert_eq!(new_error(10.0, ErrorCode::EmptyFraction, 5), try_atof64_slice_with_options(b"1_0_.", &options));
+    }
+
+    #[test]
+    fn atof_hex_float_test() {
+        // Hexadecimal floats are invalid without the hex float grammar.
+        let mut options = ParseOptions::new();
+        assert_eq!(invalid_digit_error(0.0, 1), try_atof64_slice_with_options(b"0x1p3", &options));
+
+        options.set_grammar(NumberGrammar::STANDARD | NumberGrammar::HEX_FLOAT);
+        assert_eq!(success(12.0), try_atof64_slice_with_options(b"0x1.8p+3", &options));
+        assert_eq!(success(12.0), try_atof32_slice_with_options(b"0X1.8P3", &options));
+        assert_eq!(success(-5e-324), try_atof64_slice_with_options(b"-0x1p-1074", &options));
+        assert_eq!(success(0.0), try_atof64_slice_with_options(b"0x0p+0", &options));
+        assert_eq!(success(255.0), try_atof64_slice_with_options(b"0xff", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyMantissa, 2), try_atof64_slice_with_options(b"0x", &options));
+        assert_eq!(new_error(-0.0, ErrorCode::EmptyMantissa, 3), try_atof64_slice_with_options(b"-0x.p1", &options));
+        assert_eq!(new_error(0.0, ErrorCode::EmptyExponent, 4), try_atof64_slice_with_options(b"0x1p", &options));
+        assert_eq!(invalid_digit_error(12.0, 7), try_atof64_slice_with_options(b"0x1.8p3x", &options));
+
+        // The C grammar accepts hexadecimal floats with a float suffix.
+        options.set_format(NumberFormat::C);
+        assert_eq!(success(8.0), try_atof32_slice_with_options(b"0x1p3f", &options));
+        assert_eq!(success(31.0), try_atof32_slice_with_options(b"0x1f", &options));
+        assert_eq!(success(-1.0), try_atof64_slice_with_options(b"-0x.8p1L", &options));
+        assert_eq!(new_error(0.0, ErrorCode::InvalidPositiveMantissaSign, 0), try_atof64_slice_with_options(b"+0x1p0", &options));
+    }
+
+    #[cfg(feature = "rounding")]
+    #[test]
+    fn atof_hex_float_rounding_test() {
+        let mut options = ParseOptions::new();
+        options.set_grammar(NumberGrammar::STANDARD | NumberGrammar::HEX_FLOAT);
+        assert_eq!(success(2.0), try_atof64_slice_with_options(b"0x1.fffffffffffff8p0", &options));
+        options.set_rounding(RoundingKind::TowardZero);
+        assert_eq!(success(1.9999999999999998), try_atof64_slice_with_options(b"0x1.fffffffffffff8p0", &options));
+        assert_eq!(success(-1.9999999999999998), try_atof64_slice_with_options(b"-0x1.fffffffffffff8p0", &options));
+        options.set_rounding(RoundingKind::TowardNegativeInfinity);
+        assert_eq!(success(-2.0), try_atof64_slice_with_options(b"-0x1.fffffffffffff8p0", &options));
+    }
+
     proptest! {
         #[test]
-        fn f32_invalid_proptest(i in r"[+-]?[0-9]{2}\D?\.\D?[0-9]{2}\D?e[+-]?[0-9]+\D") {
+        fn f32_invalid_proptest(i in r"[+-]?[0-9]{2}[^0-9eE]?\.[^0-9eE]?[0-9]{2}[^0-9eE]?e[+-]?[0-9]+\D") {
             let res = try_atof32_slice(i.as_bytes());
             prop_assert_eq!(res.error.code, ErrorCode::InvalidDigit);
         }
@@ -419,6 +1339,8 @@ mod tests {
             let res = try_atof32_slice(i.as_bytes());
             if i.is_empty() {
                 prop_assert_eq!(res.error.code, ErrorCode::Empty);
+            } else if i == "." {
+                prop_assert_eq!(res.error.code, ErrorCode::EmptyMantissa);
             } else {
                 prop_assert_eq!(res.error.code, ErrorCode::InvalidDigit);
             }
@@ -459,7 +1381,7 @@ mod tests {
         }
 
         #[test]
-        fn f64_invalid_proptest(i in r"[+-]?[0-9]{2}\D?\.\D?[0-9]{2}\D?e[+-]?[0-9]+\D") {
+        fn f64_invalid_proptest(i in r"[+-]?[0-9]{2}[^0-9eE]?\.[^0-9eE]?[0-9]{2}[^0-9eE]?e[+-]?[0-9]+\D") {
             let res = try_atof64_slice(i.as_bytes());
             prop_assert_eq!(res.error.code, ErrorCode::InvalidDigit);
         }
@@ -476,6 +1398,8 @@ mod tests {
             let res = try_atof64_slice(i.as_bytes());
             if i.is_empty() {
                 prop_assert_eq!(res.error.code, ErrorCode::Empty);
+            } else if i == "." {
+                prop_assert_eq!(res.error.code, ErrorCode::EmptyMantissa);
             } else {
                 prop_assert_eq!(res.error.code, ErrorCode::InvalidDigit);
             }
@@ -514,5 +1438,37 @@ mod tests {
             let input: String = format!("{:e}", i);
             prop_assert_eq!(i, atof64_slice(input.as_bytes()));
         }
+
+        #[cfg(feature = "correct")]
+        #[test]
+        fn f32_lossy_proptest(mantissa in r"[1-9][0-9]{0,39}", exponent in -90i32..60) {
+            let input: String = format!("{}e{}", mantissa, exponent);
+            prop_assert!(lossy_ulps32(input.as_bytes()) <= 1);
+        }
+
+        #[cfg(feature = "correct")]
+        #[test]
+        fn f32_lossy_halfway_proptest(bits in 0u32..0x7F7FFFFF, zeros in 0usize..40) {
+            let float = f32::from_bits(bits);
+            for input in halfway_strings(float.mantissa() as u64, float.exponent(), zeros) {
+                prop_assert!(lossy_ulps32(input.as_bytes()) <= 1);
+            }
+        }
+
+        #[cfg(feature = "correct")]
+        #[test]
+        fn f64_lossy_proptest(mantissa in r"[1-9][0-9]{0,39}", exponent in -380i32..330) {
+            let input: String = format!("{}e{}", mantissa, exponent);
+            prop_assert!(lossy_ulps64(input.as_bytes()) <= 1);
+        }
+
+        #[cfg(feature = "correct")]
+        #[test]
+        fn f64_lossy_halfway_proptest(bits in 0u64..0x7FEFFFFFFFFFFFFF, zeros in 0usize..40) {
+            let float = f64::from_bits(bits);
+            for input in halfway_strings(float.mantissa(), float.exponent(), zeros) {
+                prop_assert!(lossy_ulps64(input.as_bytes()) <= 1);
+            }
+        }
     }
 }
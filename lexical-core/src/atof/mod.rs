@@ -4,5 +4,26 @@
 mod algorithm;
 mod api;
 
+#[cfg(feature = "correct")]
+mod number;
+
+#[cfg(feature = "correct")]
+mod parts;
+
 // Re-exports
 pub use self::api::*;
+
+#[cfg(feature = "correct")]
+pub use self::number::*;
+
+#[cfg(feature = "correct")]
+pub use self::parts::*;
+
+#[cfg(feature = "correct")]
+pub(crate) use self::algorithm::decimal_digits;
+
+#[cfg(feature = "correct")]
+pub(crate) use self::algorithm::radix_digits;
+
+#[cfg(all(has_i128, feature = "correct"))]
+pub(crate) use self::algorithm::{quad_decimal_digits, quad_radix_digits};
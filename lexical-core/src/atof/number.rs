@@ -0,0 +1,323 @@
+//! Parse a number string as an integer or a float.
+//!
+//! The digits are only scanned once, by the float lexer, which parses
+//! the leading significant digits into a 64-bit mantissa. If the number
+//! has no fraction or exponent and fits in an integer, the mantissa is
+//! the integer value, otherwise, the mantissa and the lexed substrings
+//! are converted to a float.
+
+use float::*;
+use util::*;
+use util::api::{partial_from_bytes_wrapper, try_from_bytes_wrapper};
+use super::algorithm::correct::{parse_float, slice_to_double};
+use super::api::{grammar_len, is_empty_mantissa, is_inf, is_infinity, is_nan, trailing_len};
+
+// NUMBER
+
+/// Integer or float parsed from a number string.
+///
+/// Numbers without a fraction or exponent are integers, if they fit in
+/// a 64-bit or 128-bit integer, and floats otherwise. Non-negative
+/// integers are unsigned, and negative integers are signed. Negative
+/// zero is a float, `-0.0`, so the sign is not lost.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Number {
+    /// Non-negative integer that fits in 64 bits.
+    U64(u64),
+    /// Negative integer that fits in 64 bits.
+    I64(i64),
+    /// Non-negative integer that only fits in 128 bits.
+    #[cfg(has_i128)]
+    U128(u128),
+    /// Negative integer that only fits in 128 bits.
+    #[cfg(has_i128)]
+    I128(i128),
+    /// Number with a fraction or exponent, a special float, or an
+    /// integer too large for any integer type.
+    F64(f64),
+}
+
+// PARSE
+
+/// Convert a 128-bit integer to a number.
+#[cfg(has_i128)]
+#[inline]
+fn u128_to_number(value: u128, sign: Sign)
+    -> Option<Number>
+{
+    const MIN_I128: u128 = 1 << 127;
+    match sign {
+        Sign::Positive                      => Some(Number::U128(value)),
+        Sign::Negative if value == 0        => None,
+        Sign::Negative if value <= MIN_I128 => Some(Number::I128(value.as_i128().wrapping_neg())),
+        Sign::Negative                      => None,
+    }
+}
+
+/// Convert the mantissa of a number without a fraction or exponent to an integer.
+///
+/// If the mantissa was truncated, the truncated digits are parsed into
+/// a 128-bit integer, without scanning the leading digits again.
+/// Returns None if the number does not fit in an integer, or is
+/// negative zero.
+#[inline]
+fn to_integer(mantissa: u64, integer: &[u8], truncated: Option<&u8>, sign: Sign)
+    -> Option<Number>
+{
+    const MIN_I64: u64 = 1 << 63;
+    match (truncated, sign) {
+        (None, Sign::Positive)                      => Some(Number::U64(mantissa)),
+        (None, Sign::Negative) if mantissa == 0     => None,
+        (None, Sign::Negative) if mantissa <= MIN_I64 => Some(Number::I64(mantissa.as_i64().wrapping_neg())),
+        #[cfg(has_i128)]
+        (None, Sign::Negative)                      => u128_to_number(mantissa.as_u128(), sign),
+        #[cfg(has_i128)]
+        (Some(p), _)                                => {
+            // The truncated digits may contain digit or group separators.
+            let index = distance(integer.as_ptr(), p);
+            let mut value = mantissa.as_u128();
+            for &c in index!(integer[index..]).iter().filter(|c| c.is_ascii_alphanumeric()) {
+                let digit = char_to_digit(c).as_u128();
+                value = value.checked_mul(10)?.checked_add(digit)?;
+            }
+            u128_to_number(value, sign)
+        },
+        #[cfg(not(has_i128))]
+        _                                           => None,
+    }
+}
+
+/// Parse a decimal number, without a sign.
+#[inline]
+fn parse_decimal(bytes: &[u8], sign: Sign, options: &ParseOptions)
+    -> (Number, usize, ErrorCode)
+{
+    if is_empty_mantissa(10, bytes, options) {
+        // Handle case where we have a decimal point or exponent, but no
+        // leading or trailing digits, like `.` or `.e5`.
+        return (Number::U64(0), 0, ErrorCode::EmptyMantissa);
+    }
+
+    // Only lex the prefix which is valid for the number grammar.
+    let grammar = options.grammar();
+    let (len, code) = match grammar == NumberGrammar::STANDARD {
+        true  => (bytes.len(), ErrorCode::Success),
+        false => grammar_len(10, bytes, grammar, options),
+    };
+    let valid = &index!(bytes[..len]);
+    let (mantissa, slc, rest, truncated) = parse_float::<u64, u8>(10, valid, options);
+    let (processed, code) = match code {
+        ErrorCode::Success  => trailing_len(10, bytes, valid.len() - rest.len(), grammar, options),
+        _                   => (len, code),
+    };
+
+    // The number is an integer if nothing was processed after the
+    // integer digits, including any leading zeros trimmed by the lexer.
+    let integer = slc.integer();
+    let integer_len = distance(valid.as_ptr(), integer.as_ptr()) + integer.len();
+    let number = match processed == integer_len {
+        true  => to_integer(mantissa, integer, truncated, sign),
+        false => None,
+    };
+    let number = number.unwrap_or_else(|| {
+        let kind = internal_rounding(options.rounding(), sign);
+        Number::F64(slice_to_double(mantissa, slc, 10, kind))
+    });
+    (number, processed, code)
+}
+
+/// Parse special floats or a decimal number, without a sign.
+#[inline]
+fn filter_special(bytes: &[u8], sign: Sign, options: &ParseOptions)
+    -> (Number, usize, ErrorCode)
+{
+    // Check long infinity first before short infinity.
+    let special = !options.grammar().contains(NumberGrammar::NO_SPECIAL);
+    if special && is_infinity(bytes, options) {
+        (Number::F64(f64::INFINITY), options.infinity_string().len(), ErrorCode::Success)
+    } else if special && is_inf(bytes, options) {
+        (Number::F64(f64::INFINITY), options.inf_string().len(), ErrorCode::Success)
+    } else if special && is_nan(bytes, options) {
+        (Number::F64(f64::NAN), options.nan_string().len(), ErrorCode::Success)
+    } else {
+        parse_decimal(bytes, sign, options)
+    }
+}
+
+/// Handle +/- values and empty buffers.
+#[inline]
+fn filter_sign(bytes: &[u8], options: &ParseOptions)
+    -> (Number, usize, ErrorCode)
+{
+    let (sign_bytes, sign) = match bytes.first() {
+        Some(&b'+') => (1, Sign::Positive),
+        Some(&b'-') => (1, Sign::Negative),
+        _           => (0, Sign::Positive),
+    };
+
+    if sign_bytes == 1 && sign == Sign::Positive && options.grammar().contains(NumberGrammar::NO_POSITIVE_MANTISSA_SIGN) {
+        // Leading `+` is invalid for the number grammar.
+        (Number::U64(0), 0, ErrorCode::InvalidPositiveMantissaSign)
+    } else if bytes.len() > sign_bytes {
+        // `bytes.len() > sign_bytes`, so this range is always valid.
+        let bytes = &index!(bytes[sign_bytes..]);
+        let (number, len, code) = filter_special(bytes, sign, options);
+        let number = match (number, sign) {
+            (Number::F64(f), Sign::Negative) => Number::F64(-f),
+            _                                => number,
+        };
+        (number, len + sign_bytes, code)
+    } else {
+        (Number::U64(0), sign_bytes, ErrorCode::EmptyMantissa)
+    }
+}
+
+/// Parse number with custom options and return value, subslice read, and error code.
+#[inline]
+fn parse_number_impl(_: u8, bytes: &[u8], options: &ParseOptions)
+    -> (Number, usize, ErrorCode)
+{
+    filter_sign(bytes, options)
+}
+
+// API
+
+/// Parse an integer or float from a decimal string with custom options.
+///
+/// Returns a C-compatible result containing the parsed number, and an
+/// error container any errors that occurred during parsing. Numbers
+/// without a fraction or exponent are parsed as integers, if they fit
+/// in any integer type, and as floats otherwise.
+///
+/// * `bytes`   - Slice containing a numeric string.
+/// * `options` - Options to customize number parsing.
+#[inline]
+pub fn parse_number_with_options(bytes: &[u8], options: &ParseOptions)
+    -> Result<Number>
+{
+    try_from_bytes_wrapper::<Number, _, _>(10, bytes, |r, b| parse_number_impl(r, b, options))
+}
+
+/// Parse an integer or float from a decimal string.
+///
+/// Returns a C-compatible result containing the parsed number, and an
+/// error container any errors that occurred during parsing. Numbers
+/// without a fraction or exponent are parsed as integers, if they fit
+/// in any integer type, and as floats otherwise.
+///
+/// * `bytes`   - Slice containing a numeric string.
+#[inline]
+pub fn parse_number(bytes: &[u8])
+    -> Result<Number>
+{
+    parse_number_with_options(bytes, &ParseOptions::global())
+}
+
+/// Parse a leading integer or float from a decimal string with custom options.
+///
+/// Returns a C-compatible result containing the parsed number and the
+/// number of bytes processed, stopping at the first invalid digit, and
+/// an error container any errors that occurred during parsing.
+///
+/// * `bytes`   - Slice containing a numeric string.
+/// * `options` - Options to customize number parsing.
+#[inline]
+pub fn parse_number_partial_with_options(bytes: &[u8], options: &ParseOptions)
+    -> Result<(Number, usize)>
+{
+    partial_from_bytes_wrapper::<Number, _, _>(10, bytes, |r, b| parse_number_impl(r, b, options))
+}
+
+/// Parse a leading integer or float from a decimal string.
+///
+/// Returns a C-compatible result containing the parsed number and the
+/// number of bytes processed, stopping at the first invalid digit, and
+/// an error container any errors that occurred during parsing.
+///
+/// * `bytes`   - Slice containing a numeric string.
+#[inline]
+pub fn parse_number_partial(bytes: &[u8])
+    -> Result<(Number, usize)>
+{
+    parse_number_partial_with_options(bytes, &ParseOptions::global())
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_number_integer_test() {
+        assert_eq!(success(Number::U64(0)), parse_number(b"0"));
+        assert_eq!(success(Number::U64(15)), parse_number(b"+015"));
+        assert_eq!(success(Number::I64(-15)), parse_number(b"-15"));
+        assert_eq!(success(Number::U64(0)), parse_number(b"+0"));
+        assert!(match parse_number(b"-0").value { Number::F64(f) => f == 0.0 && f.is_sign_negative(), _ => false });
+        assert_eq!(success(Number::U64(12345678901234567890)), parse_number(b"12345678901234567890"));
+        assert_eq!(success(Number::U64(18446744073709551615)), parse_number(b"18446744073709551615"));
+        assert_eq!(success(Number::I64(-9223372036854775808)), parse_number(b"-9223372036854775808"));
+    }
+
+    #[cfg(has_i128)]
+    #[test]
+    fn parse_number_integer128_test() {
+        assert_eq!(success(Number::U128(18446744073709551616)), parse_number(b"18446744073709551616"));
+        assert_eq!(success(Number::I128(-9223372036854775809)), parse_number(b"-9223372036854775809"));
+        assert_eq!(success(Number::I128(-18446744073709551616)), parse_number(b"-18446744073709551616"));
+        assert_eq!(success(Number::U128(340282366920938463463374607431768211455)), parse_number(b"340282366920938463463374607431768211455"));
+        assert_eq!(success(Number::I128(-170141183460469231731687303715884105728)), parse_number(b"-170141183460469231731687303715884105728"));
+        assert_eq!(success(Number::F64(340282366920938463463374607431768211456.0)), parse_number(b"340282366920938463463374607431768211456"));
+        assert_eq!(success(Number::F64(-170141183460469231731687303715884105729.0)), parse_number(b"-170141183460469231731687303715884105729"));
+
+        let mut options = ParseOptions::new();
+        options.set_digit_separator(b'_', DigitSeparator::INTERNAL);
+        assert_eq!(success(Number::U128(100000000000000000000)), parse_number_with_options(b"100_000_000_000_000_000_000", &options));
+    }
+
+    #[test]
+    fn parse_number_float_test() {
+        assert_eq!(success(Number::F64(1500.0)), parse_number(b"1.5e3"));
+        assert_eq!(success(Number::F64(1.0)), parse_number(b"1."));
+        assert_eq!(success(Number::F64(1e2)), parse_number(b"1e2"));
+        assert_eq!(success(Number::F64(-0.5)), parse_number(b"-.5"));
+        assert_eq!(success(Number::F64(-0.0)), parse_number(b"-0.0"));
+        assert_eq!(success(Number::F64(0.1)), parse_number(b"0.1"));
+        assert_eq!(success(Number::F64(f64::INFINITY)), parse_number(b"1e400"));
+        assert_eq!(success(Number::F64(f64::NEG_INFINITY)), parse_number(b"-inf"));
+        assert_eq!(success(Number::F64(9007199254740993.0)), parse_number(b"9007199254740993.0"));
+        assert!(match parse_number(b"NaN").value { Number::F64(f) => f.is_nan(), _ => false });
+
+        #[cfg(not(has_i128))]
+        assert_eq!(success(Number::F64(18446744073709551616.0)), parse_number(b"18446744073709551616"));
+    }
+
+    #[test]
+    fn parse_number_error_test() {
+        assert_eq!(empty_error(Number::U64(0)), parse_number(b""));
+        assert_eq!(new_error(Number::U64(0), ErrorCode::EmptyMantissa, 1), parse_number(b"-"));
+        assert_eq!(new_error(Number::U64(0), ErrorCode::EmptyMantissa, 0), parse_number(b".e5"));
+        assert_eq!(invalid_digit_error(Number::U64(1), 1), parse_number(b"1a"));
+        assert_eq!(invalid_digit_error(Number::F64(1.5), 3), parse_number(b"1.5 "));
+        assert_eq!(new_error(Number::F64(1.0), ErrorCode::EmptyExponent, 2), parse_number(b"1e"));
+
+        let mut options = ParseOptions::new();
+        options.set_format(NumberFormat::Json);
+        assert_eq!(success(Number::I64(-12)), parse_number_with_options(b"-12", &options));
+        assert_eq!(success(Number::F64(-1.2e1)), parse_number_with_options(b"-1.2E+1", &options));
+        assert_eq!(new_error(Number::U64(0), ErrorCode::InvalidLeadingZeros, 0), parse_number_with_options(b"01", &options));
+        assert_eq!(new_error(Number::F64(1.0), ErrorCode::EmptyFraction, 2), parse_number_with_options(b"1.", &options));
+        assert_eq!(new_error(Number::U64(0), ErrorCode::InvalidPositiveMantissaSign, 0), parse_number_with_options(b"+1", &options));
+        assert_eq!(invalid_digit_error(Number::U64(0), 0), parse_number_with_options(b"NaN", &options));
+    }
+
+    #[test]
+    fn parse_number_partial_test() {
+        assert_eq!(success((Number::U64(12), 2)), parse_number_partial(b"12,1.5"));
+        assert_eq!(success((Number::F64(1.5), 3)), parse_number_partial(b"1.5]"));
+        assert_eq!(success((Number::I64(-1), 2)), parse_number_partial(b"-1 "));
+        assert_eq!(invalid_digit_error((Number::U64(0), 0), 0), parse_number_partial(b"]"));
+    }
+}
@@ -0,0 +1,282 @@
+//! Lexer for the decimal components of a number string.
+//!
+//! Splits a number into the integer digits, fraction digits, and
+//! exponent, validated against the number grammar, without rounding
+//! the value to a binary float.
+
+use util::*;
+use super::algorithm::correct::parse_float;
+use super::api::{grammar_len, is_empty_mantissa, trailing_len};
+
+// DECIMAL PARTS
+
+/// Decimal components of a number string.
+///
+/// The integer and fraction substrings are borrowed from the input
+/// exactly as written, including leading and trailing zeros, and any
+/// digit or group separators. The fraction does not include the
+/// decimal point, and the exponent is the value after the exponent
+/// character, or 0 if there is no exponent.
+///
+/// The significant digits are also parsed into a 64-bit mantissa, where
+/// `mantissa * 10^mantissa_exponent` is the value of the number, if no
+/// digits were truncated.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DecimalParts<'a> {
+    /// If the number has a leading `-` sign.
+    is_negative: bool,
+    /// Substring for the integer digits.
+    integer: &'a [u8],
+    /// Substring for the fraction digits.
+    fraction: &'a [u8],
+    /// Raw exponent, saturated to `i32::max_value()` on overflow.
+    exponent: i32,
+    /// Leading significant digits that fit in 64 bits.
+    mantissa: u64,
+    /// Exponent for the 64-bit mantissa.
+    mantissa_exponent: i32,
+    /// Number of significant digits truncated from the mantissa.
+    truncated: usize,
+}
+
+impl<'a> DecimalParts<'a> {
+    /// Get if the number is negative.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.is_negative
+    }
+
+    /// Get the integer substring, which may be empty.
+    #[inline]
+    pub fn integer(&self) -> &'a [u8] {
+        self.integer
+    }
+
+    /// Get the fraction substring, which may be empty.
+    #[inline]
+    pub fn fraction(&self) -> &'a [u8] {
+        self.fraction
+    }
+
+    /// Get the raw exponent.
+    ///
+    /// Exponents which overflow saturate to `i32::max_value()` or
+    /// `-i32::max_value()`.
+    #[inline]
+    pub fn exponent(&self) -> i32 {
+        self.exponent
+    }
+
+    /// Get the leading significant digits that fit in a 64-bit mantissa.
+    ///
+    /// Leading and trailing zeros are not significant, so `"1.500"`
+    /// has the mantissa `15`.
+    #[inline]
+    pub fn mantissa(&self) -> u64 {
+        self.mantissa
+    }
+
+    /// Get the exponent for the 64-bit mantissa.
+    #[inline]
+    pub fn mantissa_exponent(&self) -> i32 {
+        self.mantissa_exponent
+    }
+
+    /// Get the number of significant digits truncated from the mantissa.
+    ///
+    /// If no digits are truncated, the mantissa and mantissa exponent
+    /// represent the number exactly.
+    #[inline]
+    pub fn truncated_digits(&self) -> usize {
+        self.truncated
+    }
+}
+
+// PARSE
+
+/// Lex the decimal components of an unsigned number.
+///
+/// Returns the components, the number of bytes processed, and the error
+/// code, like the float parsers.
+#[inline]
+fn parse_parts<'a>(bytes: &'a [u8], options: &ParseOptions)
+    -> (DecimalParts<'a>, usize, ErrorCode)
+{
+    if is_empty_mantissa(10, bytes, options) {
+        // Handle case where we have a decimal point or exponent, but no
+        // leading or trailing digits, like `.` or `.e5`.
+        return (DecimalParts::default(), 0, ErrorCode::EmptyMantissa);
+    }
+
+    // Only lex the prefix which is valid for the number grammar.
+    let grammar = options.grammar();
+    let (len, code) = match grammar == NumberGrammar::STANDARD {
+        true  => (bytes.len(), ErrorCode::Success),
+        false => grammar_len(10, bytes, grammar, options),
+    };
+    let valid = &index!(bytes[..len]);
+    let (mantissa, slc, rest, _) = parse_float::<u64, u8>(10, valid, options);
+
+    // The float slice trims leading zeros from the integer, so extend
+    // it to the start of the buffer.
+    let integer = slc.integer();
+    let integer_len = distance(valid.as_ptr(), integer.as_ptr()) + integer.len();
+    let parts = DecimalParts {
+        is_negative: false,
+        integer: &index!(valid[..integer_len]),
+        fraction: slc.fraction(),
+        exponent: slc.raw_exponent(),
+        mantissa,
+        mantissa_exponent: slc.mantissa_exponent(),
+        truncated: slc.truncated_digits(),
+    };
+
+    match code {
+        ErrorCode::Success  => {
+            let processed = valid.len() - rest.len();
+            let (processed, code) = trailing_len(10, bytes, processed, grammar, options);
+            (parts, processed, code)
+        },
+        _                   => (parts, len, code),
+    }
+}
+
+/// Lex the decimal components of a number string with custom options.
+///
+/// Returns a C-compatible result containing the decimal components,
+/// and an error container any errors that occurred during parsing.
+/// The number grammar, digit separators, decimal point, and exponent
+/// character are all set by the options. Special values, like `NaN`,
+/// and hexadecimal floats are not decimal numbers, and are invalid.
+///
+/// On error, the components are for the number before the error.
+///
+/// * `bytes`   - Slice containing a numeric string.
+/// * `options` - Options to customize number parsing.
+#[inline]
+pub fn parse_decimal_parts_with_options<'a>(bytes: &'a [u8], options: &ParseOptions)
+    -> Result<DecimalParts<'a>>
+{
+    let (sign_bytes, is_negative) = match bytes.first() {
+        Some(&b'+') => (1, false),
+        Some(&b'-') => (1, true),
+        _           => (0, false),
+    };
+
+    if bytes.is_empty() {
+        empty_error(DecimalParts::default())
+    } else if sign_bytes == 1 && !is_negative && options.grammar().contains(NumberGrammar::NO_POSITIVE_MANTISSA_SIGN) {
+        // Leading `+` is invalid for the number grammar.
+        new_error(DecimalParts::default(), ErrorCode::InvalidPositiveMantissaSign, 0)
+    } else if bytes.len() == sign_bytes {
+        new_error(DecimalParts::default(), ErrorCode::EmptyMantissa, sign_bytes)
+    } else {
+        let (mut parts, processed, code) = parse_parts(&index!(bytes[sign_bytes..]), options);
+        parts.is_negative = is_negative;
+        let processed = processed + sign_bytes;
+        match code {
+            ErrorCode::Success if processed == bytes.len() => success(parts),
+            ErrorCode::Success  => invalid_digit_error(parts, processed),
+            code                => new_error(parts, code, processed),
+        }
+    }
+}
+
+/// Lex the decimal components of a number string.
+///
+/// Returns a C-compatible result containing the decimal components,
+/// and an error container any errors that occurred during parsing.
+///
+/// * `bytes`   - Slice containing a numeric string.
+#[inline]
+pub fn parse_decimal_parts<'a>(bytes: &'a [u8])
+    -> Result<DecimalParts<'a>>
+{
+    parse_decimal_parts_with_options(bytes, &ParseOptions::global())
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_parts(s: &str, integer: &str, fraction: &str, exponent: i32, mantissa: u64, mantissa_exponent: i32, truncated: usize) {
+        let parts = parse_decimal_parts(s.as_bytes()).value;
+        assert_eq!(parts.integer(), integer.as_bytes());
+        assert_eq!(parts.fraction(), fraction.as_bytes());
+        assert_eq!(parts.exponent(), exponent);
+        assert_eq!(parts.mantissa(), mantissa);
+        assert_eq!(parts.mantissa_exponent(), mantissa_exponent);
+        assert_eq!(parts.truncated_digits(), truncated);
+    }
+
+    #[test]
+    fn parse_decimal_parts_test() {
+        check_parts("0", "0", "", 0, 0, 0, 0);
+        check_parts("1.5", "1", "5", 0, 15, -1, 0);
+        check_parts("001.500e3", "001", "500", 3, 15, 2, 0);
+        check_parts(".5", "", "5", 0, 5, -1, 0);
+        check_parts("1.", "1", "", 0, 1, 0, 0);
+        check_parts("0.000123", "0", "000123", 0, 123, -6, 0);
+        check_parts("1e-10", "1", "", -10, 1, -10, 0);
+        check_parts("1e3000000000", "1", "", i32::max_value(), 1, i32::max_value(), 0);
+        check_parts("123456789012345678901234567890", "123456789012345678901234567890", "", 0, 12345678901234567890, 10, 10);
+        check_parts("0.12345678901234567890123", "0", "12345678901234567890123", 0, 12345678901234567890, -20, 3);
+        check_parts("1.5000000000000000000000", "1", "5000000000000000000000", 0, 15, -1, 0);
+
+        let parts = parse_decimal_parts(b"-1.5").value;
+        assert!(parts.is_negative());
+        assert!(!parse_decimal_parts(b"+1.5").value.is_negative());
+    }
+
+    #[test]
+    fn parse_decimal_parts_error_test() {
+        assert_eq!(empty_error(DecimalParts::default()), parse_decimal_parts(b""));
+        assert_eq!(new_error(DecimalParts::default(), ErrorCode::EmptyMantissa, 1), parse_decimal_parts(b"-"));
+        assert_eq!(new_error(DecimalParts::default(), ErrorCode::EmptyMantissa, 0), parse_decimal_parts(b".e5"));
+        assert_eq!(invalid_digit_error(DecimalParts::default(), 0), parse_decimal_parts(b"NaN"));
+        assert_eq!(invalid_digit_error(DecimalParts::default(), 0), parse_decimal_parts(b"inf"));
+
+        let result = parse_decimal_parts(b"1.5e");
+        assert_eq!(result.error, new_error((), ErrorCode::EmptyExponent, 4).error);
+        assert_eq!(result.value.fraction(), b"5");
+        let result = parse_decimal_parts(b"1.5 ");
+        assert_eq!(result.error, invalid_digit_error((), 3).error);
+        assert_eq!(result.value.mantissa(), 15);
+        let result = parse_decimal_parts(b"0x1p1");
+        assert_eq!(result.error, invalid_digit_error((), 1).error);
+    }
+
+    #[test]
+    fn parse_decimal_parts_format_test() {
+        let mut options = ParseOptions::new();
+        options.set_format(NumberFormat::Json);
+        let result = parse_decimal_parts_with_options(b"-0.5e-10", &options);
+        assert_eq!(result.error.code, ErrorCode::Success);
+        assert_eq!(result.value.fraction(), b"5");
+        assert_eq!(result.value.exponent(), -10);
+        assert_eq!(parse_decimal_parts_with_options(b"01", &options).error.code, ErrorCode::InvalidLeadingZeros);
+        assert_eq!(parse_decimal_parts_with_options(b"1.", &options).error.code, ErrorCode::EmptyFraction);
+        assert_eq!(parse_decimal_parts_with_options(b"+1", &options).error.code, ErrorCode::InvalidPositiveMantissaSign);
+
+        options.set_format(NumberFormat::Rust);
+        let result = parse_decimal_parts_with_options(b"1__000_.5_e1_0", &options);
+        assert_eq!(result.error.code, ErrorCode::Success);
+        assert_eq!(result.value.integer(), b"1__000_");
+        assert_eq!(result.value.fraction(), b"5_");
+        assert_eq!(result.value.exponent(), 10);
+        assert_eq!(result.value.mantissa(), 10005);
+
+        options.set_format(NumberFormat::C);
+        assert_eq!(parse_decimal_parts_with_options(b"1.5f", &options).error.code, ErrorCode::Success);
+
+        options.set_locale(Locale::DE_DE);
+        let result = parse_decimal_parts_with_options(b"1.234,5", &options);
+        assert_eq!(result.error.code, ErrorCode::Success);
+        assert_eq!(result.value.integer(), b"1.234");
+        assert_eq!(result.value.fraction(), b"5");
+        assert_eq!(result.value.mantissa(), 12345);
+    }
+}
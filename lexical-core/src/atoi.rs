@@ -68,18 +68,58 @@ use util::*;
 
 // ALGORITHM
 
+/// Parse chunks of decimal digits at once, while the value does not overflow.
+///
+/// Only byte strings are parsed in chunks, 16 digits at a time for 64-bit
+/// and larger integers if SIMD is available, otherwise 8 digits at a time.
+/// Returns the number of parsed bytes, and leaves the value unchanged for
+/// the chunk which would overflow, so the caller may find the exact
+/// position where the input was truncated.
+#[inline]
+fn parse_chunks<T, C, Cb>(value: &mut T, radix: T, bytes: &[C], cb: Cb)
+    -> usize
+    where T: Integer,
+          C: CodeUnit,
+          Cb: Fn(T, T) -> Option<T>
+{
+    if radix != as_cast(10) || T::BITS < 32 {
+        return 0;
+    }
+    let bytes = match C::as_bytes(bytes) {
+        Some(bytes) => bytes,
+        None        => return 0,
+    };
+
+    let max_digits = if T::BITS >= 64 { 16 } else { 8 };
+    let mut index = 0;
+    while let Some((chunk, count)) = parse_digits(&index!(bytes[index..]), max_digits) {
+        let scale: T = match count {
+            16 => as_cast(10000000000000000u64),
+            _  => as_cast(100000000u64),
+        };
+        match value.checked_mul(scale).and_then(|v| cb(v, as_cast(chunk))) {
+            Some(v) => *value = v,
+            None    => break,
+        }
+        index += count;
+    }
+
+    index
+}
+
 /// Generate both the add and sub versions of unchecked.
 macro_rules! unchecked {
-    ($func:ident, $op:ident) => (
+    ($func:ident, $op:ident, $checked_op:ident) => (
         /// Returns the number of parsed bytes and the index where the input was
         /// truncated at.
         ///
         /// Don't trim leading zeros, since the value may be non-zero and
         /// therefore invalid.
         #[inline]
-        pub(crate) fn $func<'a, T>(value: &mut T, radix: T, bytes: &'a [u8])
-            -> (usize, Option<&'a u8>)
-            where T: Integer
+        pub(crate) fn $func<'a, T, C>(value: &mut T, radix: T, bytes: &'a [C])
+            -> (usize, Option<&'a C>)
+            where T: Integer,
+                  C: CodeUnit
         {
             // Continue while we have digits.
             // Don't check for overflow, we want to avoid as many conditions
@@ -90,7 +130,9 @@ macro_rules! unchecked {
             // otherwise it may give us invalid results elsewhere.
             let mut digit: T;
             let mut truncated = None;
-            for (i, c) in bytes.iter().enumerate() {
+            let start = parse_chunks(value, radix, bytes, |v, d| v.$checked_op(d));
+            let digits = &index!(bytes[start..]);
+            for (i, c) in digits.iter().enumerate() {
                 digit = as_cast(char_to_digit(*c));
                 if digit < radix {
                     let (v, o1) = value.overflowing_mul(radix);
@@ -100,7 +142,7 @@ macro_rules! unchecked {
                         truncated = Some(c);
                     }
                 } else {
-                    return (i, truncated);
+                    return (start + i, truncated);
                 }
             }
 
@@ -109,15 +151,16 @@ macro_rules! unchecked {
     );
 }
 
-unchecked!(unchecked_positive, overflowing_add);
-unchecked!(unchecked_negative, overflowing_sub);
+unchecked!(unchecked_positive, overflowing_add, checked_add);
+unchecked!(unchecked_negative, overflowing_sub, checked_sub);
 
 /// Unchecked callback for the string-to-integer parser.
 #[allow(dead_code)]
 #[inline]
-pub(crate) fn unchecked<'a, T>(value: &mut T, radix: T, bytes: &'a [u8], sign: Sign)
-    -> (usize, Option<&'a u8>)
-    where T: Integer
+pub(crate) fn unchecked<'a, T, C>(value: &mut T, radix: T, bytes: &'a [C], sign: Sign)
+    -> (usize, Option<&'a C>)
+    where T: Integer,
+          C: CodeUnit
 {
     match sign {
         Sign::Positive => unchecked_positive(value, radix, bytes),
@@ -135,9 +178,10 @@ macro_rules! checked {
         /// therefore invalid.
         #[cfg(feature = "correct")]
         #[inline]
-        pub(crate) fn $func<'a, T>(value: &mut T, radix: T, bytes: &'a [u8])
-            -> (usize, Option<&'a u8>)
-            where T: Integer
+        pub(crate) fn $func<'a, T, C>(value: &mut T, radix: T, bytes: &'a [C])
+            -> (usize, Option<&'a C>)
+            where T: Integer,
+                  C: CodeUnit
         {
             // Continue while we have digits.
             // Don't check for overflow, we want to avoid as many conditions
@@ -148,7 +192,9 @@ macro_rules! checked {
             // otherwise it may give us invalid results elsewhere.
             let mut digit: T;
             let mut truncated = None;
-            for (i, c) in bytes.iter().enumerate() {
+            let start = parse_chunks(value, radix, bytes, |v, d| v.$op(d));
+            let digits = &index!(bytes[start..]);
+            for (i, c) in digits.iter().enumerate() {
                 digit = as_cast(char_to_digit(*c));
                 if digit < radix {
                     // Only multiply to the radix and add the parsed digit if
@@ -165,7 +211,7 @@ macro_rules! checked {
                         }
                     }
                 } else {
-                    return (i, truncated);
+                    return (start + i, truncated);
                 }
             }
 
@@ -181,9 +227,10 @@ checked!(checked_negative, checked_sub);
 #[allow(dead_code)]
 #[cfg(feature = "correct")]
 #[inline]
-pub(crate) fn checked<'a, T>(value: &mut T, radix: T, bytes: &'a [u8], sign: Sign)
-    -> (usize, Option<&'a u8>)
-    where T: Integer
+pub(crate) fn checked<'a, T, C>(value: &mut T, radix: T, bytes: &'a [C], sign: Sign)
+    -> (usize, Option<&'a C>)
+    where T: Integer,
+          C: CodeUnit
 {
     match sign {
         Sign::Positive => checked_positive(value, radix, bytes),
@@ -191,22 +238,349 @@ pub(crate) fn checked<'a, T>(value: &mut T, radix: T, bytes: &'a [u8], sign: Sig
     }
 }
 
-/// Handle +/- numbers and forward to implementation.
+// DIGIT SEPARATORS
+
+/// Get the number of bytes consumed by digits and valid digit separators.
+///
+/// Separators are only consumed if their placement is valid for the
+/// component, and the component ends at the first invalid separator.
+/// Returns 0 if the component does not contain any digits.
+#[inline]
+pub(crate) fn separator_len<C: CodeUnit>(radix: u32, bytes: &[C], separator: u8, rules: SeparatorRules)
+    -> usize
+{
+    let is_digit = |c: C| char_to_digit(c).as_u32() < radix;
+    let count_separators = |bytes: &[C]| bytes.iter().take_while(|&&c| c.to_byte() == separator).count();
+    let is_valid = |count: usize, allowed: bool| allowed && (count == 1 || rules.consecutive);
+
+    // Check for leading digit separators.
+    let mut index = count_separators(bytes);
+    if index != 0 && !is_valid(index, rules.leading) {
+        return 0;
+    }
+
+    // Process the digits and any internal or trailing separators.
+    let mut end = 0;
+    loop {
+        // Consume all digits.
+        let start = index;
+        index += bytes[index..].iter().take_while(|&&c| is_digit(c)).count();
+        if index == start {
+            // No digits, leading or internal separators must be rejected.
+            return end;
+        }
+        end = index;
+
+        // Consume separators.
+        let count = count_separators(&bytes[index..]);
+        if count == 0 {
+            return end;
+        }
+        index += count;
+        let is_internal = bytes.get(index).map_or(false, |&c| is_digit(c));
+        if is_internal && !is_valid(count, rules.internal) {
+            return end;
+        } else if !is_internal {
+            if is_valid(count, rules.trailing) {
+                end = index;
+            }
+            return end;
+        }
+    }
+}
+
+/// Generate parsers that skip valid digit separators.
+macro_rules! separator {
+    ($func:ident, $cb:ident) => (
+        /// Returns the number of parsed bytes, including digit separators,
+        /// and the index where the input was truncated at.
+        #[inline]
+        pub(crate) fn $func<'a, T, C>(value: &mut T, radix: T, bytes: &'a [C], separator: u8, rules: SeparatorRules)
+            -> (usize, Option<&'a C>)
+            where T: Integer,
+                  C: CodeUnit
+        {
+            // Validate the separators first, so every separator within
+            // `len` may just be skipped.
+            let len = separator_len(radix.as_u32(), bytes, separator, rules);
+            let mut truncated = None;
+            for digits in index!(bytes[..len]).split(|&c| c.to_byte() == separator) {
+                let (_, t) = $cb(value, radix, digits);
+                truncated = truncated.or(t);
+            }
+
+            (len, truncated)
+        }
+    );
+}
+
+separator!(unchecked_separator_positive, unchecked_positive);
+separator!(unchecked_separator_negative, unchecked_negative);
+
+/// Unchecked callback for the string-to-integer parser with digit separators.
 #[inline]
-pub(crate) fn filter_sign<'a, T, Cb>(radix: u32, bytes: &'a [u8], cb: Cb)
-    -> (T, Sign, usize, Option<&'a u8>)
+pub(crate) fn unchecked_separator<'a, T, C>(value: &mut T, radix: T, bytes: &'a [C], sign: Sign, separator: u8, rules: SeparatorRules)
+    -> (usize, Option<&'a C>)
     where T: Integer,
-          Cb: FnOnce(&mut T, T, &'a [u8], Sign) -> (usize, Option<&'a u8>)
+          C: CodeUnit
 {
-    let (sign_bytes, sign) = match bytes.get(0) {
-        Some(&b'+') => (1, Sign::Positive),
-        Some(&b'-') => (1, Sign::Negative),
-        _           => (0, Sign::Positive),
+    match sign {
+        Sign::Positive => unchecked_separator_positive(value, radix, bytes, separator, rules),
+        Sign::Negative => unchecked_separator_negative(value, radix, bytes, separator, rules),
+    }
+}
+
+// DIGIT GROUPING
+
+/// Get the number of bytes consumed by digits and valid group separators.
+///
+/// Group separators are optional, however, if any are present, every
+/// group must match the grouping pattern, otherwise the component ends
+/// at the first group separator. The most-significant group may be
+/// shorter than the pattern.
+#[inline]
+pub(crate) fn group_len<C: CodeUnit>(radix: u32, bytes: &[C], locale: &Locale)
+    -> usize
+{
+    let separator = locale.group_separator();
+    let skip = C::encoded_len(separator);
+    let count_digits = |bytes: &[C]| {
+        bytes.iter().take_while(|&&c| char_to_digit(c).as_u32() < radix).count()
     };
 
-    if bytes.len() > sign_bytes {
-        // `bytes.len() > sign_bytes`, so this range is always valid.
-        let bytes = &index!(bytes[sign_bytes..]);
+    // Find the end of the groups, and the number of separators, since
+    // the grouping pattern starts from the least-significant digits.
+    let first = count_digits(bytes);
+    let mut index = first;
+    let mut groups = 0;
+    while first != 0 && C::starts_with_encoded(&index!(bytes[index..]), separator) {
+        let count = count_digits(&index!(bytes[index + skip..]));
+        if count == 0 {
+            break;
+        }
+        index += skip + count;
+        groups += 1;
+    }
+    if groups == 0 || first > locale.group_size(groups) {
+        return first;
+    }
+
+    // Validate the size of every group after the first.
+    let mut start = first;
+    for group in (0..groups).rev() {
+        start += skip;
+        let count = count_digits(&index!(bytes[start..]));
+        if count != locale.group_size(group) {
+            return first;
+        }
+        start += count;
+    }
+
+    index
+}
+
+/// Generate parsers that skip valid group separators.
+macro_rules! group {
+    ($func:ident, $cb:ident) => (
+        /// Returns the number of parsed bytes, including group separators,
+        /// and the index where the input was truncated at.
+        #[inline]
+        pub(crate) fn $func<'a, T, C>(value: &mut T, radix: T, bytes: &'a [C], locale: &Locale)
+            -> (usize, Option<&'a C>)
+            where T: Integer,
+                  C: CodeUnit
+        {
+            // Validate the groups first, so every separator within
+            // `len` may just be skipped.
+            let len = group_len(radix.as_u32(), bytes, locale);
+            let skip = C::encoded_len(locale.group_separator());
+            let mut digits = &index!(bytes[..len]);
+            let mut truncated = None;
+            loop {
+                let (count, t) = $cb(value, radix, digits);
+                truncated = truncated.or(t);
+                if count == digits.len() {
+                    break;
+                }
+                digits = &index!(digits[count + skip..]);
+            }
+
+            (len, truncated)
+        }
+    );
+}
+
+group!(unchecked_group_positive, unchecked_positive);
+group!(unchecked_group_negative, unchecked_negative);
+
+/// Unchecked callback for the string-to-integer parser with digit grouping.
+#[inline]
+pub(crate) fn unchecked_group<'a, T, C>(value: &mut T, radix: T, bytes: &'a [C], sign: Sign, locale: &Locale)
+    -> (usize, Option<&'a C>)
+    where T: Integer,
+          C: CodeUnit
+{
+    match sign {
+        Sign::Positive => unchecked_group_positive(value, radix, bytes, locale),
+        Sign::Negative => unchecked_group_negative(value, radix, bytes, locale),
+    }
+}
+
+// FORMAT
+
+/// Get the number of bytes consumed by digits, and by valid digit
+/// separators if enabled.
+#[inline]
+pub(crate) fn digits_len<C: CodeUnit>(radix: u32, bytes: &[C], separator: Option<u8>, rules: SeparatorRules)
+    -> usize
+{
+    match separator {
+        None    => bytes.iter().take_while(|&&c| char_to_digit(c).as_u32() < radix).count(),
+        Some(s) => separator_len(radix, bytes, s, rules),
+    }
+}
+
+/// Get the number of bytes consumed by the integer component, including
+/// valid digit separators, or group separators if digits are grouped.
+#[inline]
+pub(crate) fn integer_len<C: CodeUnit>(radix: u32, bytes: &[C], options: &ParseOptions)
+    -> usize
+{
+    let locale = options.locale();
+    match options.separator() {
+        _ if locale.is_grouped() => group_len(radix, bytes, &locale),
+        separator                => {
+            let rules = options.digit_separator_flags().integer_rules();
+            digits_len(radix, bytes, separator, rules)
+        },
+    }
+}
+
+/// Get the separator rules for the integer digits after the radix prefix.
+#[inline]
+fn prefix_rules(options: &ParseOptions, prefix: usize) -> SeparatorRules {
+    let flags = options.digit_separator_flags();
+    let mut rules = flags.integer_rules();
+    // Only `0x`, `0o`, and `0b` may be followed by a separator, since the
+    // octal prefix is always followed by an octal digit.
+    rules.leading |= prefix == 2 && flags.contains(DigitSeparator::PREFIX_TRAILING);
+    rules
+}
+
+/// Get the index of the first digit, if the digits have a leading zero.
+///
+/// `bytes` must only contain digits and digit or group separators.
+/// Digits are always alphanumeric, while separators never are.
+#[inline]
+pub(crate) fn leading_zero_index<C: CodeUnit>(bytes: &[C])
+    -> Option<usize>
+{
+    let mut iter = bytes.iter().map(|&c| c.to_byte()).enumerate().filter(|&(_, c)| c.is_ascii_alphanumeric());
+    match (iter.next(), iter.next()) {
+        (Some((i, b'0')), Some(_)) => Some(i),
+        _                          => None,
+    }
+}
+
+/// Get the radix and the length of the radix prefix after the sign.
+///
+/// Only decimal integers may have a radix prefix, so any other radix
+/// is returned unchanged, without a prefix.
+#[inline]
+fn radix_prefix<C: CodeUnit>(radix: u32, bytes: &[C], grammar: NumberGrammar)
+    -> (u32, usize)
+{
+    let (sign_bytes, _) = parse_sign(bytes);
+    let prefix = radix == 10 && grammar.contains(NumberGrammar::RADIX_PREFIX);
+    let octal = radix == 10 && grammar.contains(NumberGrammar::OCTAL_PREFIX);
+    match (byte_at(bytes, sign_bytes), byte_at(bytes, sign_bytes + 1)) {
+        (Some(b'0'), Some(b'x')) | (Some(b'0'), Some(b'X')) if prefix => (16, 2),
+        (Some(b'0'), Some(b'o')) | (Some(b'0'), Some(b'O')) if prefix => (8, 2),
+        (Some(b'0'), Some(b'b')) | (Some(b'0'), Some(b'B')) if prefix => (2, 2),
+        (Some(b'0'), Some(c)) if octal && c.is_ascii_digit()          => (8, 1),
+        _                                                              => (radix, 0),
+    }
+}
+
+/// Get the number of bytes of the integer valid for the number grammar.
+///
+/// Returns the index of the first byte which violates the grammar,
+/// and the error code for the violation, or the length of the buffer
+/// and success if the grammar is not violated.
+#[inline]
+fn grammar_len<C: CodeUnit>(radix: u32, bytes: &[C], prefix: usize, grammar: NumberGrammar, options: &ParseOptions)
+    -> (usize, ErrorCode)
+{
+    let (sign_bytes, sign) = parse_sign(bytes);
+    if sign_bytes == 1 && sign == Sign::Positive && grammar.contains(NumberGrammar::NO_POSITIVE_MANTISSA_SIGN) {
+        return (0, ErrorCode::InvalidPositiveMantissaSign);
+    }
+
+    // The radix prefix is always followed by at least 1 byte, if present.
+    let start = sign_bytes + prefix;
+    let digits = &index!(bytes[start..]);
+    let len = match options.locale().is_grouped() {
+        true  => integer_len(radix, digits, options),
+        false => digits_len(radix, digits, options.separator(), prefix_rules(options, prefix)),
+    };
+    if prefix == 2 && len == 0 {
+        // Only `0x`, `0o`, and `0b` may lack digits, since the octal
+        // prefix is always followed by a digit.
+        return (start, ErrorCode::EmptyInteger);
+    } else if prefix == 0 && grammar.contains(NumberGrammar::NO_INTEGER_LEADING_ZEROS) {
+        if let Some(index) = leading_zero_index(&index!(digits[..len])) {
+            return (start + index, ErrorCode::InvalidLeadingZeros);
+        }
+    }
+
+    (bytes.len(), ErrorCode::Success)
+}
+
+/// Convert the parsed value and grammar validation to the result.
+///
+/// Overflow takes precedence over grammar errors, while grammar errors
+/// are reported at the index of the violation, since all preceding bytes
+/// were validated.
+#[inline]
+fn grammar_result<T>(value: T, processed: usize, truncated: bool, len: usize, code: ErrorCode)
+    -> (T, usize, ErrorCode)
+{
+    if truncated {
+        (value, processed, ErrorCode::Overflow)
+    } else if code != ErrorCode::Success {
+        (value, len, code)
+    } else {
+        (value, processed, ErrorCode::Success)
+    }
+}
+
+// SIGN
+
+/// Parse the sign from the start of the buffer.
+#[inline]
+fn parse_sign<C: CodeUnit>(bytes: &[C]) -> (usize, Sign) {
+    match byte_at(bytes, 0) {
+        Some(b'+') => (1, Sign::Positive),
+        Some(b'-') => (1, Sign::Negative),
+        _           => (0, Sign::Positive),
+    }
+}
+
+/// Handle +/- numbers and forward to implementation.
+///
+/// Skips `prefix` bytes of radix prefix after the sign.
+#[inline]
+pub(crate) fn filter_sign<'a, T, C, Cb>(radix: u32, bytes: &'a [C], prefix: usize, cb: Cb)
+    -> (T, Sign, usize, Option<&'a C>)
+    where T: Integer,
+          C: CodeUnit,
+          Cb: FnOnce(&mut T, T, &'a [C], Sign) -> (usize, Option<&'a C>)
+{
+    let (sign_bytes, sign) = parse_sign(bytes);
+    let start = sign_bytes + prefix;
+    if bytes.len() > start {
+        // `bytes.len() > start`, so this range is always valid.
+        let bytes = &index!(bytes[start..]);
 
         // Trim the leading 0s here, where we can guarantee the value is 0,
         // and therefore trimming these leading 0s is actually valid.
@@ -215,37 +589,96 @@ pub(crate) fn filter_sign<'a, T, Cb>(radix: u32, bytes: &'a [u8], cb: Cb)
         // Initialize a 0 version of our value, and invoke the low-level callback.
         let mut value: T = T::ZERO;
         let (len, truncated) = cb(&mut value, as_cast(radix), bytes, sign);
-        (value, sign, sign_bytes + count + len, truncated)
+        (value, sign, start + count + len, truncated)
     } else {
         (T::ZERO, sign, 0, None)
     }
 }
 
-/// Handle unsigned +/- numbers and forward to implied implementation.
-//  Can just use local namespace
+/// Handle +/- numbers with digit separators and forward to implementation.
+///
+/// Don't trim leading zeros, since the callback must validate the
+/// placement of any separators following them. Skips `prefix` bytes
+/// of radix prefix after the sign.
 #[inline]
-pub(crate) fn unsigned<'a, T, Cb>(radix: u32, bytes: &'a [u8], cb: Cb)
+pub(crate) fn filter_sign_separator<'a, T, C, Cb>(radix: u32, bytes: &'a [C], prefix: usize, cb: Cb)
+    -> (T, Sign, usize, Option<&'a C>)
+    where T: Integer,
+          C: CodeUnit,
+          Cb: FnOnce(&mut T, T, &'a [C], Sign) -> (usize, Option<&'a C>)
+{
+    let (sign_bytes, sign) = parse_sign(bytes);
+    let start = sign_bytes + prefix;
+    if bytes.len() > start {
+        // `bytes.len() > start`, so this range is always valid.
+        let bytes = &index!(bytes[start..]);
+        let mut value: T = T::ZERO;
+        let (len, truncated) = cb(&mut value, as_cast(radix), bytes, sign);
+        (value, sign, start + len, truncated)
+    } else {
+        (T::ZERO, sign, 0, None)
+    }
+}
+
+/// Convert the parsed sign and value to the unsigned result.
+#[inline]
+fn unsigned_result<T>(value: T, sign: Sign, processed: usize, truncated: bool)
     -> (T, usize, bool)
-    where T: UnsignedInteger,
-          Cb: FnOnce(&mut T, T, &'a [u8], Sign) -> (usize, Option<&'a u8>)
 {
-    let (value, sign, processed, truncated) = filter_sign::<T, Cb>(radix, bytes, cb);
     match sign {
         // Need to return 0 early if we have a 0 value.
-        Sign::Negative => (value, 0, truncated.is_some()),
-        Sign::Positive => (value, processed, truncated.is_some()),
+        Sign::Negative => (value, 0, truncated),
+        Sign::Positive => (value, processed, truncated),
     }
 }
 
+/// Handle unsigned +/- numbers and forward to implied implementation.
+//  Can just use local namespace
+#[inline]
+pub(crate) fn unsigned<'a, T, C, Cb>(radix: u32, bytes: &'a [C], prefix: usize, cb: Cb)
+    -> (T, usize, bool)
+    where T: UnsignedInteger,
+          C: CodeUnit,
+          Cb: FnOnce(&mut T, T, &'a [C], Sign) -> (usize, Option<&'a C>)
+{
+    let (value, sign, processed, truncated) = filter_sign::<T, C, Cb>(radix, bytes, prefix, cb);
+    unsigned_result(value, sign, processed, truncated.is_some())
+}
+
+/// Handle unsigned +/- numbers with digit separators.
+#[inline]
+pub(crate) fn unsigned_separator<'a, T, C, Cb>(radix: u32, bytes: &'a [C], prefix: usize, cb: Cb)
+    -> (T, usize, bool)
+    where T: UnsignedInteger,
+          C: CodeUnit,
+          Cb: FnOnce(&mut T, T, &'a [C], Sign) -> (usize, Option<&'a C>)
+{
+    let (value, sign, processed, truncated) = filter_sign_separator::<T, C, Cb>(radix, bytes, prefix, cb);
+    unsigned_result(value, sign, processed, truncated.is_some())
+}
+
 /// Handle signed +/- numbers and forward to implied implementation.
 //  Can just use local namespace
 #[inline]
-pub(crate) fn signed<'a, T, Cb>(radix: u32, bytes: &'a [u8], cb: Cb)
+pub(crate) fn signed<'a, T, C, Cb>(radix: u32, bytes: &'a [C], prefix: usize, cb: Cb)
     -> (T, usize, bool)
     where T: SignedInteger,
-          Cb: FnOnce(&mut T, T, &'a [u8], Sign) -> (usize, Option<&'a u8>)
+          C: CodeUnit,
+          Cb: FnOnce(&mut T, T, &'a [C], Sign) -> (usize, Option<&'a C>)
 {
-    let (value, _, processed, truncated) = filter_sign::<T, Cb>(radix, bytes, cb);
+    let (value, _, processed, truncated) = filter_sign::<T, C, Cb>(radix, bytes, prefix, cb);
+    (value, processed, truncated.is_some())
+}
+
+/// Handle signed +/- numbers with digit separators.
+#[inline]
+pub(crate) fn signed_separator<'a, T, C, Cb>(radix: u32, bytes: &'a [C], prefix: usize, cb: Cb)
+    -> (T, usize, bool)
+    where T: SignedInteger,
+          C: CodeUnit,
+          Cb: FnOnce(&mut T, T, &'a [C], Sign) -> (usize, Option<&'a C>)
+{
+    let (value, _, processed, truncated) = filter_sign_separator::<T, C, Cb>(radix, bytes, prefix, cb);
     (value, processed, truncated.is_some())
 }
 
@@ -253,49 +686,119 @@ pub(crate) fn signed<'a, T, Cb>(radix: u32, bytes: &'a [u8], cb: Cb)
 
 /// Expand the generic unsigned atoi function for specified types.
 macro_rules! wrap_unsigned {
-    ($func:ident, $t:tt) => (
-        /// Parse unsigned integer and return value, subslice read, and if truncated.
+    ($func:ident, $options_func:ident, $t:tt) => (
+        /// Parse unsigned integer with custom options and return value, subslice read, and error code.
+        #[inline]
+        fn $options_func<C: CodeUnit>(radix: u8, bytes: &[C], options: &ParseOptions)
+            -> ($t, usize, ErrorCode)
+        {
+            // Detect the radix prefix, and only parse the bytes valid
+            // for the number grammar.
+            let grammar = options.grammar();
+            let (radix, prefix, len, code) = match grammar == NumberGrammar::STANDARD {
+                true  => (radix.into(), 0, bytes.len(), ErrorCode::Success),
+                false => {
+                    let (radix, prefix) = radix_prefix(radix.into(), bytes, grammar);
+                    let (len, code) = grammar_len(radix, bytes, prefix, grammar, options);
+                    (radix, prefix, len, code)
+                },
+            };
+            let bytes = &index!(bytes[..len]);
+
+            let locale = options.locale();
+            let (value, processed, truncated) = match options.separator() {
+                _ if locale.is_grouped() => {
+                    unsigned_separator::<$t, _, _>(radix, bytes, prefix, |v, r, b, s| {
+                        unchecked_group::<$t, C>(v, r, b, s, &locale)
+                    })
+                },
+                None            => unsigned::<$t, _, _>(radix, bytes, prefix, unchecked::<$t, C>),
+                Some(separator) => {
+                    let rules = prefix_rules(options, prefix);
+                    unsigned_separator::<$t, _, _>(radix, bytes, prefix, |v, r, b, s| {
+                        unchecked_separator::<$t, C>(v, r, b, s, separator, rules)
+                    })
+                },
+            };
+            grammar_result(value, processed, truncated, len, code)
+        }
+
+        /// Parse unsigned integer and return value, subslice read, and error code.
         #[inline]
-        fn $func(radix: u8, bytes: &[u8])
-            -> ($t, usize, bool)
+        fn $func<C: CodeUnit>(radix: u8, bytes: &[C])
+            -> ($t, usize, ErrorCode)
         {
-            let (value, len, truncated) = unsigned::<$t, _>(radix.into(), bytes, unchecked::<$t>);
-            (value, len, truncated)
+            $options_func(radix, bytes, &ParseOptions::new())
         }
     )
 }
 
-wrap_unsigned!(atou8_impl, u8);
-wrap_unsigned!(atou16_impl, u16);
-wrap_unsigned!(atou32_impl, u32);
-wrap_unsigned!(atou64_impl, u64);
-wrap_unsigned!(atousize_impl, usize);
+wrap_unsigned!(atou8_impl, atou8_options_impl, u8);
+wrap_unsigned!(atou16_impl, atou16_options_impl, u16);
+wrap_unsigned!(atou32_impl, atou32_options_impl, u32);
+wrap_unsigned!(atou64_impl, atou64_options_impl, u64);
+wrap_unsigned!(atousize_impl, atousize_options_impl, usize);
 
 #[cfg(has_i128)]
-wrap_unsigned!(atou128_impl, u128);
+wrap_unsigned!(atou128_impl, atou128_options_impl, u128);
 
 /// Expand the generic signed atoi function for specified types.
 macro_rules! wrap_signed {
-    ($func:ident, $t:tt) => (
-        /// Parse signed integer and return value, subslice read, and if truncated.
+    ($func:ident, $options_func:ident, $t:tt) => (
+        /// Parse signed integer with custom options and return value, subslice read, and error code.
         #[inline]
-        fn $func(radix: u8, bytes: &[u8])
-            -> ($t, usize, bool)
+        fn $options_func<C: CodeUnit>(radix: u8, bytes: &[C], options: &ParseOptions)
+            -> ($t, usize, ErrorCode)
         {
-            let (value, len, truncated) = signed::<$t, _>(radix.into(), bytes, unchecked::<$t>);
-            (value, len, truncated)
+            // Detect the radix prefix, and only parse the bytes valid
+            // for the number grammar.
+            let grammar = options.grammar();
+            let (radix, prefix, len, code) = match grammar == NumberGrammar::STANDARD {
+                true  => (radix.into(), 0, bytes.len(), ErrorCode::Success),
+                false => {
+                    let (radix, prefix) = radix_prefix(radix.into(), bytes, grammar);
+                    let (len, code) = grammar_len(radix, bytes, prefix, grammar, options);
+                    (radix, prefix, len, code)
+                },
+            };
+            let bytes = &index!(bytes[..len]);
+
+            let locale = options.locale();
+            let (value, processed, truncated) = match options.separator() {
+                _ if locale.is_grouped() => {
+                    signed_separator::<$t, _, _>(radix, bytes, prefix, |v, r, b, s| {
+                        unchecked_group::<$t, C>(v, r, b, s, &locale)
+                    })
+                },
+                None            => signed::<$t, _, _>(radix, bytes, prefix, unchecked::<$t, C>),
+                Some(separator) => {
+                    let rules = prefix_rules(options, prefix);
+                    signed_separator::<$t, _, _>(radix, bytes, prefix, |v, r, b, s| {
+                        unchecked_separator::<$t, C>(v, r, b, s, separator, rules)
+                    })
+                },
+            };
+            grammar_result(value, processed, truncated, len, code)
+        }
+
+        /// Parse signed integer and return value, subslice read, and error code.
+        #[inline]
+        fn $func<C: CodeUnit>(radix: u8, bytes: &[C])
+            -> ($t, usize, ErrorCode)
+        {
+            $options_func(radix, bytes, &ParseOptions::new())
         }
     )
 }
 
-wrap_signed!(atoi8_impl, i8);
-wrap_signed!(atoi16_impl, i16);
-wrap_signed!(atoi32_impl, i32);
-wrap_signed!(atoi64_impl, i64);
-wrap_signed!(atoisize_impl, isize);
+wrap_signed!(atoi8_impl, atoi8_options_impl, i8);
+wrap_signed!(atoi16_impl, atoi16_options_impl, i16);
+wrap_signed!(atoi32_impl, atoi32_options_impl, i32);
+wrap_signed!(atoi64_impl, atoi64_options_impl, i64);
+wrap_signed!(atoisize_impl, atoisize_options_impl, isize);
 
 #[cfg(has_i128)]
-wrap_signed!(atoi128_impl, i128);
+wrap_signed!(atoi128_impl, atoi128_options_impl, i128);
 
 // RANGE API (FFI)
 generate_from_range_api!(atou8_range, atou8_radix_range, u8, atou8_impl);
@@ -324,6 +827,32 @@ generate_try_from_range_api!(try_atoisize_range, try_atoisize_radix_range, isize
 #[cfg(has_i128)] generate_try_from_range_api!(try_atou128_range, try_atou128_radix_range, u128, atou128_impl);
 #[cfg(has_i128)] generate_try_from_range_api!(try_atoi128_range, try_atoi128_radix_range, i128, atoi128_impl);
 
+generate_from_range_options_api!(atou8_range_with_options, atou8_radix_range_with_options, u8, atou8_options_impl);
+generate_from_range_options_api!(atou16_range_with_options, atou16_radix_range_with_options, u16, atou16_options_impl);
+generate_from_range_options_api!(atou32_range_with_options, atou32_radix_range_with_options, u32, atou32_options_impl);
+generate_from_range_options_api!(atou64_range_with_options, atou64_radix_range_with_options, u64, atou64_options_impl);
+generate_from_range_options_api!(atousize_range_with_options, atousize_radix_range_with_options, usize, atousize_options_impl);
+generate_from_range_options_api!(atoi8_range_with_options, atoi8_radix_range_with_options, i8, atoi8_options_impl);
+generate_from_range_options_api!(atoi16_range_with_options, atoi16_radix_range_with_options, i16, atoi16_options_impl);
+generate_from_range_options_api!(atoi32_range_with_options, atoi32_radix_range_with_options, i32, atoi32_options_impl);
+generate_from_range_options_api!(atoi64_range_with_options, atoi64_radix_range_with_options, i64, atoi64_options_impl);
+generate_from_range_options_api!(atoisize_range_with_options, atoisize_radix_range_with_options, isize, atoisize_options_impl);
+generate_try_from_range_options_api!(try_atou8_range_with_options, try_atou8_radix_range_with_options, u8, atou8_options_impl);
+generate_try_from_range_options_api!(try_atou16_range_with_options, try_atou16_radix_range_with_options, u16, atou16_options_impl);
+generate_try_from_range_options_api!(try_atou32_range_with_options, try_atou32_radix_range_with_options, u32, atou32_options_impl);
+generate_try_from_range_options_api!(try_atou64_range_with_options, try_atou64_radix_range_with_options, u64, atou64_options_impl);
+generate_try_from_range_options_api!(try_atousize_range_with_options, try_atousize_radix_range_with_options, usize, atousize_options_impl);
+generate_try_from_range_options_api!(try_atoi8_range_with_options, try_atoi8_radix_range_with_options, i8, atoi8_options_impl);
+generate_try_from_range_options_api!(try_atoi16_range_with_options, try_atoi16_radix_range_with_options, i16, atoi16_options_impl);
+generate_try_from_range_options_api!(try_atoi32_range_with_options, try_atoi32_radix_range_with_options, i32, atoi32_options_impl);
+generate_try_from_range_options_api!(try_atoi64_range_with_options, try_atoi64_radix_range_with_options, i64, atoi64_options_impl);
+generate_try_from_range_options_api!(try_atoisize_range_with_options, try_atoisize_radix_range_with_options, isize, atoisize_options_impl);
+
+#[cfg(has_i128)] generate_from_range_options_api!(atou128_range_with_options, atou128_radix_range_with_options, u128, atou128_options_impl);
+#[cfg(has_i128)] generate_from_range_options_api!(atoi128_range_with_options, atoi128_radix_range_with_options, i128, atoi128_options_impl);
+#[cfg(has_i128)] generate_try_from_range_options_api!(try_atou128_range_with_options, try_atou128_radix_range_with_options, u128, atou128_options_impl);
+#[cfg(has_i128)] generate_try_from_range_options_api!(try_atoi128_range_with_options, try_atoi128_radix_range_with_options, i128, atoi128_options_impl);
+
 // SLICE API
 generate_from_slice_api!(atou8_slice, atou8_radix_slice, u8, atou8_impl);
 generate_from_slice_api!(atou16_slice, atou16_radix_slice, u16, atou16_impl);
@@ -351,6 +880,61 @@ generate_try_from_slice_api!(try_atoisize_slice, try_atoisize_radix_slice, isize
 #[cfg(has_i128)] generate_try_from_slice_api!(try_atou128_slice, try_atou128_radix_slice, u128, atou128_impl);
 #[cfg(has_i128)] generate_try_from_slice_api!(try_atoi128_slice, try_atoi128_radix_slice, i128, atoi128_impl);
 
+generate_from_slice_options_api!(atou8_slice_with_options, atou8_radix_slice_with_options, u8, atou8_options_impl);
+generate_from_slice_options_api!(atou16_slice_with_options, atou16_radix_slice_with_options, u16, atou16_options_impl);
+generate_from_slice_options_api!(atou32_slice_with_options, atou32_radix_slice_with_options, u32, atou32_options_impl);
+generate_from_slice_options_api!(atou64_slice_with_options, atou64_radix_slice_with_options, u64, atou64_options_impl);
+generate_from_slice_options_api!(atousize_slice_with_options, atousize_radix_slice_with_options, usize, atousize_options_impl);
+generate_from_slice_options_api!(atoi8_slice_with_options, atoi8_radix_slice_with_options, i8, atoi8_options_impl);
+generate_from_slice_options_api!(atoi16_slice_with_options, atoi16_radix_slice_with_options, i16, atoi16_options_impl);
+generate_from_slice_options_api!(atoi32_slice_with_options, atoi32_radix_slice_with_options, i32, atoi32_options_impl);
+generate_from_slice_options_api!(atoi64_slice_with_options, atoi64_radix_slice_with_options, i64, atoi64_options_impl);
+generate_from_slice_options_api!(atoisize_slice_with_options, atoisize_radix_slice_with_options, isize, atoisize_options_impl);
+generate_try_from_slice_options_api!(try_atou8_slice_with_options, try_atou8_radix_slice_with_options, u8, atou8_options_impl);
+generate_try_from_slice_options_api!(try_atou16_slice_with_options, try_atou16_radix_slice_with_options, u16, atou16_options_impl);
+generate_try_from_slice_options_api!(try_atou32_slice_with_options, try_atou32_radix_slice_with_options, u32, atou32_options_impl);
+generate_try_from_slice_options_api!(try_atou64_slice_with_options, try_atou64_radix_slice_with_options, u64, atou64_options_impl);
+generate_try_from_slice_options_api!(try_atousize_slice_with_options, try_atousize_radix_slice_with_options, usize, atousize_options_impl);
+generate_try_from_slice_options_api!(try_atoi8_slice_with_options, try_atoi8_radix_slice_with_options, i8, atoi8_options_impl);
+generate_try_from_slice_options_api!(try_atoi16_slice_with_options, try_atoi16_radix_slice_with_options, i16, atoi16_options_impl);
+generate_try_from_slice_options_api!(try_atoi32_slice_with_options, try_atoi32_radix_slice_with_options, i32, atoi32_options_impl);
+generate_try_from_slice_options_api!(try_atoi64_slice_with_options, try_atoi64_radix_slice_with_options, i64, atoi64_options_impl);
+generate_try_from_slice_options_api!(try_atoisize_slice_with_options, try_atoisize_radix_slice_with_options, isize, atoisize_options_impl);
+
+#[cfg(has_i128)] generate_from_slice_options_api!(atou128_slice_with_options, atou128_radix_slice_with_options, u128, atou128_options_impl);
+#[cfg(has_i128)] generate_from_slice_options_api!(atoi128_slice_with_options, atoi128_radix_slice_with_options, i128, atoi128_options_impl);
+#[cfg(has_i128)] generate_try_from_slice_options_api!(try_atou128_slice_with_options, try_atou128_radix_slice_with_options, u128, atou128_options_impl);
+#[cfg(has_i128)] generate_try_from_slice_options_api!(try_atoi128_slice_with_options, try_atoi128_radix_slice_with_options, i128, atoi128_options_impl);
+
+// PARTIAL API
+generate_partial_from_slice_api!(atou8_partial_slice, atou8_partial_radix_slice, u8, atou8_impl);
+generate_partial_from_slice_api!(atou16_partial_slice, atou16_partial_radix_slice, u16, atou16_impl);
+generate_partial_from_slice_api!(atou32_partial_slice, atou32_partial_radix_slice, u32, atou32_impl);
+generate_partial_from_slice_api!(atou64_partial_slice, atou64_partial_radix_slice, u64, atou64_impl);
+generate_partial_from_slice_api!(atousize_partial_slice, atousize_partial_radix_slice, usize, atousize_impl);
+generate_partial_from_slice_api!(atoi8_partial_slice, atoi8_partial_radix_slice, i8, atoi8_impl);
+generate_partial_from_slice_api!(atoi16_partial_slice, atoi16_partial_radix_slice, i16, atoi16_impl);
+generate_partial_from_slice_api!(atoi32_partial_slice, atoi32_partial_radix_slice, i32, atoi32_impl);
+generate_partial_from_slice_api!(atoi64_partial_slice, atoi64_partial_radix_slice, i64, atoi64_impl);
+generate_partial_from_slice_api!(atoisize_partial_slice, atoisize_partial_radix_slice, isize, atoisize_impl);
+
+#[cfg(has_i128)] generate_partial_from_slice_api!(atou128_partial_slice, atou128_partial_radix_slice, u128, atou128_impl);
+#[cfg(has_i128)] generate_partial_from_slice_api!(atoi128_partial_slice, atoi128_partial_radix_slice, i128, atoi128_impl);
+
+generate_partial_from_slice_options_api!(atou8_partial_slice_with_options, atou8_partial_radix_slice_with_options, u8, atou8_options_impl);
+generate_partial_from_slice_options_api!(atou16_partial_slice_with_options, atou16_partial_radix_slice_with_options, u16, atou16_options_impl);
+generate_partial_from_slice_options_api!(atou32_partial_slice_with_options, atou32_partial_radix_slice_with_options, u32, atou32_options_impl);
+generate_partial_from_slice_options_api!(atou64_partial_slice_with_options, atou64_partial_radix_slice_with_options, u64, atou64_options_impl);
+generate_partial_from_slice_options_api!(atousize_partial_slice_with_options, atousize_partial_radix_slice_with_options, usize, atousize_options_impl);
+generate_partial_from_slice_options_api!(atoi8_partial_slice_with_options, atoi8_partial_radix_slice_with_options, i8, atoi8_options_impl);
+generate_partial_from_slice_options_api!(atoi16_partial_slice_with_options, atoi16_partial_radix_slice_with_options, i16, atoi16_options_impl);
+generate_partial_from_slice_options_api!(atoi32_partial_slice_with_options, atoi32_partial_radix_slice_with_options, i32, atoi32_options_impl);
+generate_partial_from_slice_options_api!(atoi64_partial_slice_with_options, atoi64_partial_radix_slice_with_options, i64, atoi64_options_impl);
+generate_partial_from_slice_options_api!(atoisize_partial_slice_with_options, atoisize_partial_radix_slice_with_options, isize, atoisize_options_impl);
+
+#[cfg(has_i128)] generate_partial_from_slice_options_api!(atou128_partial_slice_with_options, atou128_partial_radix_slice_with_options, u128, atou128_options_impl);
+#[cfg(has_i128)] generate_partial_from_slice_options_api!(atoi128_partial_slice_with_options, atoi128_partial_radix_slice_with_options, i128, atoi128_options_impl);
+
 // TESTS
 // -----
 
@@ -420,6 +1004,41 @@ mod tests {
         assert_eq!(distance(s.as_ptr(), truncated.unwrap()), s.len()-2);
     }
 
+    #[test]
+    fn parse_chunks_test() {
+        // Chunks must stop at the first non-digit and at overflow.
+        assert_eq!(success(12345678), try_atou32_slice(b"12345678"));
+        assert_eq!(success(4294967295), try_atou32_slice(b"4294967295"));
+        assert_eq!(invalid_digit_error(123456789, 9), try_atou32_slice(b"123456789.0"));
+        assert_eq!(invalid_digit_error(1234567, 7), try_atoi32_slice(b"1234567a9"));
+        assert_eq!(success(-2147483647), try_atoi32_slice(b"-2147483647"));
+        assert_eq!(success(1234567890123456), try_atou64_slice(b"1234567890123456"));
+        assert_eq!(success(18446744073709551615), try_atou64_slice(b"18446744073709551615"));
+        assert_eq!(success(18446744073709551615), try_atou64_slice(b"00000000000000000018446744073709551615"));
+        assert_eq!(overflow_error(0), try_atou64_slice(b"18446744073709551616"));
+        assert_eq!(invalid_digit_error(123456789012345, 15), try_atou64_slice(b"123456789012345e67"));
+        assert_eq!(success(-9223372036854775807), try_atoi64_slice(b"-9223372036854775807"));
+
+        // Wrapping must be identical to parsing digit-by-digit.
+        let s = "98765432109876543210987654321098765432";
+        let mut expected: u64 = 0;
+        for c in s.bytes() {
+            expected = expected.wrapping_mul(10).wrapping_add((c - b'0') as u64);
+        }
+        let mut value: u64 = 0;
+        let (processed, truncated) = unchecked_positive(&mut value, 10, s.as_bytes());
+        assert_eq!(value, expected);
+        assert_eq!(processed, s.len());
+        assert_eq!(distance(s.as_ptr(), truncated.unwrap()), 19);
+
+        // UTF-16 strings are parsed digit-by-digit, with the same result.
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let mut value: u64 = 0;
+        let (processed, _) = unchecked_positive(&mut value, 10, &utf16);
+        assert_eq!(value, expected);
+        assert_eq!(processed, s.len());
+    }
+
     #[test]
     fn atou8_base10_test() {
         assert_eq!(0, atou8_slice(b"0"));
@@ -595,6 +1214,240 @@ mod tests {
         assert_eq!(overflow_error(7125759012462002176), try_atoi64_slice(b"6260572000000000000000-3*+\x006666600099000066006660066665?666666666599990000666"));
     }
 
+    #[test]
+    fn separator_len_test() {
+        let internal = DigitSeparator::INTERNAL.integer_rules();
+        assert_eq!(separator_len(10, b"1_000_000", b'_', internal), 9);
+        assert_eq!(separator_len(10, b"1__000", b'_', internal), 1);
+        assert_eq!(separator_len(10, b"_1", b'_', internal), 0);
+        assert_eq!(separator_len(10, b"1_", b'_', internal), 1);
+        assert_eq!(separator_len(10, b"1_.0", b'_', internal), 1);
+        assert_eq!(separator_len(10, b"_", b'_', internal), 0);
+
+        let all = DigitSeparator::ALL.integer_rules();
+        assert_eq!(separator_len(10, b"__1__000__", b'_', all), 10);
+        assert_eq!(separator_len(10, b"__", b'_', all), 0);
+        assert_eq!(separator_len(10, b"1_a", b'_', all), 2);
+
+        let flags = DigitSeparator::INTEGER_LEADING | DigitSeparator::INTEGER_TRAILING;
+        let rules = flags.integer_rules();
+        assert_eq!(separator_len(10, b"_10_", b'_', rules), 4);
+        assert_eq!(separator_len(10, b"__10", b'_', rules), 0);
+        assert_eq!(separator_len(10, b"1_0", b'_', rules), 1);
+    }
+
+    #[test]
+    fn atoi_separator_test() {
+        let mut options = ParseOptions::new();
+        options.set_digit_separator(b'_', DigitSeparator::INTERNAL);
+        assert_eq!(success(1000000), try_atou32_slice_with_options(b"1_000_000", &options));
+        assert_eq!(success(-1000), try_atoi32_slice_with_options(b"-1_000", &options));
+        assert_eq!(success(1000), try_atoi32_slice_with_options(b"+0_1_000", &options));
+        assert_eq!(invalid_digit_error(1, 1), try_atou32_slice_with_options(b"1__000", &options));
+        assert_eq!(invalid_digit_error(0, 0), try_atou32_slice_with_options(b"_1", &options));
+        assert_eq!(invalid_digit_error(0, 1), try_atoi32_slice_with_options(b"-_1", &options));
+        assert_eq!(invalid_digit_error(10, 2), try_atou32_slice_with_options(b"10_", &options));
+        assert_eq!(overflow_error(0), try_atou8_slice_with_options(b"2_56", &options));
+
+        options.set_digit_separator(b'\'', DigitSeparator::ALL);
+        assert_eq!(success(1000), try_atou32_slice_with_options(b"\'\'1\'\'000\'\'", &options));
+        assert_eq!(success(-1000), try_atoi64_slice_with_options(b"-\'1000", &options));
+        assert_eq!(invalid_digit_error(0, 0), try_atou32_slice_with_options(b"\'", &options));
+
+        // Disabled separators use the default parser.
+        options.set_digit_separator(b'_', DigitSeparator::NONE);
+        assert_eq!(invalid_digit_error(1, 1), try_atou32_slice_with_options(b"1_000", &options));
+    }
+
+    #[test]
+    fn group_len_test() {
+        let locale = Locale::EN_US;
+        assert_eq!(group_len(10, b"1,234,567", &locale), 9);
+        assert_eq!(group_len(10, b"123,456.7", &locale), 7);
+        assert_eq!(group_len(10, b"1234567", &locale), 7);
+        assert_eq!(group_len(10, b"1234,567", &locale), 4);
+        assert_eq!(group_len(10, b"1,23,567", &locale), 1);
+        assert_eq!(group_len(10, b"1,234,", &locale), 5);
+        assert_eq!(group_len(10, b",234", &locale), 0);
+        assert_eq!(group_len(10, b"", &locale), 0);
+
+        let locale = Locale::EN_IN;
+        assert_eq!(group_len(10, b"12,34,56,789", &locale), 12);
+        assert_eq!(group_len(10, b"1,234,567", &locale), 1);
+
+        let locale = Locale::FR_FR;
+        assert_eq!(group_len(10, "1\u{a0}234\u{a0}567".as_bytes(), &locale), 11);
+        assert_eq!(group_len(10, "1\u{a0}23".as_bytes(), &locale), 1);
+    }
+
+    #[test]
+    fn atoi_locale_test() {
+        let mut options = ParseOptions::new();
+        options.set_locale(Locale::EN_US);
+        assert_eq!(success(1234567), try_atou32_slice_with_options(b"1,234,567", &options));
+        assert_eq!(success(-1234567), try_atoi32_slice_with_options(b"-1,234,567", &options));
+        assert_eq!(success(1234567), try_atoi64_slice_with_options(b"1234567", &options));
+        assert_eq!(invalid_digit_error(1, 1), try_atou32_slice_with_options(b"1,23,567", &options));
+        assert_eq!(invalid_digit_error(1234, 5), try_atou32_slice_with_options(b"1,234,", &options));
+        assert_eq!(overflow_error(0), try_atou16_slice_with_options(b"65,536", &options));
+
+        options.set_locale(Locale::DE_DE);
+        assert_eq!(success(1000000), try_atou32_slice_with_options(b"1.000.000", &options));
+
+        // Grouping replaces digit separators in the integer.
+        options.set_digit_separator(b'_', DigitSeparator::INTERNAL);
+        assert_eq!(invalid_digit_error(1, 1), try_atou32_slice_with_options(b"1_000", &options));
+    }
+
+    #[test]
+    fn atoi_partial_test() {
+        assert_eq!(success((123, 3)), atou32_partial_slice(b"123abc"));
+        assert_eq!(success((-123, 4)), atoi32_partial_slice(b"-123 456"));
+        assert_eq!(success((255, 3)), atou8_partial_slice(b"255"));
+        assert_eq!(empty_error((0, 0)), atou8_partial_slice(b""));
+        assert_eq!(invalid_digit_error((0, 0), 0), atou8_partial_slice(b"abc"));
+        assert_eq!(invalid_digit_error((0, 0), 0), atoi8_partial_slice(b"-"));
+        assert_eq!(overflow_error((44, 3)), atou8_partial_slice(b"300,"));
+
+        let mut options = ParseOptions::new();
+        options.set_locale(Locale::EN_US);
+        assert_eq!(success((1234, 5)), atou32_partial_slice_with_options(b"1,234 apples", &options));
+    }
+
+    #[test]
+    fn atoi_utf16_test() {
+        let utf16 = |s: &str| s.encode_utf16().collect::<Vec<u16>>();
+        assert_eq!(success(123), try_atou32_slice(&utf16("123")));
+        assert_eq!(success(-123), try_atoi32_slice(&utf16("-123")));
+        assert_eq!(invalid_digit_error(12, 2), try_atou32_slice(&utf16("12\u{661}3")));
+        assert_eq!(invalid_digit_error(12, 2), try_atou32_slice(&utf16("12\u{ff13}")));
+        assert_eq!(invalid_digit_error(1, 1), try_atou32_slice(&utf16("1\u{131}")));
+        assert_eq!(success((123, 3)), atou32_partial_slice(&utf16("123\u{1f600}")));
+
+        let mut options = ParseOptions::new();
+        options.set_locale(Locale::FR_FR);
+        assert_eq!(success(1234567), try_atou32_slice_with_options(&utf16("1\u{a0}234\u{a0}567"), &options));
+        assert_eq!(success((1234, 5)), atou32_partial_slice_with_options(&utf16("1\u{a0}234\u{a0}"), &options));
+        assert_eq!(invalid_digit_error(1, 1), try_atou32_slice_with_options(&utf16("1\u{a0}23"), &options));
+    }
+
+    #[cfg(feature = "radix")]
+    #[test]
+    fn atoi_partial_radix_test() {
+        assert_eq!(success((255, 2)), atou32_partial_radix_slice(16, b"ffz"));
+        assert_eq!(success((5, 3)), atou32_partial_radix_slice_with_options(2, b"1012", &ParseOptions::new()));
+    }
+
+    #[test]
+    fn atoi_format_test() {
+        let mut options = ParseOptions::new();
+
+        options.set_format(NumberFormat::Json);
+        assert_eq!(success(-10), try_atoi32_slice_with_options(b"-10", &options));
+        assert_eq!(success(0), try_atou32_slice_with_options(b"0", &options));
+        assert_eq!(new_error(0, ErrorCode::InvalidPositiveMantissaSign, 0), try_atoi32_slice_with_options(b"+1", &options));
+        assert_eq!(new_error(0, ErrorCode::InvalidLeadingZeros, 0), try_atou32_slice_with_options(b"01", &options));
+        assert_eq!(new_error(0, ErrorCode::InvalidLeadingZeros, 1), try_atoi32_slice_with_options(b"-00", &options));
+        assert_eq!(invalid_digit_error(1, 1), try_atoi32_slice_with_options(b"1a", &options));
+
+        options.set_format(NumberFormat::Toml);
+        assert_eq!(success(1000), try_atoi32_slice_with_options(b"+1_000", &options));
+        assert_eq!(new_error(0, ErrorCode::InvalidLeadingZeros, 0), try_atoi32_slice_with_options(b"0_1", &options));
+
+        options.set_format(NumberFormat::Python);
+        assert_eq!(success(1000), try_atou64_slice_with_options(b"1_000", &options));
+        assert_eq!(new_error(0, ErrorCode::InvalidLeadingZeros, 0), try_atou64_slice_with_options(b"01", &options));
+
+        options.set_format(NumberFormat::Yaml);
+        assert_eq!(success(1), try_atou64_slice_with_options(b"+01", &options));
+    }
+
+    #[test]
+    fn atoi_grammar_test() {
+        let mut options = ParseOptions::new();
+        options.set_grammar(NumberGrammar::NO_POSITIVE_MANTISSA_SIGN);
+        assert_eq!(success(1), try_atou8_slice_with_options(b"01", &options));
+        assert_eq!(new_error(0, ErrorCode::InvalidPositiveMantissaSign, 0), try_atou8_slice_with_options(b"+1", &options));
+
+        options.set_grammar(NumberGrammar::NO_INTEGER_LEADING_ZEROS);
+        assert_eq!(success(1), try_atoi8_slice_with_options(b"+1", &options));
+        assert_eq!(new_error(0, ErrorCode::InvalidLeadingZeros, 1), try_atoi8_slice_with_options(b"+01", &options));
+
+        // Overflow takes precedence over grammar errors.
+        options.set_grammar(NumberGrammar::NO_LEADING_ZEROS);
+        assert_eq!(ErrorCode::Overflow, try_atou8_slice_with_options(b"256", &options).error.code);
+    }
+
+    #[test]
+    fn atoi_radix_prefix_test() {
+        // Radix prefixes are invalid without the grammar flag.
+        let mut options = ParseOptions::new();
+        assert_eq!(invalid_digit_error(0, 1), try_atou32_slice_with_options(b"0x1F", &options));
+
+        options.set_grammar(NumberGrammar::STANDARD | NumberGrammar::RADIX_PREFIX);
+        assert_eq!(success(31), try_atou32_slice_with_options(b"0x1F", &options));
+        assert_eq!(success(31), try_atou32_slice_with_options(b"0X1f", &options));
+        assert_eq!(success(8), try_atou32_slice_with_options(b"0o10", &options));
+        assert_eq!(success(5), try_atou32_slice_with_options(b"0B101", &options));
+        assert_eq!(success(1), try_atou32_slice_with_options(b"+0x0001", &options));
+        assert_eq!(success(-255), try_atoi32_slice_with_options(b"-0xff", &options));
+        assert_eq!(success(-128), try_atoi8_slice_with_options(b"-0x80", &options));
+        assert_eq!(success(17), try_atou32_slice_with_options(b"017", &options));
+        assert_eq!(success(0), try_atou32_slice_with_options(b"0", &options));
+
+        // Error indexes include the sign and the prefix.
+        assert_eq!(invalid_digit_error(1, 3), try_atou32_slice_with_options(b"0x1g", &options));
+        assert_eq!(invalid_digit_error(-1, 4), try_atoi32_slice_with_options(b"-0b12", &options));
+        assert_eq!(ErrorCode::Overflow, try_atou32_slice_with_options(b"-0x1", &options).error.code);
+        assert_eq!(new_error(0, ErrorCode::EmptyInteger, 2), try_atou32_slice_with_options(b"0x", &options));
+        assert_eq!(new_error(0, ErrorCode::EmptyInteger, 3), try_atoi32_slice_with_options(b"-0og", &options));
+        assert_eq!(ErrorCode::Overflow, try_atou8_slice_with_options(b"0x100", &options).error.code);
+
+        // Legacy octal prefix.
+        options.set_grammar(NumberGrammar::OCTAL_PREFIX);
+        assert_eq!(success(15), try_atou32_slice_with_options(b"017", &options));
+        assert_eq!(success(-15), try_atoi32_slice_with_options(b"-017", &options));
+        assert_eq!(success(0), try_atou32_slice_with_options(b"00", &options));
+        assert_eq!(invalid_digit_error(0, 1), try_atou32_slice_with_options(b"08", &options));
+        assert_eq!(invalid_digit_error(0, 2), try_atoi32_slice_with_options(b"-09", &options));
+        assert_eq!(invalid_digit_error(1, 2), try_atou32_slice_with_options(b"019", &options));
+        assert_eq!(invalid_digit_error(0, 1), try_atou32_slice_with_options(b"0x1", &options));
+
+        // Digit separators are valid after the prefix.
+        options.set_format(NumberFormat::Python);
+        assert_eq!(success(0xFFFF), try_atou32_slice_with_options(b"0xFF_FF", &options));
+        assert_eq!(success(0x1), try_atou32_slice_with_options(b"0x01", &options));
+        assert_eq!(new_error(0, ErrorCode::InvalidLeadingZeros, 0), try_atou32_slice_with_options(b"01", &options));
+        assert_eq!(success(0xFF), try_atou32_slice_with_options(b"0x_FF", &options));
+
+        options.set_format(NumberFormat::Rust);
+        assert_eq!(success(0xFF), try_atou32_slice_with_options(b"0x_ff", &options));
+        assert_eq!(success(-5), try_atoi32_slice_with_options(b"-0b__101_", &options));
+        assert_eq!(new_error(0, ErrorCode::EmptyInteger, 2), try_atou32_slice_with_options(b"0x_", &options));
+        assert_eq!(invalid_digit_error(0, 0), try_atou32_slice_with_options(b"_1", &options));
+
+        options.set_format(NumberFormat::JavaScript);
+        assert_eq!(success(0xFF), try_atou32_slice_with_options(b"0xF_F", &options));
+        assert_eq!(new_error(0, ErrorCode::EmptyInteger, 2), try_atou32_slice_with_options(b"0x_FF", &options));
+
+        options.set_format(NumberFormat::C);
+        assert_eq!(success(15), try_atou32_slice_with_options(b"017", &options));
+        assert_eq!(success(-3), try_atoi32_slice_with_options(b"-0b11", &options));
+        assert_eq!(success(0), try_atou32_slice_with_options(b"0", &options));
+        assert_eq!(invalid_digit_error(0, 1), try_atou32_slice_with_options(b"08", &options));
+    }
+
+    #[cfg(feature = "radix")]
+    #[test]
+    fn atoi_radix_prefix_radix_test() {
+        // Only decimal integers may have a radix prefix.
+        let mut options = ParseOptions::new();
+        options.set_grammar(NumberGrammar::RADIX_PREFIX | NumberGrammar::OCTAL_PREFIX);
+        assert_eq!(invalid_digit_error(0, 1), try_atou32_radix_slice_with_options(16, b"0x1", &options));
+        assert_eq!(success(0xB1), try_atou32_radix_slice_with_options(16, b"0b1", &options));
+        assert_eq!(success(0o17), try_atou32_radix_slice_with_options(8, b"017", &options));
+    }
+
     proptest! {
         #[test]
         fn u8_invalid_proptest(i in r"[+]?[0-9]{2}\D") {
@@ -292,6 +292,7 @@ impl<M: Mantissa> ExtendedFloat<M> {
 
         #[cfg(feature = "rounding")] {
             // Use of static mutable `FLOAT_ROUNDING`.
+            #[allow(deprecated)]
             unsafe {
                 self.into_rounded_float::<F>(FLOAT_ROUNDING, Sign::Positive)
             }
@@ -16,4 +16,4 @@ pub use self::rounding::{FloatRounding};
 pub use self::float::ExtendedFloat160;
 
 #[cfg(feature = "correct")]
-pub(crate) use self::rounding::global_rounding;
+pub(crate) use self::rounding::internal_rounding;
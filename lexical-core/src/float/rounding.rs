@@ -201,6 +201,44 @@ float_rounding_f64! { u64 u128 }
 #[cfg(not(has_i128))]
 float_rounding_f64! { u64 }
 
+// Literals don't work for generic types, we need to use this as a hack.
+macro_rules! float_rounding_f16 {
+    ($($t:tt)*) => ($(
+        impl FloatRounding<$t> for f16 {
+            const DEFAULT_SHIFT: i32    = $t::FULL - f16::MANTISSA_SIZE - 1;
+            const CARRY_MASK: $t        = 0x800;
+        }
+    )*)
+}
+
+#[cfg(has_i128)]
+float_rounding_f16! { u64 u128 }
+
+#[cfg(not(has_i128))]
+float_rounding_f16! { u64 }
+
+// Literals don't work for generic types, we need to use this as a hack.
+macro_rules! float_rounding_bf16 {
+    ($($t:tt)*) => ($(
+        impl FloatRounding<$t> for bf16 {
+            const DEFAULT_SHIFT: i32    = $t::FULL - bf16::MANTISSA_SIZE - 1;
+            const CARRY_MASK: $t        = 0x100;
+        }
+    )*)
+}
+
+#[cfg(has_i128)]
+float_rounding_bf16! { u64 u128 }
+
+#[cfg(not(has_i128))]
+float_rounding_bf16! { u64 }
+
+#[cfg(all(has_i128, feature = "correct"))]
+impl FloatRounding<u128> for f128 {
+    const DEFAULT_SHIFT: i32    = u128::FULL - f128::MANTISSA_SIZE - 1;
+    const CARRY_MASK: u128      = 0x20000000000000000000000000000;
+}
+
 // ROUND TO FLOAT
 
 /// Shift the ExtendedFloat fraction to the fraction bits in a native float.
@@ -325,23 +363,6 @@ pub(crate) fn internal_rounding(kind: RoundingKind, sign: Sign)
     }
 }
 
-/// Get the global, default rounding scheme.
-#[cfg(feature = "correct")]
-#[inline]
-#[allow(unused_variables)]
-pub(crate) fn global_rounding(sign: Sign) -> RoundingKind {
-    #[cfg(not(feature = "rounding"))] {
-        RoundingKind::NearestTieEven
-    }
-
-    #[cfg(feature = "rounding")] {
-        // Use of static mutable `FLOAT_ROUNDING`.
-        unsafe {
-            internal_rounding(FLOAT_ROUNDING, sign)
-        }
-    }
-}
-
 // TESTS
 // -----
 
@@ -52,127 +52,316 @@
 //  ax.legend(loc=2, prop={'size': 14})
 //  plt.show()
 
+use lib::cmp;
 use util::*;
 
+use super::float16::half_decimal;
+use super::hex::{double_hex, float_hex};
+use super::layout::{write_layout, MAX_DIGITS};
+
+#[cfg(feature = "correct")]
+use super::fixed::{double_fixed, float_fixed};
+
 #[cfg(feature = "radix")]
-use super::radix::{double_radix, float_radix};
-
-// Select the back-end
-cfg_if! {
-if #[cfg(feature = "grisu3")] {
-    use super::grisu3::{double_decimal, float_decimal};
-} else if #[cfg(feature = "ryu")] {
-    use super::ryu::{double_decimal, float_decimal};
-} else {
-    use super::grisu2::{double_decimal, float_decimal};
-}}  //cfg_if
+use super::radix::{double_radix, float_radix, half_radix};
+
+#[cfg(all(has_i128, feature = "correct"))]
+use super::fixed::quad_fixed;
+
+#[cfg(all(has_i128, feature = "correct"))]
+use super::float128::quad_decimal;
+
+#[cfg(all(has_i128, feature = "correct"))]
+use super::hex::quad_hex;
+
+#[cfg(all(has_i128, feature = "correct", feature = "radix"))]
+use super::radix::quad_radix;
+
+use super::grisu2;
+
+#[cfg(feature = "grisu3")]
+use super::grisu3;
+
+#[cfg(feature = "ryu")]
+use super::ryu;
 
 // TRAITS
 
 /// Trait to define serialization of a float to string.
 pub(crate) trait FloatToString: Float {
     /// Export float to decimal string with optimized algorithm.
-    fn decimal<'a>(self, bytes: &'a mut [u8]) -> usize;
+    fn decimal<'a>(self, bytes: &'a mut [u8], algorithm: WriteAlgorithm) -> usize;
+
+    /// Export float to hexadecimal float literal.
+    fn hex<'a>(self, bytes: &'a mut [u8]) -> usize;
+
+    /// Export float to decimal string with a fixed precision.
+    #[cfg(feature = "correct")]
+    fn fixed<'a>(self, bytes: &'a mut [u8], options: &WriteOptions) -> usize;
 
     /// Export float to radix string with slow algorithm.
     #[cfg(feature = "radix")]
-    fn radix<'a>(self, radix: u32, bytes: &'a mut [u8]) -> usize;
+    fn radix<'a>(self, radix: u32, bytes: &'a mut [u8], options: &WriteOptions) -> usize;
 }
 
 impl FloatToString for f32 {
     #[inline]
-    fn decimal<'a>(self, bytes: &'a mut [u8]) -> usize {
-        float_decimal(self, bytes)
+    fn decimal<'a>(self, bytes: &'a mut [u8], algorithm: WriteAlgorithm) -> usize {
+        match algorithm {
+            WriteAlgorithm::Grisu2 => grisu2::float_decimal(self, bytes),
+            #[cfg(feature = "grisu3")]
+            WriteAlgorithm::Grisu3 => grisu3::float_decimal(self, bytes),
+            #[cfg(feature = "ryu")]
+            WriteAlgorithm::Ryu => ryu::float_decimal(self, bytes),
+        }
+    }
+
+    #[inline]
+    fn hex<'a>(self, bytes: &'a mut [u8]) -> usize {
+        float_hex(self, bytes)
+    }
+
+    #[cfg(feature = "correct")]
+    #[inline]
+    fn fixed<'a>(self, bytes: &'a mut [u8], options: &WriteOptions) -> usize {
+        float_fixed(self, bytes, options)
     }
 
     #[cfg(feature = "radix")]
     #[inline]
-    fn radix<'a>(self, radix: u32, bytes: &'a mut [u8]) -> usize {
-        float_radix(self, radix, bytes)
+    fn radix<'a>(self, radix: u32, bytes: &'a mut [u8], options: &WriteOptions) -> usize {
+        float_radix(self, radix, bytes, options)
     }
 }
 
 impl FloatToString for f64 {
     #[inline]
-    fn decimal<'a>(self, bytes: &'a mut [u8]) -> usize {
-        double_decimal(self, bytes)
+    fn decimal<'a>(self, bytes: &'a mut [u8], algorithm: WriteAlgorithm) -> usize {
+        match algorithm {
+            WriteAlgorithm::Grisu2 => grisu2::double_decimal(self, bytes),
+            #[cfg(feature = "grisu3")]
+            WriteAlgorithm::Grisu3 => grisu3::double_decimal(self, bytes),
+            #[cfg(feature = "ryu")]
+            WriteAlgorithm::Ryu => ryu::double_decimal(self, bytes),
+        }
+    }
+
+    #[inline]
+    fn hex<'a>(self, bytes: &'a mut [u8]) -> usize {
+        double_hex(self, bytes)
+    }
+
+    #[cfg(feature = "correct")]
+    #[inline]
+    fn fixed<'a>(self, bytes: &'a mut [u8], options: &WriteOptions) -> usize {
+        double_fixed(self, bytes, options)
     }
 
     #[cfg(feature = "radix")]
     #[inline]
-    fn radix<'a>(self, radix: u32, bytes: &'a mut [u8]) -> usize {
-        double_radix(self, radix, bytes)
+    fn radix<'a>(self, radix: u32, bytes: &'a mut [u8], options: &WriteOptions) -> usize {
+        double_radix(self, radix, bytes, options)
+    }
+}
+
+/// Implement float-to-string for floats narrower than a 32-bit float.
+///
+/// The hexadecimal and fixed-precision algorithms use the exact 64-bit float.
+macro_rules! half_to_string {
+    ($($t:ty)*) => ($(
+        impl FloatToString for $t {
+            #[inline]
+            fn decimal<'a>(self, bytes: &'a mut [u8], algorithm: WriteAlgorithm) -> usize {
+                half_decimal(self, bytes, algorithm)
+            }
+
+            #[inline]
+            fn hex<'a>(self, bytes: &'a mut [u8]) -> usize {
+                double_hex(self.as_f64(), bytes)
+            }
+
+            #[cfg(feature = "correct")]
+            #[inline]
+            fn fixed<'a>(self, bytes: &'a mut [u8], options: &WriteOptions) -> usize {
+                double_fixed(self.as_f64(), bytes, options)
+            }
+
+            #[cfg(feature = "radix")]
+            #[inline]
+            fn radix<'a>(self, radix: u32, bytes: &'a mut [u8], options: &WriteOptions) -> usize {
+                half_radix(self, radix, bytes, options)
+            }
+        }
+    )*)
+}
+
+half_to_string! { f16 bf16 }
+
+#[cfg(all(has_i128, feature = "correct"))]
+impl FloatToString for f128 {
+    /// Quad floats are always written with arbitrary-precision arithmetic.
+    #[inline]
+    fn decimal<'a>(self, bytes: &'a mut [u8], _: WriteAlgorithm) -> usize {
+        quad_decimal(self, bytes)
+    }
+
+    #[inline]
+    fn hex<'a>(self, bytes: &'a mut [u8]) -> usize {
+        quad_hex(self, bytes)
+    }
+
+    #[inline]
+    fn fixed<'a>(self, bytes: &'a mut [u8], options: &WriteOptions) -> usize {
+        quad_fixed(self, bytes, options)
+    }
+
+    #[cfg(feature = "radix")]
+    #[inline]
+    fn radix<'a>(self, radix: u32, bytes: &'a mut [u8], options: &WriteOptions) -> usize {
+        quad_radix(self, radix, bytes, options)
     }
 }
 
 // FTOA
 
+/// Export float to decimal string, using the custom exponent character and locale.
+#[inline]
+fn decimal<'a, F: FloatToString>(value: F, bytes: &'a mut [u8], options: &WriteOptions)
+    -> usize
+{
+    // The decimal backends always write `e` for the exponent and `.`
+    // for the decimal point, so replace them after the fact if different
+    // characters were requested.
+    let len = match options.is_default_layout() {
+        true  => value.decimal(bytes, options.algorithm()),
+        false => {
+            // Rewrite the shortest digits with the custom notation.
+            let mut buffer: [u8; 64] = [b'\x00'; 64];
+            let len = match value.is_zero() {
+                true  => copy_to_dst(&mut buffer, b"0.0"),
+                false => value.decimal(&mut buffer, options.algorithm()),
+            };
+            write_layout(&index!(buffer[..len]), bytes, options)
+        },
+    };
+    let exponent_char = options.exponent_notation_char(10);
+    if exponent_char != b'e' {
+        let bytes = &mut index_mut!(bytes[..len]);
+        if let Some(c) = bytes.iter_mut().rev().find(|c| **c == b'e') {
+            *c = exponent_char;
+        }
+    }
+    let locale = options.locale();
+    if locale.decimal_point() != b'.' {
+        let bytes = &mut index_mut!(bytes[..len]);
+        if let Some(c) = bytes.iter_mut().find(|c| **c == b'.') {
+            *c = locale.decimal_point();
+        }
+    }
+
+    // Group the integer digits, which is a no-op for exponent notation,
+    // since only a single integer digit is written.
+    let digits = index!(bytes[..len]).iter().take_while(|c| c.is_ascii_digit()).count();
+    write_groups(bytes, digits, len, &locale)
+}
+
+/// Determine if the float should be written as a hexadecimal float literal.
+#[inline]
+fn is_hex_float(radix: u32, options: &WriteOptions) -> bool {
+    radix == 10 && options.hex_float()
+}
+
+/// Determine if the float should be written with a fixed precision.
+#[inline]
+fn is_fixed(radix: u32, options: &WriteOptions) -> bool {
+    radix == 10 && !options.hex_float() && options.precision() != Precision::Shortest
+}
+
 /// Forward the correct arguments the ideal encoder.
 #[inline]
-fn forward<'a, F: FloatToString>(value: F, radix: u32, bytes: &'a mut [u8])
+fn forward<'a, F: FloatToString>(value: F, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
     -> usize
 {
     debug_assert_radix!(radix);
 
+    if is_hex_float(radix, options) {
+        return value.hex(bytes);
+    }
+
+    #[cfg(feature = "correct")] {
+        if is_fixed(radix, options) {
+            return value.fixed(bytes, options);
+        }
+    }
+
     #[cfg(not(feature = "radix"))] {
-        value.decimal(bytes)
+        decimal(value, bytes, options)
     }
 
     #[cfg(feature = "radix")] {
         match radix {
-            10 => value.decimal(bytes),
-            _  => value.radix(radix, bytes),
+            10 => decimal(value, bytes, options),
+            _  => value.radix(radix, bytes, options),
         }
     }
 }
 
 /// Convert float-to-string and handle special (positive) floats.
 #[inline]
-fn filter_special<'a, F: FloatToString>(value: F, radix: u32, bytes: &'a mut [u8])
+fn filter_special<'a, F: FloatToString>(value: F, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
     -> usize
 {
     // Logic errors, disable in release builds.
     debug_assert!(value.is_sign_positive(), "Value cannot be negative.");
     debug_assert_radix!(radix);
 
-    // We already check for 0 in `filter_sign` if value.is_zero().
-    #[cfg(not(feature = "trim_floats"))] {
-        if value.is_zero() {
-            // This is safe, because we confirmed the buffer is >= 4
-            // in total (since we also handled the sign by here).
-            return copy_to_dst(bytes, b"0.0");
+    // Write 0 as a hexadecimal float literal before any trimming.
+    if value.is_zero() && is_hex_float(radix, options) {
+        return copy_to_dst(bytes, b"0x0p+0");
+    }
+
+    // Zero with trimmed floats was already handled in `filter_sign`.
+    if value.is_zero() && !is_fixed(radix, options) {
+        if radix == 10 && !options.is_default_layout() {
+            return decimal(value, bytes, options);
+        }
+        // This is safe, because we confirmed the buffer is >= 4
+        // in total (since we also handled the sign by here).
+        let len = copy_to_dst(bytes, b"0.0");
+        if radix == 10 {
+            index_mut!(bytes[1] = options.locale().decimal_point());
         }
+        return len;
     }
 
     if value.is_nan() {
         // This is safe, because we confirmed the buffer is >= MAX_F32_SIZE.
-        // We have up to `MAX_F32_SIZE - 1` bytes from `get_nan_string()`,
+        // We have up to `MAX_F32_SIZE - 1` bytes from `nan_string()`,
         // and up to 1 byte from the sign.
-        copy_to_dst(bytes, get_nan_string())
+        copy_to_dst(bytes, options.nan_string())
     } else if value.is_special() {
         // This is safe, because we confirmed the buffer is >= MAX_F32_SIZE.
-        // We have up to `MAX_F32_SIZE - 1` bytes from `get_inf_string()`,
+        // We have up to `MAX_F32_SIZE - 1` bytes from `inf_string()`,
         // and up to 1 byte from the sign.
-        copy_to_dst(bytes, get_inf_string())
+        copy_to_dst(bytes, options.inf_string())
     } else {
-        forward(value, radix, bytes)
+        forward(value, radix, bytes, options)
     }
 }
 
 /// Handle +/- values.
 #[inline]
-fn filter_sign<'a, F: FloatToString>(value: F, radix: u32, bytes: &'a mut [u8])
+fn filter_sign<'a, F: FloatToString>(value: F, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
     -> usize
 {
     debug_assert_radix!(radix);
 
     // Export "-0.0" and "0.0" as "0" with trimmed floats.
-    #[cfg(feature = "trim_floats")] {
-        if value.is_zero() {
-            // We know this is safe, because we confirmed the buffer is >= 1.
-            index_mut!(bytes[0] = b'0');
-            return 1;
-        }
+    let is_custom = radix == 10 && !options.is_default_layout();
+    if options.trim_floats() && value.is_zero() && !is_custom && !is_hex_float(radix, options) && !is_fixed(radix, options) {
+        // We know this is safe, because we confirmed the buffer is >= 1.
+        index_mut!(bytes[0] = b'0');
+        return 1;
     }
 
     // If the sign bit is set, invert it and just set the first
@@ -182,27 +371,31 @@ fn filter_sign<'a, F: FloatToString>(value: F, radix: u32, bytes: &'a mut [u8])
         // We know this is safe, because we confirmed the buffer is >= 1.
         index_mut!(bytes[0] = b'-');
         let bytes = &mut index_mut!(bytes[1..]);
-        filter_special(value, radix, bytes) + 1
+        filter_special(value, radix, bytes, options) + 1
     } else {
-        filter_special(value, radix, bytes)
+        filter_special(value, radix, bytes, options)
     }
 }
 
 /// Iteratively filter simple cases then invoke serializer.
 #[inline]
-fn ftoa<F: FloatToString>(value: F, radix: u32, bytes: &mut [u8])
+fn ftoa<F: FloatToString>(value: F, radix: u32, bytes: &mut [u8], options: &WriteOptions)
     -> usize
 {
-    filter_sign(value, radix, bytes)
+    filter_sign(value, radix, bytes, options)
 }
 
-/// Trim a trailing ".0" from a float.
+/// Trim a trailing ".0" from a float, using the decimal point for the radix.
 #[inline]
-fn trim<'a>(bytes: &'a mut [u8])
+fn trim<'a>(bytes: &'a mut [u8], radix: u32, options: &WriteOptions)
     -> usize
 {
     // Trim a trailing ".0" from a float.
-    if cfg!(feature = "trim_floats") && ends_with_slice(bytes, b".0") {
+    let decimal_point = match radix {
+        10 => options.locale().decimal_point(),
+        _  => b'.',
+    };
+    if options.trim_floats() && !is_fixed(radix, options) && ends_with_slice(bytes, &[decimal_point, b'0']) {
         bytes.len() - 2
     } else {
         bytes.len()
@@ -213,22 +406,35 @@ fn trim<'a>(bytes: &'a mut [u8])
 
 /// Expand the generic ftoa function for specified types.
 macro_rules! wrap {
-    ($name:ident, $t:ty) => (
-        /// Serialize float and return bytes written to.
+    ($name:ident, $options_name:ident, $t:ty) => (
+        /// Serialize float with custom options and return bytes written to.
         #[inline]
-        fn $name<'a>(value: $t, base: u8, bytes: &'a mut [u8])
+        fn $options_name<'a>(value: $t, base: u8, bytes: &'a mut [u8], options: &WriteOptions)
             -> usize
         {
             // Check buffer has sufficient capacity.
-            let len = ftoa(value, base.into(), bytes);
+            let len = ftoa(value, base.into(), bytes, options);
             let bytes = &mut index_mut!(bytes[..len]);
-            trim(bytes)
+            trim(bytes, base.into(), options)
+        }
+
+        /// Serialize float and return bytes written to.
+        #[inline]
+        fn $name<'a>(value: $t, base: u8, bytes: &'a mut [u8])
+            -> usize
+        {
+            $options_name(value, base, bytes, &WriteOptions::global())
         }
     )
 }
 
-wrap!(f32toa_impl, f32);
-wrap!(f64toa_impl, f64);
+wrap!(f32toa_impl, f32toa_options_impl, f32);
+wrap!(f64toa_impl, f64toa_options_impl, f64);
+wrap!(f16toa_impl, f16toa_options_impl, f16);
+wrap!(bf16toa_impl, bf16toa_options_impl, bf16);
+
+#[cfg(all(has_i128, feature = "correct"))]
+wrap!(f128toa_impl, f128toa_options_impl, f128);
 
 // LOW-LEVEL API
 // -------------
@@ -236,10 +442,113 @@ wrap!(f64toa_impl, f64);
 // RANGE API (FFI)
 generate_to_range_api!(f32toa_range, f32toa_radix_range, f32, f32toa_impl, MAX_F32_SIZE);
 generate_to_range_api!(f64toa_range, f64toa_radix_range, f64, f64toa_impl, MAX_F64_SIZE);
+generate_to_range_options_api!(f32toa_range_with_options, f32toa_radix_range_with_options, f32, f32toa_options_impl, MAX_F32_SIZE);
+generate_to_range_options_api!(f64toa_range_with_options, f64toa_radix_range_with_options, f64, f64toa_options_impl, MAX_F64_SIZE);
 
 // SLICE API
 generate_to_slice_api!(f32toa_slice, f32toa_radix_slice, f32, f32toa_impl, MAX_F32_SIZE);
 generate_to_slice_api!(f64toa_slice, f64toa_radix_slice, f64, f64toa_impl, MAX_F64_SIZE);
+generate_to_slice_api!(f16toa_slice, f16toa_radix_slice, f16, f16toa_impl, MAX_F16_SIZE);
+generate_to_slice_api!(bf16toa_slice, bf16toa_radix_slice, bf16, bf16toa_impl, MAX_BF16_SIZE);
+generate_to_slice_options_api!(f32toa_slice_with_options, f32toa_radix_slice_with_options, f32, f32toa_options_impl, MAX_F32_SIZE);
+generate_to_slice_options_api!(f64toa_slice_with_options, f64toa_radix_slice_with_options, f64, f64toa_options_impl, MAX_F64_SIZE);
+generate_to_slice_options_api!(f16toa_slice_with_options, f16toa_radix_slice_with_options, f16, f16toa_options_impl, MAX_F16_SIZE);
+generate_to_slice_options_api!(bf16toa_slice_with_options, bf16toa_radix_slice_with_options, bf16, bf16toa_options_impl, MAX_BF16_SIZE);
+
+#[cfg(all(has_i128, feature = "correct"))]
+generate_to_slice_api!(f128toa_slice, f128toa_radix_slice, f128, f128toa_impl, MAX_F128_SIZE);
+
+#[cfg(all(has_i128, feature = "correct"))]
+generate_to_slice_options_api!(f128toa_slice_with_options, f128toa_radix_slice_with_options, f128, f128toa_options_impl, MAX_F128_SIZE);
+
+// SIZE API
+
+/// Maximum number of integer digits written by the float formatting
+/// algorithms in positional notation, from Grisu2 writing up to 17
+/// significant digits followed by up to 7 zeros.
+const MAX_SHORTEST_INTEGER_DIGITS: usize = 24;
+
+/// Get the maximum number of bytes written for a float type with custom options.
+///
+/// `size` is the maximum number of bytes written with the default options,
+/// and `min_exponent` and `max_exponent` are the decimal exponents of the
+/// smallest denormal and the largest finite value of the type.
+#[inline]
+fn max_size(size: usize, min_exponent: i32, max_exponent: i32, options: &WriteOptions)
+    -> usize
+{
+    let locale = options.locale();
+    let shortest = size + locale.separators_len(MAX_SHORTEST_INTEGER_DIGITS);
+    let precision = match options.precision() {
+        Precision::Shortest => shortest,
+        Precision::Fixed(precision) => {
+            // Sign, grouped integer digits, decimal point, and fraction digits.
+            let integer = max_exponent.as_usize() + 1;
+            let integer = integer + locale.separators_len(integer);
+            cmp::max(shortest, (integer + 2).saturating_add(precision))
+        },
+        Precision::Scientific(precision) => {
+            // Sign, digit, decimal point, fraction digits, exponent
+            // character, exponent sign, and up to 4 exponent digits.
+            cmp::max(shortest, 9usize.saturating_add(precision))
+        },
+    };
+
+    // Positional notation may pad the significant digits with zeros,
+    // up to the exponent range of the type. Scientific notation always
+    // fits in `size`.
+    let positional_exponents = match options.notation() {
+        Notation::Positional => Some((min_exponent, max_exponent)),
+        Notation::Auto       => options.positional_exponents(),
+        Notation::Scientific => None,
+    };
+    let layout = match positional_exponents {
+        Some((min, max)) => {
+            let min = cmp::max(min, min_exponent);
+            let max = cmp::min(max, max_exponent);
+            let mut layout = shortest;
+            if min <= max && max >= 0 {
+                // Sign, grouped integer digits, decimal point, and fraction digits.
+                let integer = max.as_usize() + 1;
+                let integer = integer + locale.separators_len(integer);
+                layout = cmp::max(layout, integer + 2 + MAX_DIGITS);
+            }
+            if min <= max && min < 0 {
+                // Sign, leading zero, decimal point, zeros, and significant digits.
+                let zeros = min.wrapping_neg().as_usize() - 1;
+                layout = cmp::max(layout, zeros + 3 + MAX_DIGITS);
+            }
+            layout
+        },
+        None             => shortest,
+    };
+
+    cmp::max(precision, layout)
+}
+
+/// Expand the buffer size functions for floats with custom options.
+macro_rules! max_size_with_options {
+    ($name:ident, $size:ident, $min_exponent:expr, $max_exponent:expr) => (
+        /// Get the minimum buffer size required to serialize any value with custom options.
+        ///
+        /// Digit grouping, fixed precisions, and positional notation may
+        /// write more bytes than the `MAX_*_SIZE` constant for the type,
+        /// so buffers for the `*_with_options` serializers should have at
+        /// least this many elements.
+        #[inline]
+        pub fn $name(options: &WriteOptions) -> usize {
+            max_size($size, $min_exponent, $max_exponent, options)
+        }
+    )
+}
+
+max_size_with_options!(max_f32_size_with_options, MAX_F32_SIZE, -45, 38);
+max_size_with_options!(max_f64_size_with_options, MAX_F64_SIZE, -324, 308);
+max_size_with_options!(max_f16_size_with_options, MAX_F16_SIZE, -8, 4);
+max_size_with_options!(max_bf16_size_with_options, MAX_BF16_SIZE, -41, 38);
+
+#[cfg(all(has_i128, feature = "correct"))]
+max_size_with_options!(max_f128_size_with_options, MAX_F128_SIZE, -4966, 4932);
 
 // TESTS
 // -----
@@ -343,9 +652,13 @@ mod tests {
         let mut buffer = new_buffer();
         for f in F32_DATA.iter() {
             for radix in 2..37 {
-                // The lower accuracy is due to slight rounding errors of
-                // ftoa for the Grisu method with non-10 bases.
                 let s = f32toa_radix_slice(*f, radix, &mut buffer);
+                #[cfg(feature = "correct")]
+                assert_eq!(atof32_radix_slice(radix, s), *f);
+
+                // The lower accuracy is due to slight rounding errors of
+                // the naive ftoa algorithm with non-10 bases.
+                #[cfg(not(feature = "correct"))]
                 assert_relative_eq!(atof32_radix_slice(radix, s), *f, max_relative=2e-5);
             }
         }
@@ -435,14 +748,334 @@ mod tests {
         let mut buffer = new_buffer();
         for f in F64_DATA.iter() {
             for radix in 2..37 {
-                // The lower accuracy is due to slight rounding errors of
-                // ftoa for the Grisu method with non-10 bases.
                 let s = f64toa_radix_slice(*f, radix, &mut buffer);
+                #[cfg(feature = "correct")]
+                assert_eq!(atof64_radix_slice(radix, s), *f);
+
+                // The lower accuracy is due to slight rounding errors of
+                // the naive ftoa algorithm with non-10 bases.
+                #[cfg(not(feature = "correct"))]
                 assert_relative_eq!(atof64_radix_slice(radix, s), *f, max_relative=3e-5);
             }
         }
     }
 
+    #[test]
+    fn ftoa_options_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_nan_string(b"nan");
+        options.set_inf_string(b"Infinity");
+        options.set_exponent_char(b'E');
+        assert_eq!(as_slice(b"nan"), f64toa_slice_with_options(f64::NAN, &mut buffer, &options));
+        assert_eq!(as_slice(b"-Infinity"), f32toa_slice_with_options(f32::NEG_INFINITY, &mut buffer, &options));
+        assert!(f32toa_slice_with_options(1.2345e-38, &mut buffer, &options).ends_with(b"E-38"));
+        assert_eq!(as_slice(b"1.2345E-299"), f64toa_slice_with_options(1.2345e-299, &mut buffer, &options));
+
+        // Default options are unaffected.
+        assert_eq!(as_slice(b"1.2345e-299"), f64toa_slice_with_options(1.2345e-299, &mut buffer, &WriteOptions::new()));
+    }
+
+    #[test]
+    fn ftoa_algorithm_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_algorithm(WriteAlgorithm::Grisu2);
+        assert_eq!(as_slice(b"1.5"), f64toa_slice_with_options(1.5, &mut buffer, &options));
+        assert_eq!(as_slice(b"0.5"), f32toa_slice_with_options(0.5, &mut buffer, &options));
+        assert_eq!(as_slice(b"1e+300"), f64toa_slice_with_options(1e300, &mut buffer, &options));
+        options.set_notation(Notation::Scientific);
+        assert_eq!(as_slice(b"1e300"), f64toa_slice_with_options(1e300, &mut buffer, &options));
+
+        #[cfg(feature = "ryu")] {
+            let mut options = WriteOptions::new();
+            options.set_algorithm(WriteAlgorithm::Ryu);
+            assert_eq!(as_slice(b"1e300"), f64toa_slice_with_options(1e300, &mut buffer, &options));
+        }
+
+        #[cfg(feature = "grisu3")] {
+            let mut options = WriteOptions::new();
+            options.set_algorithm(WriteAlgorithm::Grisu3);
+            assert_eq!(as_slice(b"1.5"), f64toa_slice_with_options(1.5, &mut buffer, &options));
+        }
+    }
+
+    #[test]
+    fn ftoa_hex_float_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_hex_float(true);
+        assert_eq!(as_slice(b"0x1.8p+3"), f64toa_slice_with_options(12.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"-0x1.8p+3"), f32toa_slice_with_options(-12.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"0x1.999999999999ap-4"), f64toa_slice_with_options(0.1, &mut buffer, &options));
+        assert_eq!(as_slice(b"0x1.99999ap-4"), f32toa_slice_with_options(0.1, &mut buffer, &options));
+        assert_eq!(as_slice(b"0x0.0000000000001p-1022"), f64toa_slice_with_options(5e-324, &mut buffer, &options));
+        assert_eq!(as_slice(b"0x0p+0"), f64toa_slice_with_options(0.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"-0x0p+0"), f64toa_slice_with_options(-0.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"inf"), f64toa_slice_with_options(f64::INFINITY, &mut buffer, &options));
+        assert_eq!(as_slice(b"NaN"), f32toa_slice_with_options(f32::NAN, &mut buffer, &options));
+    }
+
+    #[test]
+    fn ftoa_locale_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_locale(Locale::DE_DE);
+        assert_eq!(as_slice(b"1.234.567,5"), f64toa_slice_with_options(1234567.5, &mut buffer, &options));
+        assert_eq!(as_slice(b"-1,5"), f32toa_slice_with_options(-1.5, &mut buffer, &options));
+        assert_eq!(as_slice(b"1,2345e-299"), f64toa_slice_with_options(1.2345e-299, &mut buffer, &options));
+
+        options.set_locale(Locale::EN_IN);
+        assert_eq!(as_slice(b"12,34,567.25"), f64toa_slice_with_options(1234567.25, &mut buffer, &options));
+
+        // Hexadecimal float literals always use `.`.
+        options.set_locale(Locale::DE_DE);
+        options.set_hex_float(true);
+        assert_eq!(as_slice(b"0x1.8p+3"), f64toa_slice_with_options(12.0, &mut buffer, &options));
+    }
+
+    #[test]
+    fn ftoa_max_size_test() {
+        let mut options = WriteOptions::new();
+        assert_eq!(max_f64_size_with_options(&options), MAX_F64_SIZE);
+
+        let mut locale = Locale::new();
+        locale.set_grouping("\u{1f600}".as_bytes(), &[1]);
+        options.set_locale(locale);
+        options.set_algorithm(WriteAlgorithm::Grisu2);
+        options.set_trim_floats(false);
+        let mut buffer = vec![b'\x00'; max_f64_size_with_options(&options)];
+        let expected = "1\u{1f600}2\u{1f600}3\u{1f600}4\u{1f600}5\u{1f600}6\u{1f600}7\u{1f600}8\u{1f600}9\u{1f600}0\u{1f600}1\u{1f600}2\u{1f600}3\u{1f600}4\u{1f600}5\u{1f600}6\u{1f600}7\u{1f600}0\u{1f600}0\u{1f600}0\u{1f600}0\u{1f600}0\u{1f600}0\u{1f600}0.0";
+        assert_eq!(expected.as_bytes(), f64toa_slice_with_options(1.2345678901234567e23, &mut buffer, &options));
+    }
+
+    #[test]
+    fn ftoa_utf16_test() {
+        let utf16 = |s: &str| s.encode_utf16().collect::<Vec<u16>>();
+        let mut buffer = [0u16; BUFFER_SIZE];
+        assert_eq!(&utf16("1.5")[..], f64toa_slice(1.5, &mut buffer));
+        assert_eq!(&utf16("-1.2345e-299")[..], f64toa_slice(-1.2345e-299, &mut buffer));
+        assert_eq!(&utf16("NaN")[..], f32toa_slice(f32::NAN, &mut buffer));
+
+        let mut options = WriteOptions::new();
+        options.set_locale(Locale::FR_FR);
+        assert_eq!(&utf16("1\u{a0}234\u{a0}567,5")[..], f64toa_slice_with_options(1234567.5, &mut buffer, &options));
+    }
+
+    #[cfg(all(feature = "correct", not(feature = "trim_floats")))]
+    #[test]
+    fn ftoa_locale_roundtrip_test() {
+        let mut buffer = new_buffer();
+        let mut write_options = WriteOptions::new();
+        write_options.set_locale(Locale::FR_FR);
+        let mut parse_options = ParseOptions::new();
+        parse_options.set_locale(Locale::FR_FR);
+        assert_eq!(as_slice("0,0".as_bytes()), f64toa_slice_with_options(0.0, &mut buffer, &write_options));
+        for &f in [0.0, 1e300, 1234567.890625, -9007199254740992.0, 5e-324].iter() {
+            let bytes = f64toa_slice_with_options(f, &mut buffer, &write_options);
+            assert_eq!(f, atof64_slice_with_options(bytes, &parse_options));
+        }
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    fn ftoa_precision_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_precision(Precision::Fixed(40));
+        assert_eq!(as_slice(b"0.1000000000000000055511151231257827021182"), f64toa_slice_with_options(0.1, &mut buffer, &options));
+        options.set_precision(Precision::Fixed(17));
+        assert_eq!(as_slice(b"0.29999999999999999"), f64toa_slice_with_options(0.3, &mut buffer, &options));
+        options.set_precision(Precision::Fixed(3));
+        assert_eq!(as_slice(b"0.100"), f32toa_slice_with_options(0.1, &mut buffer, &options));
+        assert_eq!(as_slice(b"0.000"), f64toa_slice_with_options(0.0, &mut buffer, &options));
+        options.set_precision(Precision::Fixed(2));
+        assert_eq!(as_slice(b"1234567.12"), f64toa_slice_with_options(1234567.125, &mut buffer, &options));
+        assert_eq!(as_slice(b"0.12"), f64toa_slice_with_options(0.125, &mut buffer, &options));
+        assert_eq!(as_slice(b"10.00"), f64toa_slice_with_options(9.9999, &mut buffer, &options));
+        options.set_precision(Precision::Fixed(1));
+        assert_eq!(as_slice(b"-0.0"), f64toa_slice_with_options(-0.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"1.0"), f64toa_slice_with_options(1.0, &mut buffer, &options));
+        options.set_precision(Precision::Fixed(0));
+        assert_eq!(as_slice(b"2"), f64toa_slice_with_options(2.5, &mut buffer, &options));
+        assert_eq!(as_slice(b"2"), f64toa_slice_with_options(1.5, &mut buffer, &options));
+        assert_eq!(as_slice(b"0"), f64toa_slice_with_options(0.5, &mut buffer, &options));
+        assert_eq!(as_slice(b"0"), f64toa_slice_with_options(0.001, &mut buffer, &options));
+        assert_eq!(as_slice(b"inf"), f64toa_slice_with_options(f64::INFINITY, &mut buffer, &options));
+
+        options.set_precision(Precision::Scientific(2));
+        assert_eq!(as_slice(b"1.50e+03"), f64toa_slice_with_options(1500.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"1.00e+01"), f64toa_slice_with_options(9.9999, &mut buffer, &options));
+        assert_eq!(as_slice(b"0.00e+00"), f64toa_slice_with_options(0.0, &mut buffer, &options));
+        options.set_precision(Precision::Scientific(3));
+        assert_eq!(as_slice(b"4.941e-324"), f64toa_slice_with_options(5e-324, &mut buffer, &options));
+        options.set_precision(Precision::Scientific(0));
+        assert_eq!(as_slice(b"1e+100"), f64toa_slice_with_options(1e100, &mut buffer, &options));
+        assert_eq!(as_slice(b"-1e+02"), f64toa_slice_with_options(-123.456, &mut buffer, &options));
+        options.set_precision(Precision::Scientific(20));
+        assert_eq!(as_slice(b"1.79769313486231570815e+308"), f64toa_slice_with_options(1.7976931348623157e308, &mut buffer, &options));
+        options.set_precision(Precision::Scientific(10));
+        assert_eq!(as_slice(b"1.0000000149e-01"), f32toa_slice_with_options(0.1, &mut buffer, &options));
+
+        // Locales, exponent characters, and hexadecimal floats.
+        options.set_exponent_char(b'E');
+        options.set_locale(Locale::DE_DE);
+        options.set_precision(Precision::Scientific(1));
+        assert_eq!(as_slice(b"1,0E-10"), f64toa_slice_with_options(1e-10, &mut buffer, &options));
+        options.set_precision(Precision::Fixed(2));
+        assert_eq!(as_slice(b"1.234.567,12"), f64toa_slice_with_options(1234567.125, &mut buffer, &options));
+        options.set_hex_float(true);
+        assert_eq!(as_slice(b"0x1.8p+3"), f64toa_slice_with_options(12.0, &mut buffer, &options));
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    fn ftoa_precision_long_test() {
+        let mut options = WriteOptions::new();
+        options.set_precision(Precision::Fixed(0));
+        let mut buffer = vec![b'0'; max_f64_size_with_options(&options)];
+        let expected = b"1000000000000000052504760255204420248704468581108159154915854115511802457988908195786371375080447864043704443832883878176942523235360430575644792184786706982848387200926575803737830233794788090059368953234970799945081119038967640880074652742780142494579258788820056842838115669472196386865459400540160";
+        assert_eq!(&expected[..], f64toa_slice_with_options(1e300, &mut buffer, &options));
+
+        // Every value must fit in the buffer, even with grouping.
+        options.set_locale(Locale::EN_US);
+        options.set_precision(Precision::Fixed(20));
+        let mut buffer = vec![b'0'; max_f64_size_with_options(&options)];
+        for &f in [-f64::MAX, f64::MIN_POSITIVE, -5e-324].iter() {
+            f64toa_slice_with_options(f, &mut buffer, &options);
+        }
+        options.set_precision(Precision::Scientific(1000));
+        let mut buffer = vec![b'0'; max_f64_size_with_options(&options)];
+        for &f in [-f64::MAX, -5e-324].iter() {
+            f64toa_slice_with_options(f, &mut buffer, &options);
+        }
+        let mut buffer = vec![b'0'; max_f32_size_with_options(&options)];
+        f32toa_slice_with_options(-f32::MAX, &mut buffer, &options);
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    #[should_panic]
+    fn ftoa_precision_buffer_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_precision(Precision::Fixed(0));
+        f64toa_slice_with_options(1e300, &mut buffer, &options);
+    }
+
+    #[test]
+    fn ftoa_notation_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_trim_floats(false);
+
+        // Python's `repr`.
+        options.set_positional_exponents(-4, 15);
+        options.set_positive_exponent_sign(true);
+        options.set_min_exponent_digits(2);
+        assert_eq!(as_slice(b"1e+16"), f64toa_slice_with_options(1e16, &mut buffer, &options));
+        assert_eq!(as_slice(b"1000000000000000.0"), f64toa_slice_with_options(1e15, &mut buffer, &options));
+        assert_eq!(as_slice(b"0.0001"), f64toa_slice_with_options(1e-4, &mut buffer, &options));
+        assert_eq!(as_slice(b"1.5e-05"), f64toa_slice_with_options(1.5e-5, &mut buffer, &options));
+        assert_eq!(as_slice(b"-1.2345e+300"), f64toa_slice_with_options(-1.2345e300, &mut buffer, &options));
+        assert_eq!(as_slice(b"0.0"), f64toa_slice_with_options(0.0, &mut buffer, &options));
+
+        // JavaScript's `Number.prototype.toString`.
+        options.set_positional_exponents(-6, 20);
+        options.set_min_exponent_digits(1);
+        options.set_trim_floats(true);
+        assert_eq!(as_slice(b"100000000000000000000"), f64toa_slice_with_options(1e20, &mut buffer, &options));
+        assert_eq!(as_slice(b"1e+21"), f64toa_slice_with_options(1e21, &mut buffer, &options));
+        assert_eq!(as_slice(b"0.000001"), f64toa_slice_with_options(1e-6, &mut buffer, &options));
+        assert_eq!(as_slice(b"1e-7"), f64toa_slice_with_options(1e-7, &mut buffer, &options));
+        assert_eq!(as_slice(b"-0"), f64toa_slice_with_options(-0.0, &mut buffer, &options));
+
+        // Forced notation, without a leading zero.
+        options = WriteOptions::new();
+        options.set_trim_floats(false);
+        options.set_notation(Notation::Scientific);
+        assert_eq!(as_slice(b"1.5e3"), f32toa_slice_with_options(1500.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"0e0"), f64toa_slice_with_options(0.0, &mut buffer, &options));
+        options.set_notation(Notation::Positional);
+        options.set_leading_zero(false);
+        assert_eq!(as_slice(b".00000015"), f64toa_slice_with_options(1.5e-7, &mut buffer, &options));
+        assert_eq!(as_slice(b"1000000000000000000000000.0"), f64toa_slice_with_options(1e24, &mut buffer, &options));
+
+        // Locales and exponent characters.
+        options.set_notation(Notation::Scientific);
+        options.set_exponent_char(b'E');
+        options.set_locale(Locale::DE_DE);
+        assert_eq!(as_slice(b"1,5E3"), f64toa_slice_with_options(1500.0, &mut buffer, &options));
+    }
+
+    #[test]
+    fn ftoa_notation_size_test() {
+        let mut options = WriteOptions::new();
+        options.set_trim_floats(false);
+        options.set_notation(Notation::Positional);
+        options.set_locale(Locale::EN_US);
+        let mut buffer = vec![b'0'; max_f64_size_with_options(&options)];
+        let len = f64toa_slice_with_options(-1e300, &mut buffer, &options).len();
+        assert_eq!(len, 404);
+        let len = f64toa_slice_with_options(-f64::MAX, &mut buffer, &options).len();
+        assert_eq!(len, 414);
+        let len = f64toa_slice_with_options(-5e-324, &mut buffer, &options).len();
+        assert_eq!(len, 327);
+
+        let mut buffer = vec![b'0'; max_f32_size_with_options(&options)];
+        f32toa_slice_with_options(-f32::MAX, &mut buffer, &options);
+        f32toa_slice_with_options(-1e-45, &mut buffer, &options);
+
+        options = WriteOptions::new();
+        options.set_positional_exponents(-400, 400);
+        let mut buffer = vec![b'0'; max_f64_size_with_options(&options)];
+        f64toa_slice_with_options(-1.7976931348623157e308, &mut buffer, &options);
+        f64toa_slice_with_options(-2.2250738585072014e-308, &mut buffer, &options);
+
+        // Ranges outside of the exponents of the type do not change the size.
+        options.set_positional_exponents(400, 500);
+        assert_eq!(max_f64_size_with_options(&options), MAX_F64_SIZE);
+    }
+
+    #[cfg(all(has_i128, feature = "correct"))]
+    #[test]
+    fn ftoa_notation_quad_test() {
+        let mut options = WriteOptions::new();
+        options.set_notation(Notation::Positional);
+        let mut buffer = vec![b'0'; max_f128_size_with_options(&options)];
+        let value = f128::from_bits(0x0001FFFFFFFFFFFFFFFFFFFFFFFFFFFF);
+        f128toa_slice_with_options(value, &mut buffer, &options);
+        f128toa_slice_with_options(f128::from_bits(1), &mut buffer, &options);
+        options.set_notation(Notation::Scientific);
+        let expected = b"6.7242062862241870125253556346435046e-4932";
+        assert_eq!(&expected[..], f128toa_slice_with_options(value, &mut buffer, &options));
+    }
+
+    #[test]
+    fn ftoa_trim_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_trim_floats(true);
+        assert_eq!(as_slice(b"1"), f64toa_slice_with_options(1.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"0"), f64toa_slice_with_options(-0.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"1.5"), f64toa_slice_with_options(1.5, &mut buffer, &options));
+        options.set_trim_floats(false);
+        assert_eq!(as_slice(b"1.0"), f64toa_slice_with_options(1.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"-0.0"), f64toa_slice_with_options(-0.0, &mut buffer, &options));
+        assert_eq!(as_slice(b"0.0"), f32toa_slice_with_options(0.0, &mut buffer, &options));
+    }
+
+    #[cfg(feature = "radix")]
+    #[test]
+    fn ftoa_radix_options_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_exponent_char(b'E');
+        options.set_exponent_backup_char(b'@');
+        assert_eq!(as_slice(b"1.1010100000101011110001E-11011"), f32toa_radix_slice_with_options(0.000000012345, 2, &mut buffer, &options));
+        assert_eq!(b'@', f64toa_radix_slice_with_options(1.2345e-299, 16, &mut buffer, &options).iter().find(|&&c| c == b'@' || c == b'^').cloned().unwrap());
+    }
+
     #[cfg(feature = "correct")]
     quickcheck! {
         fn f32_quickcheck(f: f32) -> bool {
@@ -456,6 +1089,124 @@ mod tests {
         }
     }
 
+    #[cfg(all(feature = "correct", feature = "radix"))]
+    quickcheck! {
+        fn f32_radix_quickcheck(f: f32, radix: u8) -> bool {
+            let mut buffer = new_buffer();
+            let radix = radix % 35 + 2;
+            f.is_special() || f == atof32_radix_slice(radix, f32toa_radix_slice(f, radix, &mut buffer))
+        }
+
+        fn f64_radix_quickcheck(f: f64, radix: u8) -> bool {
+            let mut buffer = new_buffer();
+            let radix = radix % 35 + 2;
+            f.is_special() || f == atof64_radix_slice(radix, f64toa_radix_slice(f, radix, &mut buffer))
+        }
+    }
+
+    quickcheck! {
+        fn f32_hex_quickcheck(f: f32) -> bool {
+            let mut buffer = new_buffer();
+            let mut write_options = WriteOptions::new();
+            write_options.set_hex_float(true);
+            let mut parse_options = ParseOptions::new();
+            parse_options.set_grammar(NumberGrammar::STANDARD | NumberGrammar::HEX_FLOAT);
+            f == atof32_slice_with_options(f32toa_slice_with_options(f, &mut buffer, &write_options), &parse_options)
+        }
+
+        fn f64_hex_quickcheck(f: f64) -> bool {
+            let mut buffer = new_buffer();
+            let mut write_options = WriteOptions::new();
+            write_options.set_hex_float(true);
+            let mut parse_options = ParseOptions::new();
+            parse_options.set_grammar(NumberGrammar::STANDARD | NumberGrammar::HEX_FLOAT);
+            f == atof64_slice_with_options(f64toa_slice_with_options(f, &mut buffer, &write_options), &parse_options)
+        }
+    }
+
+    #[test]
+    fn f16toa_test() {
+        let mut buffer = new_buffer();
+        #[cfg(not(feature = "trim_floats"))] {
+            assert_eq!(as_slice(b"0.0"), f16toa_slice(f16::from_bits(0x0000), &mut buffer));
+            assert_eq!(as_slice(b"-0.0"), f16toa_slice(f16::from_bits(0x8000), &mut buffer));
+            assert_eq!(as_slice(b"1.0"), f16toa_slice(f16::from_bits(0x3C00), &mut buffer));
+        }
+        assert_eq!(as_slice(b"-1.5"), f16toa_slice(f16::from_bits(0xBE00), &mut buffer));
+        assert_eq!(as_slice(b"NaN"), f16toa_slice(f16::NAN, &mut buffer));
+        assert_eq!(as_slice(b"inf"), f16toa_slice(f16::INFINITY, &mut buffer));
+
+        let mut options = WriteOptions::new();
+        options.set_hex_float(true);
+        assert_eq!(as_slice(b"0x1.8p+0"), f16toa_slice_with_options(f16::from_bits(0x3E00), &mut buffer, &options));
+
+        // Every finite value round-trips.
+        for bits in 0..0x7C00u16 {
+            let value = f16::from_bits(bits);
+            assert_eq!(bits, atof16_slice(f16toa_slice(value, &mut buffer)).to_bits());
+        }
+    }
+
+    #[test]
+    fn bf16toa_test() {
+        let mut buffer = new_buffer();
+        #[cfg(not(feature = "trim_floats"))] {
+            assert_eq!(as_slice(b"1.0"), bf16toa_slice(bf16::from_bits(0x3F80), &mut buffer));
+        }
+        assert_eq!(as_slice(b"-1.5"), bf16toa_slice(bf16::from_bits(0xBFC0), &mut buffer));
+        assert_eq!(as_slice(b"inf"), bf16toa_slice(bf16::INFINITY, &mut buffer));
+
+        // Every finite value round-trips.
+        for bits in 0..0x7F80u16 {
+            let value = bf16::from_bits(bits);
+            assert_eq!(bits, atobf16_slice(bf16toa_slice(value, &mut buffer)).to_bits());
+        }
+    }
+
+    #[cfg(all(has_i128, feature = "correct"))]
+    #[test]
+    fn f128toa_test() {
+        let mut buffer = new_buffer();
+        #[cfg(not(feature = "trim_floats"))] {
+            assert_eq!(as_slice(b"0.0"), f128toa_slice(f128::ZERO, &mut buffer));
+            assert_eq!(as_slice(b"1.0"), f128toa_slice(f128::ONE, &mut buffer));
+        }
+        assert_eq!(as_slice(b"-1.5"), f128toa_slice(f128::from_f64(-1.5), &mut buffer));
+        assert_eq!(as_slice(b"0.1"), f128toa_slice(f128::from_bits(0x3FFB999999999999999999999999999A), &mut buffer));
+        assert_eq!(as_slice(b"1.189731495357231765085759326628007e4932"), f128toa_slice(f128::MAX, &mut buffer));
+        assert_eq!(as_slice(b"NaN"), f128toa_slice(f128::NAN, &mut buffer));
+        assert_eq!(as_slice(b"inf"), f128toa_slice(f128::INFINITY, &mut buffer));
+
+        let mut options = WriteOptions::new();
+        options.set_hex_float(true);
+        assert_eq!(as_slice(b"0x1.8p+0"), f128toa_slice_with_options(f128::from_f64(1.5), &mut buffer, &options));
+
+        // Values across the whole range round-trip.
+        let mut bits: u128 = 0x0123456789ABCDEF_FEDCBA9876543210;
+        for _ in 0..500 {
+            bits = bits.wrapping_mul(0x2360ED051FC65DA44385DF649FCCF645).wrapping_add(1) >> 1;
+            if bits >> 112 == 0x7FFF {
+                continue;
+            }
+            let value = f128::from_bits(bits);
+            assert_eq!(bits, atof128_slice(f128toa_slice(value, &mut buffer)).to_bits());
+        }
+    }
+
+    #[cfg(feature = "radix")]
+    #[test]
+    fn f16toa_radix_test() {
+        let mut buffer = new_buffer();
+        assert_eq!(as_slice(b"1.1"), f16toa_radix_slice(f16::from_bits(0x3E00), 2, &mut buffer));
+        assert_eq!(as_slice(b"-1.1"), bf16toa_radix_slice(bf16::from_bits(0xBFC0), 2, &mut buffer));
+        for &radix in [3, 7, 16, 36].iter() {
+            for bits in (0..0x7C00u16).filter(|x| x % 7 == 0) {
+                let value = f16::from_bits(bits);
+                assert_eq!(bits, atof16_radix_slice(radix, f16toa_radix_slice(value, radix, &mut buffer)).to_bits());
+            }
+        }
+    }
+
     #[cfg(feature = "correct")]
     proptest! {
         #[test]
@@ -0,0 +1,329 @@
+//! Float-to-string conversion with a fixed precision.
+//!
+//! Floats are written like `printf("%.Nf")` or `printf("%.Ne")`, correctly
+//! rounded from the exact decimal representation of the float, rounding
+//! halfway cases to even. If the exact representation fits in a 64-bit
+//! integer, the digits are written directly, otherwise, the digits are
+//! generated using arbitrary-precision arithmetic. For example, `0.1`
+//! written with 40 digits after the decimal point is
+//! `0.1000000000000000055511151231257827021182`.
+
+use atof::decimal_digits;
+#[cfg(has_i128)]
+use atof::quad_decimal_digits;
+use itoa;
+use util::*;
+
+/// Maximum number of exact decimal digits for a 64-bit float, rounded
+/// up to a multiple of 9 for the big-integer digit extraction.
+const MAX_DIGITS: usize = 774;
+
+/// Maximum number of exact decimal digits for a 128-bit float, rounded
+/// up to a multiple of 9 for the big-integer digit extraction.
+#[cfg(has_i128)]
+const QUAD_MAX_DIGITS: usize = 11565;
+
+// DIGITS
+
+/// Write the exact decimal digits of a positive, finite double.
+///
+/// Returns the number of digits and the position of the decimal point,
+/// such that the value is `0.d1d2...dn * 10^point`. Zero has no digits.
+fn exact_digits(value: f64, digits: &mut [u8])
+    -> (usize, i32)
+{
+    if value.is_zero() {
+        return (0, 1);
+    }
+
+    // Remove the trailing zero bits, so the digits have no trailing zeros
+    // after the decimal point.
+    let shift = value.mantissa().trailing_zeros();
+    let mant = value.mantissa() >> shift;
+    let exp = value.exponent() + shift.as_i32();
+
+    // Use native integers if `mant * 2^exp` is `integer * 10^scale`,
+    // and the integer fits in 64 bits. `5^27` is the largest power of 5
+    // that fits in 64 bits.
+    let small = if exp >= 0 {
+        match mant.leading_zeros().as_i32() > exp {
+            true  => Some((mant << exp, 0)),
+            false => None,
+        }
+    } else if exp >= -27 {
+        mant.checked_mul(5u64.pow(exp.wrapping_neg().as_u32())).map(|integer| (integer, exp))
+    } else {
+        None
+    };
+
+    match small {
+        Some((integer, scale)) => {
+            let count = itoa::forward(integer, 10, digits);
+            (count, count.as_i32() + scale)
+        },
+        None => decimal_digits(mant, exp, digits),
+    }
+}
+
+/// Round the digits to the first `keep` digits, rounding halfway cases to even.
+///
+/// Returns the number of digits and the position of the decimal point,
+/// which increases if the carry propagates past the first digit.
+fn round_digits(digits: &mut [u8], count: usize, point: i32, keep: i32)
+    -> (usize, i32)
+{
+    if keep >= count.as_i32() {
+        // Exact, no rounding required.
+        return (count, point);
+    } else if keep < 0 {
+        // Below half of the last digit, round to zero.
+        return (0, point);
+    }
+
+    // Determine the direction to round from the truncated digits.
+    let keep = keep.as_usize();
+    let digit = index!(digits[keep]);
+    let is_above = digit > b'5' || (digit == b'5' && index!(digits[keep+1..count]).iter().any(|&c| c != b'0'));
+    let is_halfway = digit == b'5' && !is_above;
+    let is_odd = keep > 0 && (index!(digits[keep-1]) - b'0') % 2 == 1;
+    if !(is_above || is_halfway && is_odd) {
+        return (keep, point);
+    }
+
+    // Round-up, propagating the carry, and drop the trailing zeros.
+    let mut index = keep;
+    while index > 0 {
+        index -= 1;
+        let c = index!(digits[index]);
+        if c != b'9' {
+            index_mut!(digits[index] = c + 1);
+            return (index + 1, point);
+        }
+    }
+
+    // Carried past the first digit, IE, `9.99` to `10.0`.
+    index_mut!(digits[0] = b'1');
+    (1, point + 1)
+}
+
+/// Get the digit at the index, which is `0` outside the written digits.
+#[inline]
+fn digit_at(digits: &[u8], count: usize, index: i32)
+    -> u8
+{
+    if index >= 0 && index < count.as_i32() {
+        index!(digits[index.as_usize()])
+    } else {
+        b'0'
+    }
+}
+
+// WRITE
+
+/// Write the digits with `precision` digits after the decimal point.
+fn write_fixed(digits: &[u8], count: usize, point: i32, precision: usize, bytes: &mut [u8], options: &WriteOptions)
+    -> usize
+{
+    let integer = if point > 0 { point.as_usize() } else { 1 };
+    let len = integer + if precision > 0 { precision + 1 } else { 0 };
+    assert!(bytes.len() >= len, "Buffer is too small for the formatted float.");
+
+    // Write the integer digits, or a leading 0.
+    for (i, c) in index_mut!(bytes[..integer]).iter_mut().enumerate() {
+        *c = digit_at(digits, count, i.as_i32());
+    }
+    if point <= 0 {
+        index_mut!(bytes[0] = b'0');
+    }
+
+    // Write the fraction digits, including any leading zeros.
+    if precision > 0 {
+        let locale = options.locale();
+        index_mut!(bytes[integer] = locale.decimal_point());
+        for i in 0..precision {
+            index_mut!(bytes[integer + 1 + i] = digit_at(digits, count, point + i.as_i32()));
+        }
+    }
+
+    write_groups(bytes, integer, len, &options.locale())
+}
+
+/// Write the digits in scientific notation, with `precision` digits after the decimal point.
+fn write_scientific(digits: &[u8], count: usize, point: i32, precision: usize, bytes: &mut [u8], options: &WriteOptions)
+    -> usize
+{
+    // The exponent has a sign and at least 2 digits, like `printf`.
+    let exponent = if count == 0 { 0 } else { point - 1 };
+    let abs_exponent = exponent.wrapping_abs().as_u32();
+    let exponent_digits = if abs_exponent >= 1000 {
+        4
+    } else if abs_exponent >= 100 {
+        3
+    } else {
+        2
+    };
+    let mantissa = 1 + if precision > 0 { precision + 1 } else { 0 };
+    let len = mantissa + 2 + exponent_digits;
+    assert!(bytes.len() >= len, "Buffer is too small for the formatted float.");
+
+    // Write the mantissa digits.
+    index_mut!(bytes[0] = digit_at(digits, count, 0));
+    if precision > 0 {
+        index_mut!(bytes[1] = options.locale().decimal_point());
+        for i in 0..precision {
+            index_mut!(bytes[2 + i] = digit_at(digits, count, 1 + i.as_i32()));
+        }
+    }
+
+    // Write the exponent.
+    index_mut!(bytes[mantissa] = options.exponent_notation_char(10));
+    index_mut!(bytes[mantissa + 1] = if exponent < 0 { b'-' } else { b'+' });
+    let mut index = mantissa + 2;
+    if abs_exponent < 10 {
+        index_mut!(bytes[index] = b'0');
+        index += 1;
+    }
+    index + itoa::forward(abs_exponent, 10, &mut index_mut!(bytes[index..]))
+}
+
+/// Round the exact digits to the precision, and write them.
+fn write_precision(digits: &mut [u8], count: usize, point: i32, bytes: &mut [u8], options: &WriteOptions)
+    -> usize
+{
+    match options.precision() {
+        Precision::Fixed(precision) => {
+            let keep = point.saturating_add(precision.min(i32::max_value().as_usize()).as_i32());
+            let (count, point) = round_digits(digits, count, point, keep);
+            write_fixed(digits, count, point, precision, bytes, options)
+        },
+        Precision::Scientific(precision) => {
+            let keep = precision.min(digits.len()).as_i32() + 1;
+            let (count, point) = round_digits(digits, count, point, keep);
+            write_scientific(digits, count, point, precision, bytes, options)
+        },
+        Precision::Shortest => unreachable!(),
+    }
+}
+
+// API
+
+/// Export a positive, finite double with a fixed precision.
+pub(crate) fn double_fixed<'a>(value: f64, bytes: &'a mut [u8], options: &WriteOptions)
+    -> usize
+{
+    debug_assert!(value >= 0.0 && !value.is_special(), "Value must be positive and finite.");
+
+    let mut digits: [u8; MAX_DIGITS] = [b'0'; MAX_DIGITS];
+    let (count, point) = exact_digits(value, &mut digits);
+    write_precision(&mut digits, count, point, bytes, options)
+}
+
+/// Export a positive, finite float with a fixed precision.
+///
+/// The float is exactly converted to the equivalent double.
+#[inline]
+pub(crate) fn float_fixed<'a>(value: f32, bytes: &'a mut [u8], options: &WriteOptions)
+    -> usize
+{
+    double_fixed(value.as_f64(), bytes, options)
+}
+
+/// Export a positive, finite quad float with a fixed precision.
+///
+/// The exact digits are always generated using arbitrary-precision
+/// arithmetic.
+#[cfg(has_i128)]
+pub(crate) fn quad_fixed<'a>(value: f128, bytes: &'a mut [u8], options: &WriteOptions)
+    -> usize
+{
+    debug_assert!(value >= f128::ZERO && !value.is_special(), "Value must be positive and finite.");
+
+    let mut digits: [u8; QUAD_MAX_DIGITS] = [b'0'; QUAD_MAX_DIGITS];
+    let (count, point) = match value.is_zero() {
+        true  => (0, 1),
+        false => {
+            let shift = value.mantissa().trailing_zeros();
+            let mant = value.mantissa() >> shift;
+            let exp = value.exponent() + shift.as_i32();
+            quad_decimal_digits(mant, exp, &mut digits)
+        },
+    };
+    write_precision(&mut digits, count, point, bytes, options)
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_exact_digits(value: f64, expected: &str, expected_point: i32) {
+        let mut digits = [b'0'; MAX_DIGITS];
+        let (count, point) = exact_digits(value, &mut digits);
+        assert_eq!(&digits[..count], expected.as_bytes());
+        assert_eq!(point, expected_point);
+    }
+
+    #[test]
+    fn exact_digits_test() {
+        check_exact_digits(0.0, "", 1);
+        check_exact_digits(1.0, "1", 1);
+        check_exact_digits(0.5, "5", 0);
+        check_exact_digits(1234.5, "12345", 4);
+        check_exact_digits(0.1, "1000000000000000055511151231257827021181583404541015625", 0);
+        check_exact_digits(1e23, "99999999999999991611392", 23);
+        check_exact_digits(9007199254740993.0 * 1024.0, "9223372036854775808", 19);
+
+        // Big-integer fallback.
+        let mut digits = [b'0'; MAX_DIGITS];
+        let (count, point) = exact_digits(5e-324, &mut digits);
+        assert_eq!(&digits[..10], b"4940656458");
+        assert_eq!(count, 751);
+        assert_eq!(point, -323);
+        let (count, point) = exact_digits(1.7976931348623157e308, &mut digits);
+        assert_eq!(&digits[..10], b"1797693134");
+        assert_eq!(count, 309);
+        assert_eq!(point, 309);
+    }
+
+    fn check_round_digits(s: &str, point: i32, keep: i32, expected: &str, expected_point: i32) {
+        let mut digits = [b'0'; 32];
+        digits[..s.len()].copy_from_slice(s.as_bytes());
+        let (count, point) = round_digits(&mut digits, s.len(), point, keep);
+        assert_eq!(&digits[..count], expected.as_bytes());
+        assert_eq!(point, expected_point);
+    }
+
+    #[test]
+    fn round_digits_test() {
+        check_round_digits("125", 0, 3, "125", 0);
+        check_round_digits("125", 0, 2, "12", 0);
+        check_round_digits("135", 0, 2, "14", 0);
+        check_round_digits("1251", 0, 2, "13", 0);
+        check_round_digits("126", 0, 2, "13", 0);
+        check_round_digits("999", 1, 2, "1", 2);
+        check_round_digits("5", 0, 0, "", 0);
+        check_round_digits("51", 0, 0, "1", 1);
+        check_round_digits("5", -1, -1, "", -1);
+    }
+
+    #[cfg(has_i128)]
+    #[test]
+    fn quad_fixed_test() {
+        let mut buffer = [b'\x00'; 128];
+        let mut options = WriteOptions::new();
+        let tenth = f128::from_bits(0x3FFB999999999999999999999999999A);
+        options.set_precision(Precision::Fixed(40));
+        let len = quad_fixed(tenth, &mut buffer, &options);
+        assert_eq!(&buffer[..len], b"0.1000000000000000000000000000000000048148");
+
+        options.set_precision(Precision::Scientific(5));
+        let len = quad_fixed(f128::MAX, &mut buffer, &options);
+        assert_eq!(&buffer[..len], b"1.18973e+4932");
+        let len = quad_fixed(f128::from_bits(1), &mut buffer, &options);
+        assert_eq!(&buffer[..len], b"6.47518e-4966");
+        let len = quad_fixed(f128::ZERO, &mut buffer, &options);
+        assert_eq!(&buffer[..len], b"0.00000e+00");
+    }
+}
@@ -0,0 +1,67 @@
+//! Float-to-string conversion for quad floats.
+//!
+//! The shortest digits that round-trip are generated using
+//! arbitrary-precision arithmetic, and written with the same
+//! notation as Ryu.
+
+use atof::quad_radix_digits;
+use util::*;
+use super::layout::{write_positional, write_scientific};
+
+/// Maximum number of digits in the shortest decimal representation.
+const MAX_DIGITS: usize = 40;
+
+/// Write the shortest decimal representation of the float.
+///
+/// `f` must be non-special (NaN or infinite), non-negative,
+/// and non-zero.
+#[inline]
+pub(crate) fn quad_decimal<'a>(f: f128, bytes: &'a mut [u8])
+    -> usize
+{
+    // The lower boundary is closer for powers of two, except for the
+    // smallest normal float, since the previous float is denormal.
+    let mant = f.mantissa();
+    let exp = f.exponent();
+    let is_lower_closer = mant == f128::HIDDEN_BIT_MASK && exp != f128::DENORMAL_EXPONENT;
+    let mut digits: [u8; MAX_DIGITS] = [b'0'; MAX_DIGITS];
+    let (count, point) = quad_radix_digits(mant, exp, is_lower_closer, 10, &mut digits);
+    let digits = &index!(digits[..count]);
+
+    // Use positional notation for exponents in `[-5, 15]`, like Ryu.
+    let exponent = point - 1;
+    let options = WriteOptions::new();
+    if exponent >= -5 && exponent <= 15 {
+        write_positional(digits, exponent, bytes, &options)
+    } else {
+        write_scientific(digits, exponent, bytes, &options)
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_quad_decimal(bits: u128, expected: &str) {
+        let mut buffer = [b'\x00'; 64];
+        let len = quad_decimal(f128::from_bits(bits), &mut buffer);
+        assert_eq!(&buffer[..len], expected.as_bytes());
+    }
+
+    #[test]
+    fn quad_decimal_test() {
+        check_quad_decimal(0x3FFF0000000000000000000000000000, "1.0");
+        check_quad_decimal(0x3FFF8000000000000000000000000000, "1.5");
+        check_quad_decimal(0x3FFB999999999999999999999999999A, "0.1");
+        check_quad_decimal(0x3FFB999999999999A000000000000000, "0.1000000000000000055511151231257827");
+        check_quad_decimal(0x3FFD5555555555555555555555555555, "0.3333333333333333333333333333333333");
+        check_quad_decimal(0x3FFF0000000000000000000000000001, "1.0000000000000000000000000000000002");
+        check_quad_decimal(0x406293E5939A08CE9DBD480000000000, "1e30");
+        check_quad_decimal(0x7FFEFFFFFFFFFFFFFFFFFFFFFFFFFFFF, "1.189731495357231765085759326628007e4932");
+        check_quad_decimal(0x00010000000000000000000000000000, "3.3621031431120935062626778173217526e-4932");
+        check_quad_decimal(0x00000000000000000000000000000001, "6e-4966");
+    }
+}
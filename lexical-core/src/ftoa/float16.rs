@@ -0,0 +1,91 @@
+//! Float-to-string conversion for floats narrower than a 32-bit float.
+//!
+//! With the correct feature, the shortest digits that round-trip are
+//! generated using arbitrary-precision arithmetic, and written with the
+//! same notation as Ryu. Otherwise, the value is exactly converted to a
+//! 32-bit float and written with the float formatting algorithm, which
+//! round-trips, but may not be the shortest representation.
+
+use util::*;
+
+cfg_if! {
+if #[cfg(feature = "correct")] {
+    use atof::radix_digits;
+    use super::layout::{write_positional, write_scientific};
+} else {
+    use super::api::FloatToString;
+}}  // cfg_if
+
+/// Maximum number of digits in the shortest decimal representation.
+#[cfg(feature = "correct")]
+const MAX_DIGITS: usize = 32;
+
+/// Write the shortest decimal representation of the float.
+///
+/// `f` must be non-special (NaN or infinite), non-negative,
+/// and non-zero.
+#[cfg(feature = "correct")]
+#[inline]
+pub(crate) fn half_decimal<'a, F: Float>(f: F, bytes: &'a mut [u8], _: WriteAlgorithm)
+    -> usize
+{
+    // The lower boundary is closer for powers of two, except for the
+    // smallest normal float, since the previous float is denormal.
+    let mant = f.mantissa().as_u64();
+    let exp = f.exponent();
+    let is_lower_closer = f.mantissa() == F::HIDDEN_BIT_MASK && exp != F::DENORMAL_EXPONENT;
+    let mut digits: [u8; MAX_DIGITS] = [b'0'; MAX_DIGITS];
+    let (count, point) = radix_digits(mant, exp, is_lower_closer, 10, &mut digits);
+    let digits = &index!(digits[..count]);
+
+    // Use positional notation for exponents in `[-5, 15]`, like Ryu.
+    let exponent = point - 1;
+    let options = WriteOptions::new();
+    if exponent >= -5 && exponent <= 15 {
+        write_positional(digits, exponent, bytes, &options)
+    } else {
+        write_scientific(digits, exponent, bytes, &options)
+    }
+}
+
+/// Forward to the 32-bit float formatting algorithm.
+///
+/// `f` must be non-special (NaN or infinite), non-negative,
+/// and non-zero.
+#[cfg(not(feature = "correct"))]
+#[inline]
+pub(crate) fn half_decimal<'a, F: Float>(f: F, bytes: &'a mut [u8], algorithm: WriteAlgorithm)
+    -> usize
+{
+    f.as_f32().decimal(bytes, algorithm)
+}
+
+// TESTS
+// -----
+
+#[cfg(all(test, feature = "correct"))]
+mod tests {
+    use super::*;
+
+    fn check_half_decimal<F: Float>(f: F, expected: &str) {
+        let mut buffer = [b'\x00'; 64];
+        let len = half_decimal(f, &mut buffer, WriteOptions::new().algorithm());
+        assert_eq!(&buffer[..len], expected.as_bytes());
+    }
+
+    #[test]
+    fn half_decimal_test() {
+        check_half_decimal(f16::from_bits(0x3C00), "1.0");
+        check_half_decimal(f16::from_f32(0.1), "0.1");
+        check_half_decimal(f16::from_f32(1.5), "1.5");
+        check_half_decimal(f16::from_bits(0x7BFF), "65500.0");
+        check_half_decimal(f16::from_bits(0x0001), "6e-8");
+        check_half_decimal(f16::from_bits(0x0400), "0.00006104");
+        check_half_decimal(f16::from_bits(0x03FF), "0.000061");
+        check_half_decimal(f16::from_bits(0x0010), "9.5e-7");
+        check_half_decimal(bf16::from_f32(0.1), "0.1");
+        check_half_decimal(bf16::from_bits(0x7F7F), "3.39e38");
+        check_half_decimal(bf16::from_bits(0x0001), "1e-40");
+        check_half_decimal(bf16::from_f32(1e10), "10000000000.0");
+    }
+}
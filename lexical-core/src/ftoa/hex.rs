@@ -0,0 +1,140 @@
+//! Float-to-string conversion for C99 hexadecimal float literals.
+//!
+//! Floats are written like `printf("%a")`, with a leading `1` digit for
+//! normal floats, a leading `0` digit for subnormal floats, the fewest
+//! hexadecimal fraction digits required to represent the float exactly,
+//! and a signed, decimal binary exponent. For example, `12.0` is written
+//! as `0x1.8p+3`, and the smallest subnormal is written as
+//! `0x0.0000000000001p-1022`.
+
+use itoa;
+use util::*;
+
+/// Write a hexadecimal digit, in lowercase.
+#[inline]
+fn hex_digit(digit: u64) -> u8 {
+    digit_to_char(digit).to_ascii_lowercase()
+}
+
+/// Export a positive, finite, non-zero float to a hexadecimal float literal.
+///
+/// The fraction bits must be a multiple of 4, so each hexadecimal
+/// digit is exactly 4 bits of the fraction.
+fn write_hex<'a, F: Float>(value: F, bytes: &'a mut [u8])
+    -> usize
+{
+    debug_assert!(value > F::ZERO && !value.is_special(), "Value must be positive, finite, and non-zero.");
+    debug_assert!(F::MANTISSA_SIZE % 4 == 0, "Fraction must be a multiple of 4 bits.");
+
+    // Get the leading digit and the binary exponent.
+    let mant = value.to_bits() & F::MANTISSA_MASK;
+    let leading = if value.is_denormal() { b'0' } else { b'1' };
+    let exponent = value.exponent() + F::MANTISSA_SIZE;
+
+    // Write the prefix and leading digit.
+    index_mut!(bytes[0] = b'0');
+    index_mut!(bytes[1] = b'x');
+    index_mut!(bytes[2] = leading);
+    let mut index = 3;
+
+    // Write the fraction digits, without trailing zeros.
+    if !mant.is_zero() {
+        let digits = (F::MANTISSA_SIZE / 4).as_usize();
+        let count = digits - (mant.trailing_zeros() / 4).as_usize();
+        index_mut!(bytes[index] = b'.');
+        index += 1;
+        for i in 0..count {
+            let shift = F::MANTISSA_SIZE - 4 - 4 * i.as_i32();
+            index_mut!(bytes[index] = hex_digit(((mant >> shift) & as_cast(0xF)).as_u64()));
+            index += 1;
+        }
+    }
+
+    // Write the binary exponent.
+    index_mut!(bytes[index] = b'p');
+    index_mut!(bytes[index+1] = if exponent < 0 { b'-' } else { b'+' });
+    index += 2;
+    let exp = exponent.wrapping_abs() as u32;
+    index + itoa::forward(exp, 10, &mut index_mut!(bytes[index..]))
+}
+
+/// Export a positive, finite, non-zero double to a hexadecimal float literal.
+///
+/// The 52-bit fraction is exactly 13 hexadecimal digits.
+#[inline]
+pub(crate) fn double_hex<'a>(value: f64, bytes: &'a mut [u8])
+    -> usize
+{
+    write_hex(value, bytes)
+}
+
+/// Export a positive, finite, non-zero float to a hexadecimal float literal.
+///
+/// The float is written as the equivalent double, like `printf("%a")`.
+#[inline]
+pub(crate) fn float_hex<'a>(value: f32, bytes: &'a mut [u8])
+    -> usize
+{
+    double_hex(value as f64, bytes)
+}
+
+/// Export a positive, finite, non-zero quad float to a hexadecimal float literal.
+///
+/// The 112-bit fraction is exactly 28 hexadecimal digits.
+#[cfg(all(has_i128, feature = "correct"))]
+#[inline]
+pub(crate) fn quad_hex<'a>(value: f128, bytes: &'a mut [u8])
+    -> usize
+{
+    write_hex(value, bytes)
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_double_hex(value: f64, expected: &str) {
+        let mut buffer = [b'\x00'; 64];
+        let len = double_hex(value, &mut buffer);
+        assert_eq!(&buffer[..len], expected.as_bytes());
+    }
+
+    #[test]
+    fn double_hex_test() {
+        check_double_hex(1.0, "0x1p+0");
+        check_double_hex(12.0, "0x1.8p+3");
+        check_double_hex(0.1, "0x1.999999999999ap-4");
+        check_double_hex(0.25, "0x1p-2");
+        check_double_hex(1.0000000000000002, "0x1.0000000000001p+0");
+        check_double_hex(f64::MAX, "0x1.fffffffffffffp+1023");
+        check_double_hex(2.2250738585072014e-308, "0x1p-1022");
+        check_double_hex(2.225073858507201e-308, "0x0.fffffffffffffp-1022");
+        check_double_hex(5e-324, "0x0.0000000000001p-1022");
+    }
+
+    #[test]
+    fn float_hex_test() {
+        let mut buffer = [b'\x00'; 64];
+        let len = float_hex(12.0, &mut buffer);
+        assert_eq!(&buffer[..len], b"0x1.8p+3");
+        let len = float_hex(f32::MAX, &mut buffer);
+        assert_eq!(&buffer[..len], b"0x1.fffffep+127");
+        let len = float_hex(1e-45, &mut buffer);
+        assert_eq!(&buffer[..len], b"0x1p-149");
+    }
+
+    #[cfg(all(has_i128, feature = "correct"))]
+    #[test]
+    fn quad_hex_test() {
+        let mut buffer = [b'\x00'; 64];
+        let len = quad_hex(f128::from_bits(0x40028000000000000000000000000000), &mut buffer);
+        assert_eq!(&buffer[..len], b"0x1.8p+3");
+        let len = quad_hex(f128::MAX, &mut buffer);
+        assert_eq!(&buffer[..len], b"0x1.ffffffffffffffffffffffffffffp+16383");
+        let len = quad_hex(f128::from_bits(1), &mut buffer);
+        assert_eq!(&buffer[..len], b"0x0.0000000000000000000000000001p-16382");
+    }
+}
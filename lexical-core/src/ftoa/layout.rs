@@ -0,0 +1,254 @@
+//! Custom notation and exponent layout for decimal floats.
+//!
+//! The float formatting algorithms write the shortest digits with their
+//! own notation and exponent style, so the digits and the decimal
+//! exponent are extracted from that output and rewritten using the
+//! layout in the write options.
+
+use itoa;
+use util::*;
+
+/// Maximum number of significant digits written by the float formatting algorithms.
+///
+/// Quad floats need up to 36 significant digits to round-trip.
+pub(crate) const MAX_DIGITS: usize = 40;
+
+// PARSE
+
+/// Extract the significant digits and the decimal exponent from a decimal float.
+///
+/// Returns the number of digits, the decimal exponent, such that the
+/// value is `d1.d2...dn * 10^exponent`, and if the float was written
+/// in scientific notation. Zero is returned as a single `0` digit.
+fn parse_digits(bytes: &[u8], digits: &mut [u8])
+    -> (usize, i32, bool)
+{
+    let mut count = 0;
+    let mut point = 0;
+    let mut has_point = false;
+    let mut index = 0;
+
+    // Collect the mantissa digits, without leading zeros.
+    while index < bytes.len() {
+        let c = index!(bytes[index]);
+        if c == b'.' {
+            has_point = true;
+        } else if c.is_ascii_digit() {
+            if count == 0 && c == b'0' {
+                // Leading zero, only shifts the decimal point.
+                if has_point {
+                    point -= 1;
+                }
+            } else {
+                index_mut!(digits[count] = c);
+                count += 1;
+                if !has_point {
+                    point += 1;
+                }
+            }
+        } else {
+            break;
+        }
+        index += 1;
+    }
+
+    // Parse the exponent, after the exponent character.
+    let is_scientific = index < bytes.len();
+    let mut exponent: i32 = 0;
+    if is_scientific {
+        let exponent_bytes = &index!(bytes[index+1..]);
+        let (sign, exponent_bytes) = match exponent_bytes.first() {
+            Some(&b'-') => (-1, &index!(exponent_bytes[1..])),
+            Some(&b'+') => (1, &index!(exponent_bytes[1..])),
+            _           => (1, exponent_bytes),
+        };
+        for &c in exponent_bytes {
+            exponent = exponent * 10 + (c - b'0').as_i32();
+        }
+        exponent *= sign;
+    }
+
+    // Remove trailing zeros, which do not change the exponent.
+    while count > 0 && index!(digits[count-1]) == b'0' {
+        count -= 1;
+    }
+    if count == 0 {
+        index_mut!(digits[0] = b'0');
+        return (1, 0, is_scientific);
+    }
+
+    (count, point + exponent - 1, is_scientific)
+}
+
+// WRITE
+
+/// Write the digits in positional notation.
+pub(crate) fn write_positional(digits: &[u8], exponent: i32, bytes: &mut [u8], options: &WriteOptions)
+    -> usize
+{
+    let count = digits.len();
+    if exponent >= 0 {
+        // Write the integer digits, padded with zeros, then the fraction,
+        // or `.0`, which may be trimmed.
+        let integer = exponent.as_usize() + 1;
+        let fraction = if count > integer { count - integer } else { 1 };
+        let len = integer + 1 + fraction;
+        assert!(bytes.len() >= len, "Buffer is too small for the formatted float.");
+        for i in 0..integer {
+            index_mut!(bytes[i] = if i < count { index!(digits[i]) } else { b'0' });
+        }
+        index_mut!(bytes[integer] = b'.');
+        if count > integer {
+            copy_to_dst(&mut index_mut!(bytes[integer+1..]), &index!(digits[integer..]));
+        } else {
+            index_mut!(bytes[integer+1] = b'0');
+        }
+        len
+    } else {
+        // Write the optional leading zero, the decimal point, the zeros
+        // after the decimal point, and the digits.
+        let integer = if options.leading_zero() { 1 } else { 0 };
+        let zeros = exponent.wrapping_neg().as_usize() - 1;
+        let len = integer + 1 + zeros + count;
+        assert!(bytes.len() >= len, "Buffer is too small for the formatted float.");
+        if integer == 1 {
+            index_mut!(bytes[0] = b'0');
+        }
+        index_mut!(bytes[integer] = b'.');
+        write_bytes(&mut index_mut!(bytes[integer+1..integer+1+zeros]), b'0');
+        copy_to_dst(&mut index_mut!(bytes[integer+1+zeros..]), digits);
+        len
+    }
+}
+
+/// Write the digits in scientific notation.
+pub(crate) fn write_scientific(digits: &[u8], exponent: i32, bytes: &mut [u8], options: &WriteOptions)
+    -> usize
+{
+    let count = digits.len();
+    let abs_exponent = exponent.wrapping_abs().as_u32();
+    let exponent_digits = if abs_exponent >= 1000 {
+        4
+    } else if abs_exponent >= 100 {
+        3
+    } else if abs_exponent >= 10 {
+        2
+    } else {
+        1
+    };
+    let padding = options.min_exponent_digits().saturating_sub(exponent_digits);
+    let mantissa = if count > 1 { count + 1 } else { 1 };
+    let len = mantissa + 2 + padding + exponent_digits;
+    assert!(bytes.len() >= len, "Buffer is too small for the formatted float.");
+
+    // Write the mantissa, without a trailing `.0`, like `1e5`.
+    index_mut!(bytes[0] = index!(digits[0]));
+    if count > 1 {
+        index_mut!(bytes[1] = b'.');
+        copy_to_dst(&mut index_mut!(bytes[2..]), &index!(digits[1..]));
+    }
+
+    // Write the exponent, with an optional sign and padding.
+    let mut index = mantissa;
+    index_mut!(bytes[index] = b'e');
+    index += 1;
+    if exponent < 0 {
+        index_mut!(bytes[index] = b'-');
+        index += 1;
+    } else if options.positive_exponent_sign() {
+        index_mut!(bytes[index] = b'+');
+        index += 1;
+    }
+    write_bytes(&mut index_mut!(bytes[index..index+padding]), b'0');
+    index += padding;
+    index + itoa::forward(abs_exponent, 10, &mut index_mut!(bytes[index..]))
+}
+
+// API
+
+/// Rewrite a decimal float from the float formatting algorithm with the layout in the options.
+///
+/// `src` must be a positive decimal float, written with `.` and `e`.
+pub(crate) fn write_layout(src: &[u8], bytes: &mut [u8], options: &WriteOptions)
+    -> usize
+{
+    let mut digits: [u8; MAX_DIGITS] = [b'0'; MAX_DIGITS];
+    let (count, exponent, is_scientific) = parse_digits(src, &mut digits);
+    let digits = &index!(digits[..count]);
+    let is_positional = match options.notation() {
+        Notation::Positional => true,
+        Notation::Scientific => false,
+        Notation::Auto       => match options.positional_exponents() {
+            Some((min, max)) => exponent >= min && exponent <= max,
+            None             => !is_scientific,
+        },
+    };
+
+    match is_positional {
+        true  => write_positional(digits, exponent, bytes, options),
+        false => write_scientific(digits, exponent, bytes, options),
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_parse_digits(s: &str, expected: &str, expected_exponent: i32, expected_scientific: bool) {
+        let mut digits = [b'0'; MAX_DIGITS];
+        let (count, exponent, is_scientific) = parse_digits(s.as_bytes(), &mut digits);
+        assert_eq!(&digits[..count], expected.as_bytes());
+        assert_eq!(exponent, expected_exponent);
+        assert_eq!(is_scientific, expected_scientific);
+    }
+
+    #[test]
+    fn parse_digits_test() {
+        check_parse_digits("0.0", "0", 0, false);
+        check_parse_digits("1.0", "1", 0, false);
+        check_parse_digits("1500.0", "15", 3, false);
+        check_parse_digits("123.456", "123456", 2, false);
+        check_parse_digits("0.00012", "12", -4, false);
+        check_parse_digits("1e300", "1", 300, true);
+        check_parse_digits("1.2345e-299", "12345", -299, true);
+        check_parse_digits("1.5e+16", "15", 16, true);
+    }
+
+    fn check_write_layout(s: &str, options: &WriteOptions, expected: &str) {
+        let mut buffer = [b'\x00'; 64];
+        let len = write_layout(s.as_bytes(), &mut buffer, options);
+        assert_eq!(&buffer[..len], expected.as_bytes());
+    }
+
+    #[test]
+    fn write_layout_test() {
+        let mut options = WriteOptions::new();
+        options.set_notation(Notation::Positional);
+        check_write_layout("1.5e3", &options, "1500.0");
+        check_write_layout("1.5e-3", &options, "0.0015");
+        check_write_layout("0.0", &options, "0.0");
+        options.set_leading_zero(false);
+        check_write_layout("1.5e-3", &options, ".0015");
+        check_write_layout("1.0", &options, "1.0");
+
+        options.set_notation(Notation::Scientific);
+        check_write_layout("1500.0", &options, "1.5e3");
+        check_write_layout("1.0", &options, "1e0");
+        check_write_layout("0.0", &options, "0e0");
+        options.set_positive_exponent_sign(true);
+        options.set_min_exponent_digits(2);
+        check_write_layout("100000.0", &options, "1e+05");
+        check_write_layout("0.00012", &options, "1.2e-04");
+        check_write_layout("1e300", &options, "1e+300");
+
+        options.set_notation(Notation::Auto);
+        options.set_positional_exponents(-4, 15);
+        check_write_layout("1e16", &options, "1e+16");
+        check_write_layout("1000000000000000.0", &options, "1000000000000000.0");
+        check_write_layout("0.00001", &options, "1e-05");
+        check_write_layout("0.0001", &options, ".0001");
+    }
+}
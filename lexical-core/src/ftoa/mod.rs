@@ -4,16 +4,24 @@
 #[cfg(feature = "radix")]
 mod radix;
 
-cfg_if! {
-if #[cfg(feature = "grisu3")] {
-    mod grisu3;
-} else if #[cfg(feature = "ryu")] {
-    mod ryu;
-} else {
-    mod grisu2;
-}}  // cfg_if
+#[cfg(feature = "correct")]
+mod fixed;
+
+mod grisu2;
+
+#[cfg(feature = "grisu3")]
+mod grisu3;
+
+#[cfg(feature = "ryu")]
+mod ryu;
 
 mod api;
+mod float16;
+mod hex;
+mod layout;
+
+#[cfg(all(has_i128, feature = "correct"))]
+mod float128;
 
 // Re-exports
 pub use self::api::*;
@@ -1,11 +1,20 @@
-//! Adaptation of the V8 ftoa algorithm with a custom radix.
+//! Float-to-string conversion with a custom radix.
 //!
-//! This algorithm is adapted from the V8 codebase,
-//! and may be found [here](https://github.com/v8/v8).
+//! With the correct feature, the shortest digits that round-trip are
+//! generated using arbitrary-precision arithmetic, which is exact for
+//! every radix, including powers of two. Otherwise, an adaptation of
+//! the V8 ftoa algorithm is used, which may be found
+//! [here](https://github.com/v8/v8).
 
 use itoa;
 use util::*;
 
+#[cfg(feature = "correct")]
+use atof::radix_digits;
+
+#[cfg(all(has_i128, feature = "correct"))]
+use atof::quad_radix_digits;
+
 // FTOA BASEN
 // ----------
 
@@ -13,6 +22,7 @@ use util::*;
 ///
 /// Don't export this for float, since it's specialized for radix.
 #[inline]
+#[cfg(not(feature = "correct"))]
 pub(crate) fn naive_exponent(d: f64, radix: u32) -> i32
 {
     // floor returns the minimal value, which is our
@@ -25,10 +35,12 @@ pub(crate) fn naive_exponent(d: f64, radix: u32) -> i32
 /// Naive algorithm for converting a floating point to a custom radix.
 ///
 /// `d` must be non-special (NaN or infinite), non-negative,
-/// and non-zero.
+/// and non-zero. The digits are approximated with floating-point
+/// arithmetic, so they may not be the shortest, or round-trip.
 ///
 /// Adapted from the V8 implementation.
-fn ftoa_naive<'a>(value: f64, radix: u32, bytes: &'a mut [u8])
+#[cfg(not(feature = "correct"))]
+fn ftoa_naive<'a>(value: f64, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
     -> usize
 {
     debug_assert_radix!(radix);
@@ -172,7 +184,7 @@ fn ftoa_naive<'a>(value: f64, radix: u32, bytes: &'a mut [u8])
         let bytes = &mut bytes[count+2..];
 
         // write the exponent component
-        bytes[0] = exponent_notation_char(radix);
+        bytes[0] = options.exponent_notation_char(radix);
         // Handle negative exponents.
         let exp: u32;
         if exponent < 0 {
@@ -210,6 +222,124 @@ fn ftoa_naive<'a>(value: f64, radix: u32, bytes: &'a mut [u8])
     }
 }
 
+// FTOA SHORTEST
+// -------------
+
+/// Maximum number of digits in the shortest representation, for radix 2.
+#[cfg(feature = "correct")]
+const MAX_DIGITS: usize = 64;
+
+/// Write the digits in scientific notation, with the exponent in the radix.
+#[cfg(feature = "correct")]
+fn write_scientific(digits: &[u8], point: i32, radix: u32, bytes: &mut [u8], options: &WriteOptions)
+    -> usize
+{
+    // Write the mantissa, without a trailing `.0`.
+    index_mut!(bytes[0] = index!(digits[0]));
+    let mut index = 1;
+    if digits.len() > 1 {
+        index_mut!(bytes[1] = b'.');
+        index += 1 + copy_to_dst(&mut index_mut!(bytes[2..]), &index!(digits[1..]));
+    }
+
+    // Write the exponent.
+    let exponent = point - 1;
+    index_mut!(bytes[index] = options.exponent_notation_char(radix));
+    index += 1;
+    if exponent < 0 {
+        index_mut!(bytes[index] = b'-');
+        index += 1;
+    }
+    let exponent = exponent.wrapping_abs().as_u32();
+    index + itoa::forward(exponent, radix, &mut index_mut!(bytes[index..]))
+}
+
+/// Write the digits in positional notation.
+#[cfg(feature = "correct")]
+fn write_positional(digits: &[u8], point: i32, bytes: &mut [u8])
+    -> usize
+{
+    let count = digits.len();
+    if point > 0 {
+        // Write the integer digits, padded with zeros, and the fraction,
+        // or `.0` if there is no fraction.
+        let integer = point.as_usize();
+        for i in 0..integer {
+            index_mut!(bytes[i] = if i < count { index!(digits[i]) } else { b'0' });
+        }
+        index_mut!(bytes[integer] = b'.');
+        if count > integer {
+            integer + 1 + copy_to_dst(&mut index_mut!(bytes[integer+1..]), &index!(digits[integer..]))
+        } else {
+            index_mut!(bytes[integer+1] = b'0');
+            integer + 2
+        }
+    } else {
+        // Write `0.`, the zeros after the decimal point, and the digits.
+        let zeros = point.wrapping_neg().as_usize();
+        index_mut!(bytes[0] = b'0');
+        index_mut!(bytes[1] = b'.');
+        write_bytes(&mut index_mut!(bytes[2..zeros+2]), b'0');
+        zeros + 2 + copy_to_dst(&mut index_mut!(bytes[zeros+2..]), digits)
+    }
+}
+
+/// Shortest algorithm for converting a float to a custom radix.
+///
+/// The value is `mant * 2^exp`, and must be non-special (NaN or
+/// infinite), non-negative, and non-zero. The digits are the
+/// shortest that parse to the same float, with round-nearest,
+/// tie-even.
+#[cfg(feature = "correct")]
+fn ftoa_shortest<'a, F: Float>(value: F, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
+    -> usize
+{
+    debug_assert_radix!(radix);
+    debug_assert!(!value.is_special());
+    debug_assert!(value > F::ZERO);
+
+    // The lower boundary is closer for powers of two, except for the
+    // smallest normal float, since the previous float is denormal.
+    let mant = value.mantissa().as_u64();
+    let exp = value.exponent();
+    let is_lower_closer = value.mantissa() == F::HIDDEN_BIT_MASK && exp != F::DENORMAL_EXPONENT;
+    let mut digits: [u8; MAX_DIGITS] = [b'0'; MAX_DIGITS];
+    let (count, point) = radix_digits(mant, exp, is_lower_closer, radix, &mut digits);
+    write_shortest(&index!(digits[..count]), point, value.as_f64(), radix, bytes, options)
+}
+
+/// Write the shortest digits, in scientific notation for small or large values.
+#[cfg(feature = "correct")]
+#[inline]
+fn write_shortest(digits: &[u8], point: i32, value: f64, radix: u32, bytes: &mut [u8], options: &WriteOptions)
+    -> usize
+{
+    if value <= 1e-5 || value >= 1e9 {
+        write_scientific(digits, point, radix, bytes, options)
+    } else {
+        write_positional(digits, point, bytes)
+    }
+}
+
+// F16
+
+/// Forward to double_radix.
+///
+/// `f` must be non-special (NaN or infinite), non-negative,
+/// and non-zero.
+#[inline]
+pub(crate) fn half_radix<'a, F: Float>(f: F, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
+    -> usize
+{
+    #[cfg(feature = "correct")] {
+        ftoa_shortest(f, radix, bytes, options)
+    }
+
+    #[cfg(not(feature = "correct"))] {
+        double_radix(f.as_f64(), radix, bytes, options)
+    }
+}
+
 // F32
 
 /// Forward to double_radix.
@@ -217,10 +347,16 @@ fn ftoa_naive<'a>(value: f64, radix: u32, bytes: &'a mut [u8])
 /// `f` must be non-special (NaN or infinite), non-negative,
 /// and non-zero.
 #[inline]
-pub(crate) fn float_radix<'a>(f: f32, radix: u32, bytes: &'a mut [u8])
+pub(crate) fn float_radix<'a>(f: f32, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
     -> usize
 {
-    double_radix(f as f64, radix, bytes)
+    #[cfg(feature = "correct")] {
+        ftoa_shortest(f, radix, bytes, options)
+    }
+
+    #[cfg(not(feature = "correct"))] {
+        double_radix(f as f64, radix, bytes, options)
+    }
 }
 
 // F64
@@ -228,10 +364,130 @@ pub(crate) fn float_radix<'a>(f: f32, radix: u32, bytes: &'a mut [u8])
 /// Algorithm for non-decimal string representations.
 ///
 /// `d` must be non-special (NaN or infinite), non-negative,
+/// and non-zero. The digits are the shortest that round-trip only
+/// with the `correct` feature.
+#[inline]
+pub(crate) fn double_radix<'a>(value: f64, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
+    -> usize
+{
+    #[cfg(feature = "correct")] {
+        ftoa_shortest(value, radix, bytes, options)
+    }
+
+    #[cfg(not(feature = "correct"))] {
+        ftoa_naive(value, radix, bytes, options)
+    }
+}
+
+// F128
+
+/// Maximum number of digits in the shortest quad representation, for radix 2.
+#[cfg(all(has_i128, feature = "correct"))]
+const QUAD_MAX_DIGITS: usize = 128;
+
+/// Shortest algorithm for converting a quad float to a custom radix.
+///
+/// `value` must be non-special (NaN or infinite), non-negative,
 /// and non-zero.
+#[cfg(all(has_i128, feature = "correct"))]
 #[inline]
-pub(crate) fn double_radix<'a>(value: f64, radix: u32, bytes: &'a mut [u8])
+pub(crate) fn quad_radix<'a>(value: f128, radix: u32, bytes: &'a mut [u8], options: &WriteOptions)
     -> usize
 {
-    ftoa_naive(value, radix, bytes)
+    debug_assert_radix!(radix);
+    debug_assert!(!value.is_special());
+    debug_assert!(value > f128::ZERO);
+
+    let mant = value.mantissa();
+    let exp = value.exponent();
+    let is_lower_closer = mant == f128::HIDDEN_BIT_MASK && exp != f128::DENORMAL_EXPONENT;
+    let mut digits: [u8; QUAD_MAX_DIGITS] = [b'0'; QUAD_MAX_DIGITS];
+    let (count, point) = quad_radix_digits(mant, exp, is_lower_closer, radix, &mut digits);
+    write_shortest(&index!(digits[..count]), point, value.as_f64(), radix, bytes, options)
+}
+
+// TESTS
+// -----
+
+#[cfg(all(test, feature = "correct"))]
+mod tests {
+    use atof::*;
+    use super::*;
+
+    /// Increment the last digit, propagating the carry.
+    fn round_up(digits: &mut [u8], mut count: usize, point: i32, radix: u32) -> (usize, i32) {
+        while count > 0 {
+            let digit = char_to_digit(digits[count-1]) as u32 + 1;
+            if digit < radix {
+                digits[count-1] = digit_to_char(digit);
+                return (count, point);
+            }
+            count -= 1;
+        }
+        digits[0] = b'1';
+        (1, point + 1)
+    }
+
+    /// Check the digits round-trip, and no digits with one fewer digit round-trip.
+    fn check_shortest(value: f64, radix: u32) {
+        let mut digits = [b'0'; MAX_DIGITS];
+        let mut buffer = [b'0'; 256];
+        let is_lower_closer = value.mantissa() == f64::HIDDEN_BIT_MASK && value.exponent() != f64::DENORMAL_EXPONENT;
+        let options = WriteOptions::new();
+        let (count, point) = radix_digits(value.mantissa(), value.exponent(), is_lower_closer, radix, &mut digits);
+        let len = write_scientific(&digits[..count], point, radix, &mut buffer, &options);
+        assert_eq!(atof64_radix_slice(radix as u8, &buffer[..len]), value);
+        if count == 1 {
+            return;
+        }
+
+        // Truncate and round-up to one fewer digit.
+        let mut truncated = digits;
+        let len = write_scientific(&truncated[..count-1], point, radix, &mut buffer, &options);
+        assert!(atof64_radix_slice(radix as u8, &buffer[..len]) != value);
+        let (count, point) = round_up(&mut truncated, count - 1, point, radix);
+        let len = write_scientific(&truncated[..count], point, radix, &mut buffer, &options);
+        assert!(atof64_radix_slice(radix as u8, &buffer[..len]) != value);
+    }
+
+    #[test]
+    fn shortest_test() {
+        let values = [1.0, 0.1, 0.3, 1.5, 123456.789, 1e-300, 5e-324, 2.2250738585072014e-308, 1.7976931348623157e308, 9007199254740993.0, 1e23, 4.0, 0.000001];
+        for &value in values.iter() {
+            for radix in 2..37 {
+                check_shortest(value, radix);
+            }
+        }
+    }
+
+    #[test]
+    fn ftoa_shortest_test() {
+        let mut buffer = [b'0'; 256];
+        let options = WriteOptions::new();
+        let mut check = |value: f64, radix: u32, expected: &[u8]| {
+            let len = ftoa_shortest(value, radix, &mut buffer, &options);
+            assert_eq!(&buffer[..len], expected);
+        };
+        check(0.5, 2, b"0.1");
+        check(0.1, 2, b"0.0001100110011001100110011001100110011001100110011001101");
+        check(255.5, 16, b"FF.8");
+        check(0.1, 36, b"0.3LLLLLLLLLM");
+        check(1e10, 36, b"4.LDQPDS^6");
+        check(5e-324, 2, b"1e-10000110010");
+    }
+
+    #[cfg(has_i128)]
+    #[test]
+    fn quad_radix_test() {
+        let mut buffer = [b'0'; 256];
+        let options = WriteOptions::new();
+        let mut check = |bits: u128, radix: u32, expected: &[u8]| {
+            let len = quad_radix(f128::from_bits(bits), radix, &mut buffer, &options);
+            assert_eq!(&buffer[..len], expected);
+        };
+        check(0x3FFF8000000000000000000000000000, 2, b"1.1");
+        check(0x40070FF0000000000000000000000000, 16, b"10F.F");
+        check(0x3FFD5555555555555555555555555555, 3, b"0.1");
+        check(0x00000000000000000000000000000001, 2, b"1e-100000001101110");
+    }
 }
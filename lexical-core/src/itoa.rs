@@ -295,51 +295,85 @@ pub(crate) fn signed<Value, UWide, IWide>(value: Value, radix: u32, bytes: &mut
     }
 }
 
+/// Insert group separators into the written integer, if digits are grouped.
+#[inline]
+fn group(bytes: &mut [u8], len: usize, options: &WriteOptions)
+    -> usize
+{
+    let locale = options.locale();
+    if !locale.is_grouped() {
+        return len;
+    }
+
+    // We know this is safe, since we always write at least 1 byte.
+    let sign_bytes = (index!(bytes[0]) == b'-') as usize;
+    let digits = len - sign_bytes;
+    sign_bytes + write_groups(&mut index_mut!(bytes[sign_bytes..]), digits, digits, &locale)
+}
+
 // UNSAFE API
 
 /// Expand the generic unsigned itoa function for specified types.
 macro_rules! wrap_unsigned {
-    ($name:ident, $t:ty, $uwide:ty) => (
+    ($name:ident, $options_name:ident, $t:ty, $uwide:ty) => (
+        /// Serialize unsigned integer with custom options and return bytes written to.
+        #[inline]
+        fn $options_name<'a>(value: $t, radix: u8, bytes: &'a mut [u8], options: &WriteOptions)
+            -> usize
+        {
+            let len = unsigned::<$t, $uwide>(value, radix.into(), bytes);
+            group(bytes, len, options)
+        }
+
         /// Serialize unsigned integer and return bytes written to.
         #[inline]
         fn $name<'a>(value: $t, radix: u8, bytes: &'a mut [u8])
             -> usize
         {
-            unsigned::<$t, $uwide>(value, radix.into(), bytes)
+            $options_name(value, radix, bytes, &WriteOptions::new())
         }
     )
 }
 
-wrap_unsigned!(u8toa_impl, u8, u32);
-wrap_unsigned!(u16toa_impl, u16, u32);
-wrap_unsigned!(u32toa_impl, u32, u32);
-wrap_unsigned!(u64toa_impl, u64, u64);
-wrap_unsigned!(usizetoa_impl, usize, usize);
+wrap_unsigned!(u8toa_impl, u8toa_options_impl, u8, u32);
+wrap_unsigned!(u16toa_impl, u16toa_options_impl, u16, u32);
+wrap_unsigned!(u32toa_impl, u32toa_options_impl, u32, u32);
+wrap_unsigned!(u64toa_impl, u64toa_options_impl, u64, u64);
+wrap_unsigned!(usizetoa_impl, usizetoa_options_impl, usize, usize);
 
 #[cfg(has_i128)]
-wrap_unsigned!(u128toa_impl, u128, u128);
+wrap_unsigned!(u128toa_impl, u128toa_options_impl, u128, u128);
 
 /// Expand the generic signed itoa function for specified types.
 macro_rules! wrap_signed {
-    ($name:ident, $t:ty, $uwide:ty, $iwide:ty) => (
+    ($name:ident, $options_name:ident, $t:ty, $uwide:ty, $iwide:ty) => (
+        /// Serialize signed integer with custom options and return bytes written to.
+        #[inline]
+        fn $options_name<'a>(value: $t, radix: u8, bytes: &'a mut [u8], options: &WriteOptions)
+            -> usize
+        {
+            let len = signed::<$t, $uwide, $iwide>(value, radix.into(), bytes);
+            group(bytes, len, options)
+        }
+
         /// Serialize signed integer and return bytes written to.
         #[inline]
         fn $name<'a>(value: $t, radix: u8, bytes: &'a mut [u8])
             -> usize
         {
-            signed::<$t, $uwide, $iwide>(value, radix.into(), bytes)
+            $options_name(value, radix, bytes, &WriteOptions::new())
         }
     )
 }
 
-wrap_signed!(i8toa_impl, i8, u32, i32);
-wrap_signed!(i16toa_impl, i16, u32, i32);
-wrap_signed!(i32toa_impl, i32, u32, i32);
-wrap_signed!(i64toa_impl, i64, u64, i64);
-wrap_signed!(isizetoa_impl, isize, usize, isize);
+wrap_signed!(i8toa_impl, i8toa_options_impl, i8, u32, i32);
+wrap_signed!(i16toa_impl, i16toa_options_impl, i16, u32, i32);
+wrap_signed!(i32toa_impl, i32toa_options_impl, i32, u32, i32);
+wrap_signed!(i64toa_impl, i64toa_options_impl, i64, u64, i64);
+wrap_signed!(isizetoa_impl, isizetoa_options_impl, isize, usize, isize);
 
 #[cfg(has_i128)]
-wrap_signed!(i128toa_impl, i128, u128, i128);
+wrap_signed!(i128toa_impl, i128toa_options_impl, i128, u128, i128);
 
 // LOW-LEVEL API
 // -------------
@@ -359,6 +393,20 @@ generate_to_range_api!(isizetoa_range, isizetoa_radix_range, isize, isizetoa_imp
 #[cfg(has_i128)] generate_to_range_api!(u128toa_range, u128toa_radix_range, u128, u128toa_impl, MAX_U128_SIZE);
 #[cfg(has_i128)] generate_to_range_api!(i128toa_range, i128toa_radix_range, i128, i128toa_impl, MAX_I128_SIZE);
 
+generate_to_range_options_api!(u8toa_range_with_options, u8toa_radix_range_with_options, u8, u8toa_options_impl, MAX_U8_SIZE);
+generate_to_range_options_api!(u16toa_range_with_options, u16toa_radix_range_with_options, u16, u16toa_options_impl, MAX_U16_SIZE);
+generate_to_range_options_api!(u32toa_range_with_options, u32toa_radix_range_with_options, u32, u32toa_options_impl, MAX_U32_SIZE);
+generate_to_range_options_api!(u64toa_range_with_options, u64toa_radix_range_with_options, u64, u64toa_options_impl, MAX_U64_SIZE);
+generate_to_range_options_api!(usizetoa_range_with_options, usizetoa_radix_range_with_options, usize, usizetoa_options_impl, MAX_USIZE_SIZE);
+generate_to_range_options_api!(i8toa_range_with_options, i8toa_radix_range_with_options, i8, i8toa_options_impl, MAX_I8_SIZE);
+generate_to_range_options_api!(i16toa_range_with_options, i16toa_radix_range_with_options, i16, i16toa_options_impl, MAX_I16_SIZE);
+generate_to_range_options_api!(i32toa_range_with_options, i32toa_radix_range_with_options, i32, i32toa_options_impl, MAX_I32_SIZE);
+generate_to_range_options_api!(i64toa_range_with_options, i64toa_radix_range_with_options, i64, i64toa_options_impl, MAX_I64_SIZE);
+generate_to_range_options_api!(isizetoa_range_with_options, isizetoa_radix_range_with_options, isize, isizetoa_options_impl, MAX_ISIZE_SIZE);
+
+#[cfg(has_i128)] generate_to_range_options_api!(u128toa_range_with_options, u128toa_radix_range_with_options, u128, u128toa_options_impl, MAX_U128_SIZE);
+#[cfg(has_i128)] generate_to_range_options_api!(i128toa_range_with_options, i128toa_radix_range_with_options, i128, i128toa_options_impl, MAX_I128_SIZE);
+
 // SLICE API
 generate_to_slice_api!(u8toa_slice, u8toa_radix_slice, u8, u8toa_impl, MAX_U8_SIZE);
 generate_to_slice_api!(u16toa_slice, u16toa_radix_slice, u16, u16toa_impl, MAX_U16_SIZE);
@@ -374,6 +422,71 @@ generate_to_slice_api!(isizetoa_slice, isizetoa_radix_slice, isize, isizetoa_imp
 #[cfg(has_i128)] generate_to_slice_api!(u128toa_slice, u128toa_radix_slice, u128, u128toa_impl, MAX_U128_SIZE);
 #[cfg(has_i128)] generate_to_slice_api!(i128toa_slice, i128toa_radix_slice, i128, i128toa_impl, MAX_I128_SIZE);
 
+generate_to_slice_options_api!(u8toa_slice_with_options, u8toa_radix_slice_with_options, u8, u8toa_options_impl, MAX_U8_SIZE);
+generate_to_slice_options_api!(u16toa_slice_with_options, u16toa_radix_slice_with_options, u16, u16toa_options_impl, MAX_U16_SIZE);
+generate_to_slice_options_api!(u32toa_slice_with_options, u32toa_radix_slice_with_options, u32, u32toa_options_impl, MAX_U32_SIZE);
+generate_to_slice_options_api!(u64toa_slice_with_options, u64toa_radix_slice_with_options, u64, u64toa_options_impl, MAX_U64_SIZE);
+generate_to_slice_options_api!(usizetoa_slice_with_options, usizetoa_radix_slice_with_options, usize, usizetoa_options_impl, MAX_USIZE_SIZE);
+generate_to_slice_options_api!(i8toa_slice_with_options, i8toa_radix_slice_with_options, i8, i8toa_options_impl, MAX_I8_SIZE);
+generate_to_slice_options_api!(i16toa_slice_with_options, i16toa_radix_slice_with_options, i16, i16toa_options_impl, MAX_I16_SIZE);
+generate_to_slice_options_api!(i32toa_slice_with_options, i32toa_radix_slice_with_options, i32, i32toa_options_impl, MAX_I32_SIZE);
+generate_to_slice_options_api!(i64toa_slice_with_options, i64toa_radix_slice_with_options, i64, i64toa_options_impl, MAX_I64_SIZE);
+generate_to_slice_options_api!(isizetoa_slice_with_options, isizetoa_radix_slice_with_options, isize, isizetoa_options_impl, MAX_ISIZE_SIZE);
+
+#[cfg(has_i128)] generate_to_slice_options_api!(u128toa_slice_with_options, u128toa_radix_slice_with_options, u128, u128toa_options_impl, MAX_U128_SIZE);
+#[cfg(has_i128)] generate_to_slice_options_api!(i128toa_slice_with_options, i128toa_radix_slice_with_options, i128, i128toa_options_impl, MAX_I128_SIZE);
+
+// SIZE API
+
+/// Get the maximum number of digits written for an integer type.
+#[inline]
+fn max_digits<T: Integer>() -> usize {
+    #[cfg(feature = "radix")] {
+        // Binary has the most digits of any radix.
+        T::BITS
+    }
+
+    #[cfg(not(feature = "radix"))] {
+        let radix: T = as_cast(10);
+        let mut value = T::max_value();
+        let mut digits = 1;
+        while value >= radix {
+            value = value / radix;
+            digits += 1;
+        }
+        digits
+    }
+}
+
+/// Expand the buffer size functions for integers with custom options.
+macro_rules! max_size_with_options {
+    ($name:ident, $t:ty, $size:ident) => (
+        /// Get the minimum buffer size required to serialize any value with custom options.
+        ///
+        /// Digit grouping may write more bytes than the `MAX_*_SIZE`
+        /// constant for the type, so buffers for the `*_with_options`
+        /// serializers should have at least this many elements.
+        #[inline]
+        pub fn $name(options: &WriteOptions) -> usize {
+            $size + options.locale().separators_len(max_digits::<$t>())
+        }
+    )
+}
+
+max_size_with_options!(max_u8_size_with_options, u8, MAX_U8_SIZE);
+max_size_with_options!(max_u16_size_with_options, u16, MAX_U16_SIZE);
+max_size_with_options!(max_u32_size_with_options, u32, MAX_U32_SIZE);
+max_size_with_options!(max_u64_size_with_options, u64, MAX_U64_SIZE);
+max_size_with_options!(max_usize_size_with_options, usize, MAX_USIZE_SIZE);
+max_size_with_options!(max_i8_size_with_options, i8, MAX_I8_SIZE);
+max_size_with_options!(max_i16_size_with_options, i16, MAX_I16_SIZE);
+max_size_with_options!(max_i32_size_with_options, i32, MAX_I32_SIZE);
+max_size_with_options!(max_i64_size_with_options, i64, MAX_I64_SIZE);
+max_size_with_options!(max_isize_size_with_options, isize, MAX_ISIZE_SIZE);
+
+#[cfg(has_i128)] max_size_with_options!(max_u128_size_with_options, u128, MAX_U128_SIZE);
+#[cfg(has_i128)] max_size_with_options!(max_i128_size_with_options, i128, MAX_I128_SIZE);
+
 // TESTS
 // -----
 
@@ -471,6 +584,59 @@ mod tests {
         assert_eq!(b"-1", i64toa_slice(-1, &mut buffer));
     }
 
+    #[test]
+    fn itoa_locale_test() {
+        let mut buffer = new_buffer();
+        let mut options = WriteOptions::new();
+        options.set_locale(Locale::EN_US);
+        assert_eq!(b"1,234,567", u32toa_slice_with_options(1234567, &mut buffer, &options));
+        assert_eq!(b"-1,234,567", i32toa_slice_with_options(-1234567, &mut buffer, &options));
+        assert_eq!(b"-123", i8toa_slice_with_options(-123, &mut buffer, &options));
+        assert_eq!(b"0", u8toa_slice_with_options(0, &mut buffer, &options));
+
+        options.set_locale(Locale::EN_IN);
+        assert_eq!(b"12,34,56,789", u64toa_slice_with_options(123456789, &mut buffer, &options));
+
+        options.set_locale(Locale::FR_FR);
+        assert_eq!("-9\u{a0}223\u{a0}372\u{a0}036\u{a0}854\u{a0}775\u{a0}808".as_bytes(), i64toa_slice_with_options(i64::min_value(), &mut buffer, &options));
+    }
+
+    #[test]
+    fn itoa_utf16_test() {
+        let utf16 = |s: &str| s.encode_utf16().collect::<Vec<u16>>();
+        let mut buffer = [0u16; BUFFER_SIZE];
+        assert_eq!(&utf16("0")[..], u8toa_slice(0, &mut buffer));
+        assert_eq!(&utf16("-128")[..], i8toa_slice(-128, &mut buffer));
+        assert_eq!(&utf16("18446744073709551615")[..], u64toa_slice(u64::max_value(), &mut buffer));
+
+        let mut options = WriteOptions::new();
+        options.set_locale(Locale::FR_FR);
+        assert_eq!(&utf16("-1\u{a0}234\u{a0}567")[..], i32toa_slice_with_options(-1234567, &mut buffer, &options));
+    }
+
+    #[test]
+    fn itoa_max_size_test() {
+        let mut options = WriteOptions::new();
+        assert_eq!(max_u64_size_with_options(&options), MAX_U64_SIZE);
+
+        options.set_locale(Locale::EN_US);
+        let mut buffer = vec![b'\x00'; max_u64_size_with_options(&options)];
+        assert_eq!(b"18,446,744,073,709,551,615", u64toa_slice_with_options(u64::max_value(), &mut buffer, &options));
+
+        options.set_locale(Locale::FR_FR);
+        let mut buffer = vec![b'\x00'; max_i64_size_with_options(&options)];
+        assert_eq!("-9\u{a0}223\u{a0}372\u{a0}036\u{a0}854\u{a0}775\u{a0}808".as_bytes(), i64toa_slice_with_options(i64::min_value(), &mut buffer, &options));
+    }
+
+    #[test]
+    #[should_panic]
+    fn itoa_locale_buffer_test() {
+        let mut buffer = [b'\x00'; 20];
+        let mut options = WriteOptions::new();
+        options.set_locale(Locale::EN_US);
+        u64toa_options_impl(u64::max_value(), 10, &mut buffer, &options);
+    }
+
     #[cfg(feature = "radix")]
     #[test]
     fn basen_test() {
@@ -135,17 +135,16 @@ extern crate alloc;
 #[macro_use]
 extern crate stackvector;
 
-// Ensure only one back-end is enabled.
-#[cfg(all(feature = "grisu3", feature = "ryu"))]
-compile_error!("Lexical only accepts one of the following backends: `grisu3` or `ryu`.");
+// Conversions to and from the half crate floats.
+#[cfg(feature = "half")]
+extern crate half;
 
-// Import the back-end, if applicable.
-cfg_if! {
-if #[cfg(feature = "grisu3")] {
-    extern crate dtoa;
-} else if #[cfg(feature = "ryu")] {
-    extern crate ryu;
-}}  // cfg_if
+// Import the back-ends, if applicable.
+#[cfg(feature = "grisu3")]
+extern crate dtoa;
+
+#[cfg(feature = "ryu")]
+extern crate ryu;
 
 /// Facade around the core features for name mangling.
 pub(crate) mod lib {
@@ -199,6 +198,7 @@ mod atoi;
 mod float;
 mod ftoa;
 mod itoa;
+mod stream;
 
 // Publicly re-export the low-level string-to-float functions.
 pub use atof::*;
@@ -212,5 +212,12 @@ pub use ftoa::*;
 // Publicly re-export the low-level integer-to-string functions.
 pub use itoa::*;
 
+// Publicly re-export the incremental number parsers.
+pub use stream::*;
+
 // Re-export configuration and utilities globally.
 pub use util::*;
+
+// Disambiguate the integer-or-float number from the internal number trait.
+#[cfg(feature = "correct")]
+pub use atof::Number;
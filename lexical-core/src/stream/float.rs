@@ -0,0 +1,421 @@
+//! Incremental parser for floats split across multiple buffers.
+//!
+//! The stream stores the first 800 significant digits, and if any later
+//! digit is non-zero: the correct parsers only use the first 769
+//! significant digits to round a 64-bit or narrower float, and whether
+//! any later digits are non-zero, so the float is rounded the same as
+//! if every digit was stored.
+
+use atof::*;
+use itoa::*;
+use util::*;
+use lib::fmt;
+use super::{is_sign, State, StreamStatus};
+
+/// Maximum number of significant digits stored for a float.
+const MAX_FLOAT_DIGITS: usize = 800;
+
+/// Buffer size to write the float: the sign, significant digits, a
+/// digit for any truncated digits, the exponent character, and exponent.
+const FLOAT_BUFFER_SIZE: usize = 1 + MAX_FLOAT_DIGITS + 2 + MAX_I64_SIZE;
+
+/// Incremental parser for a decimal float split across buffers.
+///
+/// Each buffer is fed to the stream in order, until the stream is
+/// terminated by a byte which cannot continue the float, or the input
+/// ends. The float is then parsed with one of the `finish_*` methods,
+/// which return the same correctly-rounded value, bytes processed, and
+/// error as the partial parsers for the concatenated buffers, like
+/// `atof64_partial_slice`.
+///
+/// The stream only accepts floats in the standard grammar, with the
+/// decimal point, exponent character, and rounding scheme from the
+/// options. Special values, like `NaN`, digit separators, and digit
+/// grouping are not supported.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::{FloatStream, StreamStatus};
+/// # pub fn main() {
+/// let mut stream = FloatStream::new();
+/// assert_eq!(stream.feed(b"[1.2"), StreamStatus::Terminated(0));
+///
+/// let mut stream = FloatStream::new();
+/// assert_eq!(stream.feed(b"-1.2"), StreamStatus::Open);
+/// assert_eq!(stream.feed(b"5e"), StreamStatus::Open);
+/// assert_eq!(stream.feed(b"3]"), StreamStatus::Terminated(1));
+/// assert_eq!(stream.finish_f64().value, (-1250.0, 7));
+/// # }
+/// ```
+#[derive(Copy, Clone)]
+pub struct FloatStream {
+    /// Current component of the number.
+    state: State,
+    /// Leading sign character.
+    sign: Option<u8>,
+    /// If the number has a decimal point.
+    has_point: bool,
+    /// If the mantissa has any digits.
+    has_digits: bool,
+    /// Byte that terminated the number.
+    terminator: Option<u8>,
+    /// Number of bytes consumed.
+    processed: usize,
+    /// Significant digits, without leading zeros.
+    digits: [u8; MAX_FLOAT_DIGITS],
+    /// Number of significant digits stored.
+    count: usize,
+    /// If any non-zero digits were not stored.
+    truncated: bool,
+    /// Exponent for the stored digits, without the parsed exponent.
+    scale: i64,
+    /// Absolute value of the parsed exponent, saturated on overflow.
+    exponent: i64,
+    /// If the parsed exponent is negative.
+    is_negative_exponent: bool,
+    /// Character separating the integer and fraction digits.
+    decimal_point: u8,
+    /// Options to parse the float written in the standard grammar.
+    options: ParseOptions<'static>,
+}
+
+impl FloatStream {
+    /// Create a stream for a new float with custom options.
+    ///
+    /// * `options` - Options to customize number parsing.
+    #[inline]
+    pub fn with_options(options: &ParseOptions) -> FloatStream {
+        FloatStream {
+            state: State::Start,
+            sign: None,
+            has_point: false,
+            has_digits: false,
+            terminator: None,
+            processed: 0,
+            digits: [b'0'; MAX_FLOAT_DIGITS],
+            count: 0,
+            truncated: false,
+            scale: 0,
+            exponent: 0,
+            is_negative_exponent: false,
+            decimal_point: options.decimal_point(),
+            options: options.standard(),
+        }
+    }
+
+    /// Create a stream for a new float.
+    #[inline]
+    pub fn new() -> FloatStream {
+        FloatStream::with_options(&ParseOptions::global())
+    }
+
+    /// Get if the float has been terminated.
+    #[inline]
+    pub fn is_terminated(&self) -> bool {
+        self.terminator.is_some()
+    }
+
+    /// Get the number of bytes consumed from all buffers.
+    #[inline]
+    pub fn processed(&self) -> usize {
+        self.processed
+    }
+
+    /// Store a significant digit, returning if the digit was stored.
+    #[inline]
+    fn push_digit(&mut self, c: u8) -> bool {
+        if self.count < MAX_FLOAT_DIGITS {
+            self.digits[self.count] = c;
+            self.count += 1;
+            true
+        } else {
+            self.truncated |= c != b'0';
+            false
+        }
+    }
+
+    /// Consume a single byte, returning if the byte continues the float.
+    #[inline]
+    fn consume(&mut self, c: u8) -> bool {
+        let is_exponent = case_insensitive_equal(c, self.options.exponent_char());
+        match self.state {
+            State::Start if is_sign(c) => {
+                self.sign = Some(c);
+                self.state = State::Sign;
+            },
+            State::Start | State::Sign | State::Integer if c.is_ascii_digit() => {
+                // Skip leading zeros, and scale any digits which were not stored.
+                if (self.count != 0 || c != b'0') && !self.push_digit(c) {
+                    self.scale = self.scale.saturating_add(1);
+                }
+                self.has_digits = true;
+                self.state = State::Integer;
+            },
+            State::Start | State::Sign | State::Integer if c == self.decimal_point => {
+                self.has_point = true;
+                self.state = State::Fraction;
+            },
+            State::Fraction if c.is_ascii_digit() => {
+                // Leading zeros are not stored, but still scale the value.
+                if self.count == 0 && c == b'0' || self.push_digit(c) {
+                    self.scale = self.scale.saturating_sub(1);
+                }
+                self.has_digits = true;
+            },
+            State::Integer | State::Fraction if is_exponent && self.has_digits => {
+                self.state = State::Exponent;
+            },
+            State::Exponent if is_sign(c) => {
+                self.is_negative_exponent = c == b'-';
+                self.state = State::ExponentSign;
+            },
+            State::Exponent | State::ExponentSign | State::ExponentDigits if c.is_ascii_digit() => {
+                let digit = (c - b'0').as_i64();
+                self.exponent = self.exponent.saturating_mul(10).saturating_add(digit);
+                self.state = State::ExponentDigits;
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// Consume bytes from the next buffer containing the float.
+    ///
+    /// Returns `StreamStatus::Terminated` with the index of the first
+    /// byte after the float if the float ended in the buffer, or
+    /// `StreamStatus::Open` if the float may continue in the next
+    /// buffer. Once terminated, the stream does not consume any more
+    /// bytes.
+    pub fn feed(&mut self, bytes: &[u8]) -> StreamStatus {
+        if self.is_terminated() {
+            return StreamStatus::Terminated(0);
+        }
+        for (index, &c) in bytes.iter().enumerate() {
+            if !self.consume(c) {
+                self.terminator = Some(c);
+                return StreamStatus::Terminated(index);
+            }
+            self.processed += 1;
+        }
+        StreamStatus::Open
+    }
+
+    /// Write the float in the standard grammar, and parse it.
+    #[inline]
+    fn finish<T, Cb>(&self, cb: Cb)
+        -> Result<(T, usize)>
+        where Cb: FnOnce(&[u8], &ParseOptions) -> Result<(T, usize)>
+    {
+        let mut buffer = [b'0'; FLOAT_BUFFER_SIZE];
+        let mut len = 0;
+        if let Some(c) = self.sign {
+            buffer[len] = c;
+            len += 1;
+        }
+
+        if !self.has_digits {
+            // No digits, the sign, decimal point and terminator are the
+            // entire input the partial parser would process. The
+            // terminator is never a decimal point for the stream, so
+            // replace it with an invalid digit if it is one for the
+            // standard grammar.
+            if self.has_point {
+                buffer[len] = b'.';
+                len += 1;
+            }
+            if let Some(c) = self.terminator {
+                buffer[len] = if c == b'.' { b'x' } else { c };
+                len += 1;
+            }
+            return cb(&buffer[..len], &self.options);
+        }
+
+        // Write the significant digits, or a single zero, a non-zero
+        // digit if any digits were truncated, and the exponent. Trim
+        // trailing zeros, since the parsers assume any digits past the
+        // maximum significant digits in the integer are non-zero.
+        let mut count = self.count.max(1);
+        let mut scale = self.scale;
+        if self.truncated {
+            copy_to_dst(&mut buffer[len..], &self.digits[..count]);
+            len += count;
+            buffer[len] = b'1';
+            len += 1;
+            scale = scale.saturating_sub(1);
+        } else {
+            while count > 1 && self.digits[count - 1] == b'0' {
+                count -= 1;
+                scale = scale.saturating_add(1);
+            }
+            copy_to_dst(&mut buffer[len..], &self.digits[..count]);
+            len += count;
+        }
+        buffer[len] = self.options.exponent_char();
+        len += 1;
+        let exponent = match self.is_negative_exponent {
+            true  => scale.saturating_sub(self.exponent),
+            false => scale.saturating_add(self.exponent),
+        };
+        len += i64toa_slice(exponent, &mut buffer[len..]).len();
+
+        let mut result = cb(&buffer[..len], &self.options);
+        result.value.1 = self.processed;
+        match self.state {
+            // The standard grammar does not process an exponent without
+            // digits, or its sign.
+            State::Exponent     => result.value.1 -= 1,
+            State::ExponentSign => result.value.1 -= 2,
+            _                   => (),
+        }
+        result
+    }
+}
+
+impl Default for FloatStream {
+    #[inline]
+    fn default() -> FloatStream {
+        FloatStream::new()
+    }
+}
+
+impl fmt::Debug for FloatStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FloatStream")
+            .field("state", &self.state)
+            .field("sign", &self.sign)
+            .field("terminator", &self.terminator)
+            .field("processed", &self.processed)
+            .field("digits", &&self.digits[..self.count])
+            .field("truncated", &self.truncated)
+            .field("scale", &self.scale)
+            .field("exponent", &self.exponent)
+            .field("is_negative_exponent", &self.is_negative_exponent)
+            .finish()
+    }
+}
+
+/// Generate the methods to finish a float stream.
+macro_rules! float_stream_finish {
+    ($($name:ident, $t:ty, $cb:ident ;)*) => ($(
+        /// Parse the float from the consumed bytes.
+        ///
+        /// Returns a C-compatible result containing the parsed value
+        /// and the number of bytes processed, and an error container
+        /// any errors that occurred during parsing.
+        #[inline]
+        pub fn $name(&self) -> Result<($t, usize)> {
+            self.finish($cb)
+        }
+    )*);
+}
+
+impl FloatStream {
+    float_stream_finish! {
+        finish_f16, f16, atof16_partial_slice_with_options ;
+        finish_bf16, bf16, atobf16_partial_slice_with_options ;
+        finish_f32, f32, atof32_partial_slice_with_options ;
+        finish_f64, f64, atof64_partial_slice_with_options ;
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed the bytes split at every index, and check the stream matches the partial parser.
+    fn check_split(bytes: &[u8]) {
+        let expected = atof64_partial_slice(bytes);
+        for i in 0..bytes.len() + 1 {
+            let mut stream = FloatStream::new();
+            if stream.feed(&bytes[..i]) == StreamStatus::Open {
+                stream.feed(&bytes[i..]);
+            }
+            let result = stream.finish_f64();
+            assert_eq!(expected, result, "{:?} at {}", bytes, i);
+            assert!(expected.value.0.to_bits() == result.value.0.to_bits(), "{:?} at {}", bytes, i);
+        }
+    }
+
+    #[test]
+    fn float_stream_split_test() {
+        let inputs: &[&[u8]] = &[
+            b"", b"-", b"+", b".", b"-.", b"..", b"e5", b"-e", b"a", b"1",
+            b"-0", b"1.", b"1.5", b".5", b"1e", b"1e+", b"1.5e", b"1e5x",
+            b"1..2", b"1.5e-3.", b"-12.50e3,", b"0.000123", b"1e-400",
+            b"1e400", b"1e99999999999999999999999", b"123456789012345678901234567890",
+            b"0.1", b"5e-324", b"2.4703282292062327e-324", b"9007199254740993",
+            b"1.7976931348623157e308",
+        ];
+        for bytes in inputs.iter() {
+            check_split(bytes);
+        }
+    }
+
+    #[test]
+    fn float_stream_long_test() {
+        // Halfway between 2^53 and 2^53+2, where only the digits past
+        // the stored digits determine the rounding.
+        let mut bytes = [b'0'; 2000];
+        bytes[..16].copy_from_slice(b"9007199254740993");
+        bytes[16] = b'.';
+        check_split(&bytes);
+        bytes[1999] = b'1';
+        check_split(&bytes);
+
+        let mut stream = FloatStream::new();
+        for chunk in bytes.chunks(7) {
+            stream.feed(chunk);
+        }
+        assert_eq!(stream.finish_f64().value, (9007199254740994.0, 2000));
+
+        // Leading zeros are not significant digits.
+        let mut bytes = [b'0'; 2000];
+        bytes[1] = b'.';
+        bytes[1999] = b'1';
+        check_split(&bytes);
+        bytes[1989] = b'5';
+        bytes[1990] = b'e';
+        bytes[1991] = b'+';
+        bytes[1992..].copy_from_slice(b"00001990");
+        check_split(&bytes);
+        assert_eq!(atof64_partial_slice(&bytes[..]).value, (500.0, 2000));
+    }
+
+    #[test]
+    fn float_stream_type_test() {
+        let mut stream = FloatStream::new();
+        assert_eq!(stream.feed(b"0.1"), StreamStatus::Open);
+        assert_eq!(stream.finish_f32().value, (0.1, 3));
+        assert_eq!(stream.finish_f64().value, (0.1, 3));
+        assert_eq!(stream.finish_f16().value.0, f16::from_f32(0.1));
+        assert_eq!(stream.finish_bf16().value.0, bf16::from_f32(0.1));
+    }
+
+    #[test]
+    fn float_stream_options_test() {
+        let mut options = ParseOptions::new();
+        options.set_locale(Locale::DE_DE);
+        options.set_exponent_char(b'd');
+
+        let mut stream = FloatStream::with_options(&options);
+        assert_eq!(stream.feed(b"1,5D"), StreamStatus::Open);
+        assert_eq!(stream.feed(b"3."), StreamStatus::Terminated(1));
+        assert_eq!(stream.finish_f64(), atof64_partial_slice_with_options(b"1,5D3.", &options));
+
+        let mut stream = FloatStream::with_options(&options);
+        assert_eq!(stream.feed(b"-."), StreamStatus::Terminated(1));
+        assert_eq!(stream.finish_f64(), atof64_partial_slice_with_options(b"-.", &options));
+    }
+
+    proptest! {
+        #[test]
+        fn float_stream_split_proptest(i in r"[+-]?[0-9]{0,30}(\.[0-9]{0,30})?([eE][+-]?[0-9]{0,4})?[.,eE\]]?") {
+            check_split(i.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Incremental parser for integers split across multiple buffers.
+//!
+//! The stream stores at most 40 significant digits, since any integer
+//! with more digits overflows a 128-bit integer.
+
+use atoi::*;
+use util::*;
+use lib::fmt;
+use super::{is_sign, State, StreamStatus};
+
+/// Maximum number of significant digits stored for an integer.
+const MAX_INTEGER_DIGITS: usize = 40;
+
+/// Buffer size to write the integer: the sign and significant digits.
+const INTEGER_BUFFER_SIZE: usize = 1 + MAX_INTEGER_DIGITS;
+
+/// Incremental parser for a decimal integer split across buffers.
+///
+/// Each buffer is fed to the stream in order, until the stream is
+/// terminated by a byte which cannot continue the integer, or the input
+/// ends. The integer is then parsed with one of the `finish_*` methods,
+/// which return the same value, bytes processed, and error as the
+/// partial parsers for the concatenated buffers, like `atoi64_partial_slice`.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::{IntegerStream, StreamStatus};
+/// # pub fn main() {
+/// let mut stream = IntegerStream::new();
+/// assert_eq!(stream.feed(b"-123"), StreamStatus::Open);
+/// assert_eq!(stream.feed(b"45,67"), StreamStatus::Terminated(2));
+/// assert_eq!(stream.finish_i32().value, (-12345, 6));
+/// # }
+/// ```
+#[derive(Copy, Clone)]
+pub struct IntegerStream {
+    /// Current component of the number.
+    state: State,
+    /// Leading sign character.
+    sign: Option<u8>,
+    /// Byte that terminated the number.
+    terminator: Option<u8>,
+    /// Number of bytes consumed.
+    processed: usize,
+    /// Significant digits, without leading zeros.
+    digits: [u8; MAX_INTEGER_DIGITS],
+    /// Number of significant digits stored.
+    count: usize,
+}
+
+impl IntegerStream {
+    /// Create a stream for a new integer.
+    #[inline]
+    pub fn new() -> IntegerStream {
+        IntegerStream {
+            state: State::Start,
+            sign: None,
+            terminator: None,
+            processed: 0,
+            digits: [b'0'; MAX_INTEGER_DIGITS],
+            count: 0,
+        }
+    }
+
+    /// Get if the integer has been terminated.
+    #[inline]
+    pub fn is_terminated(&self) -> bool {
+        self.terminator.is_some()
+    }
+
+    /// Get the number of bytes consumed from all buffers.
+    #[inline]
+    pub fn processed(&self) -> usize {
+        self.processed
+    }
+
+    /// Consume a single byte, returning if the byte continues the integer.
+    #[inline]
+    fn consume(&mut self, c: u8) -> bool {
+        match self.state {
+            State::Start if is_sign(c) => {
+                self.sign = Some(c);
+                self.state = State::Sign;
+            },
+            State::Start | State::Sign | State::Integer if c.is_ascii_digit() => {
+                // Skip leading zeros, and any digits past the maximum,
+                // which always overflow.
+                if (self.count != 0 || c != b'0') && self.count < MAX_INTEGER_DIGITS {
+                    self.digits[self.count] = c;
+                    self.count += 1;
+                }
+                self.state = State::Integer;
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// Consume bytes from the next buffer containing the integer.
+    ///
+    /// Returns `StreamStatus::Terminated` with the index of the first
+    /// byte after the integer if the integer ended in the buffer, or
+    /// `StreamStatus::Open` if the integer may continue in the next
+    /// buffer. Once terminated, the stream does not consume any more
+    /// bytes.
+    pub fn feed(&mut self, bytes: &[u8]) -> StreamStatus {
+        if self.is_terminated() {
+            return StreamStatus::Terminated(0);
+        }
+        for (index, &c) in bytes.iter().enumerate() {
+            if !self.consume(c) {
+                self.terminator = Some(c);
+                return StreamStatus::Terminated(index);
+            }
+            self.processed += 1;
+        }
+        StreamStatus::Open
+    }
+
+    /// Write the integer in the standard grammar, and parse it.
+    #[inline]
+    fn finish<T, Cb>(&self, cb: Cb)
+        -> Result<(T, usize)>
+        where Cb: FnOnce(&[u8]) -> Result<(T, usize)>
+    {
+        let mut buffer = [b'0'; INTEGER_BUFFER_SIZE];
+        let mut len = 0;
+        if let Some(c) = self.sign {
+            buffer[len] = c;
+            len += 1;
+        }
+
+        if self.state == State::Integer {
+            // Write the significant digits, or a single zero.
+            let count = self.count.max(1);
+            copy_to_dst(&mut buffer[len..], &self.digits[..count]);
+            len += count;
+            let mut result = cb(&buffer[..len]);
+            if result.value.1 == len {
+                result.value.1 = self.processed;
+            }
+            result
+        } else {
+            // No digits, the sign and terminator are the entire input
+            // the partial parser would process.
+            if let Some(c) = self.terminator {
+                buffer[len] = c;
+                len += 1;
+            }
+            cb(&buffer[..len])
+        }
+    }
+}
+
+impl Default for IntegerStream {
+    #[inline]
+    fn default() -> IntegerStream {
+        IntegerStream::new()
+    }
+}
+
+impl fmt::Debug for IntegerStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IntegerStream")
+            .field("state", &self.state)
+            .field("sign", &self.sign)
+            .field("terminator", &self.terminator)
+            .field("processed", &self.processed)
+            .field("digits", &&self.digits[..self.count])
+            .finish()
+    }
+}
+
+/// Generate the methods to finish an integer stream.
+macro_rules! integer_stream_finish {
+    ($($name:ident, $t:ty, $cb:ident ;)*) => ($(
+        /// Parse the integer from the consumed bytes.
+        ///
+        /// Returns a C-compatible result containing the parsed value
+        /// and the number of bytes processed, and an error container
+        /// any errors that occurred during parsing.
+        #[inline]
+        pub fn $name(&self) -> Result<($t, usize)> {
+            self.finish($cb)
+        }
+    )*);
+}
+
+impl IntegerStream {
+    integer_stream_finish! {
+        finish_u8, u8, atou8_partial_slice ;
+        finish_u16, u16, atou16_partial_slice ;
+        finish_u32, u32, atou32_partial_slice ;
+        finish_u64, u64, atou64_partial_slice ;
+        finish_usize, usize, atousize_partial_slice ;
+        finish_i8, i8, atoi8_partial_slice ;
+        finish_i16, i16, atoi16_partial_slice ;
+        finish_i32, i32, atoi32_partial_slice ;
+        finish_i64, i64, atoi64_partial_slice ;
+        finish_isize, isize, atoisize_partial_slice ;
+    }
+
+    #[cfg(has_i128)]
+    integer_stream_finish! {
+        finish_u128, u128, atou128_partial_slice ;
+        finish_i128, i128, atoi128_partial_slice ;
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed the bytes split at every index, and check the stream matches the partial parser.
+    fn check_split(bytes: &[u8]) {
+        let expected = atoi64_partial_slice(bytes);
+        for i in 0..bytes.len() + 1 {
+            let mut stream = IntegerStream::new();
+            if stream.feed(&bytes[..i]) == StreamStatus::Open {
+                stream.feed(&bytes[i..]);
+            }
+            assert_eq!(expected, stream.finish_i64(), "{:?} at {}", bytes, i);
+        }
+    }
+
+    #[test]
+    fn integer_stream_split_test() {
+        let inputs: &[&[u8]] = &[
+            b"", b"-", b"+", b"-.", b"a", b"1", b"-0", b"0001", b"1.5",
+            b"12,", b"123456789012345678901234567890", b"-9223372036854775808",
+            b"9223372036854775808", b"-00000000000000000000000000000000000000000001",
+        ];
+        for bytes in inputs.iter() {
+            check_split(bytes);
+        }
+    }
+
+    #[test]
+    fn integer_stream_test() {
+        let mut stream = IntegerStream::new();
+        assert_eq!(stream.feed(b"300"), StreamStatus::Open);
+        assert_eq!(stream.finish_u8().error.code, ErrorCode::Overflow);
+        assert_eq!(stream.finish_u16().value, (300, 3));
+        assert_eq!(stream.feed(b"1]"), StreamStatus::Terminated(1));
+        assert!(stream.is_terminated());
+        assert_eq!(stream.feed(b"1"), StreamStatus::Terminated(0));
+        assert_eq!(stream.finish_u32().value, (3001, 4));
+        assert_eq!(stream.processed(), 4);
+
+        let mut stream = IntegerStream::new();
+        stream.feed(b"-1");
+        assert_eq!(stream.finish_u8(), atou8_partial_slice(b"-1"));
+    }
+
+    proptest! {
+        #[test]
+        fn integer_stream_split_proptest(i in r"[+-]?[0-9]{0,30}[.,eE\]]?") {
+            check_split(i.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Incremental parsers for numbers split across multiple buffers.
+//!
+//! The streams lex a decimal number one buffer at a time, and only store
+//! the state required to parse the number once it ends. Once the number
+//! ends, the digits are written to a buffer in the standard grammar,
+//! and parsed with the partial parsers, so the result is identical to
+//! parsing the number in a single buffer.
+
+#[cfg(feature = "correct")]
+mod float;
+mod integer;
+
+// Re-exports
+#[cfg(feature = "correct")]
+pub use self::float::*;
+pub use self::integer::*;
+
+// STATUS
+
+/// Status of a number stream after consuming a buffer.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StreamStatus {
+    /// The entire buffer was consumed, and the number may continue in
+    /// the next buffer.
+    Open,
+    /// The number ended after the given number of bytes from the buffer.
+    ///
+    /// The byte at the index is not part of the number, and may start
+    /// the next token.
+    Terminated(usize),
+}
+
+/// Current component of the number being lexed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum State {
+    /// No bytes have been consumed.
+    Start,
+    /// Consumed the mantissa sign.
+    Sign,
+    /// Consumed integer digits.
+    Integer,
+    /// Consumed the decimal point, and any fraction digits.
+    #[cfg(feature = "correct")]
+    Fraction,
+    /// Consumed the exponent character.
+    #[cfg(feature = "correct")]
+    Exponent,
+    /// Consumed the exponent sign.
+    #[cfg(feature = "correct")]
+    ExponentSign,
+    /// Consumed exponent digits.
+    #[cfg(feature = "correct")]
+    ExponentDigits,
+}
+
+/// Determine if the byte is a sign character.
+#[inline]
+fn is_sign(c: u8) -> bool {
+    c == b'+' || c == b'-'
+}
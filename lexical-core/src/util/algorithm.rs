@@ -2,10 +2,11 @@
 
 use lib::convert::AsRef;
 use lib::{mem, ptr, slice};
+use super::code_unit::CodeUnit;
 
 // ALGORITHMS
 
-/// Calculate the difference between two pointers.
+/// Calculate the number of elements between two pointers.
 #[inline]
 pub fn distance<T>(first: *const T, last: *const T)
     -> usize
@@ -13,38 +14,38 @@ pub fn distance<T>(first: *const T, last: *const T)
     debug_assert!(last >= first, "range must be positive.");
     let f = first as usize;
     let l = last as usize;
-    l - f
+    (l - f) / mem::size_of::<T>()
 }
 
 /// Check if two slices are equal to each other.
 #[inline]
-pub fn equal_to_slice(l: &[u8], r: &[u8])
+pub fn equal_to_slice<C: CodeUnit>(l: &[C], r: &[u8])
     -> bool
 {
-    l == r
+    l.len() == r.len() && l.iter().zip(r.iter()).all(|(&li, &ri)| li.to_byte() == ri)
 }
 
 /// Check if values are equal to each other without case-sensitivity.
 #[inline]
-pub fn case_insensitive_equal(l: u8, r: u8)
+pub fn case_insensitive_equal<C: CodeUnit>(l: C, r: u8)
     -> bool
 {
-    l.to_ascii_lowercase() == r.to_ascii_lowercase()
+    l.to_byte().to_ascii_lowercase() == r.to_ascii_lowercase()
 }
 
 /// Check if two slices are equal to each other without case-sensitivity.
 #[inline]
-pub fn case_insensitive_equal_to_slice(l: &[u8], r: &[u8])
+pub fn case_insensitive_equal_to_slice<C: CodeUnit>(l: &[C], r: &[u8])
     -> bool
 {
-    let liter = l.iter().map(|li| li.to_ascii_lowercase());
+    let liter = l.iter().map(|li| li.to_byte().to_ascii_lowercase());
     let riter = r.iter().map(|ri| ri.to_ascii_lowercase());
     l.len() == r.len() && liter.eq(riter)
 }
 
 /// Check if left slice starts with right slice without case-sensitivity.
 #[inline]
-pub fn case_insensitive_starts_with_slice(l: &[u8], r: &[u8])
+pub fn case_insensitive_starts_with_slice<C: CodeUnit>(l: &[C], r: &[u8])
     -> bool
 {
     // This cannot be out-of-bounds, since we check `l.len() >= r.len()`
@@ -55,7 +56,7 @@ pub fn case_insensitive_starts_with_slice(l: &[u8], r: &[u8])
 
 /// Check if left slice ends with right slice.
 #[inline]
-pub fn ends_with_slice(l: &[u8], r: &[u8])
+pub fn ends_with_slice<C: CodeUnit>(l: &[C], r: &[u8])
     -> bool
 {
     // This cannot be out-of-bounds, since we check `l.len() >= r.len()`
@@ -67,10 +68,10 @@ pub fn ends_with_slice(l: &[u8], r: &[u8])
 
 /// Trim character from the left-side of a slice.
 #[inline]
-pub fn ltrim_char_slice<'a>(slc: &'a [u8], c: u8)
-    -> (&'a [u8], usize)
+pub fn ltrim_char_slice<'a, C: CodeUnit>(slc: &'a [C], c: u8)
+    -> (&'a [C], usize)
 {
-    let count = slc.iter().take_while(|&&si| si == c).count();
+    let count = slc.iter().take_while(|&&si| si.to_byte() == c).count();
     //  This count cannot exceed the bounds of the slice, since it is
     // derived from an iterator using the standard library to generate it.
     debug_assert!(count <= slc.len());
@@ -81,10 +82,10 @@ pub fn ltrim_char_slice<'a>(slc: &'a [u8], c: u8)
 /// Trim character from the right-side of a slice.
 #[cfg(any(feature = "correct", feature = "radix"))]
 #[inline]
-pub fn rtrim_char_slice<'a>(slc: &'a [u8], c: u8)
-    -> (&'a [u8], usize)
+pub fn rtrim_char_slice<'a, C: CodeUnit>(slc: &'a [C], c: u8)
+    -> (&'a [C], usize)
 {
-    let count = slc.iter().rev().take_while(|&&si| si == c).count();
+    let count = slc.iter().rev().take_while(|&&si| si.to_byte() == c).count();
     let index = slc.len() - count;
     // Count must be <= slc.len(), and therefore, slc.len() - count must
     // also be <= slc.len(), since this is derived from an iterator
@@ -111,7 +112,6 @@ pub fn copy_to_dst<'a, Bytes: AsRef<[u8]>>(dst: &'a mut [u8], src: Bytes)
 }
 
 /// Length-check variant of ptr::write_bytes for a slice.
-#[cfg(not(any(feature = "grisu3", feature = "ryu")))]
 #[inline]
 pub fn write_bytes(dst: &mut [u8], byte: u8)
 {
@@ -169,6 +169,11 @@ mod tests {
             let first: *const u8 = x.as_ptr();
             let last = first.add(x.len());
             assert_eq!(distance(first, last), 10);
+
+            let y: [u16; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+            let first: *const u16 = y.as_ptr();
+            let last = first.add(y.len());
+            assert_eq!(distance(first, last), 10);
         }
     }
 
@@ -2,7 +2,7 @@
 
 use lib::{self, slice};
 use super::algorithm::distance;
-use super::num::Number;
+use super::error::ErrorCode;
 use super::pointer_methods::PointerMethods;
 use super::result::*;
 
@@ -28,20 +28,39 @@ pub(crate) unsafe fn slice_from_range_mut<'a, T>(first: *mut T, last: *mut T)
 
 /// Wrap the unsafe API into the safe, parse API trying to parse raw bytes.
 #[inline]
-pub(crate) fn try_from_bytes_wrapper<'a, T, Cb>(radix: u8, bytes: &'a [u8], cb: Cb)
+pub(crate) fn try_from_bytes_wrapper<'a, T, C, Cb>(radix: u8, bytes: &'a [C], cb: Cb)
     -> Result<T>
-    where T: Number,
-          Cb: FnOnce(u8, &'a [u8]) -> (T, usize, bool)
+    where Cb: FnOnce(u8, &'a [C]) -> (T, usize, ErrorCode)
 {
-    let (value, processed, overflow) = cb(radix, bytes);
+    let (value, processed, code) = cb(radix, bytes);
     if bytes.is_empty() {
         empty_error(value)
-    } else if overflow {
-        overflow_error(value)
-    } else if processed == bytes.len() {
-        success(value)
     } else {
-        invalid_digit_error(value, processed)
+        match code {
+            ErrorCode::Success if processed == bytes.len() => success(value),
+            ErrorCode::Success  => invalid_digit_error(value, processed),
+            ErrorCode::Overflow => overflow_error(value),
+            code                => new_error(value, code, processed),
+        }
+    }
+}
+
+/// Wrap the unsafe API into the safe, partial parse API, returning the processed bytes.
+#[inline]
+pub(crate) fn partial_from_bytes_wrapper<'a, T, C, Cb>(radix: u8, bytes: &'a [C], cb: Cb)
+    -> Result<(T, usize)>
+    where Cb: FnOnce(u8, &'a [C]) -> (T, usize, ErrorCode)
+{
+    let (value, processed, code) = cb(radix, bytes);
+    if bytes.is_empty() {
+        empty_error((value, 0))
+    } else {
+        match code {
+            ErrorCode::Success if processed == 0 => invalid_digit_error((value, 0), 0),
+            ErrorCode::Success  => success((value, processed)),
+            ErrorCode::Overflow => overflow_error((value, processed)),
+            code                => new_error((value, processed), code, processed),
+        }
     }
 }
 
@@ -115,9 +134,9 @@ macro_rules! generate_from_slice_api {
         /// Returns the parsed value, ignoring any trailing invalid digits,
         /// and explicitly wrapping on arithmetic overflow.
         ///
-        /// * `bytes`   - Slice containing a numeric string.
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
         #[inline]
-        pub fn $decimal_name(bytes: &[u8])
+        pub fn $decimal_name<C: CodeUnit>(bytes: &[C])
             -> $t
         {
             $cb(10, bytes).0
@@ -129,14 +148,14 @@ macro_rules! generate_from_slice_api {
         /// and explicitly wrapping on arithmetic overflow.
         ///
         /// * `radix`   - Radix for the number parsing.
-        /// * `bytes`   - Slice containing a numeric string.
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
         ///
         /// # Panics
         ///
         /// Panics if the radix is not in the range `[2, 36]`.
         #[cfg(feature = "radix")]
         #[inline]
-        pub fn $radix_name(radix: u8, bytes: &[u8])
+        pub fn $radix_name<C: CodeUnit>(radix: u8, bytes: &[C])
             -> $t
         {
             assert_radix!(radix);
@@ -168,7 +187,7 @@ macro_rules! generate_try_from_range_api {
             -> Result<$t>
         {
             let bytes = $crate::util::api::slice_from_range(first, last);
-            $crate::util::api::try_from_bytes_wrapper::<$t, _>(10, bytes, $cb)
+            $crate::util::api::try_from_bytes_wrapper::<$t, _, _>(10, bytes, $cb)
         }
 
         /// Checked parser for a string-to-number conversion using Rust pointer ranges.
@@ -194,7 +213,7 @@ macro_rules! generate_try_from_range_api {
         {
             assert_radix!(radix);
             let bytes = $crate::util::api::slice_from_range(first, last);
-            $crate::util::api::try_from_bytes_wrapper::<$t, _>(radix, bytes, $cb)
+            $crate::util::api::try_from_bytes_wrapper::<$t, _, _>(radix, bytes, $cb)
         }
     )
 }
@@ -211,12 +230,12 @@ macro_rules! generate_try_from_slice_api {
         /// Numeric overflow takes precedence over the presence of an invalid
         /// digit, and therefore may mask an invalid digit error.
         ///
-        /// * `bytes`   - Slice containing a numeric string.
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
         #[inline]
-        pub fn $decimal_name(bytes: &[u8])
+        pub fn $decimal_name<C: CodeUnit>(bytes: &[C])
             -> Result<$t>
         {
-            $crate::util::api::try_from_bytes_wrapper::<$t, _>(10, bytes, $cb)
+            $crate::util::api::try_from_bytes_wrapper::<$t, _, _>(10, bytes, $cb)
         }
 
         /// Checked parser for a string-to-number conversion using Rust slices.
@@ -228,18 +247,63 @@ macro_rules! generate_try_from_slice_api {
         /// digit, and therefore may mask an invalid digit error.
         ///
         /// * `radix`   - Radix for the number parsing.
-        /// * `bytes`   - Slice containing a numeric string.
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
         ///
         /// # Panics
         ///
         /// Panics if the radix is not in the range `[2, 36]`.
         #[cfg(feature = "radix")]
         #[inline]
-        pub fn $radix_name(radix: u8, bytes: &[u8])
+        pub fn $radix_name<C: CodeUnit>(radix: u8, bytes: &[C])
             -> Result<$t>
         {
             assert_radix!(radix);
-            $crate::util::api::try_from_bytes_wrapper::<$t, _>(radix, bytes, $cb)
+            $crate::util::api::try_from_bytes_wrapper::<$t, _, _>(radix, bytes, $cb)
+        }
+    )
+}
+
+/// Macro to generate the low-level, safe, partial parse API using a slice.
+#[doc(hidden)]
+macro_rules! generate_partial_from_slice_api {
+    ($decimal_name:ident, $radix_name:ident, $t:ty, $cb:ident) => (
+        /// Partial parser for a string-to-number conversion using Rust slices.
+        ///
+        /// Returns a C-compatible result containing the parsed value and
+        /// the number of bytes processed, stopping at the first invalid
+        /// digit, and an error container any errors that occurred during
+        /// parsing. Empty input and input without a leading number are
+        /// errors, while trailing bytes are not.
+        ///
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
+        #[inline]
+        pub fn $decimal_name<C: CodeUnit>(bytes: &[C])
+            -> Result<($t, usize)>
+        {
+            $crate::util::api::partial_from_bytes_wrapper::<$t, _, _>(10, bytes, $cb)
+        }
+
+        /// Partial parser for a string-to-number conversion using Rust slices.
+        ///
+        /// Returns a C-compatible result containing the parsed value and
+        /// the number of bytes processed, stopping at the first invalid
+        /// digit, and an error container any errors that occurred during
+        /// parsing. Empty input and input without a leading number are
+        /// errors, while trailing bytes are not.
+        ///
+        /// * `radix`   - Radix for the number parsing.
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the radix is not in the range `[2, 36]`.
+        #[cfg(feature = "radix")]
+        #[inline]
+        pub fn $radix_name<C: CodeUnit>(radix: u8, bytes: &[C])
+            -> Result<($t, usize)>
+        {
+            assert_radix!(radix);
+            $crate::util::api::partial_from_bytes_wrapper::<$t, _, _>(radix, bytes, $cb)
         }
     )
 }
@@ -337,7 +401,7 @@ macro_rules! generate_to_slice_api {
         /// uninitialized, these values are never read, only written to.
         ///
         /// * `value`   - Number to serialize.
-        /// * `bytes`   - Slice containing a numeric string.
+        /// * `bytes`   - Buffer of bytes or UTF-16 code units to write to.
         ///
         /// # Panics
         ///
@@ -347,11 +411,11 @@ macro_rules! generate_to_slice_api {
         /// `MAX_*_SIZE` elements, using the proper constant for the
         /// serialized type from the lexical_core crate root.
         #[inline]
-        pub fn $decimal_name<'a>(value: $t, bytes: &'a mut [u8])
-            -> &'a mut [u8]
+        pub fn $decimal_name<'a, C: CodeUnit>(value: $t, bytes: &'a mut [C])
+            -> &'a mut [C]
         {
             assert_buffer!(bytes, $size);
-            let len = $cb(value, 10, bytes);
+            let len = C::write_bytes(bytes, |bytes| $cb(value, 10, bytes));
             &mut index_mut!(bytes[..len])
         }
 
@@ -368,7 +432,7 @@ macro_rules! generate_to_slice_api {
         ///
         /// * `value`   - Number to serialize.
         /// * `radix`   - Radix for number encoding.
-        /// * `bytes`   - Slice containing a numeric string.
+        /// * `bytes`   - Buffer of bytes or UTF-16 code units to write to.
         ///
         /// # Panics
         ///
@@ -381,15 +445,404 @@ macro_rules! generate_to_slice_api {
         /// serialized type from the lexical_core crate root.
         #[cfg(feature = "radix")]
         #[inline]
-        pub fn $radix_name<'a>(value: $t, radix: u8, bytes: &'a mut [u8])
-            -> &'a mut [u8]
+        pub fn $radix_name<'a, C: CodeUnit>(value: $t, radix: u8, bytes: &'a mut [C])
+            -> &'a mut [C]
         {
             assert_radix!(radix);
             assert_buffer!(bytes, $size);
             // This is always safe, since len is returned as
             // `distance(bytes.as_ptr(), slc.as_ptr())`, where `slc` is
             // a subslice from writes.
-            let len = $cb(value, radix, bytes);
+            let len = C::write_bytes(bytes, |bytes| $cb(value, radix, bytes));
+            &mut index_mut!(bytes[..len])
+        }
+    )
+}
+
+// OPTIONS WRAPPERS
+
+/// Macro to generate the low-level, unsafe API with options using a pointer range.
+#[doc(hidden)]
+macro_rules! generate_from_range_options_api {
+    ($decimal_name:ident, $radix_name:ident, $t:ty, $cb:ident) => (
+        /// Unchecked parser for a string-to-number conversion using pointer ranges and custom options.
+        ///
+        /// Returns the parsed value, ignoring any trailing invalid digits,
+        /// and explicitly wrapping on arithmetic overflow.
+        ///
+        /// * `first`   - Pointer to the start of the input data.
+        /// * `last`    - Pointer to the one-past-the-end of the input data.
+        /// * `options` - Options to customize number parsing.
+        ///
+        /// # Panics
+        ///
+        /// Panics if either pointer is null.
+        pub unsafe fn $decimal_name(first: *const u8, last: *const u8, options: &ParseOptions)
+            -> $t
+        {
+            assert!(first <= last && !first.is_null() && !last.is_null());
+            let bytes = $crate::lib::slice::from_raw_parts(first, distance(first, last));
+            $cb(10, bytes, options).0
+        }
+
+        /// Unchecked parser for a string-to-number conversion using pointer ranges and custom options.
+        ///
+        /// Returns the parsed value, ignoring any trailing invalid digits,
+        /// and explicitly wrapping on arithmetic overflow.
+        ///
+        /// * `radix`   - Radix for the number parsing.
+        /// * `first`   - Pointer to the start of the input data.
+        /// * `last`    - Pointer to the one-past-the-end of the input data.
+        /// * `options` - Options to customize number parsing.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the radix is not in the range `[2, 36]`. Also panics
+        /// if either pointer is null.
+        #[cfg(feature = "radix")]
+        pub unsafe fn $radix_name(radix: u8, first: *const u8, last: *const u8, options: &ParseOptions)
+            -> $t
+        {
+            assert_radix!(radix);
+            assert!(first <= last && !first.is_null() && !last.is_null());
+            let bytes = $crate::lib::slice::from_raw_parts(first, distance(first, last));
+            $cb(radix, bytes, options).0
+        }
+    )
+}
+
+/// Macro to generate the low-level, safe, parse API with options using a slice.
+#[doc(hidden)]
+macro_rules! generate_from_slice_options_api {
+    ($decimal_name:ident, $radix_name:ident, $t:ty, $cb:ident) => (
+        /// Unchecked parser for a string-to-number conversion using Rust slices and custom options.
+        ///
+        /// Returns the parsed value, ignoring any trailing invalid digits,
+        /// and explicitly wrapping on arithmetic overflow.
+        ///
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
+        /// * `options` - Options to customize number parsing.
+        #[inline]
+        pub fn $decimal_name<C: CodeUnit>(bytes: &[C], options: &ParseOptions)
+            -> $t
+        {
+            $cb(10, bytes, options).0
+        }
+
+        /// Unchecked parser for a string-to-number conversion using Rust slices and custom options.
+        ///
+        /// Returns the parsed value, ignoring any trailing invalid digits,
+        /// and explicitly wrapping on arithmetic overflow.
+        ///
+        /// * `radix`   - Radix for the number parsing.
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
+        /// * `options` - Options to customize number parsing.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the radix is not in the range `[2, 36]`.
+        #[cfg(feature = "radix")]
+        #[inline]
+        pub fn $radix_name<C: CodeUnit>(radix: u8, bytes: &[C], options: &ParseOptions)
+            -> $t
+        {
+            assert_radix!(radix);
+            $cb(radix, bytes, options).0
+        }
+    )
+}
+
+/// Macro to generate the low-level, unsafe, try_parse API with options using a pointer range.
+#[doc(hidden)]
+macro_rules! generate_try_from_range_options_api {
+    ($decimal_name:ident, $radix_name:ident, $t:ty, $cb:ident) => (
+        /// Checked parser for a string-to-number conversion using Rust pointer ranges and custom options.
+        ///
+        /// Returns a C-compatible result containing the parsed value,
+        /// and an error container any errors that occurred during parser.
+        ///
+        /// Numeric overflow takes precedence over the presence of an invalid
+        /// digit, and therefore may mask an invalid digit error.
+        ///
+        /// * `first`   - Pointer to the start of the input data.
+        /// * `last`    - Pointer to the one-past-the-end of the input data.
+        /// * `options` - Options to customize number parsing.
+        ///
+        /// # Panics
+        ///
+        /// Panics if either pointer is null.
+        pub unsafe fn $decimal_name(first: *const u8, last: *const u8, options: &ParseOptions)
+            -> Result<$t>
+        {
+            let bytes = $crate::util::api::slice_from_range(first, last);
+            $crate::util::api::try_from_bytes_wrapper::<$t, _, _>(10, bytes, |r, b| $cb(r, b, options))
+        }
+
+        /// Checked parser for a string-to-number conversion using Rust pointer ranges and custom options.
+        ///
+        /// Returns a C-compatible result containing the parsed value,
+        /// and an error container any errors that occurred during parser.
+        ///
+        /// Numeric overflow takes precedence over the presence of an invalid
+        /// digit, and therefore may mask an invalid digit error.
+        ///
+        /// * `radix`   - Radix for the number parsing.
+        /// * `first`   - Pointer to the start of the input data.
+        /// * `last`    - Pointer to the one-past-the-end of the input data.
+        /// * `options` - Options to customize number parsing.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the radix is not in the range `[2, 36]`. Also panics
+        /// if either pointer is null.
+        #[cfg(feature = "radix")]
+        pub unsafe fn $radix_name(radix: u8, first: *const u8, last: *const u8, options: &ParseOptions)
+            -> Result<$t>
+        {
+            assert_radix!(radix);
+            let bytes = $crate::util::api::slice_from_range(first, last);
+            $crate::util::api::try_from_bytes_wrapper::<$t, _, _>(radix, bytes, |r, b| $cb(r, b, options))
+        }
+    )
+}
+
+/// Macro to generate the low-level, safe, try_parse API with options using a slice.
+#[doc(hidden)]
+macro_rules! generate_try_from_slice_options_api {
+    ($decimal_name:ident, $radix_name:ident, $t:ty, $cb:ident) => (
+        /// Checked parser for a string-to-number conversion using Rust slices and custom options.
+        ///
+        /// Returns a C-compatible result containing the parsed value,
+        /// and an error container any errors that occurred during parser.
+        ///
+        /// Numeric overflow takes precedence over the presence of an invalid
+        /// digit, and therefore may mask an invalid digit error.
+        ///
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
+        /// * `options` - Options to customize number parsing.
+        #[inline]
+        pub fn $decimal_name<C: CodeUnit>(bytes: &[C], options: &ParseOptions)
+            -> Result<$t>
+        {
+            $crate::util::api::try_from_bytes_wrapper::<$t, _, _>(10, bytes, |r, b| $cb(r, b, options))
+        }
+
+        /// Checked parser for a string-to-number conversion using Rust slices and custom options.
+        ///
+        /// Returns a C-compatible result containing the parsed value,
+        /// and an error container any errors that occurred during parser.
+        ///
+        /// Numeric overflow takes precedence over the presence of an invalid
+        /// digit, and therefore may mask an invalid digit error.
+        ///
+        /// * `radix`   - Radix for the number parsing.
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
+        /// * `options` - Options to customize number parsing.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the radix is not in the range `[2, 36]`.
+        #[cfg(feature = "radix")]
+        #[inline]
+        pub fn $radix_name<C: CodeUnit>(radix: u8, bytes: &[C], options: &ParseOptions)
+            -> Result<$t>
+        {
+            assert_radix!(radix);
+            $crate::util::api::try_from_bytes_wrapper::<$t, _, _>(radix, bytes, |r, b| $cb(r, b, options))
+        }
+    )
+}
+
+/// Macro to generate the low-level, safe, partial parse API with options using a slice.
+#[doc(hidden)]
+macro_rules! generate_partial_from_slice_options_api {
+    ($decimal_name:ident, $radix_name:ident, $t:ty, $cb:ident) => (
+        /// Partial parser for a string-to-number conversion using Rust slices and custom options.
+        ///
+        /// Returns a C-compatible result containing the parsed value and
+        /// the number of bytes processed, stopping at the first invalid
+        /// digit, and an error container any errors that occurred during
+        /// parsing. Empty input and input without a leading number are
+        /// errors, while trailing bytes are not.
+        ///
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
+        /// * `options` - Options to customize number parsing.
+        #[inline]
+        pub fn $decimal_name<C: CodeUnit>(bytes: &[C], options: &ParseOptions)
+            -> Result<($t, usize)>
+        {
+            $crate::util::api::partial_from_bytes_wrapper::<$t, _, _>(10, bytes, |r, b| $cb(r, b, options))
+        }
+
+        /// Partial parser for a string-to-number conversion using Rust slices and custom options.
+        ///
+        /// Returns a C-compatible result containing the parsed value and
+        /// the number of bytes processed, stopping at the first invalid
+        /// digit, and an error container any errors that occurred during
+        /// parsing. Empty input and input without a leading number are
+        /// errors, while trailing bytes are not.
+        ///
+        /// * `radix`   - Radix for the number parsing.
+        /// * `bytes`   - Slice of bytes or UTF-16 code units containing a numeric string.
+        /// * `options` - Options to customize number parsing.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the radix is not in the range `[2, 36]`.
+        #[cfg(feature = "radix")]
+        #[inline]
+        pub fn $radix_name<C: CodeUnit>(radix: u8, bytes: &[C], options: &ParseOptions)
+            -> Result<($t, usize)>
+        {
+            assert_radix!(radix);
+            $crate::util::api::partial_from_bytes_wrapper::<$t, _, _>(radix, bytes, |r, b| $cb(r, b, options))
+        }
+    )
+}
+
+/// Macro to generate the low-level, unsafe, to_string API with options using a range.
+#[doc(hidden)]
+macro_rules! generate_to_range_options_api {
+    ($decimal_name:ident, $radix_name:ident, $t:ty, $cb:ident, $size:ident) => (
+        /// Serializer for a number-to-string conversion using pointer ranges and custom options.
+        ///
+        /// Returns a pointer to the 1-past-the-last-byte-written, so that
+        /// the range `[first, last)` contains the written bytes. No
+        /// null-terminator is written.
+        ///
+        /// The data in the range may be uninitialized, these values are
+        /// never read, only written to.
+        ///
+        /// * `value`   - Number to serialize.
+        /// * `first`   - Pointer to the start of the buffer to write to.
+        /// * `last`    - Pointer to the one-past-the-end of the buffer to write to.
+        /// * `options` - Options to customize number serialization.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the buffer is not of sufficient size, The caller
+        /// must provide a range of sufficient size, and neither pointer
+        /// may be null. In order to ensure the function will not panic,
+        /// ensure the buffer has at least `max_*_size_with_options(options)`
+        /// elements, using the proper function for the serialized type
+        /// from the lexical_core crate root.
+        pub unsafe fn $decimal_name(value: $t, first: *mut u8, last: *mut u8, options: &WriteOptions)
+            -> *mut u8
+        {
+            let bytes = $crate::util::api::slice_from_range_mut(first, last);
+
+            assert_buffer!(bytes, $size);
+            let len = $cb(value, 10, bytes, options);
+            bytes.as_mut_ptr().padd(len)
+        }
+
+        /// Serializer for a number-to-string conversion using pointer ranges and custom options.
+        ///
+        /// Returns a pointer to the 1-past-the-last-byte-written, so that
+        /// the range `[first, last)` contains the written bytes. No
+        /// null-terminator is written.
+        ///
+        /// The data in the range may be uninitialized, these values are
+        /// never read, only written to.
+        ///
+        /// * `value`   - Number to serialize.
+        /// * `radix`   - Radix for number encoding.
+        /// * `first`   - Pointer to the start of the buffer to write to.
+        /// * `last`    - Pointer to the one-past-the-end of the buffer to write to.
+        /// * `options` - Options to customize number serialization.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the radix is not in the range `[2, 36]`.
+        ///
+        /// Also panics if the buffer is not of sufficient size, The caller
+        /// must provide a range of sufficient size, and neither pointer
+        /// may be null. In order to ensure the function will not panic,
+        /// ensure the buffer has at least `max_*_size_with_options(options)`
+        /// elements, using the proper function for the serialized type
+        /// from the lexical_core crate root.
+        #[cfg(feature = "radix")]
+        pub unsafe fn $radix_name(value: $t, radix: u8, first: *mut u8, last: *mut u8, options: &WriteOptions)
+            -> *mut u8
+        {
+            assert_radix!(radix);
+            let bytes = $crate::util::api::slice_from_range_mut(first, last);
+
+            assert_buffer!(bytes, $size);
+            let len = $cb(value, radix, bytes, options);
+            bytes.as_mut_ptr().padd(len)
+        }
+    )
+}
+
+/// Macro to generate the low-level, safe, to_string API with options using a slice.
+#[doc(hidden)]
+macro_rules! generate_to_slice_options_api {
+    ($decimal_name:ident, $radix_name:ident, $t:ty, $cb:ident, $size:ident) => (
+        /// Serializer for a number-to-string conversion using Rust slices and custom options.
+        ///
+        /// Returns a subslice of the input buffer containing the written bytes,
+        /// starting from the same address in memory as the input slice.
+        ///
+        /// If the buffer is not of sufficient size (see the functions
+        /// named `max_*_size_with_options` in the lexical_core crate), this function
+        /// will panic (and call abort). You must provide a slice
+        /// of sufficient length. The data in the slice may be
+        /// uninitialized, these values are never read, only written to.
+        ///
+        /// * `value`   - Number to serialize.
+        /// * `bytes`   - Buffer of bytes or UTF-16 code units to write to.
+        /// * `options` - Options to customize number serialization.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the buffer is not of sufficient size, The caller
+        /// must provide a slice of sufficient size. In order to ensure
+        /// the function will not panic, ensure the buffer has at least
+        /// `max_*_size_with_options(options)` elements, using the proper
+        /// function for the serialized type from the lexical_core crate root.
+        #[inline]
+        pub fn $decimal_name<'a, C: CodeUnit>(value: $t, bytes: &'a mut [C], options: &WriteOptions)
+            -> &'a mut [C]
+        {
+            assert_buffer!(bytes, $size);
+            let len = C::write_bytes(bytes, |bytes| $cb(value, 10, bytes, options));
+            &mut index_mut!(bytes[..len])
+        }
+
+        /// Serializer for a number-to-string conversion using Rust slices and custom options.
+        ///
+        /// Returns a subslice of the input buffer containing the written bytes,
+        /// starting from the same address in memory as the input slice.
+        ///
+        /// If the buffer is not of sufficient size (see the functions
+        /// named `max_*_size_with_options` in the lexical_core crate), this function
+        /// will panic (and call abort). You must provide a slice
+        /// of sufficient length. The data in the slice may be
+        /// uninitialized, these values are never read, only written to.
+        ///
+        /// * `value`   - Number to serialize.
+        /// * `radix`   - Radix for number encoding.
+        /// * `bytes`   - Buffer of bytes or UTF-16 code units to write to.
+        /// * `options` - Options to customize number serialization.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the radix is not in the range `[2, 36]`.
+        ///
+        /// Also panics if the buffer is not of sufficient size, The caller
+        /// must provide a slice of sufficient size. In order to ensure
+        /// the function will not panic, ensure the buffer has at least
+        /// `max_*_size_with_options(options)` elements, using the proper
+        /// function for the serialized type from the lexical_core crate root.
+        #[cfg(feature = "radix")]
+        #[inline]
+        pub fn $radix_name<'a, C: CodeUnit>(value: $t, radix: u8, bytes: &'a mut [C], options: &WriteOptions)
+            -> &'a mut [C]
+        {
+            assert_radix!(radix);
+            assert_buffer!(bytes, $size);
+            let len = C::write_bytes(bytes, |bytes| $cb(value, radix, bytes, options));
             &mut index_mut!(bytes[..len])
         }
     )
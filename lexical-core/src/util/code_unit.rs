@@ -0,0 +1,276 @@
+//! Code units for byte (UTF-8 or ASCII) and UTF-16 strings.
+
+use lib::{cmp, fmt};
+use super::pointer_methods::PointerMethods;
+
+// UTF-8
+
+/// Decode the UTF-8 sequence at the start of a non-empty buffer.
+///
+/// Returns the code point and the number of bytes in the sequence, or
+/// the first byte as the code point if the sequence is not valid UTF-8.
+#[inline]
+fn decode_utf8(bytes: &[u8]) -> (u32, usize) {
+    let lead = index!(bytes[0]);
+    let (width, mut code) = if lead < 0x80 {
+        return (lead as u32, 1);
+    } else if lead & 0xE0 == 0xC0 {
+        (2, (lead & 0x1F) as u32)
+    } else if lead & 0xF0 == 0xE0 {
+        (3, (lead & 0x0F) as u32)
+    } else if lead & 0xF8 == 0xF0 {
+        (4, (lead & 0x07) as u32)
+    } else {
+        return (lead as u32, 1);
+    };
+
+    if bytes.len() < width {
+        return (lead as u32, 1);
+    }
+    for &byte in &index!(bytes[1..width]) {
+        if byte & 0xC0 != 0x80 {
+            return (lead as u32, 1);
+        }
+        code = (code << 6) | (byte & 0x3F) as u32;
+    }
+    match code <= 0x10FFFF {
+        true  => (code, width),
+        false => (lead as u32, 1),
+    }
+}
+
+/// Encode a code point as UTF-16, and return the number of code units.
+#[inline]
+fn encode_utf16(code: u32, units: &mut [u16; 2]) -> usize {
+    if code < 0x10000 {
+        units[0] = code as u16;
+        1
+    } else {
+        let code = code - 0x10000;
+        units[0] = 0xD800 | (code >> 10) as u16;
+        units[1] = 0xDC00 | (code & 0x3FF) as u16;
+        2
+    }
+}
+
+// CODE UNIT
+
+/// Code unit of a numeric string.
+///
+/// Numbers are parsed from and written to slices of code units, either
+/// bytes for ASCII or UTF-8 strings, or `u16` for UTF-16 strings.
+/// Numbers only contain ASCII characters, so every code unit outside of
+/// the ASCII range is treated as an invalid digit, and reported at its
+/// index in the slice of code units. Group separators are UTF-8, and
+/// are transcoded to UTF-16 when parsing and writing UTF-16 strings.
+pub trait CodeUnit: Copy + Eq + fmt::Debug {
+    /// Convert the code unit to a byte.
+    ///
+    /// Code units outside of the ASCII range in UTF-16 strings are
+    /// converted to `0xFF`, which is never valid in a number.
+    fn to_byte(self) -> u8;
+
+    /// Get the code units as bytes, if the code units are bytes.
+    #[doc(hidden)]
+    fn as_bytes(units: &[Self]) -> Option<&[u8]>;
+
+    /// Get the number of code units to encode UTF-8 bytes.
+    #[doc(hidden)]
+    fn encoded_len(bytes: &[u8]) -> usize;
+
+    /// Determine if the code units start with the encoded UTF-8 bytes.
+    #[doc(hidden)]
+    fn starts_with_encoded(units: &[Self], bytes: &[u8]) -> bool;
+
+    /// Write UTF-8 bytes to a buffer of code units.
+    ///
+    /// The callback writes bytes to a buffer of the same length as
+    /// `buffer`, and returns the number of bytes written, which are
+    /// then encoded to the start of `buffer`. Returns the number of
+    /// code units written.
+    #[doc(hidden)]
+    fn write_bytes<Cb>(buffer: &mut [Self], cb: Cb) -> usize
+        where Cb: FnOnce(&mut [u8]) -> usize;
+}
+
+impl CodeUnit for u8 {
+    #[inline(always)]
+    fn to_byte(self) -> u8 {
+        self
+    }
+
+    #[inline(always)]
+    fn as_bytes(units: &[u8]) -> Option<&[u8]> {
+        Some(units)
+    }
+
+    #[inline(always)]
+    fn encoded_len(bytes: &[u8]) -> usize {
+        bytes.len()
+    }
+
+    #[inline(always)]
+    fn starts_with_encoded(units: &[u8], bytes: &[u8]) -> bool {
+        units.starts_with(bytes)
+    }
+
+    #[inline(always)]
+    fn write_bytes<Cb>(buffer: &mut [u8], cb: Cb) -> usize
+        where Cb: FnOnce(&mut [u8]) -> usize
+    {
+        cb(buffer)
+    }
+}
+
+impl CodeUnit for u16 {
+    #[inline(always)]
+    fn to_byte(self) -> u8 {
+        match self < 0x80 {
+            true  => self as u8,
+            false => 0xFF,
+        }
+    }
+
+    #[inline(always)]
+    fn as_bytes(_: &[u16]) -> Option<&[u8]> {
+        None
+    }
+
+    #[inline]
+    fn encoded_len(bytes: &[u8]) -> usize {
+        let mut units = [0u16; 2];
+        let mut index = 0;
+        let mut count = 0;
+        while index < bytes.len() {
+            let (code, width) = decode_utf8(&index!(bytes[index..]));
+            index += width;
+            count += encode_utf16(code, &mut units);
+        }
+        count
+    }
+
+    #[inline]
+    fn starts_with_encoded(units: &[u16], bytes: &[u8]) -> bool {
+        let mut encoded = [0u16; 2];
+        let mut index = 0;
+        let mut units = units;
+        while index < bytes.len() {
+            let (code, width) = decode_utf8(&index!(bytes[index..]));
+            index += width;
+            let count = encode_utf16(code, &mut encoded);
+            if !units.starts_with(&encoded[..count]) {
+                return false;
+            }
+            units = &index!(units[count..]);
+        }
+        true
+    }
+
+    #[inline]
+    fn write_bytes<Cb>(buffer: &mut [u16], cb: Cb) -> usize
+        where Cb: FnOnce(&mut [u8]) -> usize
+    {
+        // Write the bytes to the upper half of the buffer, and encode them
+        // in-place from the front. UTF-16 never needs more code units than
+        // UTF-8 needs bytes, so every code unit only overwrites bytes which
+        // were previously encoded: after encoding `read` bytes to `write`
+        // code units, code units occupy bytes `[0, 2*write)`, and the next
+        // byte is at `len + read`, where `2*write <= len + read`.
+        let len = buffer.len();
+        let ptr = buffer.as_mut_ptr();
+        let count = unsafe {
+            let bytes = ::lib::slice::from_raw_parts_mut(ptr as *mut u8, 2 * len);
+            cb(&mut index_mut!(bytes[len..]))
+        };
+        debug_assert!(count <= len);
+
+        let mut sequence = [0u8; 4];
+        let mut units = [0u16; 2];
+        let mut read = 0;
+        let mut write = 0;
+        while read < count {
+            // Copy the sequence before any code units overwrite it.
+            let size = cmp::min(4, count - read);
+            for (i, c) in sequence[..size].iter_mut().enumerate() {
+                *c = unsafe { *(ptr as *const u8).padd(len + read + i) };
+            }
+            let (code, width) = decode_utf8(&sequence[..size]);
+            read += width;
+            let encoded = encode_utf16(code, &mut units);
+            for &unit in &units[..encoded] {
+                unsafe { *ptr.padd(write) = unit };
+                write += 1;
+            }
+        }
+
+        write
+    }
+}
+
+/// Get the code unit at the index as a byte.
+#[inline(always)]
+pub(crate) fn byte_at<C: CodeUnit>(bytes: &[C], index: usize) -> Option<u8> {
+    bytes.get(index).map(|&c| c.to_byte())
+}
+
+// TEST
+// ----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_byte_test() {
+        assert_eq!(b'1'.to_byte(), b'1');
+        assert_eq!(0xC2u8.to_byte(), 0xC2);
+        assert_eq!(0x31u16.to_byte(), b'1');
+        assert_eq!(0x7Fu16.to_byte(), 0x7F);
+        assert_eq!(0x80u16.to_byte(), 0xFF);
+        assert_eq!(0x0661u16.to_byte(), 0xFF);
+        assert_eq!(0xFF11u16.to_byte(), 0xFF);
+    }
+
+    #[test]
+    fn encoded_test() {
+        let units: Vec<u16> = "\u{a0}1\u{202f}2\u{1f600}".encode_utf16().collect();
+        assert_eq!(u8::encoded_len("\u{202f}".as_bytes()), 3);
+        assert_eq!(u16::encoded_len("\u{202f}".as_bytes()), 1);
+        assert_eq!(u16::encoded_len("\u{1f600}".as_bytes()), 2);
+        assert_eq!(u16::encoded_len(b"\xFF,"), 2);
+        assert!(u16::starts_with_encoded(&units, "\u{a0}".as_bytes()));
+        assert!(u16::starts_with_encoded(&units[2..], "\u{202f}".as_bytes()));
+        assert!(u16::starts_with_encoded(&units[4..], "\u{1f600}".as_bytes()));
+        assert!(!u16::starts_with_encoded(&units, b"\xC2"));
+        assert!(!u16::starts_with_encoded(&units[..1], "\u{a0}1".as_bytes()));
+    }
+
+    #[test]
+    fn write_bytes_test() {
+        let mut buffer = [0u16; 8];
+        let count = u16::write_bytes(&mut buffer, |bytes| {
+            assert_eq!(bytes.len(), 8);
+            bytes.copy_from_slice(b"12345678");
+            8
+        });
+        assert_eq!(count, 8);
+        assert_eq!(buffer, [0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38]);
+
+        let mut buffer = [0u16; 8];
+        let count = u16::write_bytes(&mut buffer, |bytes| {
+            bytes[..3].copy_from_slice(b"-1.");
+            3
+        });
+        assert_eq!(count, 3);
+        assert_eq!(&buffer[..3], &[0x2D, 0x31, 0x2E]);
+
+        let expected: Vec<u16> = "1\u{a0}2\u{202f}3\u{1f600}".encode_utf16().collect();
+        let mut buffer = [0u16; 12];
+        let count = u16::write_bytes(&mut buffer, |bytes| {
+            let utf8 = "1\u{a0}2\u{202f}3\u{1f600}".as_bytes();
+            bytes[..utf8.len()].copy_from_slice(utf8);
+            utf8.len()
+        });
+        assert_eq!(&buffer[..count], &expected[..]);
+    }
+}
@@ -89,6 +89,7 @@ if #[cfg(feature = "radix")] {
 /// # Safety
 ///
 /// Do not modify this value in threaded-code, as it is not thread-safe.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[no_mangle]
 pub static mut EXPONENT_DEFAULT_CHAR: u8 = b'e';
 
@@ -103,6 +104,7 @@ pub static mut EXPONENT_DEFAULT_CHAR: u8 = b'e';
 /// # Safety
 ///
 /// Do not modify this value in threaded-code, as it is not thread-safe.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[cfg(feature ="radix")]
 #[no_mangle]
 pub static mut EXPONENT_BACKUP_CHAR: u8 = b'^';
@@ -141,6 +143,7 @@ unsafe extern fn set_string_ffi(ptr: *const u8, size: usize, string: &'static mu
 }
 
 /// Get string representation of Not a Number as a byte slice.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[inline]
 pub fn get_nan_string() -> &'static [u8]
 {
@@ -162,6 +165,7 @@ pub fn get_nan_string() -> &'static [u8]
 /// Only use this in C-FFI code, otherwise, you use [`get_nan_string`].
 ///
 /// [`get_nan_string`]: fn.get_nan_string.html
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[no_mangle]
 pub unsafe extern fn get_nan_string_ffi(ptr: *mut *const u8, size: *mut usize)
     -> i32
@@ -180,6 +184,7 @@ pub unsafe extern fn get_nan_string_ffi(ptr: *mut *const u8, size: *mut usize)
 /// # Panics
 ///
 /// Panics if `bytes.len() >= MAX_F32_SIZE`.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[inline]
 pub unsafe fn set_nan_string(bytes: &[u8])
 {
@@ -203,6 +208,7 @@ pub unsafe fn set_nan_string(bytes: &[u8])
 /// # Panics
 ///
 /// Panics if `bytes.len() >= MAX_F32_SIZE`.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[no_mangle]
 pub unsafe extern fn set_nan_string_ffi(ptr: *const u8, size: usize)
     -> i32
@@ -211,6 +217,7 @@ pub unsafe extern fn set_nan_string_ffi(ptr: *const u8, size: usize)
 }
 
 /// Get the short representation of an Infinity literal as a byte slice.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[inline]
 pub fn get_inf_string() -> &'static [u8]
 {
@@ -232,6 +239,7 @@ pub fn get_inf_string() -> &'static [u8]
 /// Only use this in C-FFI code, otherwise, you use [`get_inf_string`].
 ///
 /// [`get_inf_string`]: fn.get_inf_string.html
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[no_mangle]
 pub unsafe extern fn get_inf_string_ffi(ptr: *mut *const u8, size: *mut usize)
     -> i32
@@ -250,6 +258,7 @@ pub unsafe extern fn get_inf_string_ffi(ptr: *mut *const u8, size: *mut usize)
 /// # Panics
 ///
 /// Panics if `bytes.len() >= MAX_F32_SIZE`.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[inline]
 pub unsafe fn set_inf_string(bytes: &[u8])
 {
@@ -273,6 +282,7 @@ pub unsafe fn set_inf_string(bytes: &[u8])
 /// # Panics
 ///
 /// Panics if `bytes.len() >= MAX_F32_SIZE`.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[no_mangle]
 pub unsafe extern fn set_inf_string_ffi(ptr: *const u8, size: usize)
     -> i32
@@ -281,6 +291,7 @@ pub unsafe extern fn set_inf_string_ffi(ptr: *const u8, size: usize)
 }
 
 /// Get the long representation of an Infinity literal as a byte slice.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[inline]
 pub fn get_infinity_string() -> &'static [u8]
 {
@@ -302,6 +313,7 @@ pub fn get_infinity_string() -> &'static [u8]
 /// Only use this in C-FFI code, otherwise, you use [`get_infinity_string`].
 ///
 /// [`get_infinity_string`]: fn.get_infinity_string.html
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[no_mangle]
 pub unsafe extern fn get_infinity_string_ffi(ptr: *mut *const u8, size: *mut usize)
     -> i32
@@ -320,6 +332,7 @@ pub unsafe extern fn get_infinity_string_ffi(ptr: *mut *const u8, size: *mut usi
 /// # Panics
 ///
 /// Panics if `bytes.len() >= MAX_F32_SIZE`.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[inline]
 pub unsafe fn set_infinity_string(bytes: &[u8])
 {
@@ -343,6 +356,7 @@ pub unsafe fn set_infinity_string(bytes: &[u8])
 /// # Panics
 ///
 /// Panics if `bytes.len() >= MAX_F32_SIZE`.
+#[deprecated(since = "0.5.0", note = "use `ParseOptions` or `WriteOptions` instead")]
 #[no_mangle]
 pub unsafe extern fn set_infinity_string_ffi(ptr: *const u8, size: usize)
     -> i32
@@ -360,100 +374,118 @@ if #[cfg(feature = "radix")] {
     // Use 256, actually, since we seem to have memory issues with f64.
     // Clearly not sufficient memory allocated for non-base10 values.
 
-    /// The minimum buffer size required to serialize any `i8` value.
+    /// The minimum buffer size required to serialize any `i8` value with the default options.
     pub const MAX_I8_SIZE: usize = 16;
 
-    /// The minimum buffer size required to serialize any `i16` value.
+    /// The minimum buffer size required to serialize any `i16` value with the default options.
     pub const MAX_I16_SIZE: usize = 32;
 
-    /// The minimum buffer size required to serialize any `i32` value.
+    /// The minimum buffer size required to serialize any `i32` value with the default options.
     pub const MAX_I32_SIZE: usize = 64;
 
-    /// The minimum buffer size required to serialize any `i64` value.
+    /// The minimum buffer size required to serialize any `i64` value with the default options.
     pub const MAX_I64_SIZE: usize = 128;
 
-    /// The minimum buffer size required to serialize any `i128` value.
+    /// The minimum buffer size required to serialize any `i128` value with the default options.
     pub const MAX_I128_SIZE: usize = 256;
 
-    /// The minimum buffer size required to serialize any `u8` value.
+    /// The minimum buffer size required to serialize any `u8` value with the default options.
     pub const MAX_U8_SIZE: usize = 16;
 
-    /// The minimum buffer size required to serialize any `u16` value.
+    /// The minimum buffer size required to serialize any `u16` value with the default options.
     pub const MAX_U16_SIZE: usize = 32;
 
-    /// The minimum buffer size required to serialize any `u32` value.
+    /// The minimum buffer size required to serialize any `u32` value with the default options.
     pub const MAX_U32_SIZE: usize = 64;
 
-    /// The minimum buffer size required to serialize any `u64` value.
+    /// The minimum buffer size required to serialize any `u64` value with the default options.
     pub const MAX_U64_SIZE: usize = 128;
 
-    /// The minimum buffer size required to serialize any `u128` value.
+    /// The minimum buffer size required to serialize any `u128` value with the default options.
     pub const MAX_U128_SIZE: usize = 256;
 
-    /// The minimum buffer size required to serialize any `f32` value.
+    /// The minimum buffer size required to serialize any `f32` value with the default options.
     pub const MAX_F32_SIZE: usize = 256;
 
-    /// The minimum buffer size required to serialize any `f64` value.
+    /// The minimum buffer size required to serialize any `f64` value with the default options.
     pub const MAX_F64_SIZE: usize = 256;
+
+    /// The minimum buffer size required to serialize any `f16` value with the default options.
+    pub const MAX_F16_SIZE: usize = 256;
+
+    /// The minimum buffer size required to serialize any `bf16` value with the default options.
+    pub const MAX_BF16_SIZE: usize = 256;
+
+    /// The minimum buffer size required to serialize any `f128` value with the default options.
+    pub const MAX_F128_SIZE: usize = 256;
 } else {
     // The f64 buffer is actually a size of 60, but use 64 since it's a
     // power of 2.
 
-    /// The minimum buffer size required to serialize any `i8` value.
+    /// The minimum buffer size required to serialize any `i8` value with the default options.
     pub const MAX_I8_SIZE: usize = 4;
 
-    /// The minimum buffer size required to serialize any `i16` value.
+    /// The minimum buffer size required to serialize any `i16` value with the default options.
     pub const MAX_I16_SIZE: usize = 6;
 
-    /// The minimum buffer size required to serialize any `i32` value.
+    /// The minimum buffer size required to serialize any `i32` value with the default options.
     pub const MAX_I32_SIZE: usize = 11;
 
-    /// The minimum buffer size required to serialize any `i64` value.
+    /// The minimum buffer size required to serialize any `i64` value with the default options.
     pub const MAX_I64_SIZE: usize = 20;
 
-    /// The minimum buffer size required to serialize any `i128` value.
+    /// The minimum buffer size required to serialize any `i128` value with the default options.
     pub const MAX_I128_SIZE: usize = 40;
 
-    /// The minimum buffer size required to serialize any `u8` value.
+    /// The minimum buffer size required to serialize any `u8` value with the default options.
     pub const MAX_U8_SIZE: usize = 3;
 
-    /// The minimum buffer size required to serialize any `u16` value.
+    /// The minimum buffer size required to serialize any `u16` value with the default options.
     pub const MAX_U16_SIZE: usize = 5;
 
-    /// The minimum buffer size required to serialize any `u32` value.
+    /// The minimum buffer size required to serialize any `u32` value with the default options.
     pub const MAX_U32_SIZE: usize = 10;
 
-    /// The minimum buffer size required to serialize any `u64` value.
+    /// The minimum buffer size required to serialize any `u64` value with the default options.
     pub const MAX_U64_SIZE: usize = 20;
 
-    /// The minimum buffer size required to serialize any `u128` value.
+    /// The minimum buffer size required to serialize any `u128` value with the default options.
     pub const MAX_U128_SIZE: usize = 39;
 
-    /// The minimum buffer size required to serialize any `f32` value.
+    /// The minimum buffer size required to serialize any `f32` value with the default options.
     pub const MAX_F32_SIZE: usize = 64;
 
-    /// The minimum buffer size required to serialize any `f64` value.
+    /// The minimum buffer size required to serialize any `f64` value with the default options.
     pub const MAX_F64_SIZE: usize = 64;
+
+    /// The minimum buffer size required to serialize any `f16` value with the default options.
+    pub const MAX_F16_SIZE: usize = 64;
+
+    /// The minimum buffer size required to serialize any `bf16` value with the default options.
+    pub const MAX_BF16_SIZE: usize = 64;
+
+    /// The minimum buffer size required to serialize any `f128` value with the default options.
+    pub const MAX_F128_SIZE: usize = 64;
 }} // cfg_if
 
 cfg_if! {
 if #[cfg(target_pointer_width = "16")] {
-    /// The minimum buffer size required to serialize any `isize` value.
+    /// The minimum buffer size required to serialize any `isize` value with the default options.
     pub const MAX_ISIZE_SIZE: usize = MAX_I16_SIZE;
 
-    /// The minimum buffer size required to serialize any `usize` value.
+    /// The minimum buffer size required to serialize any `usize` value with the default options.
     pub const MAX_USIZE_SIZE: usize = MAX_U16_SIZE;
 } else if #[cfg(target_pointer_width = "32")] {
-    /// The minimum buffer size required to serialize any `isize` value.
+    /// The minimum buffer size required to serialize any `isize` value with the default options.
     pub const MAX_ISIZE_SIZE: usize = MAX_I32_SIZE;
 
-    /// The minimum buffer size required to serialize any `usize` value.
+    /// The minimum buffer size required to serialize any `usize` value with the default options.
     pub const MAX_USIZE_SIZE: usize = MAX_U32_SIZE;
 } else if #[cfg(target_pointer_width = "64")] {
-    /// The minimum buffer size required to serialize any `isize` value.
+    /// The minimum buffer size required to serialize any `isize` value with the default options.
     pub const MAX_ISIZE_SIZE: usize = MAX_I64_SIZE;
 
-    /// The minimum buffer size required to serialize any `usize` value.
+    /// The minimum buffer size required to serialize any `usize` value with the default options.
     pub const MAX_USIZE_SIZE: usize = MAX_U64_SIZE;
 }}  // cfg_if
 
@@ -534,31 +566,16 @@ pub static BUFFER_SIZE_FFI: usize = BUFFER_SIZE;
 /// values of `FLOAT_ROUNDING` in FFI-code.
 ///
 /// [`RoundingKind`]: enum.RoundingKind.html
+#[deprecated(since = "0.5.0", note = "use `ParseOptions::set_rounding` instead")]
 #[no_mangle]
 #[cfg(feature = "rounding")]
 pub static mut FLOAT_ROUNDING: RoundingKind = RoundingKind::NearestTieEven;
 
-// FUNCTIONS
-
-/// Get the exponent notation character.
-#[inline]
-#[allow(unused_variables)]
-pub(crate) fn exponent_notation_char(radix: u32) -> u8 {
-    unsafe {
-        #[cfg(not(feature ="radix"))] {
-            EXPONENT_DEFAULT_CHAR
-        }
-
-        #[cfg(feature ="radix")] {
-            if radix >= 15 { EXPONENT_BACKUP_CHAR } else { EXPONENT_DEFAULT_CHAR }
-        }
-    }
-}
-
 // TEST
 // ----
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use atof::*;
     use ftoa::*;
@@ -566,19 +583,6 @@ mod tests {
     use util::test::*;
     use super::*;
 
-    #[cfg(feature ="radix")]
-    #[test]
-    fn exponent_notation_char_test() {
-        unsafe {
-            assert_eq!(exponent_notation_char(2), EXPONENT_DEFAULT_CHAR);
-            assert_eq!(exponent_notation_char(8), EXPONENT_DEFAULT_CHAR);
-            assert_eq!(exponent_notation_char(10), EXPONENT_DEFAULT_CHAR);
-            assert_eq!(exponent_notation_char(15), EXPONENT_BACKUP_CHAR);
-            assert_eq!(exponent_notation_char(16), EXPONENT_BACKUP_CHAR);
-            assert_eq!(exponent_notation_char(32), EXPONENT_BACKUP_CHAR);
-        }
-    }
-
     // Only enable when no other threads touch NAN_STRING or INFINITY_STRING.
     #[test]
     #[ignore]
@@ -14,11 +14,19 @@
 /// const int32_t OVERFLOW = -1;
 /// const int32_t INVALID_DIGIT = -2;
 /// const int32_t EMPTY = -3;
+/// const int32_t EMPTY_MANTISSA = -5;
+/// const int32_t EMPTY_INTEGER = -6;
+/// const int32_t EMPTY_FRACTION = -7;
+/// const int32_t EMPTY_EXPONENT = -8;
+/// const int32_t INVALID_POSITIVE_MANTISSA_SIGN = -9;
+/// const int32_t INVALID_POSITIVE_EXPONENT_SIGN = -10;
+/// const int32_t MISSING_EXPONENT_SIGN = -11;
+/// const int32_t INVALID_LEADING_ZEROS = -12;
 /// ```
 ///
 /// # Safety
 ///
-/// Assigning any value outside the range `[-3, 0]` to value of type
+/// Assigning any value outside the range `[-12, 0]` to value of type
 /// ErrorCode may invoke undefined-behavior.
 #[repr(i32)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
@@ -34,6 +42,24 @@ pub enum ErrorCode {
     InvalidDigit = -2,
     /// Empty byte array found.
     Empty = -3,
+    /// Float has no integer or fraction digits, like `.` or `.e5`.
+    EmptyMantissa = -5,
+    /// Float has no integer digits, and the grammar requires them.
+    EmptyInteger = -6,
+    /// Float has a decimal point but no fraction digits, and the grammar
+    /// requires them.
+    EmptyFraction = -7,
+    /// Float has an exponent but no exponent digits, and the grammar
+    /// requires them.
+    EmptyExponent = -8,
+    /// Number has a leading `+` sign, and the grammar forbids it.
+    InvalidPositiveMantissaSign = -9,
+    /// Exponent has a `+` sign, and the grammar forbids it.
+    InvalidPositiveExponentSign = -10,
+    /// Exponent has no sign, and the grammar requires one.
+    MissingExponentSign = -11,
+    /// Number has leading zeros, and the grammar forbids them.
+    InvalidLeadingZeros = -12,
 
     // We may add additional variants later, so ensure that client matching
     // does not depend on exhaustive matching.
@@ -75,6 +101,54 @@ pub extern fn is_empty(error: Error) -> bool {
     error.code == ErrorCode::Empty
 }
 
+/// Check if the error code designates a float without integer or fraction digits.
+#[no_mangle]
+pub extern fn is_empty_mantissa(error: Error) -> bool {
+    error.code == ErrorCode::EmptyMantissa
+}
+
+/// Check if the error code designates a float without required integer digits.
+#[no_mangle]
+pub extern fn is_empty_integer(error: Error) -> bool {
+    error.code == ErrorCode::EmptyInteger
+}
+
+/// Check if the error code designates a float without required fraction digits.
+#[no_mangle]
+pub extern fn is_empty_fraction(error: Error) -> bool {
+    error.code == ErrorCode::EmptyFraction
+}
+
+/// Check if the error code designates a float without required exponent digits.
+#[no_mangle]
+pub extern fn is_empty_exponent(error: Error) -> bool {
+    error.code == ErrorCode::EmptyExponent
+}
+
+/// Check if the error code designates an invalid leading `+` sign.
+#[no_mangle]
+pub extern fn is_invalid_positive_mantissa_sign(error: Error) -> bool {
+    error.code == ErrorCode::InvalidPositiveMantissaSign
+}
+
+/// Check if the error code designates an invalid exponent `+` sign.
+#[no_mangle]
+pub extern fn is_invalid_positive_exponent_sign(error: Error) -> bool {
+    error.code == ErrorCode::InvalidPositiveExponentSign
+}
+
+/// Check if the error code designates a missing exponent sign.
+#[no_mangle]
+pub extern fn is_missing_exponent_sign(error: Error) -> bool {
+    error.code == ErrorCode::MissingExponentSign
+}
+
+/// Check if the error code designates invalid leading zeros.
+#[no_mangle]
+pub extern fn is_invalid_leading_zeros(error: Error) -> bool {
+    error.code == ErrorCode::InvalidLeadingZeros
+}
+
 /// Helper function to create a success message.
 #[inline]
 pub(crate) fn success() -> Error {
@@ -90,7 +164,7 @@ pub(crate) fn overflow_error() -> Error {
 /// Helper function to create an invalid digit error.
 #[inline]
 pub(crate) fn invalid_digit_error(index: usize) -> Error {
-    Error { code: ErrorCode::InvalidDigit, index: index }
+    Error { code: ErrorCode::InvalidDigit, index }
 }
 
 /// Helper function to create an empty error.
@@ -98,3 +172,9 @@ pub(crate) fn invalid_digit_error(index: usize) -> Error {
 pub(crate) fn empty_error() -> Error {
     Error { code: ErrorCode::Empty, index: 0 }
 }
+
+/// Helper function to create an error from a code and index.
+#[inline]
+pub(crate) fn new_error(code: ErrorCode, index: usize) -> Error {
+    Error { code, index }
+}
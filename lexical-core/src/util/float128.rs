@@ -0,0 +1,849 @@
+//! Quadruple-precision floating-point type.
+//!
+//! Rust does not have a native 128-bit float, so this type stores the
+//! raw bits, and implements the arithmetic in software. The operations
+//! are done on an extended-precision float with a 128-bit mantissa,
+//! where any bits lost to the mantissa are collapsed into the lowest
+//! (sticky) bit, which is always below the rounding bits, and the result
+//! is correctly rounded to the nearest float, with ties to even. The
+//! transcendental functions are only approximated with an `f64`.
+
+use lib::{cmp, fmt, iter, ops};
+use float::*;
+use ftoa::f128toa_slice;
+use super::cast::*;
+use super::config::MAX_F128_SIZE;
+use super::num::*;
+use super::primitive::*;
+use super::rounding::RoundingKind;
+
+// HELPERS
+
+/// Number of bits to shift the 113-bit mantissas left for addition,
+/// so the sum of two mantissas fits in 128 bits.
+const ADD_SHIFT: i32 = 14;
+
+/// Round an extended-precision float to the nearest float, with ties to even.
+///
+/// Any bits lost from the mantissa must be collapsed into the lowest
+/// bit, which requires at least 115 significant bits, so the lowest
+/// bit is below the rounding bits.
+#[inline]
+fn round_extended(mant: u128, exp: i32, is_negative: bool)
+    -> f128
+{
+    let fp = ExtendedFloat { mant, exp };
+    let float: f128 = fp.into_rounded_float_impl(RoundingKind::NearestTieEven);
+    match is_negative {
+        true  => -float,
+        false => float,
+    }
+}
+
+/// Shift right, collapsing any non-zero bits shifted out into the lowest bit.
+#[inline]
+fn shr_sticky(x: u128, shift: i32)
+    -> u128
+{
+    if shift == 0 {
+        x
+    } else if shift >= 128 {
+        (x != 0) as u128
+    } else {
+        let mask = (1u128 << shift) - 1;
+        (x >> shift) | ((x & mask != 0) as u128)
+    }
+}
+
+/// Multiply two 128-bit integers, and return the high and low 128 bits.
+#[inline]
+fn mul_wide(x: u128, y: u128)
+    -> (u128, u128)
+{
+    let mask = u64::max_value() as u128;
+    let (x1, x0) = (x >> 64, x & mask);
+    let (y1, y0) = (y >> 64, y & mask);
+    let w0 = x0 * y0;
+    let t = x1 * y0 + (w0 >> 64);
+    let w1 = (t & mask) + x0 * y1;
+    let hi = x1 * y1 + (t >> 64) + (w1 >> 64);
+    let lo = (w1 << 64) | (w0 & mask);
+    (hi, lo)
+}
+
+/// Get the magnitude of the float as bits, which orders like the float.
+#[inline]
+fn magnitude(x: f128)
+    -> u128
+{
+    x.bits & !f128::SIGN_MASK
+}
+
+/// Get the normalized mantissa and exponent, with the leading bit at bit 126.
+#[inline]
+fn normalized(x: f128)
+    -> (u128, i32)
+{
+    let mant = x.mantissa();
+    let shift = mant.leading_zeros().as_i32() - 1;
+    (mant << shift, x.exponent() - shift)
+}
+
+/// Get the integral magnitude of a finite float, saturated at `max`.
+#[inline]
+fn to_integer(x: f128, max: u128)
+    -> u128
+{
+    if x.is_nan() {
+        return 0;
+    }
+
+    let mant = x.mantissa();
+    let exp = x.exponent();
+    let integer = if x.is_inf() {
+        max
+    } else if exp >= 0 {
+        match mant.leading_zeros().as_i32() > exp {
+            true  => mant << exp,
+            false => max,
+        }
+    } else if exp > -128 {
+        mant >> exp.wrapping_neg()
+    } else {
+        0
+    };
+    integer.min(max)
+}
+
+// ARITHMETIC
+
+/// Add two floats, or subtract if the signs differ.
+fn add(x: f128, y: f128)
+    -> f128
+{
+    if x.is_nan() || y.is_nan() {
+        return f128::NAN;
+    } else if x.is_inf() && y.is_inf() {
+        return match x.is_sign_negative() == y.is_sign_negative() {
+            true  => x,
+            false => f128::NAN,
+        };
+    } else if x.is_inf() || y.is_zero() {
+        // `-0.0 + -0.0` is `-0.0`, and `+0.0` otherwise.
+        return match x.is_zero() && x.is_sign_negative() != y.is_sign_negative() {
+            true  => f128::ZERO,
+            false => x,
+        };
+    } else if y.is_inf() || x.is_zero() {
+        return y;
+    }
+
+    // Order by magnitude, so the larger value has the larger exponent.
+    let (x, y) = match magnitude(x) >= magnitude(y) {
+        true  => (x, y),
+        false => (y, x),
+    };
+    let is_negative = x.is_sign_negative();
+    let xm = x.mantissa() << ADD_SHIFT;
+    let ym = y.mantissa() << ADD_SHIFT;
+    let exp = x.exponent() - ADD_SHIFT;
+    let ym = shr_sticky(ym, x.exponent() - y.exponent());
+
+    if x.is_sign_negative() == y.is_sign_negative() {
+        round_extended(xm + ym, exp, is_negative)
+    } else if magnitude(x) == magnitude(y) {
+        f128::ZERO
+    } else {
+        round_extended(xm - ym, exp, is_negative)
+    }
+}
+
+/// Multiply two floats.
+fn mul(x: f128, y: f128)
+    -> f128
+{
+    let is_negative = x.is_sign_negative() != y.is_sign_negative();
+    let zero = match is_negative {
+        true  => -f128::ZERO,
+        false => f128::ZERO,
+    };
+    let inf = match is_negative {
+        true  => f128::NEG_INFINITY,
+        false => f128::INFINITY,
+    };
+
+    if x.is_nan() || y.is_nan() {
+        return f128::NAN;
+    } else if x.is_inf() || y.is_inf() {
+        return match x.is_zero() || y.is_zero() {
+            true  => f128::NAN,
+            false => inf,
+        };
+    } else if x.is_zero() || y.is_zero() {
+        return zero;
+    }
+
+    // Get the full 226-bit product, and keep the upper 128 bits.
+    let (hi, lo) = mul_wide(x.mantissa(), y.mantissa());
+    let exp = x.exponent() + y.exponent();
+    if hi == 0 {
+        round_extended(lo, exp, is_negative)
+    } else {
+        let shift = hi.leading_zeros();
+        let mant = match shift {
+            0 => hi,
+            _ => (hi << shift) | (lo >> (128 - shift)),
+        };
+        let sticky = (lo << shift) != 0;
+        let exp = exp + 128 - shift.as_i32();
+        round_extended(mant | sticky as u128, exp, is_negative)
+    }
+}
+
+/// Divide two floats.
+fn div(x: f128, y: f128)
+    -> f128
+{
+    let is_negative = x.is_sign_negative() != y.is_sign_negative();
+    let zero = match is_negative {
+        true  => -f128::ZERO,
+        false => f128::ZERO,
+    };
+    let inf = match is_negative {
+        true  => f128::NEG_INFINITY,
+        false => f128::INFINITY,
+    };
+
+    if x.is_nan() || y.is_nan() || (x.is_inf() && y.is_inf()) || (x.is_zero() && y.is_zero()) {
+        return f128::NAN;
+    } else if x.is_inf() || y.is_zero() {
+        return inf;
+    } else if y.is_inf() || x.is_zero() {
+        return zero;
+    }
+
+    // Long division, one bit at a time. Both mantissas have the leading
+    // bit at bit 126, so the quotient has 126 or 127 bits, and the
+    // remainder never overflows when shifted.
+    let (xm, xe) = normalized(x);
+    let (ym, ye) = normalized(y);
+    let mut quotient: u128 = 0;
+    let mut remainder = xm;
+    for _ in 0..127 {
+        quotient <<= 1;
+        if remainder >= ym {
+            remainder -= ym;
+            quotient |= 1;
+        }
+        remainder <<= 1;
+    }
+    let sticky = remainder != 0;
+    round_extended(quotient | sticky as u128, xe - ye - 126, is_negative)
+}
+
+/// Calculate the remainder of truncated division, which is always exact.
+fn rem(x: f128, y: f128)
+    -> f128
+{
+    if x.is_nan() || y.is_nan() || x.is_inf() || y.is_zero() {
+        return f128::NAN;
+    } else if y.is_inf() || x.is_zero() || magnitude(x) < magnitude(y) {
+        return x;
+    }
+
+    // Calculate `xm * 2^(xe - ye) % ym`, shifting at most 14 bits at a
+    // time, so the shifted remainder fits in 128 bits.
+    let is_negative = x.is_sign_negative();
+    let ym = y.mantissa();
+    let mut remainder = x.mantissa() % ym;
+    let mut diff = x.exponent() - y.exponent();
+    while diff > 0 {
+        let shift = diff.min(ADD_SHIFT);
+        remainder = (remainder << shift) % ym;
+        diff -= shift;
+    }
+
+    match remainder {
+        0 if is_negative => -f128::ZERO,
+        0                => f128::ZERO,
+        _                => round_extended(remainder, y.exponent(), is_negative),
+    }
+}
+
+// QUAD FLOAT
+
+/// IEEE754 quadruple-precision (binary128) float.
+///
+/// Has 1 sign bit, 15 exponent bits, and 112 mantissa bits.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct f128 {
+    /// Raw bits of the float.
+    bits: u128,
+}
+
+impl f128 {
+    /// Create float from its raw bits.
+    #[inline]
+    pub fn from_bits(bits: u128) -> f128 {
+        f128 { bits }
+    }
+
+    /// Get the raw bits of the float.
+    #[inline]
+    pub fn to_bits(self) -> u128 {
+        self.bits
+    }
+
+    /// Convert a 32-bit float to the float, which is always exact.
+    #[inline]
+    pub fn from_f32(value: f32) -> f128 {
+        f128::from_f64(value as f64)
+    }
+
+    /// Convert a 64-bit float to the float, which is always exact.
+    #[inline]
+    pub fn from_f64(value: f64) -> f128 {
+        let float = if value.is_nan() {
+            f128::NAN
+        } else if value.is_inf() {
+            f128::INFINITY
+        } else if value.is_zero() {
+            f128::ZERO
+        } else {
+            let fp = ExtendedFloat { mant: value.mantissa() as u128, exp: value.exponent() };
+            fp.into_rounded_float_impl::<f128>(RoundingKind::NearestTieEven)
+        };
+
+        match value.is_sign_negative() {
+            true  => -float,
+            false => float,
+        }
+    }
+
+    /// Convert the float to the nearest 32-bit float, with ties to even.
+    #[inline]
+    pub fn to_f32(self) -> f32 {
+        self.narrow()
+    }
+
+    /// Convert the float to the nearest 64-bit float, with ties to even.
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.narrow()
+    }
+
+    /// Round the float to a narrower float, with ties to even.
+    #[inline]
+    fn narrow<F: FloatRounding<u128>>(self) -> F {
+        let float = if self.is_nan() {
+            F::NAN
+        } else if self.is_inf() {
+            F::INFINITY
+        } else if self.is_zero() {
+            F::ZERO
+        } else {
+            let fp = ExtendedFloat { mant: self.mantissa(), exp: self.exponent() };
+            fp.into_rounded_float_impl::<F>(RoundingKind::NearestTieEven)
+        };
+
+        match self.is_sign_negative() {
+            true  => -float,
+            false => float,
+        }
+    }
+
+    /// Round toward zero to an integral value.
+    #[inline]
+    fn trunc(self) -> f128 {
+        let exp = self.exponent();
+        if self.is_special() || self.is_zero() || exp >= 0 {
+            self
+        } else if exp < -f128::MANTISSA_SIZE {
+            // Less than 1, only keep the sign.
+            f128 { bits: self.bits & f128::SIGN_MASK }
+        } else {
+            // Clear the fraction bits of the mantissa.
+            let mask = (1u128 << exp.wrapping_neg()) - 1;
+            f128 { bits: self.bits & !mask }
+        }
+    }
+}
+
+impl From<f32> for f128 {
+    #[inline]
+    fn from(value: f32) -> f128 {
+        f128::from_f32(value)
+    }
+}
+
+impl From<f64> for f128 {
+    #[inline]
+    fn from(value: f64) -> f128 {
+        f128::from_f64(value)
+    }
+}
+
+impl PartialEq for f128 {
+    #[inline]
+    fn eq(&self, other: &f128) -> bool {
+        if self.is_nan() || other.is_nan() {
+            false
+        } else {
+            self.bits == other.bits || (self.is_zero() && other.is_zero())
+        }
+    }
+}
+
+impl PartialOrd for f128 {
+    #[inline]
+    fn partial_cmp(&self, other: &f128) -> Option<cmp::Ordering> {
+        // Order as signed integers of the magnitude, where `-0.0 == +0.0`.
+        let key = |x: &f128| -> i128 {
+            match x.is_sign_negative() {
+                true  => (magnitude(*x) as i128).wrapping_neg(),
+                false => magnitude(*x) as i128,
+            }
+        };
+        if self.is_nan() || other.is_nan() {
+            None
+        } else {
+            Some(key(self).cmp(&key(other)))
+        }
+    }
+}
+
+impl fmt::Display for f128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buffer = [b'0'; MAX_F128_SIZE];
+        let bytes = f128toa_slice(*self, &mut buffer);
+        // The serializer only writes ASCII characters.
+        f.pad(unsafe { ::lib::str::from_utf8_unchecked(bytes) })
+    }
+}
+
+impl fmt::Debug for f128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// IMPL AS PRIMITIVE
+
+/// Convert to an unsigned integer, saturating at the bounds.
+macro_rules! as_unsigned {
+    ($($t:ident $meth:ident ;)*) => ($(
+        #[inline]
+        fn $meth(self) -> $t {
+            self.as_u128().min($t::max_value() as u128) as $t
+        }
+    )*);
+}
+
+/// Convert to a signed integer, saturating at the bounds.
+macro_rules! as_signed {
+    ($($t:ident $meth:ident ;)*) => ($(
+        #[inline]
+        fn $meth(self) -> $t {
+            self.as_i128().max($t::min_value() as i128).min($t::max_value() as i128) as $t
+        }
+    )*);
+}
+
+impl AsPrimitive for f128 {
+    as_unsigned! {
+        u8 as_u8 ;
+        u16 as_u16 ;
+        u32 as_u32 ;
+        u64 as_u64 ;
+        usize as_usize ;
+    }
+
+    as_signed! {
+        i8 as_i8 ;
+        i16 as_i16 ;
+        i32 as_i32 ;
+        i64 as_i64 ;
+        isize as_isize ;
+    }
+
+    #[inline]
+    fn as_u128(self) -> u128 {
+        match self.is_sign_negative() {
+            true  => 0,
+            false => to_integer(self, u128::max_value()),
+        }
+    }
+
+    #[inline]
+    fn as_i128(self) -> i128 {
+        match self.is_sign_negative() {
+            true  => (to_integer(self, 1 << 127) as i128).wrapping_neg(),
+            false => to_integer(self, i128::max_value() as u128) as i128,
+        }
+    }
+
+    #[inline]
+    fn as_f32(self) -> f32 {
+        self.to_f32()
+    }
+
+    #[inline]
+    fn as_f64(self) -> f64 {
+        self.to_f64()
+    }
+}
+
+// IMPL AS CAST
+
+impl AsCast for f128 {
+    #[inline]
+    fn as_cast<N: AsPrimitive>(n: N) -> f128 {
+        f128::from_f64(n.as_f64())
+    }
+}
+
+// IMPL TRY CAST
+
+impl<N: Primitive> TryCast<N> for f128 where f64: TryCast<N> {
+    #[inline]
+    fn try_cast(self) -> Option<N> {
+        try_cast(self.to_f64())
+    }
+}
+
+impl TryPrimitive for f128 {
+}
+
+impl Primitive for f128 {
+}
+
+// IMPL NUMBER
+
+impl iter::Product for f128 {
+    #[inline]
+    fn product<Iter: Iterator<Item=f128>>(iter: Iter) -> f128 {
+        iter.fold(f128::ONE, ops::Mul::mul)
+    }
+}
+
+impl iter::Sum for f128 {
+    #[inline]
+    fn sum<Iter: Iterator<Item=f128>>(iter: Iter) -> f128 {
+        iter.fold(f128::ZERO, ops::Add::add)
+    }
+}
+
+/// Implement arithmetic and arithmetic assignment operations.
+macro_rules! quad_float_ops_impl {
+    ($($op:ident, $meth:ident, $op_assign:ident, $meth_assign:ident, $impl:ident ;)*) => ($(
+        impl ops::$op for f128 {
+            type Output = f128;
+
+            #[inline]
+            fn $meth(self, other: f128) -> f128 {
+                $impl(self, other)
+            }
+        }
+
+        impl ops::$op_assign for f128 {
+            #[inline]
+            fn $meth_assign(&mut self, other: f128) {
+                *self = $impl(*self, other);
+            }
+        }
+    )*);
+}
+
+quad_float_ops_impl! {
+    Add, add, AddAssign, add_assign, add ;
+    Div, div, DivAssign, div_assign, div ;
+    Mul, mul, MulAssign, mul_assign, mul ;
+    Rem, rem, RemAssign, rem_assign, rem ;
+}
+
+impl ops::Sub for f128 {
+    type Output = f128;
+
+    #[inline]
+    fn sub(self, other: f128) -> f128 {
+        add(self, -other)
+    }
+}
+
+impl ops::SubAssign for f128 {
+    #[inline]
+    fn sub_assign(&mut self, other: f128) {
+        *self = add(*self, -other);
+    }
+}
+
+impl ops::Neg for f128 {
+    type Output = f128;
+
+    #[inline]
+    fn neg(self) -> f128 {
+        f128 { bits: self.bits ^ f128::SIGN_MASK }
+    }
+}
+
+impl Number for f128 {
+}
+
+// IMPL FLOAT
+
+impl Float for f128 {
+    type Unsigned = u128;
+    const ZERO: f128 = f128 { bits: 0 };
+    const ONE: f128 = f128 { bits: 0x3FFF << 112 };
+    const TWO: f128 = f128 { bits: 0x4000 << 112 };
+    const MAX: f128 = f128 { bits: (0x7FFE << 112) | Self::MANTISSA_MASK };
+    const MIN: f128 = f128 { bits: (0x7FFE << 112) | Self::MANTISSA_MASK | Self::SIGN_MASK };
+    const INFINITY: f128 = f128 { bits: Self::INFINITY_BITS };
+    const NEG_INFINITY: f128 = f128 { bits: Self::NEGATIVE_INFINITY_BITS };
+    const NAN: f128 = f128 { bits: 0xFFFF << 111 };
+    const BITS: usize = 128;
+    const SIGN_MASK: u128           = 1 << 127;
+    const EXPONENT_MASK: u128       = 0x7FFF << 112;
+    const HIDDEN_BIT_MASK: u128     = 1 << 112;
+    const MANTISSA_MASK: u128       = (1 << 112) - 1;
+    const INFINITY_BITS: u128       = 0x7FFF << 112;
+    const NEGATIVE_INFINITY_BITS: u128 = Self::INFINITY_BITS | Self::SIGN_MASK;
+    const MANTISSA_SIZE: i32        = 112;
+    const EXPONENT_BIAS: i32        = 16383 + Self::MANTISSA_SIZE;
+    const DENORMAL_EXPONENT: i32    = 1 - Self::EXPONENT_BIAS;
+    const MAX_EXPONENT: i32         = 0x7FFF - Self::EXPONENT_BIAS;
+
+    #[inline]
+    fn abs(self) -> f128 {
+        f128 { bits: self.bits & !Self::SIGN_MASK }
+    }
+
+    #[inline]
+    fn ceil(self) -> f128 {
+        let trunc = self.trunc();
+        match trunc == self || self.is_sign_negative() {
+            true  => trunc,
+            false => trunc + Self::ONE,
+        }
+    }
+
+    #[inline]
+    fn exp(self) -> f128 {
+        f128::from_f64(self.to_f64().exp())
+    }
+
+    #[inline]
+    fn floor(self) -> f128 {
+        let trunc = self.trunc();
+        match trunc == self || self.is_sign_positive() {
+            true  => trunc,
+            false => trunc - Self::ONE,
+        }
+    }
+
+    #[inline]
+    fn ln(self) -> f128 {
+        f128::from_f64(self.to_f64().ln())
+    }
+
+    #[inline]
+    fn powi(self, n: i32) -> f128 {
+        // Exponentiation by squaring.
+        let mut result = Self::ONE;
+        let mut base = self;
+        let mut exp = n.wrapping_abs().as_u32();
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        match n < 0 {
+            true  => Self::ONE / result,
+            false => result,
+        }
+    }
+
+    #[inline]
+    fn powf(self, n: f128) -> f128 {
+        f128::from_f64(self.to_f64().powf(n.to_f64()))
+    }
+
+    #[inline]
+    fn round(self) -> f128 {
+        // Round halfway cases away from zero.
+        let trunc = self.trunc();
+        let half = f128 { bits: 0x3FFE << 112 };
+        match (self - trunc).abs() >= half {
+            true if self.is_sign_negative() => trunc - Self::ONE,
+            true                            => trunc + Self::ONE,
+            false                           => trunc,
+        }
+    }
+
+    #[inline]
+    fn to_bits(self) -> u128 {
+        self.bits
+    }
+
+    #[inline]
+    fn from_bits(u: u128) -> f128 {
+        f128 { bits: u }
+    }
+
+    #[inline]
+    fn is_sign_positive(self) -> bool {
+        !self.is_sign_negative()
+    }
+
+    #[inline]
+    fn is_sign_negative(self) -> bool {
+        self.bits & Self::SIGN_MASK != 0
+    }
+
+    // Override, since equality checks for zero.
+    #[inline]
+    fn is_zero(self) -> bool {
+        self.bits & !Self::SIGN_MASK == 0
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_f64_test() {
+        assert_eq!(f128::from_f64(1.0).to_bits(), 0x3FFF << 112);
+        assert_eq!(f128::from_f64(-2.0).to_bits(), 0xC000 << 112);
+        assert_eq!(f128::from_f64(0.1).to_bits(), 0x3FFB999999999999A000000000000000);
+        assert_eq!(f128::from_f64(5e-324).to_bits(), 0x3BCD0000000000000000000000000000);
+        assert_eq!(f128::from_f64(f64::MAX).to_bits(), 0x43FEFFFFFFFFFFFFF000000000000000);
+        assert_eq!(f128::from_f64(-0.0).to_bits(), 1 << 127);
+        assert_eq!(f128::from_f64(f64::INFINITY), f128::INFINITY);
+        assert!(f128::from_f64(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn to_f64_test() {
+        assert_eq!(f128::ONE.to_f64(), 1.0);
+        assert_eq!(f128::MAX.to_f64(), f64::INFINITY);
+        assert_eq!(f128::from_bits(1).to_f64(), 0.0);
+        assert_eq!(f128::NEG_INFINITY.to_f64(), f64::NEG_INFINITY);
+        assert_eq!(f128::from_bits(0x3FFB999999999999A000000000000000).to_f64(), 0.1);
+        assert_eq!(f128::from_bits(0x3FFB999999999999999999999999999A).to_f64(), 0.1);
+        assert_eq!(f128::from_bits(0x3FFB999999999999999999999999999A).to_f32(), 0.1);
+
+        // Halfway cases, which round to even.
+        assert_eq!(f128::from_bits(0x3FFF0000000000000800000000000000).to_f64(), 1.0);
+        assert_eq!(f128::from_bits(0x3FFF0000000000001800000000000000).to_f64(), 1.0000000000000004);
+        assert_eq!(f128::from_bits(0x3FFF0000000000000800000000000001).to_f64(), 1.0000000000000002);
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let one = f128::ONE;
+        let two = f128::TWO;
+        let three = f128::from_f64(3.0);
+        let tenth = f128::from_bits(0x3FFB999999999999999999999999999A);
+
+        assert_eq!(one + two, three);
+        assert_eq!(one - two, -one);
+        assert_eq!(two * three, f128::from_f64(6.0));
+        assert_eq!(three / two, f128::from_f64(1.5));
+        assert_eq!(f128::from_f64(7.0) % three, one);
+        assert_eq!(f128::from_f64(-7.0) % three, -one);
+        assert_eq!(one / f128::from_f64(10.0), tenth);
+        assert_eq!((one / three).to_bits(), 0x3FFD5555555555555555555555555555);
+        assert_eq!((two / three).to_bits(), 0x3FFE5555555555555555555555555555);
+        assert_eq!(tenth * f128::from_f64(10.0), one);
+        assert_eq!((tenth + tenth + tenth).to_bits(), 0x3FFD3333333333333333333333333334);
+
+        // The smallest increment is only visible with enough precision.
+        let epsilon = f128::from_bits(0x3F8F << 112);
+        assert_eq!((one + epsilon).to_bits(), (0x3FFF << 112) + 1);
+        assert_eq!(one + epsilon / two, one);
+        assert_eq!(one + epsilon / two + epsilon / two, one);
+        assert_eq!((one - epsilon / two).to_bits(), (0x3FFF << 112) - 1);
+
+        // Denormals and overflow.
+        let denormal = f128::from_bits(1);
+        assert_eq!(denormal + denormal, f128::from_bits(2));
+        assert_eq!(denormal / two, f128::ZERO);
+        assert_eq!(f128::from_bits(3) / two, f128::from_bits(2));
+        assert_eq!(f128::MAX * two, f128::INFINITY);
+        assert_eq!(f128::MAX + f128::MAX, f128::INFINITY);
+        assert_eq!(f128::MAX - f128::MAX, f128::ZERO);
+
+        // Special values.
+        assert!((f128::INFINITY - f128::INFINITY).is_nan());
+        assert!((f128::ZERO * f128::INFINITY).is_nan());
+        assert!((f128::ZERO / f128::ZERO).is_nan());
+        assert!((one % f128::ZERO).is_nan());
+        assert_eq!(one / f128::ZERO, f128::INFINITY);
+        assert_eq!(-one / f128::ZERO, f128::NEG_INFINITY);
+        assert!((-f128::ZERO + -f128::ZERO).is_sign_negative());
+        assert!((f128::ZERO + -f128::ZERO).is_sign_positive());
+        assert!((-one * f128::ZERO).is_sign_negative());
+    }
+
+    #[test]
+    fn float_test() {
+        let x = f128::from_f64(1.5);
+        assert_eq!(-x, f128::from_f64(-1.5));
+        assert_eq!((-x).abs(), x);
+        assert!(f128::ONE < x);
+        assert!(-x < -f128::ONE);
+        assert!(f128::NAN != f128::NAN);
+        assert!(f128::NAN.partial_cmp(&f128::ONE).is_none());
+        assert_eq!(f128::ZERO, -f128::ZERO);
+        assert_eq!(f128::ONE.exponent(), -112);
+        assert_eq!(f128::from_bits(1).exponent(), -16494);
+        assert_eq!(f128::MAX.next(), f128::INFINITY);
+        assert_eq!(f128::TWO.powi(10), f128::from_f64(1024.0));
+        assert_eq!(f128::TWO.powi(-2), f128::from_f64(0.25));
+        assert_eq!(vec![x, x, x].into_iter().sum::<f128>(), f128::from_f64(4.5));
+    }
+
+    #[test]
+    fn round_test() {
+        let values = [0.0, 0.4, 0.5, 1.5, 2.5, 3.7, 1e20, 1e-20];
+        for &value in values.iter() {
+            for &value in [value, -value].iter() {
+                let x = f128::from_f64(value);
+                assert_eq!(x.floor().to_f64(), value.floor());
+                assert_eq!(x.ceil().to_f64(), value.ceil());
+                assert_eq!(x.round().to_f64(), value.round());
+            }
+        }
+    }
+
+    #[test]
+    fn as_primitive_test() {
+        assert_eq!(f128::from_f64(1.5).as_u8(), 1);
+        assert_eq!(f128::from_f64(-1.5).as_i32(), -1);
+        assert_eq!(f128::from_f64(-1.5).as_u32(), 0);
+        assert_eq!(f128::from_f64(300.0).as_u8(), 255);
+        assert_eq!(f128::from_f64(-300.0).as_i8(), -128);
+        assert_eq!(f128::from_f64(1e40).as_u128(), u128::max_value());
+        assert_eq!(f128::from_f64(-1e40).as_i128(), i128::min_value());
+        assert_eq!(f128::from_f64(2f64.powi(100)).as_u128(), 1 << 100);
+        assert_eq!(f128::from_bits((0x3FFF + 112) << 112 | 1).as_u128(), (1 << 112) | 1);
+        assert_eq!(f128::NAN.as_u64(), 0);
+        assert_eq!(f128::INFINITY.as_i64(), i64::max_value());
+        assert_eq!(f128::from_f64(1.5).as_f64(), 1.5);
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(format!("{}", f128::from_f64(1.5)), "1.5");
+        assert_eq!(format!("{:?}", f128::from_f64(-2.5)), "-2.5");
+        assert_eq!(format!("{}", f128::ONE / f128::from_f64(3.0)), "0.3333333333333333333333333333333333");
+    }
+}
@@ -0,0 +1,605 @@
+//! Half-precision and brain floating-point types.
+//!
+//! Rust does not have native 16-bit floats, so these types store the
+//! raw bits, and implement the numeric traits by converting to and
+//! from wider floats. Arithmetic is done in `f32` and rounded back,
+//! which is correctly rounded since `f32` has more than twice the
+//! precision (plus 2 bits) of either type. Conversions from wider
+//! floats use the extended-float rounding, so they are correctly
+//! rounded, including for denormals.
+
+use lib::{cmp, fmt, iter, ops};
+use float::*;
+use ftoa::{bf16toa_slice, f16toa_slice};
+use super::cast::*;
+use super::config::{MAX_BF16_SIZE, MAX_F16_SIZE};
+use super::num::*;
+use super::primitive::*;
+use super::rounding::RoundingKind;
+
+// CONVERSIONS
+
+/// Round a 64-bit float to a narrower float, using the rounding kind.
+///
+/// The rounding kind must already be converted for the sign of the
+/// value, so it is one of the nearest, upward or downward kinds.
+#[inline]
+pub(crate) fn narrow_f64<F>(value: f64, kind: RoundingKind)
+    -> F
+    where F: FloatRounding<u64>
+{
+    let float = if value.is_nan() {
+        F::NAN
+    } else if value.is_inf() {
+        F::INFINITY
+    } else if value.is_zero() {
+        F::ZERO
+    } else {
+        let fp: ExtendedFloat<u64> = value.abs().into();
+        fp.into_rounded_float_impl::<F>(kind)
+    };
+
+    match value.is_sign_negative() {
+        true  => -float,
+        false => float,
+    }
+}
+
+/// Convert a narrower float to a 32-bit float, which is always exact.
+#[inline]
+fn widen_f32<F: Float>(value: F)
+    -> f32
+{
+    let float = if value.is_nan() {
+        f32::NAN
+    } else if value.is_inf() {
+        f32::INFINITY
+    } else if value.is_zero() {
+        0.0
+    } else {
+        let fp = ExtendedFloat { mant: value.mantissa().as_u64(), exp: value.exponent() };
+        fp.into_rounded_float_impl::<f32>(RoundingKind::NearestTieEven)
+    };
+
+    match value.is_sign_negative() {
+        true  => -float,
+        false => float,
+    }
+}
+
+// HALF FLOAT
+
+/// Implement the numeric traits for a 16-bit float type.
+macro_rules! half_float_impl {
+    (
+        $t:ident,
+        $size:ident,
+        $toa:ident,
+        exponent_mask: $exponent_mask:expr,
+        hidden_bit_mask: $hidden_bit_mask:expr,
+        mantissa_mask: $mantissa_mask:expr,
+        mantissa_size: $mantissa_size:expr,
+        exponent_bias: $exponent_bias:expr,
+        max_exponent: $max_exponent:expr,
+        one: $one:expr,
+        two: $two:expr,
+        max: $max:expr,
+        nan: $nan:expr
+    ) => (
+        impl $t {
+            /// Create float from its raw bits.
+            #[inline]
+            pub fn from_bits(bits: u16) -> $t {
+                $t { bits }
+            }
+
+            /// Get the raw bits of the float.
+            #[inline]
+            pub fn to_bits(self) -> u16 {
+                self.bits
+            }
+
+            /// Convert a 32-bit float to the nearest float, with ties to even.
+            #[inline]
+            pub fn from_f32(value: f32) -> $t {
+                $t::from_f64(value as f64)
+            }
+
+            /// Convert a 64-bit float to the nearest float, with ties to even.
+            #[inline]
+            pub fn from_f64(value: f64) -> $t {
+                narrow_f64(value, RoundingKind::NearestTieEven)
+            }
+
+            /// Convert the float to a 32-bit float, which is always exact.
+            #[inline]
+            pub fn to_f32(self) -> f32 {
+                widen_f32(self)
+            }
+
+            /// Convert the float to a 64-bit float, which is always exact.
+            #[inline]
+            pub fn to_f64(self) -> f64 {
+                self.to_f32() as f64
+            }
+        }
+
+        impl From<$t> for f32 {
+            #[inline]
+            fn from(value: $t) -> f32 {
+                value.to_f32()
+            }
+        }
+
+        impl From<$t> for f64 {
+            #[inline]
+            fn from(value: $t) -> f64 {
+                value.to_f64()
+            }
+        }
+
+        impl PartialEq for $t {
+            #[inline]
+            fn eq(&self, other: &$t) -> bool {
+                self.to_f32() == other.to_f32()
+            }
+        }
+
+        impl PartialOrd for $t {
+            #[inline]
+            fn partial_cmp(&self, other: &$t) -> Option<cmp::Ordering> {
+                self.to_f32().partial_cmp(&other.to_f32())
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let mut buffer = [b'0'; $size];
+                let bytes = $toa(*self, &mut buffer);
+                // The serializer only writes ASCII characters.
+                f.pad(unsafe { ::lib::str::from_utf8_unchecked(bytes) })
+            }
+        }
+
+        impl fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+
+        // IMPL AS PRIMITIVE
+
+        impl AsPrimitive for $t {
+            #[inline]
+            fn as_u8(self) -> u8 {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_u16(self) -> u16 {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_u32(self) -> u32 {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_u64(self) -> u64 {
+                as_cast(self.to_f32())
+            }
+
+            #[cfg(has_i128)]
+            #[inline]
+            fn as_u128(self) -> u128 {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_usize(self) -> usize {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_i8(self) -> i8 {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_i16(self) -> i16 {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_i32(self) -> i32 {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_i64(self) -> i64 {
+                as_cast(self.to_f32())
+            }
+
+            #[cfg(has_i128)]
+            #[inline]
+            fn as_i128(self) -> i128 {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_isize(self) -> isize {
+                as_cast(self.to_f32())
+            }
+
+            #[inline]
+            fn as_f32(self) -> f32 {
+                self.to_f32()
+            }
+
+            #[inline]
+            fn as_f64(self) -> f64 {
+                self.to_f64()
+            }
+        }
+
+        // IMPL AS CAST
+
+        impl AsCast for $t {
+            #[inline]
+            fn as_cast<N: AsPrimitive>(n: N) -> $t {
+                $t::from_f64(n.as_f64())
+            }
+        }
+
+        // IMPL TRY CAST
+
+        impl<N: Primitive> TryCast<N> for $t where f32: TryCast<N> {
+            #[inline]
+            fn try_cast(self) -> Option<N> {
+                try_cast(self.to_f32())
+            }
+        }
+
+        impl TryPrimitive for $t {
+        }
+
+        impl Primitive for $t {
+        }
+
+        // IMPL NUMBER
+
+        impl iter::Product for $t {
+            #[inline]
+            fn product<Iter: Iterator<Item=$t>>(iter: Iter) -> $t {
+                iter.fold(<$t as Float>::ONE, ops::Mul::mul)
+            }
+        }
+
+        impl iter::Sum for $t {
+            #[inline]
+            fn sum<Iter: Iterator<Item=$t>>(iter: Iter) -> $t {
+                iter.fold(<$t as Float>::ZERO, ops::Add::add)
+            }
+        }
+
+        half_float_ops_impl! {
+            $t ;
+            Add, add, AddAssign, add_assign ;
+            Div, div, DivAssign, div_assign ;
+            Mul, mul, MulAssign, mul_assign ;
+            Rem, rem, RemAssign, rem_assign ;
+            Sub, sub, SubAssign, sub_assign ;
+        }
+
+        impl ops::Neg for $t {
+            type Output = $t;
+
+            #[inline]
+            fn neg(self) -> $t {
+                $t { bits: self.bits ^ <$t as Float>::SIGN_MASK }
+            }
+        }
+
+        impl Number for $t {
+        }
+
+        // IMPL FLOAT
+
+        impl Float for $t {
+            type Unsigned = u16;
+            const ZERO: $t = $t { bits: 0 };
+            const ONE: $t = $t { bits: $one };
+            const TWO: $t = $t { bits: $two };
+            const MAX: $t = $t { bits: $max };
+            const MIN: $t = $t { bits: $max | Self::SIGN_MASK };
+            const INFINITY: $t = $t { bits: Self::INFINITY_BITS };
+            const NEG_INFINITY: $t = $t { bits: Self::NEGATIVE_INFINITY_BITS };
+            const NAN: $t = $t { bits: $nan };
+            const BITS: usize = 16;
+            const SIGN_MASK: u16            = 0x8000;
+            const EXPONENT_MASK: u16        = $exponent_mask;
+            const HIDDEN_BIT_MASK: u16      = $hidden_bit_mask;
+            const MANTISSA_MASK: u16        = $mantissa_mask;
+            const INFINITY_BITS: u16        = $exponent_mask;
+            const NEGATIVE_INFINITY_BITS: u16 = Self::INFINITY_BITS | Self::SIGN_MASK;
+            const MANTISSA_SIZE: i32        = $mantissa_size;
+            const EXPONENT_BIAS: i32        = $exponent_bias + Self::MANTISSA_SIZE;
+            const DENORMAL_EXPONENT: i32    = 1 - Self::EXPONENT_BIAS;
+            const MAX_EXPONENT: i32         = $max_exponent - Self::EXPONENT_BIAS;
+
+            #[inline]
+            fn abs(self) -> $t {
+                $t { bits: self.bits & !Self::SIGN_MASK }
+            }
+
+            #[inline]
+            fn ceil(self) -> $t {
+                $t::from_f32(self.to_f32().ceil())
+            }
+
+            #[inline]
+            fn exp(self) -> $t {
+                $t::from_f32(self.to_f32().exp())
+            }
+
+            #[inline]
+            fn floor(self) -> $t {
+                $t::from_f32(self.to_f32().floor())
+            }
+
+            #[inline]
+            fn ln(self) -> $t {
+                $t::from_f32(self.to_f32().ln())
+            }
+
+            #[inline]
+            fn powi(self, n: i32) -> $t {
+                $t::from_f32(self.to_f32().powi(n))
+            }
+
+            #[inline]
+            fn powf(self, n: $t) -> $t {
+                $t::from_f32(self.to_f32().powf(n.to_f32()))
+            }
+
+            #[inline]
+            fn round(self) -> $t {
+                $t::from_f32(self.to_f32().round())
+            }
+
+            #[inline]
+            fn to_bits(self) -> u16 {
+                self.bits
+            }
+
+            #[inline]
+            fn from_bits(u: u16) -> $t {
+                $t { bits: u }
+            }
+
+            #[inline]
+            fn is_sign_positive(self) -> bool {
+                !self.is_sign_negative()
+            }
+
+            #[inline]
+            fn is_sign_negative(self) -> bool {
+                self.bits & Self::SIGN_MASK != 0
+            }
+
+            // Override, since equality widens to a 32-bit float, which
+            // checks for zero.
+            #[inline]
+            fn is_zero(self) -> bool {
+                self.bits & !Self::SIGN_MASK == 0
+            }
+        }
+    );
+}
+
+/// Implement arithmetic and arithmetic assignment operations.
+///
+/// The operations are done in `f32`, and rounded back to the type.
+macro_rules! half_float_ops_impl {
+    ($t:ident ; $($op:ident, $meth:ident, $op_assign:ident, $meth_assign:ident ;)*) => ($(
+        impl ops::$op for $t {
+            type Output = $t;
+
+            #[inline]
+            fn $meth(self, other: $t) -> $t {
+                $t::from_f32(ops::$op::$meth(self.to_f32(), other.to_f32()))
+            }
+        }
+
+        impl ops::$op_assign for $t {
+            #[inline]
+            fn $meth_assign(&mut self, other: $t) {
+                *self = ops::$op::$meth(*self, other);
+            }
+        }
+    )*);
+}
+
+/// IEEE754 half-precision (binary16) float.
+///
+/// Has 1 sign bit, 5 exponent bits, and 10 mantissa bits.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct f16 {
+    /// Raw bits of the float.
+    bits: u16,
+}
+
+half_float_impl!(
+    f16,
+    MAX_F16_SIZE,
+    f16toa_slice,
+    exponent_mask: 0x7C00,
+    hidden_bit_mask: 0x0400,
+    mantissa_mask: 0x03FF,
+    mantissa_size: 10,
+    exponent_bias: 15,
+    max_exponent: 0x1F,
+    one: 0x3C00,
+    two: 0x4000,
+    max: 0x7BFF,
+    nan: 0x7E00
+);
+
+/// Brain floating-point (bfloat16) float.
+///
+/// Has 1 sign bit, 8 exponent bits, and 7 mantissa bits, and is
+/// the upper 16 bits of an `f32`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct bf16 {
+    /// Raw bits of the float.
+    bits: u16,
+}
+
+half_float_impl!(
+    bf16,
+    MAX_BF16_SIZE,
+    bf16toa_slice,
+    exponent_mask: 0x7F80,
+    hidden_bit_mask: 0x0080,
+    mantissa_mask: 0x007F,
+    mantissa_size: 7,
+    exponent_bias: 127,
+    max_exponent: 0xFF,
+    one: 0x3F80,
+    two: 0x4000,
+    max: 0x7F7F,
+    nan: 0x7FC0
+);
+
+// HALF INTEROP
+
+#[cfg(feature = "half")]
+impl From<::half::f16> for f16 {
+    #[inline]
+    fn from(value: ::half::f16) -> f16 {
+        f16::from_bits(value.to_bits())
+    }
+}
+
+#[cfg(feature = "half")]
+impl From<f16> for ::half::f16 {
+    #[inline]
+    fn from(value: f16) -> ::half::f16 {
+        ::half::f16::from_bits(value.to_bits())
+    }
+}
+
+#[cfg(feature = "half")]
+impl From<::half::bf16> for bf16 {
+    #[inline]
+    fn from(value: ::half::bf16) -> bf16 {
+        bf16::from_bits(value.to_bits())
+    }
+}
+
+#[cfg(feature = "half")]
+impl From<bf16> for ::half::bf16 {
+    #[inline]
+    fn from(value: bf16) -> ::half::bf16 {
+        ::half::bf16::from_bits(value.to_bits())
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_f64_test() {
+        // Normal and exact values.
+        assert_eq!(f16::from_f64(1.0).to_bits(), 0x3C00);
+        assert_eq!(f16::from_f64(-2.0).to_bits(), 0xC000);
+        assert_eq!(f16::from_f64(65504.0).to_bits(), 0x7BFF);
+        assert_eq!(bf16::from_f64(1.0).to_bits(), 0x3F80);
+
+        // Halfway cases, which round to even.
+        assert_eq!(f16::from_f64(2049.0).to_bits(), f16::from_f64(2048.0).to_bits());
+        assert_eq!(f16::from_f64(2051.0).to_bits(), f16::from_f64(2052.0).to_bits());
+        assert_eq!(bf16::from_f64(257.0).to_bits(), bf16::from_f64(256.0).to_bits());
+
+        // Overflow, including halfway to the next power of two.
+        assert_eq!(f16::from_f64(65519.0).to_bits(), 0x7BFF);
+        assert_eq!(f16::from_f64(65520.0).to_bits(), 0x7C00);
+        assert_eq!(f16::from_f64(1e10).to_bits(), 0x7C00);
+        assert_eq!(bf16::from_f64(1e39).to_bits(), 0x7F80);
+
+        // Denormals and underflow.
+        assert_eq!(f16::from_f64(5.960464477539063e-08).to_bits(), 0x0001);
+        assert_eq!(f16::from_f64(6.097555160522461e-05).to_bits(), 0x03FF);
+        assert_eq!(f16::from_f64(2.9802322387695312e-08).to_bits(), 0x0000);
+        assert_eq!(f16::from_f64(2.980232238769532e-08).to_bits(), 0x0001);
+        assert_eq!(bf16::from_f64(9.183549615799121e-41).to_bits(), 0x0001);
+
+        // Special values.
+        assert!(f16::from_f64(f64::NAN).is_nan());
+        assert_eq!(f16::from_f64(f64::NEG_INFINITY).to_bits(), 0xFC00);
+        assert_eq!(f16::from_f64(-0.0).to_bits(), 0x8000);
+    }
+
+    #[test]
+    fn to_f32_test() {
+        assert_eq!(f16::from_bits(0x3C00).to_f32(), 1.0);
+        assert_eq!(f16::from_bits(0x0001).to_f32(), 5.9604645e-08);
+        assert_eq!(f16::from_bits(0x7BFF).to_f32(), 65504.0);
+        assert_eq!(f16::from_bits(0xFC00).to_f32(), f32::NEG_INFINITY);
+        assert!(f16::from_bits(0x8000).to_f32().is_sign_negative());
+        assert_eq!(bf16::from_bits(0x3F80).to_f32(), 1.0);
+        assert_eq!(bf16::from_bits(0x0001).to_f32(), f32::from_bits(0x00010000));
+
+        // Every finite value round-trips.
+        for bits in 0..0x7C00u16 {
+            assert_eq!(f16::from_f32(f16::from_bits(bits).to_f32()).to_bits(), bits);
+        }
+        for bits in 0..0x7F80u16 {
+            assert_eq!(bf16::from_f32(bf16::from_bits(bits).to_f32()).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn float_test() {
+        let x = f16::from_f32(1.5);
+        assert_eq!(x + f16::ONE, f16::from_f32(2.5));
+        assert_eq!(-x, f16::from_f32(-1.5));
+        assert_eq!((-x).abs(), x);
+        assert!(f16::ONE < x);
+        assert!(f16::NAN != f16::NAN);
+        assert_eq!(f16::ZERO, -f16::ZERO);
+        assert_eq!(f16::MAX.to_f32(), 65504.0);
+        assert_eq!(f16::MIN.to_f32(), -65504.0);
+        assert_eq!(bf16::MAX.to_f32(), 3.3895314e38);
+        assert_eq!(f16::ONE.exponent(), -10);
+        assert_eq!(f16::from_bits(1).exponent(), -24);
+        assert_eq!(bf16::from_bits(1).exponent(), -133);
+        assert_eq!(f16::from_bits(0x7BFF).next(), f16::INFINITY);
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(format!("{}", f16::from_f32(1.5)), "1.5");
+        assert_eq!(format!("{:?}", bf16::from_f32(-2.5)), "-2.5");
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn half_test() {
+        let x: ::half::f16 = f16::from_f32(1.5).into();
+        assert_eq!(x.to_bits(), 0x3E00);
+        assert_eq!(f16::from(x).to_bits(), 0x3E00);
+        let y: ::half::bf16 = bf16::from_f32(1.5).into();
+        assert_eq!(y.to_bits(), 0x3FC0);
+        assert_eq!(bf16::from(y).to_bits(), 0x3FC0);
+    }
+}
@@ -0,0 +1,494 @@
+//! Number format and grammar flags for parsing.
+
+use lib::ops;
+
+// FLAGS
+
+/// Implement the shared bitflag methods and operators for a flags type.
+macro_rules! flags_impl {
+    ($t:ident) => (
+        impl $t {
+            /// Create flags from raw bits, if all bits are valid flags.
+            #[inline]
+            pub fn from_bits(bits: u32) -> Option<$t> {
+                match bits & !Self::ALL.bits {
+                    0 => Some($t { bits }),
+                    _ => None,
+                }
+            }
+
+            /// Create flags from raw bits, ignoring any invalid bits.
+            #[inline]
+            pub fn from_bits_truncate(bits: u32) -> $t {
+                $t { bits: bits & Self::ALL.bits }
+            }
+
+            /// Get the raw bits for the flags.
+            #[inline]
+            pub fn bits(&self) -> u32 {
+                self.bits
+            }
+
+            /// Determine if no flags are set.
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.bits == 0
+            }
+
+            /// Determine if all the flags in `other` are set.
+            #[inline]
+            pub fn contains(&self, other: $t) -> bool {
+                self.bits & other.bits == other.bits
+            }
+        }
+
+        impl ops::BitOr for $t {
+            type Output = $t;
+
+            #[inline]
+            fn bitor(self, rhs: $t) -> $t {
+                $t { bits: self.bits | rhs.bits }
+            }
+        }
+
+        impl ops::BitOrAssign for $t {
+            #[inline]
+            fn bitor_assign(&mut self, rhs: $t) {
+                self.bits |= rhs.bits;
+            }
+        }
+
+        impl ops::BitAnd for $t {
+            type Output = $t;
+
+            #[inline]
+            fn bitand(self, rhs: $t) -> $t {
+                $t { bits: self.bits & rhs.bits }
+            }
+        }
+
+        impl ops::BitAndAssign for $t {
+            #[inline]
+            fn bitand_assign(&mut self, rhs: $t) {
+                self.bits &= rhs.bits;
+            }
+        }
+
+        impl ops::Not for $t {
+            type Output = $t;
+
+            #[inline]
+            fn not(self) -> $t {
+                $t { bits: !self.bits & Self::ALL.bits }
+            }
+        }
+    );
+}
+
+// DIGIT SEPARATOR
+
+/// Bitflags for where a digit separator may appear in a number.
+///
+/// Digit separators are only valid between digits by default, however,
+/// they may also be placed before the first digit (leading), after the
+/// last digit (trailing), or repeated (consecutive), separately for the
+/// integer, fraction, and exponent components of a number.
+///
+/// Internal, leading, and trailing describe where a separator may
+/// appear, while consecutive allows any valid separator to be repeated.
+/// An empty set of flags disables digit separators.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let flags = DigitSeparator::INTERNAL | DigitSeparator::FRACTION_TRAILING;
+/// assert!(flags.contains(DigitSeparator::INTEGER_INTERNAL));
+/// assert!(!flags.contains(DigitSeparator::INTEGER_TRAILING));
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DigitSeparator {
+    bits: u32,
+}
+
+impl DigitSeparator {
+    /// Digit separators are not allowed.
+    pub const NONE: DigitSeparator = DigitSeparator { bits: 0 };
+
+    /// Digit separators may appear between integer digits.
+    pub const INTEGER_INTERNAL: DigitSeparator = DigitSeparator { bits: 0x1 };
+    /// Digit separators may appear before the first integer digit.
+    pub const INTEGER_LEADING: DigitSeparator = DigitSeparator { bits: 0x2 };
+    /// Digit separators may appear after the last integer digit.
+    pub const INTEGER_TRAILING: DigitSeparator = DigitSeparator { bits: 0x4 };
+    /// Multiple consecutive digit separators may appear in the integer.
+    pub const INTEGER_CONSECUTIVE: DigitSeparator = DigitSeparator { bits: 0x8 };
+
+    /// Digit separators may appear between fraction digits.
+    pub const FRACTION_INTERNAL: DigitSeparator = DigitSeparator { bits: 0x10 };
+    /// Digit separators may appear before the first fraction digit.
+    pub const FRACTION_LEADING: DigitSeparator = DigitSeparator { bits: 0x20 };
+    /// Digit separators may appear after the last fraction digit.
+    pub const FRACTION_TRAILING: DigitSeparator = DigitSeparator { bits: 0x40 };
+    /// Multiple consecutive digit separators may appear in the fraction.
+    pub const FRACTION_CONSECUTIVE: DigitSeparator = DigitSeparator { bits: 0x80 };
+
+    /// Digit separators may appear between exponent digits.
+    pub const EXPONENT_INTERNAL: DigitSeparator = DigitSeparator { bits: 0x100 };
+    /// Digit separators may appear before the first exponent digit.
+    pub const EXPONENT_LEADING: DigitSeparator = DigitSeparator { bits: 0x200 };
+    /// Digit separators may appear after the last exponent digit.
+    pub const EXPONENT_TRAILING: DigitSeparator = DigitSeparator { bits: 0x400 };
+    /// Multiple consecutive digit separators may appear in the exponent.
+    pub const EXPONENT_CONSECUTIVE: DigitSeparator = DigitSeparator { bits: 0x800 };
+
+    /// Digit separators may appear after a `0x`, `0o`, or `0b` radix
+    /// prefix, before the first integer digit, like `0x_FF`.
+    pub const PREFIX_TRAILING: DigitSeparator = DigitSeparator { bits: 0x1000 };
+
+    /// Digit separators may appear between digits in any component.
+    pub const INTERNAL: DigitSeparator = DigitSeparator { bits: 0x111 };
+    /// Digit separators may appear before the first digit of any component.
+    pub const LEADING: DigitSeparator = DigitSeparator { bits: 0x222 };
+    /// Digit separators may appear after the last digit of any component.
+    pub const TRAILING: DigitSeparator = DigitSeparator { bits: 0x444 };
+    /// Multiple consecutive digit separators may appear in any component.
+    pub const CONSECUTIVE: DigitSeparator = DigitSeparator { bits: 0x888 };
+    /// Digit separators may appear anywhere in any component.
+    pub const ALL: DigitSeparator = DigitSeparator { bits: 0x1FFF };
+
+    /// Get the separator rules for the integer component.
+    #[inline]
+    pub(crate) fn integer_rules(&self) -> SeparatorRules {
+        SeparatorRules::new(self.bits)
+    }
+
+    /// Get the separator rules for the fraction component.
+    #[inline]
+    pub(crate) fn fraction_rules(&self) -> SeparatorRules {
+        SeparatorRules::new(self.bits >> 4)
+    }
+
+    /// Get the separator rules for the exponent component.
+    #[inline]
+    pub(crate) fn exponent_rules(&self) -> SeparatorRules {
+        SeparatorRules::new(self.bits >> 8)
+    }
+}
+
+flags_impl!(DigitSeparator);
+
+// SEPARATOR RULES
+
+/// Where digit separators may appear in a single component of a number.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) struct SeparatorRules {
+    /// Separators may appear between digits.
+    pub(crate) internal: bool,
+    /// Separators may appear before the first digit.
+    pub(crate) leading: bool,
+    /// Separators may appear after the last digit.
+    pub(crate) trailing: bool,
+    /// Valid separators may be repeated.
+    pub(crate) consecutive: bool,
+}
+
+impl SeparatorRules {
+    /// Create rules from the low 4 bits of the separator flags.
+    #[inline]
+    fn new(bits: u32) -> SeparatorRules {
+        SeparatorRules {
+            internal: bits & 0x1 != 0,
+            leading: bits & 0x2 != 0,
+            trailing: bits & 0x4 != 0,
+            consecutive: bits & 0x8 != 0,
+        }
+    }
+}
+
+// NUMBER FORMAT
+
+/// Preset number grammars for common languages and file formats.
+///
+/// Each preset validates parsed numbers against the grammar of the
+/// language or file format, rejecting inputs the language itself
+/// would reject. The presets describe decimal numbers, and always
+/// accept a leading `-`, so negative values may be parsed directly.
+///
+/// Setting a format on `ParseOptions` sets the grammar flags and the
+/// digit separator for the format, if any.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let mut options = ParseOptions::new();
+/// options.set_format(NumberFormat::Json);
+/// assert_eq!(atof64_slice_with_options(b"1.5", &options), 1.5);
+/// assert_eq!(try_atof64_slice_with_options(b"01", &options).error.code, ErrorCode::InvalidLeadingZeros);
+/// assert_eq!(try_atof64_slice_with_options(b"+1", &options).error.code, ErrorCode::InvalidPositiveMantissaSign);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum NumberFormat {
+    /// Permissive format accepting any number lexical can parse.
+    Standard,
+    /// Rust literals.
+    ///
+    /// Requires integer digits (`.5` is invalid), forbids a leading `+`,
+    /// and does not accept special values. Accepts `_` digit separators
+    /// anywhere after the first digit of the integer or fraction, and
+    /// anywhere in the exponent. Integers may have a `0x`, `0o`, or `0b`
+    /// radix prefix.
+    Rust,
+    /// JSON numbers, as defined by RFC 8259.
+    ///
+    /// Requires integer and fraction digits (`.5` and `1.` are invalid),
+    /// forbids a leading `+`, leading zeros (`01`), and special values.
+    Json,
+    /// TOML integers and floats.
+    ///
+    /// Requires integer and fraction digits, forbids leading zeros, and
+    /// accepts single `_` digit separators between digits. Integers may
+    /// have a `0x`, `0o`, or `0b` radix prefix.
+    Toml,
+    /// YAML 1.2 core schema integers and floats.
+    ///
+    /// Accepts a leading `+`, `.5`, and `1.`, but no digit separators.
+    Yaml,
+    /// Python literals.
+    ///
+    /// Forbids a leading `+`, leading zeros in integers, and special
+    /// values, and accepts single `_` digit separators between digits.
+    /// Integers may have a `0x`, `0o`, or `0b` radix prefix.
+    Python,
+    /// C literals.
+    ///
+    /// Forbids a leading `+` and special values, and accepts a trailing
+    /// `f`, `F`, `l`, or `L` float suffix if the float has a fraction or
+    /// exponent, and hexadecimal floats like `0x1.8p+3`. Integers may have
+    /// a `0x`, `0o`, or `0b` radix prefix, and integers with a leading
+    /// `0` are octal.
+    C,
+    /// JavaScript literals.
+    ///
+    /// Forbids a leading `+`, leading zeros (legacy octal), and special
+    /// values, and accepts single `_` digit separators between digits.
+    /// Integers may have a `0x`, `0o`, or `0b` radix prefix.
+    JavaScript,
+}
+
+impl NumberFormat {
+    /// Get the digit separator and its flags for the format.
+    ///
+    /// Formats without digit separators return empty flags.
+    #[inline]
+    pub fn digit_separator(&self) -> (u8, DigitSeparator) {
+        match *self {
+            NumberFormat::Rust      => {
+                let flags = DigitSeparator::INTERNAL
+                    | DigitSeparator::INTEGER_TRAILING
+                    | DigitSeparator::FRACTION_TRAILING
+                    | DigitSeparator::EXPONENT_LEADING
+                    | DigitSeparator::EXPONENT_TRAILING
+                    | DigitSeparator::CONSECUTIVE
+                    | DigitSeparator::PREFIX_TRAILING;
+                (b'_', flags)
+            },
+            NumberFormat::Python        => (b'_', DigitSeparator::INTERNAL | DigitSeparator::PREFIX_TRAILING),
+            NumberFormat::Toml          |
+            NumberFormat::JavaScript    => (b'_', DigitSeparator::INTERNAL),
+            _                           => (b'_', DigitSeparator::NONE),
+        }
+    }
+
+    /// Get the grammar flags for the format.
+    #[inline]
+    pub fn grammar(&self) -> NumberGrammar {
+        match *self {
+            NumberFormat::Standard      => NumberGrammar::STANDARD,
+            NumberFormat::Rust          => {
+                NumberGrammar::REQUIRED_INTEGER_DIGITS
+                    | NumberGrammar::REQUIRED_EXPONENT_DIGITS
+                    | NumberGrammar::NO_POSITIVE_MANTISSA_SIGN
+                    | NumberGrammar::NO_SPECIAL
+                    | NumberGrammar::RADIX_PREFIX
+            },
+            NumberFormat::Json          => {
+                NumberGrammar::REQUIRED_DIGITS
+                    | NumberGrammar::NO_POSITIVE_MANTISSA_SIGN
+                    | NumberGrammar::NO_LEADING_ZEROS
+                    | NumberGrammar::NO_SPECIAL
+            },
+            NumberFormat::Toml          => {
+                NumberGrammar::REQUIRED_DIGITS
+                    | NumberGrammar::NO_LEADING_ZEROS
+                    | NumberGrammar::RADIX_PREFIX
+            },
+            NumberFormat::Yaml          => NumberGrammar::STANDARD,
+            NumberFormat::Python        => {
+                NumberGrammar::REQUIRED_EXPONENT_DIGITS
+                    | NumberGrammar::NO_POSITIVE_MANTISSA_SIGN
+                    | NumberGrammar::NO_INTEGER_LEADING_ZEROS
+                    | NumberGrammar::NO_SPECIAL
+                    | NumberGrammar::RADIX_PREFIX
+            },
+            NumberFormat::C             => {
+                NumberGrammar::REQUIRED_EXPONENT_DIGITS
+                    | NumberGrammar::NO_POSITIVE_MANTISSA_SIGN
+                    | NumberGrammar::NO_INTEGER_LEADING_ZEROS
+                    | NumberGrammar::NO_SPECIAL
+                    | NumberGrammar::FLOAT_SUFFIX
+                    | NumberGrammar::HEX_FLOAT
+                    | NumberGrammar::RADIX_PREFIX
+                    | NumberGrammar::OCTAL_PREFIX
+            },
+            NumberFormat::JavaScript    => {
+                NumberGrammar::REQUIRED_EXPONENT_DIGITS
+                    | NumberGrammar::NO_POSITIVE_MANTISSA_SIGN
+                    | NumberGrammar::NO_LEADING_ZEROS
+                    | NumberGrammar::NO_SPECIAL
+                    | NumberGrammar::RADIX_PREFIX
+            },
+        }
+    }
+}
+
+impl Default for NumberFormat {
+    #[inline]
+    fn default() -> NumberFormat {
+        NumberFormat::Standard
+    }
+}
+
+// NUMBER GRAMMAR
+
+/// Bitflags for the grammar of a number.
+///
+/// Each flag restricts the numbers accepted by the parser, except for
+/// `FLOAT_SUFFIX`, which accepts a trailing C float suffix, `HEX_FLOAT`,
+/// which accepts C99 hexadecimal float literals, and `RADIX_PREFIX` and
+/// `OCTAL_PREFIX`, which detect the radix of integers from a prefix. Numbers
+/// which violate the grammar are reported with a distinct error code,
+/// and the index where the violation occurred. The standard grammar
+/// only requires exponent digits, and is the default. For compatibility,
+/// the standard grammar reports violations as invalid digits, other than
+/// a lone decimal point, which has an empty mantissa.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let mut options = ParseOptions::new();
+/// options.set_grammar(NumberGrammar::REQUIRED_DIGITS | NumberGrammar::NO_POSITIVE_EXPONENT_SIGN);
+/// assert_eq!(try_atof64_slice_with_options(b"1.", &options).error.code, ErrorCode::EmptyFraction);
+/// assert_eq!(try_atof64_slice_with_options(b"1e+5", &options).error.code, ErrorCode::InvalidPositiveExponentSign);
+/// assert_eq!(try_atof64_slice_with_options(b"1e+5", &options).error.index, 2);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct NumberGrammar {
+    bits: u32,
+}
+
+impl NumberGrammar {
+    /// Floats must have integer digits, so `.5` is invalid.
+    pub const REQUIRED_INTEGER_DIGITS: NumberGrammar = NumberGrammar { bits: 0x1 };
+    /// Floats with a decimal point must have fraction digits, so `1.` is invalid.
+    pub const REQUIRED_FRACTION_DIGITS: NumberGrammar = NumberGrammar { bits: 0x2 };
+    /// Floats with an exponent must have exponent digits, so `1e` is invalid.
+    pub const REQUIRED_EXPONENT_DIGITS: NumberGrammar = NumberGrammar { bits: 0x4 };
+    /// Numbers may not have a leading `+` sign.
+    pub const NO_POSITIVE_MANTISSA_SIGN: NumberGrammar = NumberGrammar { bits: 0x8 };
+    /// Exponents may not have a `+` sign.
+    pub const NO_POSITIVE_EXPONENT_SIGN: NumberGrammar = NumberGrammar { bits: 0x10 };
+    /// Exponents must have a `+` or `-` sign.
+    pub const REQUIRED_EXPONENT_SIGN: NumberGrammar = NumberGrammar { bits: 0x20 };
+    /// Integers may not have leading zeros, so `01` is invalid.
+    pub const NO_INTEGER_LEADING_ZEROS: NumberGrammar = NumberGrammar { bits: 0x40 };
+    /// Floats may not have leading zeros, so `01.5` is invalid.
+    pub const NO_FLOAT_LEADING_ZEROS: NumberGrammar = NumberGrammar { bits: 0x80 };
+    /// Special values, NaN and infinity, are invalid.
+    pub const NO_SPECIAL: NumberGrammar = NumberGrammar { bits: 0x100 };
+    /// Floats with a fraction or exponent may have a trailing C float
+    /// suffix, `f`, `F`, `l`, or `L`.
+    pub const FLOAT_SUFFIX: NumberGrammar = NumberGrammar { bits: 0x200 };
+    /// Decimal floats may be C99 hexadecimal float literals, with a
+    /// hexadecimal mantissa and a binary exponent, like `0x1.8p+3`.
+    pub const HEX_FLOAT: NumberGrammar = NumberGrammar { bits: 0x400 };
+    /// Decimal integers may have a radix prefix after the sign, `0x` or
+    /// `0X` for hexadecimal, `0o` or `0O` for octal, and `0b` or `0B`
+    /// for binary, like `0xFF`. Leading zeros are valid after the prefix.
+    pub const RADIX_PREFIX: NumberGrammar = NumberGrammar { bits: 0x800 };
+    /// Decimal integers with a leading `0` followed by a digit are octal,
+    /// like the C literal `017`, so `8` and `9` are invalid digits after
+    /// the leading `0`.
+    pub const OCTAL_PREFIX: NumberGrammar = NumberGrammar { bits: 0x1000 };
+
+    /// Floats must have integer, fraction, and exponent digits.
+    pub const REQUIRED_DIGITS: NumberGrammar = NumberGrammar { bits: 0x7 };
+    /// Integers and floats may not have leading zeros.
+    pub const NO_LEADING_ZEROS: NumberGrammar = NumberGrammar { bits: 0xC0 };
+    /// Default grammar, accepting any number lexical can parse.
+    pub const STANDARD: NumberGrammar = NumberGrammar { bits: 0x4 };
+    /// All grammar flags.
+    pub const ALL: NumberGrammar = NumberGrammar { bits: 0x1FFF };
+}
+
+flags_impl!(NumberGrammar);
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bits_test() {
+        assert_eq!(DigitSeparator::from_bits(0), Some(DigitSeparator::NONE));
+        assert_eq!(DigitSeparator::from_bits(0x111), Some(DigitSeparator::INTERNAL));
+        assert_eq!(DigitSeparator::from_bits(0x1000), Some(DigitSeparator::PREFIX_TRAILING));
+        assert_eq!(DigitSeparator::from_bits(0x2000), None);
+        assert_eq!(DigitSeparator::from_bits_truncate(0x2001), DigitSeparator::INTEGER_INTERNAL);
+        assert_eq!(NumberGrammar::from_bits(0x7), Some(NumberGrammar::REQUIRED_DIGITS));
+        assert_eq!(NumberGrammar::from_bits(0x2000), None);
+    }
+
+    #[test]
+    fn rules_test() {
+        let flags = DigitSeparator::INTEGER_INTERNAL
+            | DigitSeparator::FRACTION_TRAILING
+            | DigitSeparator::EXPONENT_LEADING
+            | DigitSeparator::EXPONENT_CONSECUTIVE;
+        let integer = flags.integer_rules();
+        assert!(integer.internal && !integer.leading && !integer.trailing && !integer.consecutive);
+        let fraction = flags.fraction_rules();
+        assert!(!fraction.internal && !fraction.leading && fraction.trailing && !fraction.consecutive);
+        let exponent = flags.exponent_rules();
+        assert!(!exponent.internal && exponent.leading && !exponent.trailing && exponent.consecutive);
+    }
+
+    #[test]
+    fn number_format_test() {
+        assert_eq!(NumberFormat::default(), NumberFormat::Standard);
+        assert!(NumberFormat::Standard.digit_separator().1.is_empty());
+        assert!(NumberFormat::Json.digit_separator().1.is_empty());
+        assert_eq!(NumberFormat::Toml.digit_separator(), (b'_', DigitSeparator::INTERNAL));
+        assert!(NumberFormat::Rust.digit_separator().1.contains(DigitSeparator::PREFIX_TRAILING));
+        assert!(!NumberFormat::JavaScript.digit_separator().1.contains(DigitSeparator::PREFIX_TRAILING));
+        assert!(NumberFormat::Json.grammar().contains(NumberGrammar::NO_POSITIVE_MANTISSA_SIGN));
+        assert_eq!(NumberFormat::Yaml.grammar(), NumberGrammar::STANDARD);
+        assert!(NumberFormat::C.grammar().contains(NumberGrammar::FLOAT_SUFFIX));
+        assert!(NumberFormat::C.grammar().contains(NumberGrammar::HEX_FLOAT));
+        assert!(NumberFormat::C.grammar().contains(NumberGrammar::RADIX_PREFIX | NumberGrammar::OCTAL_PREFIX));
+        assert!(!NumberFormat::Json.grammar().contains(NumberGrammar::RADIX_PREFIX));
+    }
+}
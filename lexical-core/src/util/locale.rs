@@ -0,0 +1,335 @@
+//! Locale-specific decimal points and digit grouping.
+
+use lib::str;
+
+/// Assert a decimal point is valid for parsing or formatting.
+#[inline]
+fn assert_decimal_point(ch: u8) {
+    let is_valid = match ch {
+        b'+' | b'-' => false,
+        _ => ch.is_ascii() && !ch.is_ascii_alphanumeric(),
+    };
+    assert!(is_valid, "Decimal points must be a non-alphanumeric ASCII character, and not `+` or `-`.");
+}
+
+/// Assert a group separator and grouping pattern are valid.
+#[inline]
+fn assert_grouping(separator: &[u8], grouping: &[u8], decimal_point: u8) {
+    let is_valid = !separator.is_empty() && separator.len() <= 4 && separator.iter().all(|&c| {
+        match c {
+            b'+' | b'-' => false,
+            _ => c != decimal_point && !c.is_ascii_alphanumeric(),
+        }
+    });
+    assert!(is_valid, "Group separators must be 1-4 non-alphanumeric bytes, and not contain `+`, `-`, or the decimal point.");
+    assert!(str::from_utf8(separator).is_ok(), "Group separators must be valid UTF-8.");
+    assert!(grouping.iter().all(|&size| size != 0), "Group sizes must be non-zero.");
+}
+
+/// Decimal point and digit grouping for a locale.
+///
+/// The grouping pattern lists the number of digits in each group of
+/// the integer component, starting from the decimal point, and the
+/// last group size repeats for any remaining digits. For example,
+/// `[3]` groups digits as `1,234,567`, and `[3, 2]` groups digits
+/// as `12,34,567`. An empty pattern disables digit grouping.
+///
+/// When parsing, digit grouping is optional, however, if any group
+/// separators are present, every group must match the pattern.
+/// Group separators may be multi-byte, such as the UTF-8 encoding
+/// of a non-breaking space, `b"\xC2\xA0"`.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let mut options = ParseOptions::new();
+/// options.set_locale(Locale::DE_DE);
+/// assert_eq!(atof64_slice_with_options(b"1.234.567,5", &options), 1234567.5);
+/// assert_eq!(atof64_slice_with_options(b"1234567,5", &options), 1234567.5);
+///
+/// let mut options = WriteOptions::new();
+/// options.set_locale(Locale::EN_IN);
+/// let mut buf = [b'0'; 64];
+/// assert_eq!(u32toa_slice_with_options(123456789, &mut buf, &options), b"12,34,56,789");
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Locale<'a> {
+    /// Character separating the integer and fraction components.
+    decimal_point: u8,
+    /// Bytes separating groups of integer digits.
+    group_separator: &'a [u8],
+    /// Number of digits in each group, from the decimal point.
+    grouping: &'a [u8],
+}
+
+impl<'a> Locale<'a> {
+    /// Create the C locale, with a `.` decimal point and no grouping.
+    #[inline]
+    pub fn new() -> Locale<'a> {
+        Locale {
+            decimal_point: b'.',
+            group_separator: b"",
+            grouping: &[],
+        }
+    }
+
+    /// Get the character separating the integer and fraction components.
+    #[inline]
+    pub fn decimal_point(&self) -> u8 {
+        self.decimal_point
+    }
+
+    /// Set the character separating the integer and fraction components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is alphanumeric, non-ASCII, `+`, `-`, or in
+    /// the group separator.
+    #[inline]
+    pub fn set_decimal_point(&mut self, ch: u8) {
+        assert_decimal_point(ch);
+        assert!(!self.group_separator.contains(&ch), "Decimal points cannot be in the group separator.");
+        self.decimal_point = ch;
+    }
+
+    /// Get the bytes separating groups of integer digits.
+    #[inline]
+    pub fn group_separator(&self) -> &'a [u8] {
+        self.group_separator
+    }
+
+    /// Get the number of digits in each group, from the decimal point.
+    ///
+    /// Digit grouping is disabled if the pattern is empty.
+    #[inline]
+    pub fn grouping(&self) -> &'a [u8] {
+        self.grouping
+    }
+
+    /// Set the bytes separating groups of integer digits and the grouping pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `separator` is empty, longer than 4 bytes, not valid
+    /// UTF-8, contains an alphanumeric character, `+`, `-`, or the
+    /// decimal point, or if any group size is 0.
+    #[inline]
+    pub fn set_grouping(&mut self, separator: &'a [u8], grouping: &'a [u8]) {
+        assert_grouping(separator, grouping, self.decimal_point);
+        self.group_separator = separator;
+        self.grouping = grouping;
+    }
+
+    /// Get if digit grouping is enabled.
+    #[inline]
+    pub(crate) fn is_grouped(&self) -> bool {
+        !self.grouping.is_empty()
+    }
+
+    /// Get the size of the group at the index, from the decimal point.
+    #[inline]
+    pub(crate) fn group_size(&self, index: usize) -> usize {
+        match self.grouping.get(index) {
+            Some(&size) => size as usize,
+            None        => *self.grouping.last().unwrap() as usize,
+        }
+    }
+
+    /// Get the number of bytes of group separators for the integer digits.
+    #[inline]
+    pub(crate) fn separators_len(&self, digits: usize) -> usize {
+        if !self.is_grouped() {
+            return 0;
+        }
+
+        let mut count = 0;
+        let mut remaining = digits;
+        while remaining > self.group_size(count) {
+            remaining -= self.group_size(count);
+            count += 1;
+        }
+        count * self.group_separator.len()
+    }
+}
+
+impl Locale<'static> {
+    /// C locale, like `1234567.5`.
+    pub const C: Locale<'static> = Locale {
+        decimal_point: b'.',
+        group_separator: b"",
+        grouping: &[],
+    };
+
+    /// American English locale, like `1,234,567.5`.
+    pub const EN_US: Locale<'static> = Locale {
+        decimal_point: b'.',
+        group_separator: b",",
+        grouping: &[3],
+    };
+
+    /// German locale, like `1.234.567,5`.
+    pub const DE_DE: Locale<'static> = Locale {
+        decimal_point: b',',
+        group_separator: b".",
+        grouping: &[3],
+    };
+
+    /// French locale, like `1 234 567,5`, using a non-breaking space.
+    pub const FR_FR: Locale<'static> = Locale {
+        decimal_point: b',',
+        group_separator: b"\xC2\xA0",
+        grouping: &[3],
+    };
+
+    /// Indian English locale, like `12,34,567.5`.
+    pub const EN_IN: Locale<'static> = Locale {
+        decimal_point: b'.',
+        group_separator: b",",
+        grouping: &[3, 2],
+    };
+}
+
+impl<'a> Default for Locale<'a> {
+    #[inline]
+    fn default() -> Locale<'a> {
+        Locale::new()
+    }
+}
+
+// WRITE
+
+/// Insert group separators between the integer digits.
+///
+/// `bytes[..digits]` must be the integer digits, and `bytes[..len]` the
+/// written number. Returns the length of the number with the separators.
+///
+/// # Panics
+///
+/// Panics if the buffer is too small for the grouped number.
+pub(crate) fn write_groups(bytes: &mut [u8], digits: usize, len: usize, locale: &Locale)
+    -> usize
+{
+    if !locale.is_grouped() {
+        return len;
+    }
+
+    let separator = locale.group_separator();
+    let shift = locale.separators_len(digits);
+    let count = shift / separator.len();
+    assert!(bytes.len() >= len + shift, "Buffer is too small for the grouped number.");
+
+    // Shift any bytes after the integer, then shift each group from the
+    // least-significant digits, followed by its separator. The digits
+    // in the most-significant group are already in place.
+    for i in (digits..len).rev() {
+        let c = index!(bytes[i]);
+        index_mut!(bytes[i + shift] = c);
+    }
+    let mut src = digits;
+    let mut dst = digits + shift;
+    for group in 0..count {
+        for _ in 0..locale.group_size(group) {
+            src -= 1;
+            dst -= 1;
+            let c = index!(bytes[src]);
+            index_mut!(bytes[dst] = c);
+        }
+        for &c in separator.iter().rev() {
+            dst -= 1;
+            index_mut!(bytes[dst] = c);
+        }
+    }
+
+    len + shift
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_size_test() {
+        assert!(!Locale::C.is_grouped());
+        assert!(Locale::EN_US.is_grouped());
+        assert_eq!(Locale::EN_US.group_size(0), 3);
+        assert_eq!(Locale::EN_US.group_size(5), 3);
+        assert_eq!(Locale::EN_IN.group_size(0), 3);
+        assert_eq!(Locale::EN_IN.group_size(1), 2);
+        assert_eq!(Locale::EN_IN.group_size(4), 2);
+    }
+
+    #[test]
+    fn separators_len_test() {
+        assert_eq!(Locale::C.separators_len(20), 0);
+        assert_eq!(Locale::EN_US.separators_len(3), 0);
+        assert_eq!(Locale::EN_US.separators_len(4), 1);
+        assert_eq!(Locale::EN_US.separators_len(20), 6);
+        assert_eq!(Locale::FR_FR.separators_len(19), 12);
+        assert_eq!(Locale::EN_IN.separators_len(6), 2);
+    }
+
+    #[test]
+    fn set_grouping_test() {
+        let mut locale = Locale::new();
+        locale.set_decimal_point(b',');
+        locale.set_grouping(b"'", &[3]);
+        assert_eq!(locale.decimal_point(), b',');
+        assert_eq!(locale.group_separator(), b"'");
+        assert_eq!(locale.grouping(), &[3]);
+    }
+
+    fn check_write_groups(s: &str, digits: usize, locale: Locale, expected: &str) {
+        let mut buffer = [b'\x00'; 64];
+        buffer[..s.len()].copy_from_slice(s.as_bytes());
+        let len = write_groups(&mut buffer, digits, s.len(), &locale);
+        assert_eq!(&buffer[..len], expected.as_bytes());
+    }
+
+    #[test]
+    fn write_groups_test() {
+        check_write_groups("1234567.5", 7, Locale::EN_US, "1,234,567.5");
+        check_write_groups("123456", 6, Locale::EN_US, "123,456");
+        check_write_groups("123", 3, Locale::EN_US, "123");
+        check_write_groups("123456789", 9, Locale::EN_IN, "12,34,56,789");
+        check_write_groups("1234,5", 4, Locale::FR_FR, "1\u{a0}234,5");
+        check_write_groups("1234.5", 4, Locale::C, "1234.5");
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_groups_overflow_test() {
+        let mut buffer = *b"1234";
+        write_groups(&mut buffer, 4, 4, &Locale::EN_US);
+    }
+
+    #[test]
+    #[should_panic]
+    fn alphanumeric_decimal_point_test() {
+        Locale::new().set_decimal_point(b'e');
+    }
+
+    #[test]
+    #[should_panic]
+    fn decimal_point_group_separator_test() {
+        Locale::new().set_grouping(b".", &[3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_utf8_group_separator_test() {
+        Locale::new().set_grouping(b"\xA0", &[3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_group_test() {
+        Locale::new().set_grouping(b",", &[3, 0]);
+    }
+}
@@ -19,17 +19,26 @@ pub(crate) mod test;
 // Hide implementation details.
 mod algorithm;
 mod cast;
+mod code_unit;
 mod config;
 mod error;
+mod float16;
+#[cfg(all(has_i128, feature = "correct"))]
+mod float128;
+mod format;
+mod locale;
 mod mask;
 mod num;
+mod options;
 mod pointer_methods;
 mod primitive;
 mod pow;
 mod result;
 mod rounding;
 mod sign;
+mod swar;
 mod table;
+mod wrapped;
 
 cfg_if! {
 if #[cfg(feature = "correct")] {
@@ -37,13 +46,15 @@ if #[cfg(feature = "correct")] {
     mod range_bounds;
     mod slice_index;
     mod sequence;
-} else {
-    mod wrapped;
 }}  // cfg_if
 
 // Publicly export everything with crate-visibility.
 pub(crate) use self::algorithm::*;
 pub(crate) use self::cast::*;
+pub(crate) use self::code_unit::byte_at;
+pub(crate) use self::float16::narrow_f64;
+pub(crate) use self::format::SeparatorRules;
+pub(crate) use self::locale::write_groups;
 pub(crate) use self::mask::*;
 pub(crate) use self::num::*;
 pub(crate) use self::pointer_methods::*;
@@ -51,17 +62,25 @@ pub(crate) use self::primitive::*;
 pub(crate) use self::pow::*;
 pub(crate) use self::rounding::*;
 pub(crate) use self::sign::*;
+pub(crate) use self::swar::parse_digits;
 pub(crate) use self::table::*;
+pub(crate) use self::wrapped::*;
 
-cfg_if! {
-if #[cfg(feature = "correct")] {
-    pub(crate) use self::sequence::*;
-} else {
-    pub(crate) use self::wrapped::*;
-}}  // cfg_if
+#[cfg(feature = "correct")]
+pub(crate) use self::sequence::*;
 
 // Publicly export config globally.
+pub use self::code_unit::CodeUnit;
 pub use self::config::*;
+pub use self::format::{DigitSeparator, NumberFormat, NumberGrammar};
+pub use self::locale::Locale;
+pub use self::options::*;
 pub use self::error::{Error, ErrorCode, is_empty, is_invalid_digit, is_overflow, is_success};
+pub use self::float16::{bf16, f16};
+#[cfg(all(has_i128, feature = "correct"))]
+pub use self::float128::f128;
+pub use self::error::{is_empty_mantissa, is_empty_integer, is_empty_fraction, is_empty_exponent};
+pub use self::error::{is_invalid_positive_mantissa_sign, is_invalid_positive_exponent_sign};
+pub use self::error::{is_missing_exponent_sign, is_invalid_leading_zeros};
 pub use self::result::*;
 pub use self::rounding::RoundingKind;
@@ -0,0 +1,1102 @@
+//! Per-call options for parsing and formatting numbers.
+//!
+//! Unlike the global configuration in `config`, these options are
+//! immutable values passed explicitly to the `*_with_options` functions,
+//! and therefore may differ between threads or libraries within the
+//! same binary.
+
+use super::config::*;
+use super::format::{DigitSeparator, NumberFormat, NumberGrammar};
+use super::locale::Locale;
+use super::rounding::RoundingKind;
+
+// HELPERS
+
+/// Select the exponent character from the default and backup characters.
+#[inline]
+#[allow(unused_variables)]
+fn select_exponent_char(default: u8, backup: u8, radix: u32) -> u8 {
+    #[cfg(not(feature = "radix"))] {
+        default
+    }
+
+    #[cfg(feature = "radix")] {
+        if radix >= 15 { backup } else { default }
+    }
+}
+
+/// Assert a special string is valid for parsing or formatting.
+#[inline]
+fn assert_special_string(bytes: &[u8]) {
+    assert!(!bytes.is_empty(), "Special strings cannot be empty.");
+    assert!(bytes.len() < MAX_F32_SIZE, "Special strings must be < MAX_F32_SIZE.");
+    assert!(bytes.is_ascii(), "Special strings must be ASCII.");
+}
+
+/// Assert an exponent character is valid for parsing or formatting.
+#[inline]
+fn assert_exponent_char(ch: u8) {
+    let is_valid = match ch {
+        b'.' | b'+' | b'-' => false,
+        _ => ch.is_ascii() && !ch.is_ascii_digit(),
+    };
+    assert!(is_valid, "Exponent characters must be a non-digit ASCII character, and not `.`, `+`, or `-`.");
+}
+
+/// Assert a digit separator is valid for parsing.
+#[inline]
+fn assert_digit_separator(ch: u8) {
+    let is_valid = match ch {
+        b'.' | b'+' | b'-' => false,
+        _ => ch.is_ascii() && !ch.is_ascii_alphanumeric(),
+    };
+    assert!(is_valid, "Digit separators must be a non-alphanumeric ASCII character, and not `.`, `+`, or `-`.");
+}
+
+// PRECISION
+
+/// Number of digits written when formatting decimal floats.
+///
+/// Fixed precisions are correctly rounded from the exact value of the
+/// float, rounding halfway cases to even, like `printf` with the default
+/// rounding mode, and may write any number of digits. Fixed precisions
+/// require the `correct` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// # #[cfg(feature = "correct")] {
+/// let mut options = WriteOptions::new();
+/// let mut buf = [b'0'; MAX_F64_SIZE];
+/// options.set_precision(Precision::Fixed(20));
+/// assert_eq!(f64toa_slice_with_options(0.1, &mut buf, &options), b"0.10000000000000000555");
+/// options.set_precision(Precision::Scientific(2));
+/// assert_eq!(f64toa_slice_with_options(1500.0, &mut buf, &options), b"1.50e+03");
+/// # }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Precision {
+    /// Shortest representation that parses back to the same float.
+    Shortest,
+    /// `N` digits after the decimal point, like `printf("%.Nf")`.
+    Fixed(usize),
+    /// `N` digits after the decimal point in scientific notation,
+    /// or `N+1` significant digits, like `printf("%.Ne")`.
+    Scientific(usize),
+}
+
+// NOTATION
+
+/// Notation for formatting decimal floats.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let mut options = WriteOptions::new();
+/// let mut buf = [b'0'; MAX_F64_SIZE];
+/// options.set_notation(Notation::Scientific);
+/// assert_eq!(f64toa_slice_with_options(1500.0, &mut buf, &options), b"1.5e3");
+/// options.set_notation(Notation::Positional);
+/// assert_eq!(f64toa_slice_with_options(1.5e-7, &mut buf, &options), b"0.00000015");
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Notation {
+    /// Positional notation for decimal exponents in the positional
+    /// exponent range, otherwise scientific notation.
+    Auto,
+    /// Always use positional notation, like `1500.0`.
+    Positional,
+    /// Always use scientific notation, like `1.5e3`.
+    Scientific,
+}
+
+// ALGORITHM
+
+/// Algorithm for parsing decimal and radix floats.
+///
+/// The incorrect algorithm is always available, while the correct and
+/// lossy algorithms require the `correct` feature. The default algorithm
+/// is the correct algorithm if the `correct` feature is enabled.
+///
+/// The lossy algorithm returns the correctly-rounded float, or one of its
+/// neighbors, that is, it differs from the correct algorithm by at most
+/// 1 ULP (unit in the last place). It skips the arbitrary-precision
+/// arithmetic, which is only required for values nearly halfway between
+/// two floats, and is slow for numbers with many digits. The lossy
+/// parsers, like `atof64_lossy_slice`, use the lossy algorithm unless
+/// the incorrect algorithm is selected.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let mut options = ParseOptions::new();
+/// options.set_algorithm(ParseAlgorithm::Incorrect);
+/// assert_eq!(atof64_slice_with_options(b"1.5", &options), 1.5);
+/// # #[cfg(feature = "correct")] {
+/// options.set_algorithm(ParseAlgorithm::Lossy);
+/// let value = atof64_slice_with_options(b"9007199254740993.00000000000000000001", &options);
+/// assert!(value == 9007199254740992.0 || value == 9007199254740994.0);
+/// # }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ParseAlgorithm {
+    /// Correctly-rounded parsing, using arbitrary-precision arithmetic
+    /// for near-halfway cases.
+    #[cfg(feature = "correct")]
+    Correct,
+    /// Parsing within 1 ULP of the correctly-rounded float, using
+    /// extended-precision arithmetic for near-halfway cases.
+    #[cfg(feature = "correct")]
+    Lossy,
+    /// Fast parsing with floating-point arithmetic, without a bound on
+    /// the error, which may be large for numbers with many digits or
+    /// exponents of high magnitude.
+    Incorrect,
+}
+
+impl ParseAlgorithm {
+    /// Get the algorithm selected by the enabled features.
+    #[inline]
+    fn default_algorithm() -> ParseAlgorithm {
+        #[cfg(feature = "correct")] {
+            ParseAlgorithm::Correct
+        }
+
+        #[cfg(not(feature = "correct"))] {
+            ParseAlgorithm::Incorrect
+        }
+    }
+}
+
+/// Algorithm for writing the shortest representation of decimal floats.
+///
+/// Grisu2 is always available, while Ryu and Grisu3 require the `ryu`
+/// and `grisu3` features. The default algorithm is Ryu if the `ryu`
+/// feature is enabled, otherwise, Grisu3 if the `grisu3` feature is
+/// enabled, otherwise, Grisu2.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let mut options = WriteOptions::new();
+/// let mut buf = [b'0'; MAX_F64_SIZE];
+/// options.set_algorithm(WriteAlgorithm::Grisu2);
+/// assert_eq!(f64toa_slice_with_options(1.5, &mut buf, &options), b"1.5");
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum WriteAlgorithm {
+    /// Grisu2, which round-trips, but may not write the shortest digits.
+    Grisu2,
+    /// The optimized Grisu implementation from the `dtoa` crate.
+    #[cfg(feature = "grisu3")]
+    Grisu3,
+    /// Ryu from the `ryu` crate, which always writes the shortest digits.
+    #[cfg(feature = "ryu")]
+    Ryu,
+}
+
+impl WriteAlgorithm {
+    /// Get the algorithm selected by the enabled features.
+    #[inline]
+    fn default_algorithm() -> WriteAlgorithm {
+        #[cfg(feature = "ryu")] {
+            WriteAlgorithm::Ryu
+        }
+
+        #[cfg(all(feature = "grisu3", not(feature = "ryu")))] {
+            WriteAlgorithm::Grisu3
+        }
+
+        #[cfg(not(any(feature = "grisu3", feature = "ryu")))] {
+            WriteAlgorithm::Grisu2
+        }
+    }
+}
+
+// PARSE OPTIONS
+
+/// Options to customize string-to-number conversions.
+///
+/// The default options match the default values of the global,
+/// deprecated configuration variables. Options may be shared between
+/// threads freely, since they are never modified during parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let mut options = ParseOptions::new();
+/// options.set_nan_string(b"null");
+/// assert!(atof64_slice_with_options(b"null", &options).is_nan());
+/// assert_eq!(try_atof64_slice_with_options(b"NaN", &options).error.code, ErrorCode::InvalidDigit);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ParseOptions<'a> {
+    /// Character to designate the exponent component of a float.
+    exponent_char: u8,
+    /// Backup character to designate the exponent component of a float.
+    exponent_backup_char: u8,
+    /// IEEE754 rounding scheme for float parsing.
+    rounding: RoundingKind,
+    /// Representation of Not a Number.
+    nan_string: &'a [u8],
+    /// Short representation of Infinity.
+    inf_string: &'a [u8],
+    /// Long representation of Infinity.
+    infinity_string: &'a [u8],
+    /// Character to separate digits.
+    digit_separator: u8,
+    /// Where digit separators may appear.
+    digit_separator_flags: DigitSeparator,
+    /// Number grammar to validate against.
+    grammar: NumberGrammar,
+    /// Decimal point and digit grouping.
+    locale: Locale<'a>,
+    /// Algorithm for parsing floats.
+    algorithm: ParseAlgorithm,
+}
+
+impl<'a> ParseOptions<'a> {
+    /// Create options with the default values.
+    #[inline]
+    pub fn new() -> ParseOptions<'a> {
+        ParseOptions {
+            exponent_char: b'e',
+            exponent_backup_char: b'^',
+            rounding: RoundingKind::NearestTieEven,
+            nan_string: b"NaN",
+            inf_string: b"inf",
+            infinity_string: b"infinity",
+            digit_separator: b'_',
+            digit_separator_flags: DigitSeparator::NONE,
+            grammar: NumberGrammar::STANDARD,
+            locale: Locale::new(),
+            algorithm: ParseAlgorithm::default_algorithm(),
+        }
+    }
+
+    /// Get the default character designating the exponent component of a float.
+    #[inline]
+    pub fn exponent_char(&self) -> u8 {
+        self.exponent_char
+    }
+
+    /// Set the default character designating the exponent component of a float.
+    ///
+    /// This character is used for strings with a radix less than 15,
+    /// and is compared case-insensitively. It should not be in
+    /// the character set `[0-9a-eA-E]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is not ASCII, or is a digit, `.`, `+`, or `-`.
+    #[inline]
+    pub fn set_exponent_char(&mut self, ch: u8) {
+        assert_exponent_char(ch);
+        self.exponent_char = ch;
+    }
+
+    /// Get the backup character designating the exponent component of a float.
+    #[cfg(feature = "radix")]
+    #[inline]
+    pub fn exponent_backup_char(&self) -> u8 {
+        self.exponent_backup_char
+    }
+
+    /// Set the backup character designating the exponent component of a float.
+    ///
+    /// This character is used for strings with a radix greater than or
+    /// equal to 15, and should not be an alphanumeric character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is not ASCII, or is a digit, `.`, `+`, or `-`.
+    #[cfg(feature = "radix")]
+    #[inline]
+    pub fn set_exponent_backup_char(&mut self, ch: u8) {
+        assert_exponent_char(ch);
+        self.exponent_backup_char = ch;
+    }
+
+    /// Get the IEEE754 rounding scheme used during float parsing.
+    #[inline]
+    pub fn rounding(&self) -> RoundingKind {
+        self.rounding
+    }
+
+    /// Set the IEEE754 rounding scheme used during float parsing.
+    #[cfg(feature = "rounding")]
+    #[inline]
+    pub fn set_rounding(&mut self, kind: RoundingKind) {
+        self.rounding = kind;
+    }
+
+    /// Get the string representation of Not a Number.
+    #[inline]
+    pub fn nan_string(&self) -> &'a [u8] {
+        self.nan_string
+    }
+
+    /// Set the string representation of Not a Number.
+    ///
+    /// The string is compared case-insensitively.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is empty, is not ASCII, or `bytes.len() >= MAX_F32_SIZE`.
+    #[inline]
+    pub fn set_nan_string(&mut self, bytes: &'a [u8]) {
+        assert_special_string(bytes);
+        self.nan_string = bytes;
+    }
+
+    /// Get the short string representation of Infinity.
+    #[inline]
+    pub fn inf_string(&self) -> &'a [u8] {
+        self.inf_string
+    }
+
+    /// Set the short string representation of Infinity.
+    ///
+    /// The string is compared case-insensitively.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is empty, is not ASCII, or `bytes.len() >= MAX_F32_SIZE`.
+    #[inline]
+    pub fn set_inf_string(&mut self, bytes: &'a [u8]) {
+        assert_special_string(bytes);
+        self.inf_string = bytes;
+    }
+
+    /// Get the long string representation of Infinity.
+    #[inline]
+    pub fn infinity_string(&self) -> &'a [u8] {
+        self.infinity_string
+    }
+
+    /// Set the long string representation of Infinity.
+    ///
+    /// The string is compared case-insensitively, and must be at least
+    /// as long as the short representation of Infinity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is empty, is not ASCII, or `bytes.len() >= MAX_F32_SIZE`.
+    #[inline]
+    pub fn set_infinity_string(&mut self, bytes: &'a [u8]) {
+        assert_special_string(bytes);
+        self.infinity_string = bytes;
+    }
+
+    /// Get the character separating digits.
+    #[inline]
+    pub fn digit_separator(&self) -> u8 {
+        self.digit_separator
+    }
+
+    /// Get where digit separators may appear.
+    ///
+    /// Digit separators are disabled if the flags are empty.
+    #[inline]
+    pub fn digit_separator_flags(&self) -> DigitSeparator {
+        self.digit_separator_flags
+    }
+
+    /// Set the character separating digits and where it may appear.
+    ///
+    /// Digit separators are disabled if `flags` is empty, which is
+    /// the default. When disabled, parsing uses the same routines
+    /// as if no separator was configured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is alphanumeric, non-ASCII, `.`, `+`, or `-`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate lexical_core;
+    /// # use lexical_core::*;
+    /// # pub fn main() {
+    /// let mut options = ParseOptions::new();
+    /// options.set_digit_separator(b'_', DigitSeparator::INTERNAL);
+    /// assert_eq!(atou32_slice_with_options(b"1_000_000", &options), 1000000);
+    /// assert_eq!(atof64_slice_with_options(b"1_000.000_5", &options), 1000.0005);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_digit_separator(&mut self, ch: u8, flags: DigitSeparator) {
+        assert_digit_separator(ch);
+        self.digit_separator = ch;
+        self.digit_separator_flags = flags;
+    }
+
+    /// Get the number grammar to validate against.
+    #[inline]
+    pub fn grammar(&self) -> NumberGrammar {
+        self.grammar
+    }
+
+    /// Set the number grammar to validate against.
+    #[inline]
+    pub fn set_grammar(&mut self, grammar: NumberGrammar) {
+        self.grammar = grammar;
+    }
+
+    /// Set the number grammar and digit separator from a preset format.
+    ///
+    /// This disables digit separators if the format does not use them.
+    /// To use a custom digit separator or grammar, call
+    /// `set_digit_separator` or `set_grammar` after setting the format.
+    #[inline]
+    pub fn set_format(&mut self, format: NumberFormat) {
+        let (separator, flags) = format.digit_separator();
+        self.grammar = format.grammar();
+        self.digit_separator = separator;
+        self.digit_separator_flags = flags;
+    }
+
+    /// Get the decimal point and digit grouping.
+    #[inline]
+    pub fn locale(&self) -> Locale<'a> {
+        self.locale
+    }
+
+    /// Set the decimal point and digit grouping.
+    ///
+    /// If the locale groups digits, the group separator is used in
+    /// the integer component instead of any digit separator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate lexical_core;
+    /// # use lexical_core::*;
+    /// # pub fn main() {
+    /// let mut options = ParseOptions::new();
+    /// options.set_locale(Locale::EN_IN);
+    /// assert_eq!(atou64_slice_with_options(b"12,34,56,789", &options), 123456789);
+    /// assert_eq!(try_atou64_slice_with_options(b"1,234,567", &options).error.code, ErrorCode::InvalidDigit);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_locale(&mut self, locale: Locale<'a>) {
+        self.locale = locale;
+    }
+
+    /// Get the algorithm for parsing floats.
+    #[inline]
+    pub fn algorithm(&self) -> ParseAlgorithm {
+        self.algorithm
+    }
+
+    /// Set the algorithm for parsing floats.
+    ///
+    /// The algorithm applies to decimal and radix floats parsed as a float
+    /// type, and is ignored for hexadecimal float literals and quad floats.
+    /// `parse_number`, `parse_decimal_parts`, and `FloatStream` always use
+    /// the correct algorithm.
+    #[inline]
+    pub fn set_algorithm(&mut self, algorithm: ParseAlgorithm) {
+        self.algorithm = algorithm;
+    }
+
+    /// Get the exponent character for a given radix.
+    #[inline]
+    pub(crate) fn exponent_notation_char(&self, radix: u32) -> u8 {
+        select_exponent_char(self.exponent_char, self.exponent_backup_char, radix)
+    }
+
+    /// Get the character separating the integer and fraction components.
+    #[inline]
+    pub(crate) fn decimal_point(&self) -> u8 {
+        self.locale.decimal_point()
+    }
+
+    /// Get the digit separator, if digit separators are enabled.
+    #[inline]
+    pub(crate) fn separator(&self) -> Option<u8> {
+        match self.digit_separator_flags.is_empty() {
+            true  => None,
+            false => Some(self.digit_separator),
+        }
+    }
+
+    /// Get the default options with the same exponent character and rounding.
+    ///
+    /// Used to parse numbers re-written in the standard grammar.
+    #[cfg(feature = "correct")]
+    #[inline]
+    pub(crate) fn standard(&self) -> ParseOptions<'static> {
+        let mut options = ParseOptions::new();
+        options.exponent_char = self.exponent_char;
+        options.rounding = self.rounding;
+        options.algorithm = self.algorithm;
+        options
+    }
+}
+
+impl ParseOptions<'static> {
+    /// Create options from the deprecated, global configuration.
+    #[inline]
+    #[allow(deprecated)]
+    pub(crate) fn global() -> ParseOptions<'static> {
+        unsafe {
+            ParseOptions {
+                exponent_char: EXPONENT_DEFAULT_CHAR,
+                #[cfg(feature = "radix")]
+                exponent_backup_char: EXPONENT_BACKUP_CHAR,
+                #[cfg(not(feature = "radix"))]
+                exponent_backup_char: b'^',
+                #[cfg(feature = "rounding")]
+                rounding: FLOAT_ROUNDING,
+                #[cfg(not(feature = "rounding"))]
+                rounding: RoundingKind::NearestTieEven,
+                nan_string: get_nan_string(),
+                inf_string: get_inf_string(),
+                infinity_string: get_infinity_string(),
+                digit_separator: b'_',
+                digit_separator_flags: DigitSeparator::NONE,
+                grammar: NumberGrammar::STANDARD,
+                locale: Locale::new(),
+                algorithm: ParseAlgorithm::default_algorithm(),
+            }
+        }
+    }
+}
+
+impl<'a> Default for ParseOptions<'a> {
+    #[inline]
+    fn default() -> ParseOptions<'a> {
+        ParseOptions::new()
+    }
+}
+
+// WRITE OPTIONS
+
+/// Options to customize number-to-string conversions.
+///
+/// The default options match the default values of the global,
+/// deprecated configuration variables. Options may be shared between
+/// threads freely, since they are never modified during formatting.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical_core;
+/// # use lexical_core::*;
+/// # pub fn main() {
+/// let mut options = WriteOptions::new();
+/// options.set_nan_string(b"nan");
+/// let mut buf = [b'0'; MAX_F64_SIZE];
+/// assert_eq!(f64toa_slice_with_options(f64::NAN, &mut buf, &options), b"nan");
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WriteOptions<'a> {
+    /// Character to designate the exponent component of a float.
+    exponent_char: u8,
+    /// Backup character to designate the exponent component of a float.
+    exponent_backup_char: u8,
+    /// Representation of Not a Number.
+    nan_string: &'a [u8],
+    /// Representation of Infinity.
+    inf_string: &'a [u8],
+    /// Write decimal floats as C99 hexadecimal float literals.
+    hex_float: bool,
+    /// Decimal point and digit grouping.
+    locale: Locale<'a>,
+    /// Number of digits written for decimal floats.
+    precision: Precision,
+    /// Notation for decimal floats.
+    notation: Notation,
+    /// Decimal exponents written with positional notation, if not the default.
+    positional_exponents: Option<(i32, i32)>,
+    /// Trim a trailing `.0`, and write `-0.0` as `0`.
+    trim_floats: bool,
+    /// Write a leading `0` for decimal floats less than 1.
+    leading_zero: bool,
+    /// Write a `+` sign for positive exponents.
+    positive_exponent_sign: bool,
+    /// Minimum number of exponent digits.
+    min_exponent_digits: usize,
+    /// Algorithm for writing the shortest decimal floats.
+    algorithm: WriteAlgorithm,
+}
+
+impl<'a> WriteOptions<'a> {
+    /// Create options with the default values.
+    #[inline]
+    pub fn new() -> WriteOptions<'a> {
+        WriteOptions {
+            exponent_char: b'e',
+            exponent_backup_char: b'^',
+            nan_string: b"NaN",
+            inf_string: b"inf",
+            hex_float: false,
+            locale: Locale::new(),
+            precision: Precision::Shortest,
+            notation: Notation::Auto,
+            positional_exponents: None,
+            trim_floats: cfg!(feature = "trim_floats"),
+            leading_zero: true,
+            positive_exponent_sign: false,
+            min_exponent_digits: 1,
+            algorithm: WriteAlgorithm::default_algorithm(),
+        }
+    }
+
+    /// Get the default character designating the exponent component of a float.
+    #[inline]
+    pub fn exponent_char(&self) -> u8 {
+        self.exponent_char
+    }
+
+    /// Set the default character designating the exponent component of a float.
+    ///
+    /// This character is used for strings with a radix less than 15.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is not ASCII, or is a digit, `.`, `+`, or `-`.
+    #[inline]
+    pub fn set_exponent_char(&mut self, ch: u8) {
+        assert_exponent_char(ch);
+        self.exponent_char = ch;
+    }
+
+    /// Get the backup character designating the exponent component of a float.
+    #[cfg(feature = "radix")]
+    #[inline]
+    pub fn exponent_backup_char(&self) -> u8 {
+        self.exponent_backup_char
+    }
+
+    /// Set the backup character designating the exponent component of a float.
+    ///
+    /// This character is used for strings with a radix greater than or
+    /// equal to 15, and should not be an alphanumeric character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` is not ASCII, or is a digit, `.`, `+`, or `-`.
+    #[cfg(feature = "radix")]
+    #[inline]
+    pub fn set_exponent_backup_char(&mut self, ch: u8) {
+        assert_exponent_char(ch);
+        self.exponent_backup_char = ch;
+    }
+
+    /// Get the string representation of Not a Number.
+    #[inline]
+    pub fn nan_string(&self) -> &'a [u8] {
+        self.nan_string
+    }
+
+    /// Set the string representation of Not a Number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is empty, is not ASCII, or `bytes.len() >= MAX_F32_SIZE`.
+    #[inline]
+    pub fn set_nan_string(&mut self, bytes: &'a [u8]) {
+        assert_special_string(bytes);
+        self.nan_string = bytes;
+    }
+
+    /// Get the string representation of Infinity.
+    #[inline]
+    pub fn inf_string(&self) -> &'a [u8] {
+        self.inf_string
+    }
+
+    /// Set the string representation of Infinity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is empty, is not ASCII, or `bytes.len() >= MAX_F32_SIZE`.
+    #[inline]
+    pub fn set_inf_string(&mut self, bytes: &'a [u8]) {
+        assert_special_string(bytes);
+        self.inf_string = bytes;
+    }
+
+    /// Get if decimal floats are written as hexadecimal float literals.
+    #[inline]
+    pub fn hex_float(&self) -> bool {
+        self.hex_float
+    }
+
+    /// Set if decimal floats are written as hexadecimal float literals.
+    ///
+    /// Floats are written like `printf("%a")`, such as `0x1.8p+3`, and
+    /// `f32` is written as the equivalent `f64`.
+    #[inline]
+    pub fn set_hex_float(&mut self, hex_float: bool) {
+        self.hex_float = hex_float;
+    }
+
+    /// Get the decimal point and digit grouping.
+    #[inline]
+    pub fn locale(&self) -> Locale<'a> {
+        self.locale
+    }
+
+    /// Set the decimal point and digit grouping.
+    ///
+    /// Locales apply to integers and to decimal floats, while floats
+    /// in other radixes and hexadecimal float literals always use `.`,
+    /// without digit grouping.
+    ///
+    /// # Panics
+    ///
+    /// Digit grouping may write more than `MAX_*_SIZE` bytes, and
+    /// writing panics if the buffer is too small for the grouped number.
+    #[inline]
+    pub fn set_locale(&mut self, locale: Locale<'a>) {
+        self.locale = locale;
+    }
+
+    /// Get the number of digits written for decimal floats.
+    #[inline]
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    /// Set the number of digits written for decimal floats.
+    ///
+    /// Fixed precisions apply to decimal floats, and are ignored for
+    /// floats in other radixes and hexadecimal float literals. Floats
+    /// are never trimmed with fixed precisions, and scientific notation
+    /// always writes a signed exponent with at least 2 digits, like
+    /// `1.50e+03`.
+    ///
+    /// Fixed precisions may write more than `MAX_*_SIZE` bytes, and
+    /// the slice serializers panic if the buffer is too small for the
+    /// number. Use `max_*_size_with_options` to get the required size.
+    #[cfg(feature = "correct")]
+    #[inline]
+    pub fn set_precision(&mut self, precision: Precision) {
+        self.precision = precision;
+    }
+
+    /// Get the notation for decimal floats.
+    #[inline]
+    pub fn notation(&self) -> Notation {
+        self.notation
+    }
+
+    /// Set the notation for decimal floats.
+    ///
+    /// Positional notation may write more than `MAX_*_SIZE` bytes, and
+    /// the slice serializers panic if the buffer is too small for the
+    /// number. Use `max_*_size_with_options` to get the required size.
+    #[inline]
+    pub fn set_notation(&mut self, notation: Notation) {
+        self.notation = notation;
+    }
+
+    /// Get the range of decimal exponents written with positional notation.
+    ///
+    /// Returns `None` if the range is the default for the float
+    /// formatting algorithm, which for `ryu` is `[-5, 15]`.
+    #[inline]
+    pub fn positional_exponents(&self) -> Option<(i32, i32)> {
+        self.positional_exponents
+    }
+
+    /// Set the range of decimal exponents written with positional notation.
+    ///
+    /// With `Notation::Auto`, floats with a decimal exponent in the
+    /// inclusive range `[min, max]` are written in positional notation,
+    /// otherwise, floats are written in scientific notation. For example,
+    /// Python uses `[-4, 15]`, and JavaScript uses `[-6, 20]`. Wide
+    /// ranges may write more than `MAX_*_SIZE` bytes, like positional
+    /// notation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`.
+    #[inline]
+    pub fn set_positional_exponents(&mut self, min: i32, max: i32) {
+        assert!(min <= max, "Minimum positional exponent must be <= the maximum.");
+        self.positional_exponents = Some((min, max));
+    }
+
+    /// Get if a trailing `.0` is trimmed from floats.
+    #[inline]
+    pub fn trim_floats(&self) -> bool {
+        self.trim_floats
+    }
+
+    /// Set if a trailing `.0` is trimmed from floats.
+    ///
+    /// Trimmed floats without a fraction are written as an integer, and
+    /// `-0.0` is written as `0`. The default is `true` if and only if
+    /// the `trim_floats` feature is enabled.
+    #[inline]
+    pub fn set_trim_floats(&mut self, trim_floats: bool) {
+        self.trim_floats = trim_floats;
+    }
+
+    /// Get if a leading `0` is written for decimal floats less than 1.
+    #[inline]
+    pub fn leading_zero(&self) -> bool {
+        self.leading_zero
+    }
+
+    /// Set if a leading `0` is written for decimal floats less than 1.
+    ///
+    /// If `false`, `0.5` is written as `.5`. Zero is always written
+    /// with an integer digit.
+    #[inline]
+    pub fn set_leading_zero(&mut self, leading_zero: bool) {
+        self.leading_zero = leading_zero;
+    }
+
+    /// Get if a `+` sign is written for positive exponents of decimal floats.
+    #[inline]
+    pub fn positive_exponent_sign(&self) -> bool {
+        self.positive_exponent_sign
+    }
+
+    /// Set if a `+` sign is written for positive exponents of decimal floats.
+    #[inline]
+    pub fn set_positive_exponent_sign(&mut self, positive_exponent_sign: bool) {
+        self.positive_exponent_sign = positive_exponent_sign;
+    }
+
+    /// Get the minimum number of exponent digits for decimal floats.
+    #[inline]
+    pub fn min_exponent_digits(&self) -> usize {
+        self.min_exponent_digits
+    }
+
+    /// Set the minimum number of exponent digits for decimal floats.
+    ///
+    /// Exponents are padded with leading zeros, for example, `1e+05`
+    /// with 2 digits and a positive exponent sign.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is 0 or greater than 4.
+    #[inline]
+    pub fn set_min_exponent_digits(&mut self, digits: usize) {
+        assert!(digits >= 1 && digits <= 4, "Minimum exponent digits must be in the range `[1, 4]`.");
+        self.min_exponent_digits = digits;
+    }
+
+    /// Get the algorithm for writing the shortest decimal floats.
+    #[inline]
+    pub fn algorithm(&self) -> WriteAlgorithm {
+        self.algorithm
+    }
+
+    /// Set the algorithm for writing the shortest decimal floats.
+    ///
+    /// The algorithm applies to 32-bit and 64-bit floats with the shortest
+    /// precision, and to 16-bit floats without the `correct` feature.
+    /// Grisu2 and Grisu3 may write different digits than Ryu, and write
+    /// exponents differently, unless the notation or exponent layout
+    /// options are customized.
+    #[inline]
+    pub fn set_algorithm(&mut self, algorithm: WriteAlgorithm) {
+        self.algorithm = algorithm;
+    }
+
+    /// Get if the notation and exponent use the defaults of the float formatting algorithm.
+    #[inline]
+    pub(crate) fn is_default_layout(&self) -> bool {
+        self.notation == Notation::Auto
+            && self.positional_exponents.is_none()
+            && self.leading_zero
+            && !self.positive_exponent_sign
+            && self.min_exponent_digits == 1
+    }
+
+    /// Get the exponent character for a given radix.
+    #[inline]
+    pub(crate) fn exponent_notation_char(&self, radix: u32) -> u8 {
+        select_exponent_char(self.exponent_char, self.exponent_backup_char, radix)
+    }
+}
+
+impl WriteOptions<'static> {
+    /// Create options from the deprecated, global configuration.
+    #[inline]
+    #[allow(deprecated)]
+    pub(crate) fn global() -> WriteOptions<'static> {
+        unsafe {
+            WriteOptions {
+                exponent_char: EXPONENT_DEFAULT_CHAR,
+                #[cfg(feature = "radix")]
+                exponent_backup_char: EXPONENT_BACKUP_CHAR,
+                #[cfg(not(feature = "radix"))]
+                exponent_backup_char: b'^',
+                nan_string: get_nan_string(),
+                inf_string: get_inf_string(),
+                hex_float: false,
+                locale: Locale::new(),
+                precision: Precision::Shortest,
+                notation: Notation::Auto,
+                positional_exponents: None,
+                trim_floats: cfg!(feature = "trim_floats"),
+                leading_zero: true,
+                positive_exponent_sign: false,
+                min_exponent_digits: 1,
+                algorithm: WriteAlgorithm::default_algorithm(),
+            }
+        }
+    }
+}
+
+impl<'a> Default for WriteOptions<'a> {
+    #[inline]
+    fn default() -> WriteOptions<'a> {
+        WriteOptions::new()
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "radix")]
+    #[test]
+    fn exponent_notation_char_test() {
+        let options = ParseOptions::new();
+        assert_eq!(options.exponent_notation_char(2), b'e');
+        assert_eq!(options.exponent_notation_char(8), b'e');
+        assert_eq!(options.exponent_notation_char(10), b'e');
+        assert_eq!(options.exponent_notation_char(15), b'^');
+        assert_eq!(options.exponent_notation_char(16), b'^');
+        assert_eq!(options.exponent_notation_char(32), b'^');
+
+        let mut options = WriteOptions::new();
+        options.set_exponent_char(b'E');
+        options.set_exponent_backup_char(b'@');
+        assert_eq!(options.exponent_notation_char(10), b'E');
+        assert_eq!(options.exponent_notation_char(16), b'@');
+    }
+
+    #[test]
+    fn algorithm_test() {
+        let mut options = ParseOptions::new();
+        #[cfg(feature = "correct")]
+        assert_eq!(options.algorithm(), ParseAlgorithm::Correct);
+        #[cfg(not(feature = "correct"))]
+        assert_eq!(options.algorithm(), ParseAlgorithm::Incorrect);
+        options.set_algorithm(ParseAlgorithm::Incorrect);
+        assert_eq!(options.algorithm(), ParseAlgorithm::Incorrect);
+
+        let mut options = WriteOptions::new();
+        #[cfg(feature = "ryu")]
+        assert_eq!(options.algorithm(), WriteAlgorithm::Ryu);
+        #[cfg(all(feature = "grisu3", not(feature = "ryu")))]
+        assert_eq!(options.algorithm(), WriteAlgorithm::Grisu3);
+        #[cfg(not(any(feature = "grisu3", feature = "ryu")))]
+        assert_eq!(options.algorithm(), WriteAlgorithm::Grisu2);
+        options.set_algorithm(WriteAlgorithm::Grisu2);
+        assert_eq!(options.algorithm(), WriteAlgorithm::Grisu2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_nan_string_test() {
+        ParseOptions::new().set_nan_string(b"");
+    }
+
+    #[test]
+    fn digit_separator_test() {
+        let mut options = ParseOptions::new();
+        assert_eq!(options.separator(), None);
+        options.set_digit_separator(b'\'', DigitSeparator::INTERNAL);
+        assert_eq!(options.separator(), Some(b'\''));
+        options.set_digit_separator(b'\'', DigitSeparator::NONE);
+        assert_eq!(options.separator(), None);
+    }
+
+    #[test]
+    fn format_test() {
+        let mut options = ParseOptions::new();
+        options.set_format(NumberFormat::Toml);
+        assert_eq!(options.grammar(), NumberFormat::Toml.grammar());
+        assert_eq!(options.separator(), Some(b'_'));
+        options.set_format(NumberFormat::Json);
+        assert_eq!(options.separator(), None);
+        options.set_grammar(NumberGrammar::STANDARD);
+        assert_eq!(options.grammar(), NumberGrammar::STANDARD);
+    }
+
+    #[test]
+    #[should_panic]
+    fn alphanumeric_digit_separator_test() {
+        ParseOptions::new().set_digit_separator(b'a', DigitSeparator::INTERNAL);
+    }
+
+    #[test]
+    #[should_panic]
+    fn long_inf_string_test() {
+        WriteOptions::new().set_inf_string(&[b'i'; MAX_F32_SIZE]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_ascii_nan_string_test() {
+        WriteOptions::new().set_nan_string(b"\xffNaN");
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_ascii_exponent_char_test() {
+        WriteOptions::new().set_exponent_char(0xC3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn digit_exponent_char_test() {
+        ParseOptions::new().set_exponent_char(b'1');
+    }
+
+    #[test]
+    #[should_panic]
+    fn sign_exponent_char_test() {
+        WriteOptions::new().set_exponent_char(b'-');
+    }
+
+    #[test]
+    #[should_panic]
+    fn point_exponent_char_test() {
+        ParseOptions::new().set_exponent_char(b'.');
+    }
+
+    #[cfg(feature = "radix")]
+    #[test]
+    #[should_panic]
+    fn digit_exponent_backup_char_test() {
+        WriteOptions::new().set_exponent_backup_char(b'0');
+    }
+}
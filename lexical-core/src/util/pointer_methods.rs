@@ -8,9 +8,6 @@
 pub(crate) trait PointerMethods {
     // Add to the pointer (use padd to avoid conflict with ptr::add).
     unsafe fn padd(self, count: usize) -> Self;
-
-    // Subtract from the pointer (use psub to avoid conflict with ptr::sub).
-    unsafe fn psub(self, count: usize) -> Self;
 }
 
 impl<T> PointerMethods for *const T {
@@ -22,15 +19,6 @@ impl<T> PointerMethods for *const T {
         #[cfg(not(has_pointer_methods))]
         return self.offset(count as isize);
     }
-
-    #[inline(always)]
-    unsafe fn psub(self, count: usize) -> Self {
-        #[cfg(has_pointer_methods)]
-        return self.sub(count);
-
-        #[cfg(not(has_pointer_methods))]
-        return self.offset((count as isize).wrapping_neg());
-    }
 }
 
 impl<T> PointerMethods for *mut T {
@@ -42,13 +30,4 @@ impl<T> PointerMethods for *mut T {
         #[cfg(not(has_pointer_methods))]
         return self.offset(count as isize);
     }
-
-    #[inline(always)]
-    unsafe fn psub(self, count: usize) -> Self {
-        #[cfg(has_pointer_methods)]
-        return self.sub(count);
-
-        #[cfg(not(has_pointer_methods))]
-        return self.offset((count as isize).wrapping_neg());
-    }
 }
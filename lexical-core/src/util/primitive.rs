@@ -112,79 +112,7 @@ macro_rules! def_try_primitive {
         pub trait TryPrimitive:
             AsCast +
             $(TryCast<$t> +)*
-        {
-            #[inline]
-            fn try_u8(self) -> Option<u8> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_u16(self) -> Option<u16> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_u32(self) -> Option<u32> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_u64(self) -> Option<u64> {
-                self.try_cast()
-            }
-
-            #[cfg(has_i128)]
-            #[inline]
-            fn try_u128(self) -> Option<u128> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_usize(self) -> Option<usize> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_i8(self) -> Option<i8> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_i16(self) -> Option<i16> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_i32(self) -> Option<i32> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_i64(self) -> Option<i64> {
-                self.try_cast()
-            }
-
-            #[cfg(has_i128)]
-            #[inline]
-            fn try_i128(self) -> Option<i128> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_isize(self) -> Option<isize> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_f32(self) -> Option<f32> {
-                self.try_cast()
-            }
-
-            #[inline]
-            fn try_f64(self) -> Option<f64> {
-                self.try_cast()
-            }
-        }
+        {}
     );
 }
 
@@ -1,6 +1,6 @@
 //! C-compatible result type.
 
-use super::error::{self, Error};
+use super::error::{self, Error, ErrorCode};
 
 /// C-compatible result type from parsing strings-to-numbers for FFI.
 #[repr(C)]
@@ -17,7 +17,7 @@ pub struct Result<T> {
 pub(crate) fn success<T>(value: T)
     -> Result<T>
 {
-    Result { value: value, error: error::success() }
+    Result { value, error: error::success() }
 }
 
 /// Helper function to create an overflow error.
@@ -25,7 +25,7 @@ pub(crate) fn success<T>(value: T)
 pub(crate) fn overflow_error<T>(value: T)
     -> Result<T>
 {
-    Result { value: value, error: error::overflow_error() }
+    Result { value, error: error::overflow_error() }
 }
 
 /// Helper function to create an invalid digit error.
@@ -33,14 +33,22 @@ pub(crate) fn overflow_error<T>(value: T)
 pub(crate) fn invalid_digit_error<T>(value: T, index: usize)
     -> Result<T>
 {
-    Result { value: value, error: error::invalid_digit_error(index) }
+    Result { value, error: error::invalid_digit_error(index) }
 }
 
 #[inline]
 pub(crate) fn empty_error<T>(value: T)
     -> Result<T>
 {
-    Result { value: value, error: error::empty_error() }
+    Result { value, error: error::empty_error() }
+}
+
+/// Helper function to create an error from a code and index.
+#[inline]
+pub(crate) fn new_error<T>(value: T, code: ErrorCode, index: usize)
+    -> Result<T>
+{
+    Result { value, error: error::new_error(code, index) }
 }
 
 // FFI
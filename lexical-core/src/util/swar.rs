@@ -0,0 +1,177 @@
+//! Parse multiple decimal digits at once.
+//!
+//! Uses SWAR (SIMD within a register) to validate and convert 8 digits
+//! in a 64-bit word, and on x86_64, SSSE3 to convert 16 digits, if the
+//! CPU supports it. 16 digits are enough for most 64-bit integers and
+//! float mantissas, so wider vectors would rarely be filled.
+
+use lib::ptr;
+
+// SWAR
+
+/// Determine if all 8 bytes in a little-endian word are decimal digits.
+#[inline]
+fn is_8digits(word: u64) -> bool {
+    // Adding 6 to a byte in `[0x30, 0x39]` keeps its high nibble as 3,
+    // while it becomes 4 for any byte in `[0x3A, 0x3F]`.
+    let a = word.wrapping_add(0x0606060606060606);
+    ((word & 0xF0F0F0F0F0F0F0F0) | ((a & 0xF0F0F0F0F0F0F0F0) >> 4)) == 0x3333333333333333
+}
+
+/// Convert 8 decimal digits in a little-endian word to their value.
+#[inline]
+fn parse_8digits_word(word: u64) -> u64 {
+    const MASK: u64 = 0x000000FF000000FF;
+    const MUL1: u64 = 0x000F424000000064;   // 100 + (1000000 << 32)
+    const MUL2: u64 = 0x0000271000000001;   // 1 + (10000 << 32)
+
+    // Combine adjacent digits into 2-digit values, then into a 4-digit
+    // value in the low 32 bits and a 4-digit value in the high 32 bits,
+    // and then into the 8-digit value.
+    let word = word.wrapping_sub(0x3030303030303030);
+    let word = word.wrapping_mul(10).wrapping_add(word >> 8);
+    let lo = (word & MASK).wrapping_mul(MUL1);
+    let hi = ((word >> 16) & MASK).wrapping_mul(MUL2);
+    lo.wrapping_add(hi) >> 32
+}
+
+/// Parse 8 decimal digits from the start of the buffer.
+#[inline]
+fn parse_8digits(bytes: &[u8]) -> Option<u64> {
+    if bytes.len() < 8 {
+        return None;
+    }
+    // We know this is safe, since we checked the buffer has 8 bytes.
+    let word = u64::from_le(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const u64) });
+    match is_8digits(word) {
+        true  => Some(parse_8digits_word(word)),
+        false => None,
+    }
+}
+
+// SIMD
+
+/// Parse 16 decimal digits from the start of the buffer with SSSE3.
+#[cfg(all(has_simd, feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn parse_16digits_ssse3(bytes: &[u8]) -> Option<u64> {
+    use lib::arch::x86_64::*;
+
+    debug_assert!(bytes.len() >= 16);
+    let chunk = _mm_loadu_si128(bytes.as_ptr() as *const __m128i);
+    let digits = _mm_sub_epi8(chunk, _mm_set1_epi8(b'0' as i8));
+
+    // Every byte must be in `[0, 9]` after subtracting '0', as unsigned.
+    let nine = _mm_set1_epi8(9);
+    let valid = _mm_cmpeq_epi8(_mm_max_epu8(digits, nine), nine);
+    if _mm_movemask_epi8(valid) != 0xFFFF {
+        return None;
+    }
+
+    // Combine adjacent digits into 2-digit, then into 4-digit values.
+    let pairs = _mm_maddubs_epi16(digits, _mm_set1_epi16(0x010A));
+    let quads = _mm_madd_epi16(pairs, _mm_set1_epi32(0x00010064));
+    let mut values = [0u32; 4];
+    _mm_storeu_si128(values.as_mut_ptr() as *mut __m128i, quads);
+    let hi = values[0] as u64 * 10000 + values[1] as u64;
+    let lo = values[2] as u64 * 10000 + values[3] as u64;
+    Some(hi * 100000000 + lo)
+}
+
+/// Parse 16 decimal digits from the start of the buffer.
+#[cfg(all(has_simd, feature = "std", target_arch = "x86_64"))]
+#[inline]
+fn parse_16digits(bytes: &[u8]) -> Option<u64> {
+    if bytes.len() >= 16 && is_x86_feature_detected!("ssse3") {
+        // We know this is safe, since the CPU supports SSSE3.
+        unsafe { parse_16digits_ssse3(bytes) }
+    } else {
+        None
+    }
+}
+
+/// Parse 16 decimal digits from the start of the buffer.
+#[cfg(not(all(has_simd, feature = "std", target_arch = "x86_64")))]
+#[inline(always)]
+fn parse_16digits(_: &[u8]) -> Option<u64> {
+    None
+}
+
+// API
+
+/// Parse multiple decimal digits from the start of the buffer.
+///
+/// Parses 16 digits if `max_digits` is at least 16 and SIMD is
+/// available, otherwise 8 digits if `max_digits` is at least 8.
+/// Returns the value and the number of digits, or None if the buffer
+/// does not start with enough digits.
+#[inline]
+pub(crate) fn parse_digits(bytes: &[u8], max_digits: usize) -> Option<(u64, usize)> {
+    if max_digits >= 16 {
+        if let Some(value) = parse_16digits(bytes) {
+            return Some((value, 16));
+        }
+    }
+    if max_digits >= 8 {
+        parse_8digits(bytes).map(|value| (value, 8))
+    } else {
+        None
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(bytes: &[u8; 8]) -> u64 {
+        u64::from_le(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const u64) })
+    }
+
+    #[test]
+    fn is_8digits_test() {
+        assert!(is_8digits(word(b"01234567")));
+        assert!(is_8digits(word(b"99999999")));
+        assert!(!is_8digits(word(b"0123456:")));
+        assert!(!is_8digits(word(b"/1234567")));
+        assert!(!is_8digits(word(b"0123.567")));
+        assert!(!is_8digits(word(b"01234\xFF67")));
+        assert!(!is_8digits(word(b"0123456\xF9")));
+    }
+
+    #[test]
+    fn parse_8digits_test() {
+        assert_eq!(parse_8digits(b"01234567"), Some(1234567));
+        assert_eq!(parse_8digits(b"99999999"), Some(99999999));
+        assert_eq!(parse_8digits(b"123456789"), Some(12345678));
+        assert_eq!(parse_8digits(b"1234567"), None);
+        assert_eq!(parse_8digits(b"1234567a"), None);
+    }
+
+    #[test]
+    fn parse_digits_test() {
+        assert_eq!(parse_digits(b"1234567890123456", 8), Some((12345678, 8)));
+        assert_eq!(parse_digits(b"1234567890123456", 7), None);
+        assert_eq!(parse_digits(b"123456789012345a", 16), Some((12345678, 8)));
+        assert_eq!(parse_digits(b"1234567", 16), None);
+
+        let (value, count) = parse_digits(b"98765432109876543", 16).unwrap();
+        assert_eq!(value, if count == 16 { 9876543210987654 } else { 98765432 });
+    }
+
+    #[cfg(all(has_simd, feature = "std", target_arch = "x86_64"))]
+    #[test]
+    fn parse_16digits_test() {
+        if !is_x86_feature_detected!("ssse3") {
+            return;
+        }
+        assert_eq!(parse_16digits(b"0123456789012345"), Some(123456789012345));
+        assert_eq!(parse_16digits(b"9999999999999999"), Some(9999999999999999));
+        assert_eq!(parse_16digits(b"123456789012345"), None);
+        assert_eq!(parse_16digits(b"123456789012345/"), None);
+        assert_eq!(parse_16digits(b":234567890123456"), None);
+        assert_eq!(parse_16digits(b"1234567\x8090123456"), None);
+    }
+}
@@ -47,10 +47,10 @@ const CHAR_TO_DIGIT: [u8; 256] = [
 
 /// Get digit from character.
 #[inline]
-pub(crate) fn char_to_digit(c: u8) -> u8 {
+pub(crate) fn char_to_digit<C: CodeUnit>(c: C) -> u8 {
     // This is always safe, since c must be [0, 255],and CHAR_TO_DIGIT is
     // 256 items long.
-    unsafe {*CHAR_TO_DIGIT.get_unchecked(c.as_usize())}
+    unsafe {*CHAR_TO_DIGIT.get_unchecked(c.to_byte().as_usize())}
 }
 
 // Conditionally compile the precompiled radix**2 tables.
@@ -16,7 +16,7 @@ use super::primitive::*;
 /// Wrap a float to act like an integer.
 ///
 /// Required for the lossy atof algorithm.
-#[derive(Clone, Copy, Debug, PartialOrd)]
+#[derive(Clone, Copy, Debug)]
 pub(crate) struct WrappedFloat<T: Float>
 {
     /// Internal data.
@@ -52,6 +52,13 @@ impl<T: Float> PartialEq for WrappedFloat<T> {
     }
 }
 
+impl<T: Float> PartialOrd for WrappedFloat<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.data.partial_cmp(&other.data)
+    }
+}
+
 impl<T: Float> AsPrimitive for WrappedFloat<T> {
     #[inline]
     fn as_u8(self) -> u8 {
@@ -127,75 +134,6 @@ impl<T: Float> AsPrimitive for WrappedFloat<T> {
 // IMPL TRY PRIMITIVE
 
 impl<T: Float> TryPrimitive for WrappedFloat<T> {
-    #[inline]
-    fn try_u8(self) -> Option<u8> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_u16(self) -> Option<u16> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_u32(self) -> Option<u32> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_u64(self) -> Option<u64> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_u128(self) -> Option<u128> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_usize(self) -> Option<usize> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_i8(self) -> Option<i8> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_i16(self) -> Option<i16> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_i32(self) -> Option<i32> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_i64(self) -> Option<i64> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_i128(self) -> Option<i128> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_isize(self) -> Option<isize> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_f32(self) -> Option<f32> {
-        try_cast(self.data)
-    }
-
-    #[inline]
-    fn try_f64(self) -> Option<f64> {
-        try_cast(self.data)
-    }
 }
 
 // IMPL AS CAST
@@ -300,7 +238,7 @@ impl<T: Float> Ord for WrappedFloat<T> {
         // PartialOrd are fails to provide an Ordering if
         // either are NaN, so we just need to provide consistent
         // ordering if either is NaN.
-        if let Some(ordering) = self.partial_cmp(&other) {
+        if let Some(ordering) = self.partial_cmp(other) {
             ordering
         } else if !self.data.is_nan() {
             cmp::Ordering::Less
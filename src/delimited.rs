@@ -0,0 +1,334 @@
+//! Batch parsers for delimited buffers of numbers.
+//!
+//! Rows are separated by `\n`, optionally preceded by `\r`, and fields
+//! within a row are separated by the delimiter. Every field is parsed
+//! as a complete number, without quoting or trimming whitespace, so
+//! locales with group separators equal to the delimiter never merge
+//! fields. Empty lines are skipped.
+
+use lexical_core::ParseOptions;
+use lib::{fmt, Vec};
+#[cfg(feature = "std")]
+use lib::error::Error as StdError;
+use error::*;
+use traits::FromLexical;
+
+// ERROR
+
+/// Error while parsing a delimited buffer, with the position of the invalid field.
+///
+/// The row is the 0-based index of the line, including skipped empty
+/// lines, and the column is the 0-based index of the field within the
+/// row. The index in the error is relative to the start of the field.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DelimitedError {
+    row: usize,
+    column: usize,
+    error: Error,
+}
+
+impl DelimitedError {
+    /// Get the 0-based index of the line containing the invalid field.
+    #[inline]
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Get the 0-based index of the invalid field within the row.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get the error for the invalid field.
+    #[inline]
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+impl fmt::Display for DelimitedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (row {}, column {})", self.error, self.row, self.column)
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for DelimitedError {
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        self.error.description()
+    }
+
+    // Remove when we drop support below 1.27.
+    #[allow(unknown_lints, bare_trait_objects)]
+    fn cause(&self) -> Option<&StdError> {
+        Some(&self.error)
+    }
+}
+
+// PARSE
+
+/// Parse every field, and call the callback with the value and its position.
+///
+/// Returns the number of rows, including a final row without a newline.
+#[inline]
+fn parse_fields<N, Cb>(bytes: &[u8], delimiter: u8, options: &ParseOptions, mut cb: Cb)
+    -> Result<usize, DelimitedError>
+    where N: FromLexical,
+          Cb: FnMut(N, usize, usize) -> Result<(), DelimitedError>
+{
+    let mut row = 0;
+    let mut column = 0;
+    let mut start = 0;
+    // Whether a delimiter was the last byte, so an empty field remains.
+    let mut pending = false;
+    while start < bytes.len() || pending {
+        let rest = &bytes[start..];
+        let end = rest.iter()
+            .position(|&c| c == b'\n' || c == delimiter)
+            .unwrap_or(rest.len());
+        let newline = end == rest.len() || rest[end] == b'\n';
+        let mut field = &rest[..end];
+        if newline && field.last() == Some(&b'\r') {
+            field = &field[..field.len() - 1];
+        }
+
+        // Skip empty lines, but not empty fields.
+        if !(newline && column == 0 && field.is_empty()) {
+            let value = N::try_from_lexical_with_options(field, options)
+                .map_err(|error| DelimitedError { row, column, error })?;
+            cb(value, row, column)?;
+        }
+
+        start += end + 1;
+        pending = !newline && start == bytes.len();
+        if newline {
+            row += 1;
+            column = 0;
+        } else {
+            column += 1;
+        }
+    }
+
+    Ok(row)
+}
+
+/// Parse a delimited buffer of numbers into a vector.
+///
+/// * `bytes`     - Byte slice of delimited numbers.
+/// * `delimiter` - Byte separating fields within a row.
+/// * `options`   - Options to customize number parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let options = lexical::ParseOptions::new();
+/// let values = lexical::parse_delimited::<f64, _>("0.5,1\n2,3.5\n", b',', &options);
+/// assert_eq!(values, Ok(vec![0.5, 1.0, 2.0, 3.5]));
+///
+/// let error = lexical::parse_delimited::<i32, _>("1,2\n3,4a\n", b',', &options).unwrap_err();
+/// assert_eq!((error.row(), error.column()), (1, 1));
+/// assert_eq!(error.error().kind(), &lexical::ErrorKind::InvalidDigit(1));
+/// # }
+/// ```
+#[inline]
+pub fn parse_delimited<N: FromLexical, Bytes: AsRef<[u8]>>(bytes: Bytes, delimiter: u8, options: &ParseOptions)
+    -> Result<Vec<N>, DelimitedError>
+{
+    let mut values = Vec::new();
+    parse_fields(bytes.as_ref(), delimiter, options, |value, _, _| {
+        values.push(value);
+        Ok(())
+    })?;
+    Ok(values)
+}
+
+/// Parse a delimited buffer of numbers into a slice.
+///
+/// Returns the number of values written to the start of the slice.
+/// If the buffer has more values than the slice, returns an error
+/// with `ErrorKind::TooManyValues` at the first extra value.
+///
+/// * `bytes`     - Byte slice of delimited numbers.
+/// * `delimiter` - Byte separating fields within a row.
+/// * `options`   - Options to customize number parsing.
+/// * `values`    - Slice to write the parsed values to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let options = lexical::ParseOptions::new();
+/// let mut values = [0u8; 4];
+/// assert_eq!(lexical::parse_delimited_into("1;2;3", b';', &options, &mut values), Ok(3));
+/// assert_eq!(values, [1, 2, 3, 0]);
+/// # }
+/// ```
+#[inline]
+pub fn parse_delimited_into<N: FromLexical, Bytes: AsRef<[u8]>>(bytes: Bytes, delimiter: u8, options: &ParseOptions, values: &mut [N])
+    -> Result<usize, DelimitedError>
+{
+    let mut count = 0;
+    parse_fields(bytes.as_ref(), delimiter, options, |value, row, column| {
+        match values.get_mut(count) {
+            Some(v) => *v = value,
+            None    => return Err(DelimitedError { row, column, error: too_many_values() }),
+        }
+        count += 1;
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+// PARALLEL
+
+/// Minimum number of bytes parsed by each thread.
+#[cfg(feature = "rayon")]
+const MIN_CHUNK_SIZE: usize = 1 << 16;
+
+/// Split a buffer into chunks ending with a newline, for each thread.
+#[cfg(feature = "rayon")]
+fn split_rows(bytes: &[u8]) -> Vec<&[u8]> {
+    let threads = ::rayon::current_num_threads();
+    let size = ::lib::cmp::max(MIN_CHUNK_SIZE, bytes.len() / threads + 1);
+    let mut chunks = Vec::with_capacity(threads);
+    let mut rest = bytes;
+    while rest.len() > size {
+        match rest[size..].iter().position(|&c| c == b'\n') {
+            Some(index) => {
+                let (chunk, next) = rest.split_at(size + index + 1);
+                chunks.push(chunk);
+                rest = next;
+            },
+            None => break,
+        }
+    }
+    chunks.push(rest);
+    chunks
+}
+
+/// Parse a delimited buffer of numbers into a vector, using multiple threads.
+///
+/// Large buffers are split at row boundaries, and each thread parses
+/// whole rows, so the values and errors are the same as for
+/// [`parse_delimited`]. Requires the `rayon` feature.
+///
+/// * `bytes`     - Byte slice of delimited numbers.
+/// * `delimiter` - Byte separating fields within a row.
+/// * `options`   - Options to customize number parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let options = lexical::ParseOptions::new();
+/// let values = lexical::par_parse_delimited::<f32, _>("0.5,1\n2,3.5\n", b',', &options);
+/// assert_eq!(values, Ok(vec![0.5, 1.0, 2.0, 3.5]));
+/// # }
+/// ```
+///
+/// [`parse_delimited`]: fn.parse_delimited.html
+#[cfg(feature = "rayon")]
+pub fn par_parse_delimited<N: FromLexical + Send, Bytes: AsRef<[u8]>>(bytes: Bytes, delimiter: u8, options: &ParseOptions)
+    -> Result<Vec<N>, DelimitedError>
+{
+    use rayon::prelude::*;
+
+    let chunks = split_rows(bytes.as_ref());
+    let results: Vec<_> = chunks.par_iter()
+        .map(|chunk| {
+            let mut values = Vec::new();
+            let rows = parse_fields(chunk, delimiter, options, |value, _, _| {
+                values.push(value);
+                Ok(())
+            });
+            rows.map(|rows| (values, rows))
+        })
+        .collect();
+
+    // Report the first error, with the row from the start of the buffer.
+    let mut values = Vec::new();
+    let mut row = 0;
+    for result in results {
+        match result {
+            Ok((chunk, rows)) => {
+                values.extend(chunk);
+                row += rows;
+            },
+            Err(error) => return Err(DelimitedError { row: row + error.row, ..error }),
+        }
+    }
+    Ok(values)
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use lexical_core::Locale;
+    use super::*;
+
+    #[test]
+    fn parse_delimited_test() {
+        let options = ParseOptions::new();
+        assert_eq!(parse_delimited::<u32, _>("", b',', &options), Ok(vec![]));
+        assert_eq!(parse_delimited::<u32, _>("1", b',', &options), Ok(vec![1]));
+        assert_eq!(parse_delimited::<u32, _>("1,2\r\n3,4\r\n", b',', &options), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(parse_delimited::<u32, _>("1\t2\n\n3", b'\t', &options), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_delimited::<u32, _>("1\n2\n3\n", b'\n', &options), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_delimited::<f64, _>("1e5;-0.5", b';', &options), Ok(vec![1e5, -0.5]));
+
+        // Errors.
+        let error = parse_delimited::<u32, _>("1,2\n3,,4", b',', &options).unwrap_err();
+        assert_eq!((error.row(), error.column(), error.error()), (1, 1, &empty()));
+        let error = parse_delimited::<u32, _>("1,2,", b',', &options).unwrap_err();
+        assert_eq!((error.row(), error.column(), error.error()), (0, 2, &empty()));
+        let error = parse_delimited::<u8, _>("1\n\n2,256", b',', &options).unwrap_err();
+        assert_eq!((error.row(), error.column(), error.error()), (2, 1, &overflow()));
+        let error = parse_delimited::<f32, _>("1.5, 2.5", b',', &options).unwrap_err();
+        assert_eq!((error.row(), error.column(), error.error()), (0, 1, &invalid_digit(0)));
+        assert_eq!(format!("{}", error), "lexical error: invalid digit found at 0. (row 0, column 1)");
+
+        // Group separators never merge fields.
+        let mut options = ParseOptions::new();
+        options.set_locale(Locale::EN_US);
+        assert_eq!(parse_delimited::<u32, _>("1,234", b',', &options), Ok(vec![1, 234]));
+        assert_eq!(parse_delimited::<u32, _>("1,234;5", b';', &options), Ok(vec![1234, 5]));
+    }
+
+    #[test]
+    fn parse_delimited_into_test() {
+        let options = ParseOptions::new();
+        let mut values = [0i16; 3];
+        assert_eq!(parse_delimited_into("-1,2\n3", b',', &options, &mut values), Ok(3));
+        assert_eq!(values, [-1, 2, 3]);
+        assert_eq!(parse_delimited_into("4", b',', &options, &mut values), Ok(1));
+        assert_eq!(values, [4, 2, 3]);
+
+        let error = parse_delimited_into("1,2\n3,4", b',', &options, &mut values).unwrap_err();
+        assert_eq!((error.row(), error.column(), error.error()), (1, 1, &too_many_values()));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_parse_delimited_test() {
+        let options = ParseOptions::new();
+        let mut bytes = Vec::new();
+        for i in 0..100_000u32 {
+            bytes.extend_from_slice(format!("{},{}.5\n", i, i).as_bytes());
+        }
+        assert!(split_rows(&bytes).len() > 1 || ::rayon::current_num_threads() == 1);
+        let expected = parse_delimited::<f64, _>(&bytes, b',', &options);
+        assert_eq!(par_parse_delimited::<f64, _>(&bytes, b',', &options), expected);
+
+        bytes.extend_from_slice(b"1,a\n");
+        let error = par_parse_delimited::<f64, _>(&bytes, b',', &options).unwrap_err();
+        assert_eq!((error.row(), error.column(), error.error()), (100_000, 1, &invalid_digit(0)));
+    }
+}
@@ -1,5 +1,6 @@
 //! Error definitions for lexical.
 
+use lexical_core::ErrorCode;
 use lib::fmt;
 use lib::error::Error as StdError;
 
@@ -14,6 +15,24 @@ pub enum ErrorKind {
     InvalidDigit(usize),
     /// Empty byte array found.
     Empty,
+    /// Float has no integer or fraction digits.
+    EmptyMantissa(usize),
+    /// Float has no integer digits, and the grammar requires them.
+    EmptyInteger(usize),
+    /// Float has no fraction digits, and the grammar requires them.
+    EmptyFraction(usize),
+    /// Float has no exponent digits, and the grammar requires them.
+    EmptyExponent(usize),
+    /// Number has a leading `+` sign, and the grammar forbids it.
+    InvalidPositiveMantissaSign(usize),
+    /// Exponent has a `+` sign, and the grammar forbids it.
+    InvalidPositiveExponentSign(usize),
+    /// Exponent has no sign, and the grammar requires one.
+    MissingExponentSign(usize),
+    /// Number has leading zeros, and the grammar forbids them.
+    InvalidLeadingZeros(usize),
+    /// Delimited buffer has more values than the output slice.
+    TooManyValues,
 
     // We may add additional variants later, so ensure that client matching
     // does not depend on exhaustive matching.
@@ -43,10 +62,19 @@ impl Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.kind() {
-            &ErrorKind::Overflow        => write!(f, "lexical error: integer overflow occurred during integer parsing."),
-            &ErrorKind::InvalidDigit(u) => write!(f, "lexical error: invalid digit found at {}.", u),
-            &ErrorKind::Empty           => write!(f, "lexical error: empty input data."),
-            _                           => unreachable!(),
+            &ErrorKind::Overflow                       => write!(f, "lexical error: integer overflow occurred during integer parsing."),
+            &ErrorKind::InvalidDigit(u)                => write!(f, "lexical error: invalid digit found at {}.", u),
+            &ErrorKind::Empty                          => write!(f, "lexical error: empty input data."),
+            &ErrorKind::EmptyMantissa(u)               => write!(f, "lexical error: float has no mantissa digits at {}.", u),
+            &ErrorKind::EmptyInteger(u)                => write!(f, "lexical error: float has no integer digits at {}.", u),
+            &ErrorKind::EmptyFraction(u)               => write!(f, "lexical error: float has no fraction digits at {}.", u),
+            &ErrorKind::EmptyExponent(u)               => write!(f, "lexical error: float has no exponent digits at {}.", u),
+            &ErrorKind::InvalidPositiveMantissaSign(u) => write!(f, "lexical error: invalid positive sign found at {}.", u),
+            &ErrorKind::InvalidPositiveExponentSign(u) => write!(f, "lexical error: invalid positive exponent sign found at {}.", u),
+            &ErrorKind::MissingExponentSign(u)         => write!(f, "lexical error: missing exponent sign at {}.", u),
+            &ErrorKind::InvalidLeadingZeros(u)         => write!(f, "lexical error: invalid leading zeros found at {}.", u),
+            &ErrorKind::TooManyValues                  => write!(f, "lexical error: too many values for the output slice."),
+            _                                          => unreachable!(),
         }
     }
 }
@@ -54,10 +82,19 @@ impl fmt::Display for Error {
 impl StdError for Error {
     fn description(&self) -> &str {
         match self.kind() {
-            &ErrorKind::Overflow        => "lexical error: integer overflow occurred during integer parsing.",
-            &ErrorKind::InvalidDigit(_) => "lexical error: invalid digit found in string.",
-            &ErrorKind::Empty           => "lexical error: empty input data.",
-            _                           => unreachable!(),
+            &ErrorKind::Overflow                       => "lexical error: integer overflow occurred during integer parsing.",
+            &ErrorKind::InvalidDigit(_)                => "lexical error: invalid digit found in string.",
+            &ErrorKind::Empty                          => "lexical error: empty input data.",
+            &ErrorKind::EmptyMantissa(_)               => "lexical error: float has no mantissa digits.",
+            &ErrorKind::EmptyInteger(_)                => "lexical error: float has no integer digits.",
+            &ErrorKind::EmptyFraction(_)               => "lexical error: float has no fraction digits.",
+            &ErrorKind::EmptyExponent(_)               => "lexical error: float has no exponent digits.",
+            &ErrorKind::InvalidPositiveMantissaSign(_) => "lexical error: invalid positive sign found in string.",
+            &ErrorKind::InvalidPositiveExponentSign(_) => "lexical error: invalid positive exponent sign found in string.",
+            &ErrorKind::MissingExponentSign(_)         => "lexical error: missing exponent sign in string.",
+            &ErrorKind::InvalidLeadingZeros(_)         => "lexical error: invalid leading zeros found in string.",
+            &ErrorKind::TooManyValues                  => "lexical error: too many values for the output slice.",
+            _                                          => unreachable!(),
         }
     }
 
@@ -89,3 +126,25 @@ pub(crate) fn invalid_digit(position: usize) -> Error {
 pub(crate) fn empty() -> Error {
     ErrorKind::Empty.into()
 }
+
+/// Return an error for too many values for the output slice.
+#[inline]
+pub(crate) fn too_many_values() -> Error {
+    ErrorKind::TooManyValues.into()
+}
+
+/// Return an error for a number which violates the number grammar.
+#[inline]
+pub(crate) fn grammar(code: ErrorCode, position: usize) -> Error {
+    match code {
+        ErrorCode::EmptyMantissa                => ErrorKind::EmptyMantissa(position),
+        ErrorCode::EmptyInteger                 => ErrorKind::EmptyInteger(position),
+        ErrorCode::EmptyFraction                => ErrorKind::EmptyFraction(position),
+        ErrorCode::EmptyExponent                => ErrorKind::EmptyExponent(position),
+        ErrorCode::InvalidPositiveMantissaSign  => ErrorKind::InvalidPositiveMantissaSign(position),
+        ErrorCode::InvalidPositiveExponentSign  => ErrorKind::InvalidPositiveExponentSign(position),
+        ErrorCode::MissingExponentSign          => ErrorKind::MissingExponentSign(position),
+        ErrorCode::InvalidLeadingZeros          => ErrorKind::InvalidLeadingZeros(position),
+        _                                       => unreachable!(),
+    }.into()
+}
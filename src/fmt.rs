@@ -0,0 +1,158 @@
+//! Wrappers to format numbers with lexical in `core::fmt`.
+//!
+//! The wrappers serialize numbers to a stack buffer sized by the
+//! `MAX_*_SIZE` constants, and may be used with `format!`, `write!`,
+//! and the logging macros without allocating, unless the options
+//! require a larger buffer, like for digit grouping, fixed precisions,
+//! or positional notation.
+//!
+//! Width, fill, alignment, the `+` flag, and zero-padding are supported,
+//! like for the integer `Display` implementations, except NaN and
+//! infinity are padded with spaces instead of zeros. The precision is
+//! ignored: use [`WriteOptions::set_precision`] with [`WithOptions`].
+//!
+//! ```rust
+//! # extern crate lexical;
+//! # pub fn main() {
+//! assert_eq!(format!("{}", lexical::Display(1.5)), "1.5");
+//! assert_eq!(format!("[{:>6}]", lexical::Display(-15)), "[   -15]");
+//! assert_eq!(format!("{:+06}", lexical::Display(15)), "+00015");
+//!
+//! let mut options = lexical::WriteOptions::new();
+//! options.set_exponent_char(b'E');
+//! let value = lexical::fmt::WithOptions(1.5e-300, &options);
+//! assert_eq!(format!("{}", value), "1.5E-300");
+//! # }
+//! ```
+//!
+//! [`WriteOptions::set_precision`]: ../struct.WriteOptions.html#method.set_precision
+//! [`WithOptions`]: struct.WithOptions.html
+
+use lexical_core::WriteOptions;
+use lib::{fmt, str};
+use traits::ToLexical;
+
+/// Write serialized bytes to the formatter, with its padding.
+///
+/// Special floats, NaN and infinity, have no digits, so they are never
+/// padded with zeros: zero-padding pads them with spaces instead.
+#[inline]
+fn pad(f: &mut fmt::Formatter, bytes: &[u8], options: &WriteOptions) -> fmt::Result {
+    let string = str::from_utf8(bytes).map_err(|_| fmt::Error)?;
+    let (is_nonnegative, digits) = match string.starts_with('-') {
+        true  => (false, &string[1..]),
+        false => (true, string),
+    };
+    let is_special = digits.as_bytes() == options.nan_string() || digits.as_bytes() == options.inf_string();
+    if !is_special || !f.sign_aware_zero_pad() {
+        return f.pad_integral(is_nonnegative, "", digits);
+    }
+
+    let sign = match is_nonnegative {
+        true if f.sign_plus() => "+",
+        true                  => "",
+        false                 => "-",
+    };
+    let width = f.width().unwrap_or(0);
+    for _ in sign.len() + digits.len()..width {
+        f.write_str(" ")?;
+    }
+    f.write_str(sign)?;
+    f.write_str(digits)
+}
+
+/// Format a number as a decimal string.
+///
+/// Writes the same string as [`to_string`].
+///
+/// [`to_string`]: ../fn.to_string.html
+#[derive(Debug, Copy, Clone)]
+pub struct Display<N>(pub N);
+
+impl<N: ToLexical> fmt::Display for Display<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.to_lexical_buffer(|bytes| pad(f, bytes, &WriteOptions::new()))
+    }
+}
+
+/// Format a number as a string with a custom radix.
+///
+/// Writes the same string as [`to_string_radix`].
+///
+/// # Panics
+///
+/// Formatting panics if the radix is not in the range `[2, 36]`.
+///
+/// [`to_string_radix`]: ../fn.to_string_radix.html
+#[cfg(feature = "radix")]
+#[derive(Debug, Copy, Clone)]
+pub struct Radix<N>(pub N, pub u8);
+
+#[cfg(feature = "radix")]
+impl<N: ToLexical> fmt::Display for Radix<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.to_lexical_radix_buffer(self.1, |bytes| pad(f, bytes, &WriteOptions::new()))
+    }
+}
+
+/// Format a number as a decimal string with custom options.
+///
+/// Writes the same string as [`to_string_with_options`].
+///
+/// [`to_string_with_options`]: ../fn.to_string_with_options.html
+#[derive(Debug, Copy, Clone)]
+pub struct WithOptions<'a, N>(pub N, pub &'a WriteOptions<'a>);
+
+impl<'a, N: ToLexical> fmt::Display for WithOptions<'a, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.to_lexical_with_options_buffer(self.1, |bytes| pad(f, bytes, self.1))
+    }
+}
+
+// TESTS
+// -----
+
+#[cfg(test)]
+mod tests {
+    use lexical_core::Locale;
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        assert_eq!(format!("{}", Display(15u8)), "15");
+        assert_eq!(format!("{}", Display(-15i64)), "-15");
+        assert_eq!(format!("{}", Display(::lib::f64::NAN)), "NaN");
+        assert_eq!(format!("{}", Display(-2.5)), "-2.5");
+        assert_eq!(format!("{:>5}|{:<5}|{:^5}", Display(1), Display(2), Display(3)), "    1|2    |  3  ");
+        assert_eq!(format!("{:+}", Display(1.5f32)), "+1.5");
+        assert_eq!(format!("{:06}", Display(-1.5)), "-001.5");
+        assert_eq!(format!("{:.1}", Display(1.25)), "1.25");
+        assert_eq!(format!("{:06}", Display(::lib::f64::NAN)), "   NaN");
+        assert_eq!(format!("{:+06}", Display(::lib::f64::INFINITY)), "  +inf");
+        assert_eq!(format!("{:06}", Display(::lib::f32::NEG_INFINITY)), "  -inf");
+        assert_eq!(format!("{:<6}", Display(::lib::f64::NAN)), "NaN   ");
+    }
+
+    #[cfg(feature = "radix")]
+    #[test]
+    fn radix_test() {
+        assert_eq!(format!("{}", Radix(255u32, 16)), "FF");
+        assert_eq!(format!("{:>4}", Radix(-5i8, 2)), "-101");
+        assert_eq!(format!("{}", Radix(0.5, 2)), "0.1");
+        assert_eq!(format!("{:06}", Radix(255u32, 16)), "0000FF");
+    }
+
+    #[test]
+    fn with_options_test() {
+        let mut options = WriteOptions::new();
+        options.set_locale(Locale::EN_US);
+        assert_eq!(format!("{}", WithOptions(1234567, &options)), "1,234,567");
+        assert_eq!(format!("{:>12}", WithOptions(-1234.5, &options)), "    -1,234.5");
+        assert_eq!(format!("{}", WithOptions(u64::max_value(), &options)), "18,446,744,073,709,551,615");
+        options.set_inf_string(b"Infinity");
+        assert_eq!(format!("{:010}", WithOptions(-::lib::f64::INFINITY, &options)), " -Infinity");
+    }
+}
@@ -41,6 +41,9 @@ extern crate cfg_if;
 
 extern crate lexical_core;
 
+#[cfg(feature = "rayon")]
+extern crate rayon_dep as rayon;
+
 // CONFIG
 
 // Need an allocator for String/Vec.
@@ -69,28 +72,66 @@ if #[cfg(feature = "std")] {
 // API
 
 // Hide the implementation details.
+mod delimited;
 mod error;
 mod traits;
 
+// Format numbers without allocating.
+pub mod fmt;
+
+// Re-export the parse and write options.
+pub use lexical_core::{DigitSeparator, Locale, Notation, NumberFormat, NumberGrammar, ParseAlgorithm, ParseOptions, Precision, WriteAlgorithm, WriteOptions};
+
 // Re-export EXPONENT_DEFAULT_CHAR and EXPONENT_BACKUP_CHAR globally.
+#[allow(deprecated)]
 pub use lexical_core::EXPONENT_DEFAULT_CHAR;
 
 #[cfg(feature = "radix")]
+#[allow(deprecated)]
 pub use lexical_core::EXPONENT_BACKUP_CHAR;
 
 // Re-export NaN, short INF, and long INFINITY string getters and setters.
+#[allow(deprecated)]
 pub use lexical_core::{get_inf_string, get_infinity_string, get_nan_string};
+#[allow(deprecated)]
 pub use lexical_core::{set_inf_string, set_infinity_string, set_nan_string};
 
+// Re-export the 16-bit float types.
+pub use lexical_core::{bf16, f16};
+
+// Re-export the 128-bit float type.
+#[cfg(all(has_i128, feature = "correct"))]
+pub use lexical_core::f128;
+
+// Re-export the integer-or-float number type.
+#[cfg(feature = "correct")]
+pub use lexical_core::Number;
+
 // Re-export the float rounding scheme used.
 #[cfg(all(feature = "correct", feature = "rounding"))]
-pub use lexical_core::{FLOAT_ROUNDING, RoundingKind};
+pub use lexical_core::RoundingKind;
+
+#[cfg(all(feature = "correct", feature = "rounding"))]
+#[allow(deprecated)]
+pub use lexical_core::FLOAT_ROUNDING;
 
 // Re-export the Error and ErrorKind globally.
 pub use error::{Error, ErrorKind};
 
 // Publicly expose traits so they may be used for generic programming.
-pub use traits::{FromLexical, FromLexicalLossy, ToLexical};
+pub use traits::{AppendBytes, FromLexical, FromLexicalLossy, ToLexical};
+
+// Re-export the decimal formatting wrapper globally.
+pub use fmt::Display;
+
+// Re-export the batch parsers for delimited buffers.
+pub use delimited::{parse_delimited, parse_delimited_into, DelimitedError};
+
+#[cfg(feature = "rayon")]
+pub use delimited::par_parse_delimited;
+
+#[cfg(feature = "correct")]
+use traits::convert_result;
 
 // HIGH LEVEL
 
@@ -122,6 +163,10 @@ pub fn to_string<N: ToLexical>(n: N) -> lib::String {
 /// * `n`       - Number to convert to string.
 /// * `base`    - Number of unique digits for the number (radix).
 ///
+/// Floats are written with the shortest digits that round-trip with
+/// the `correct` feature. Otherwise, floats in non-decimal radixes are
+/// approximated, and may not round-trip.
+///
 /// # Examples
 ///
 /// ```rust
@@ -144,6 +189,283 @@ pub fn to_string_radix<N: ToLexical>(n: N, radix: u8) -> lib::String {
     }
 }
 
+/// High-level conversion of a number to a decimal-encoded string with custom options.
+///
+/// * `n`       - Number to convert to string.
+/// * `options` - Options to customize number serialization.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut options = lexical::WriteOptions::new();
+/// options.set_exponent_char(b'E');
+/// options.set_inf_string(b"Infinity");
+/// assert_eq!(lexical::to_string_with_options(1.5e-300, &options), "1.5E-300");
+/// assert_eq!(lexical::to_string_with_options(-std::f64::INFINITY, &options), "-Infinity");
+/// # }
+/// ```
+#[inline]
+pub fn to_string_with_options<N: ToLexical>(n: N, options: &WriteOptions) -> lib::String {
+    let bytes = n.to_lexical_with_options(options);
+    lib::String::from_utf8(bytes).expect("Options must only write valid UTF-8.")
+}
+
+/// High-level conversion of a number to a decimal-encoded string, written to a writer.
+///
+/// The number is serialized to a stack buffer, without allocating.
+///
+/// * `n`       - Number to convert to string.
+/// * `writer`  - Writer to write the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut vec = Vec::new();
+/// lexical::write_to(5, &mut vec).unwrap();
+/// lexical::write_to(-1.5, &mut vec).unwrap();
+/// assert_eq!(vec, b"5-1.5");
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[inline]
+pub fn write_to<N: ToLexical, W: lib::io::Write>(n: N, writer: &mut W) -> lib::io::Result<()> {
+    n.to_lexical_buffer(|bytes| writer.write_all(bytes))
+}
+
+/// High-level conversion of a number to string with a custom radix, written to a writer.
+///
+/// The number is serialized to a stack buffer, without allocating.
+///
+/// * `n`       - Number to convert to string.
+/// * `base`    - Number of unique digits for the number (radix).
+/// * `writer`  - Writer to write the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut vec = Vec::new();
+/// lexical::write_to_radix(255, 16, &mut vec).unwrap();
+/// assert_eq!(vec, b"FF");
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if radix is not in the range `[2, 36]`
+#[cfg(all(feature = "std", feature = "radix"))]
+#[inline]
+pub fn write_to_radix<N: ToLexical, W: lib::io::Write>(n: N, radix: u8, writer: &mut W) -> lib::io::Result<()> {
+    n.to_lexical_radix_buffer(radix, |bytes| writer.write_all(bytes))
+}
+
+/// High-level conversion of a number to a decimal-encoded string with custom options, written to a writer.
+///
+/// The number is serialized to a stack buffer, without allocating.
+///
+/// * `n`       - Number to convert to string.
+/// * `options` - Options to customize number serialization.
+/// * `writer`  - Writer to write the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut options = lexical::WriteOptions::new();
+/// options.set_exponent_char(b'E');
+/// let mut vec = Vec::new();
+/// lexical::write_to_with_options(1.5e-300, &options, &mut vec).unwrap();
+/// assert_eq!(vec, b"1.5E-300");
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[inline]
+pub fn write_to_with_options<N: ToLexical, W: lib::io::Write>(n: N, options: &WriteOptions, writer: &mut W) -> lib::io::Result<()> {
+    n.to_lexical_with_options_buffer(options, |bytes| writer.write_all(bytes))
+}
+
+/// High-level conversion of a number to a decimal-encoded string, appended to a buffer.
+///
+/// The number is serialized to a stack buffer, and appended to the
+/// end of a `Vec<u8>` or `String`.
+///
+/// * `n`       - Number to convert to string.
+/// * `buf`     - Buffer to append the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut vec = b"[".to_vec();
+/// lexical::write_append(5, &mut vec);
+/// assert_eq!(vec, b"[5");
+///
+/// let mut string = String::from("x=");
+/// lexical::write_append(-1.5, &mut string);
+/// assert_eq!(string, "x=-1.5");
+/// # }
+/// ```
+#[inline]
+pub fn write_append<N: ToLexical, Buf: AppendBytes>(n: N, buf: &mut Buf) {
+    n.to_lexical_buffer(|bytes| buf.append_bytes(bytes))
+}
+
+/// High-level conversion of a number to string with a custom radix, appended to a buffer.
+///
+/// The number is serialized to a stack buffer, and appended to the
+/// end of a `Vec<u8>` or `String`.
+///
+/// * `n`       - Number to convert to string.
+/// * `base`    - Number of unique digits for the number (radix).
+/// * `buf`     - Buffer to append the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut string = String::from("0x");
+/// lexical::write_append_radix(255, 16, &mut string);
+/// assert_eq!(string, "0xFF");
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if radix is not in the range `[2, 36]`
+#[cfg(feature = "radix")]
+#[inline]
+pub fn write_append_radix<N: ToLexical, Buf: AppendBytes>(n: N, radix: u8, buf: &mut Buf) {
+    n.to_lexical_radix_buffer(radix, |bytes| buf.append_bytes(bytes))
+}
+
+/// High-level conversion of a number to a decimal-encoded string with custom options, appended to a buffer.
+///
+/// The number is serialized to a stack buffer, and appended to the
+/// end of a `Vec<u8>` or `String`.
+///
+/// * `n`       - Number to convert to string.
+/// * `options` - Options to customize number serialization.
+/// * `buf`     - Buffer to append the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut options = lexical::WriteOptions::new();
+/// options.set_exponent_char(b'E');
+/// let mut vec = Vec::new();
+/// lexical::write_append_with_options(1.5e-300, &options, &mut vec);
+/// assert_eq!(vec, b"1.5E-300");
+/// # }
+/// ```
+#[inline]
+pub fn write_append_with_options<N: ToLexical, Buf: AppendBytes>(n: N, options: &WriteOptions, buf: &mut Buf) {
+    n.to_lexical_with_options_buffer(options, |bytes| buf.append_bytes(bytes))
+}
+
+/// High-level conversion of a number to a decimal-encoded string, written to uninitialized memory.
+///
+/// Returns the initialized subslice containing the string, which
+/// always starts at the start of the buffer.
+///
+/// * `n`       - Number to convert to string.
+/// * `buf`     - Uninitialized buffer to write the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # use std::mem::MaybeUninit;
+/// # pub fn main() {
+/// let mut buf = [MaybeUninit::<u8>::uninit(); 32];
+/// assert_eq!(lexical::write_uninit(-15, &mut buf), b"-15");
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the buffer is too small for the string. Buffers with
+/// at least `MAX_*_SIZE` elements, using the proper constant for the
+/// serialized type from the lexical_core crate, are always large enough.
+#[cfg(has_maybe_uninit)]
+#[inline]
+pub fn write_uninit<'a, N: ToLexical>(n: N, buf: &'a mut [lib::mem::MaybeUninit<u8>]) -> &'a mut [u8] {
+    n.to_lexical_buffer(move |bytes| traits::copy_to_uninit(bytes, buf))
+}
+
+/// High-level conversion of a number to string with a custom radix, written to uninitialized memory.
+///
+/// Returns the initialized subslice containing the string, which
+/// always starts at the start of the buffer.
+///
+/// * `n`       - Number to convert to string.
+/// * `base`    - Number of unique digits for the number (radix).
+/// * `buf`     - Uninitialized buffer to write the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # use std::mem::MaybeUninit;
+/// # pub fn main() {
+/// let mut buf = [MaybeUninit::<u8>::uninit(); 32];
+/// assert_eq!(lexical::write_uninit_radix(255, 16, &mut buf), b"FF");
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if radix is not in the range `[2, 36]`, or if the buffer is
+/// too small for the string.
+#[cfg(all(has_maybe_uninit, feature = "radix"))]
+#[inline]
+pub fn write_uninit_radix<'a, N: ToLexical>(n: N, radix: u8, buf: &'a mut [lib::mem::MaybeUninit<u8>]) -> &'a mut [u8] {
+    n.to_lexical_radix_buffer(radix, move |bytes| traits::copy_to_uninit(bytes, buf))
+}
+
+/// High-level conversion of a number to a decimal-encoded string with custom options, written to uninitialized memory.
+///
+/// Returns the initialized subslice containing the string, which
+/// always starts at the start of the buffer.
+///
+/// * `n`       - Number to convert to string.
+/// * `options` - Options to customize number serialization.
+/// * `buf`     - Uninitialized buffer to write the string to.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # use std::mem::MaybeUninit;
+/// # pub fn main() {
+/// let mut options = lexical::WriteOptions::new();
+/// options.set_exponent_char(b'E');
+/// let mut buf = [MaybeUninit::<u8>::uninit(); 32];
+/// assert_eq!(lexical::write_uninit_with_options(1.5e-300, &options, &mut buf), b"1.5E-300");
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the buffer is too small for the string. Buffers with at
+/// least `max_*_size_with_options(options)` elements, using the proper
+/// function for the serialized type from the lexical_core crate, are
+/// large enough for any number.
+#[cfg(has_maybe_uninit)]
+#[inline]
+pub fn write_uninit_with_options<'a, N: ToLexical>(n: N, options: &WriteOptions, buf: &'a mut [lib::mem::MaybeUninit<u8>]) -> &'a mut [u8] {
+    n.to_lexical_with_options_buffer(options, move |bytes| traits::copy_to_uninit(bytes, buf))
+}
+
 /// High-level conversion of decimal-encoded bytes to a number.
 ///
 /// This function **always** returns a number, parsing until invalid
@@ -215,6 +537,33 @@ pub fn parse_lossy<N: FromLexicalLossy, Bytes: AsRef<[u8]>>(bytes: Bytes) -> N {
     N::from_lexical_lossy(bytes.as_ref())
 }
 
+/// High-level conversion of decimal-encoded bytes to a number with custom options.
+///
+/// This function **always** returns a number, parsing until invalid
+/// digits are found. For an error-checking version of this function,
+/// use [`try_parse_with_options`].
+///
+/// * `bytes`   - Byte slice to convert to number.
+/// * `options` - Options to customize number parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut options = lexical::ParseOptions::new();
+/// options.set_nan_string(b"null");
+/// assert!(lexical::parse_with_options::<f64, _>("null", &options).is_nan());
+/// assert_eq!(lexical::parse_with_options::<i32, _>("1a", &options), 1);
+/// # }
+/// ```
+///
+/// [`try_parse_with_options`]: fn.try_parse_with_options.html
+#[inline]
+pub fn parse_with_options<N: FromLexical, Bytes: AsRef<[u8]>>(bytes: Bytes, options: &ParseOptions) -> N {
+    N::from_lexical_with_options(bytes.as_ref(), options)
+}
+
 /// High-level conversion of bytes to a number with a custom radix.
 ///
 /// This function **always** returns a number, parsing until invalid
@@ -478,3 +827,201 @@ pub fn try_parse_lossy_radix<N: FromLexicalLossy, Bytes: AsRef<[u8]>>(bytes: Byt
 {
     N::try_from_lexical_lossy_radix(bytes.as_ref(), radix)
 }
+
+/// High-level conversion of decimal-encoded bytes to a number with custom options.
+///
+/// This function only returns a value if the entire string is
+/// successfully parsed.
+///
+/// * `bytes`   - Byte slice to convert to number.
+/// * `options` - Options to customize number parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # use lexical::ErrorKind;
+/// # pub fn main() {
+/// // Create our error.
+/// let err = |u| ErrorKind::InvalidDigit(u).into();
+///
+/// let mut options = lexical::ParseOptions::new();
+/// options.set_exponent_char(b'd');
+/// assert_eq!(lexical::try_parse_with_options::<f64, _>("1.5d3", &options), Ok(1500.0));
+/// assert_eq!(lexical::try_parse_with_options::<f64, _>("1.5e3", &options), Err(err(3)));
+/// # }
+/// ```
+#[inline]
+pub fn try_parse_with_options<N: FromLexical, Bytes: AsRef<[u8]>>(bytes: Bytes, options: &ParseOptions)
+    -> Result<N, Error>
+{
+    N::try_from_lexical_with_options(bytes.as_ref(), options)
+}
+
+/// High-level conversion of the leading number in decimal-encoded bytes.
+///
+/// This function parses a number from the start of the bytes, stopping
+/// at the first byte that cannot continue the number, and returns the
+/// value and the number of bytes processed. Empty input, input without
+/// a leading number, and overflow are errors.
+///
+/// * `bytes`   - Byte slice to convert to number.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # use lexical::ErrorKind;
+/// # pub fn main() {
+/// // String overloads
+/// assert_eq!(lexical::parse_partial::<i32, _>("5 apples"), Ok((5, 1)));
+/// assert_eq!(lexical::parse_partial::<f32, _>("1.5,2.5"), Ok((1.5, 3)));
+///
+/// // Bytes overloads
+/// assert_eq!(lexical::parse_partial::<i32, _>(b"-12]"), Ok((-12, 3)));
+/// assert_eq!(lexical::parse_partial::<u8, _>(b"256"), Err(ErrorKind::Overflow.into()));
+/// assert_eq!(lexical::parse_partial::<u8, _>(b""), Err(ErrorKind::Empty.into()));
+/// assert_eq!(lexical::parse_partial::<u8, _>(b"a"), Err(ErrorKind::InvalidDigit(0).into()));
+/// # }
+/// ```
+#[inline]
+pub fn parse_partial<N: FromLexical, Bytes: AsRef<[u8]>>(bytes: Bytes)
+    -> Result<(N, usize), Error>
+{
+    N::from_lexical_partial(bytes.as_ref())
+}
+
+/// High-level conversion of the leading number in bytes with a custom radix.
+///
+/// This function parses a number from the start of the bytes, stopping
+/// at the first byte that cannot continue the number, and returns the
+/// value and the number of bytes processed.
+///
+/// * `bytes`   - Byte slice to convert to number.
+/// * `radix`   - Number of unique digits for the number (base).
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// assert_eq!(lexical::parse_partial_radix::<u32, _>("ffz", 16), Ok((255, 2)));
+/// assert_eq!(lexical::parse_partial_radix::<f32, _>(b"1.1 ", 2), Ok((1.5, 3)));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if radix is not in the range `[2, 36]`
+#[cfg(feature = "radix")]
+#[inline]
+pub fn parse_partial_radix<N: FromLexical, Bytes: AsRef<[u8]>>(bytes: Bytes, radix: u8)
+    -> Result<(N, usize), Error>
+{
+    N::from_lexical_partial_radix(bytes.as_ref(), radix)
+}
+
+/// High-level conversion of the leading number in decimal-encoded bytes with custom options.
+///
+/// This function parses a number from the start of the bytes, stopping
+/// at the first byte that cannot continue the number, and returns the
+/// value and the number of bytes processed.
+///
+/// * `bytes`   - Byte slice to convert to number.
+/// * `options` - Options to customize number parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # pub fn main() {
+/// let mut options = lexical::ParseOptions::new();
+/// options.set_locale(lexical::Locale::EN_US);
+/// assert_eq!(lexical::parse_partial_with_options::<u32, _>("1,234 apples", &options), Ok((1234, 5)));
+/// # }
+/// ```
+#[inline]
+pub fn parse_partial_with_options<N: FromLexical, Bytes: AsRef<[u8]>>(bytes: Bytes, options: &ParseOptions)
+    -> Result<(N, usize), Error>
+{
+    N::from_lexical_partial_with_options(bytes.as_ref(), options)
+}
+
+/// High-level conversion of decimal-encoded bytes to an integer or float.
+///
+/// Numbers without a fraction or exponent are parsed as integers, if
+/// they fit in any integer type, and as floats otherwise, from a single
+/// scan of the digits.
+///
+/// * `bytes`   - Byte slice to convert to number.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # use lexical::{ErrorKind, Number};
+/// # pub fn main() {
+/// assert_eq!(lexical::parse_number("15"), Ok(Number::U64(15)));
+/// assert_eq!(lexical::parse_number("-15"), Ok(Number::I64(-15)));
+/// assert_eq!(lexical::parse_number("1.5e3"), Ok(Number::F64(1500.0)));
+/// assert_eq!(lexical::parse_number(b"1a"), Err(ErrorKind::InvalidDigit(1).into()));
+/// # }
+/// ```
+#[cfg(feature = "correct")]
+#[inline]
+pub fn parse_number<Bytes: AsRef<[u8]>>(bytes: Bytes)
+    -> Result<Number, Error>
+{
+    convert_result(lexical_core::parse_number(bytes.as_ref()))
+}
+
+/// High-level conversion of decimal-encoded bytes to an integer or float with custom options.
+///
+/// * `bytes`   - Byte slice to convert to number.
+/// * `options` - Options to customize number parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # use lexical::Number;
+/// # pub fn main() {
+/// let mut options = lexical::ParseOptions::new();
+/// options.set_format(lexical::NumberFormat::Json);
+/// assert_eq!(lexical::parse_number_with_options("-12", &options), Ok(Number::I64(-12)));
+/// assert!(lexical::parse_number_with_options("+12", &options).is_err());
+/// # }
+/// ```
+#[cfg(feature = "correct")]
+#[inline]
+pub fn parse_number_with_options<Bytes: AsRef<[u8]>>(bytes: Bytes, options: &ParseOptions)
+    -> Result<Number, Error>
+{
+    convert_result(lexical_core::parse_number_with_options(bytes.as_ref(), options))
+}
+
+/// High-level conversion of the leading integer or float in decimal-encoded bytes.
+///
+/// This function parses a number from the start of the bytes, stopping
+/// at the first byte that cannot continue the number, and returns the
+/// value and the number of bytes processed.
+///
+/// * `bytes`   - Byte slice to convert to number.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate lexical;
+/// # use lexical::Number;
+/// # pub fn main() {
+/// assert_eq!(lexical::parse_number_partial("12,1.5"), Ok((Number::U64(12), 2)));
+/// assert_eq!(lexical::parse_number_partial("1.5]"), Ok((Number::F64(1.5), 3)));
+/// # }
+/// ```
+#[cfg(feature = "correct")]
+#[inline]
+pub fn parse_number_partial<Bytes: AsRef<[u8]>>(bytes: Bytes)
+    -> Result<(Number, usize), Error>
+{
+    convert_result(lexical_core::parse_number_partial(bytes.as_ref()))
+}
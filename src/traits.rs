@@ -1,29 +1,40 @@
 //! High-level traits to translate the low-level API to idiomatic Rust.
 
-use lexical_core::{self, ErrorCode};
-use lib::{slice, Vec};
+use lexical_core::{self, bf16, f16, ErrorCode, ParseOptions, WriteOptions};
+#[cfg(all(has_i128, feature = "correct"))]
+use lexical_core::f128;
+use lib::{str, String, Vec};
+#[cfg(has_maybe_uninit)]
+use lib::{mem, slice};
 use error::*;
 
 // HELPERS
 
-/// Get a vector as a slice, including the capacity.
+/// Copy serialized bytes to an uninitialized buffer, and return the initialized bytes.
+///
+/// Panics if the buffer is too small for the bytes.
+#[cfg(has_maybe_uninit)]
 #[inline]
-unsafe fn vector_as_slice<'a, T>(buf: &'a mut Vec<T>)
-    -> &'a mut [T]
+pub(crate) fn copy_to_uninit<'a>(bytes: &[u8], buf: &'a mut [mem::MaybeUninit<u8>])
+    -> &'a mut [u8]
 {
-    let first = buf.as_mut_ptr();
-    slice::from_raw_parts_mut(first, buf.capacity())
+    assert!(buf.len() >= bytes.len());
+    for (dst, &src) in buf.iter_mut().zip(bytes) {
+        *dst = mem::MaybeUninit::new(src);
+    }
+    // We just initialized the first `bytes.len()` elements, so this is safe.
+    unsafe { slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, bytes.len()) }
 }
 
 /// Convert a C-compatible result to an idiomatic Rust one.
 #[inline]
-fn convert_result<T>(result: lexical_core::Result<T>) -> Result<T, Error> {
+pub(crate) fn convert_result<T>(result: lexical_core::Result<T>) -> Result<T, Error> {
     match result.error.code {
         ErrorCode::Success      => Ok(result.value),
         ErrorCode::Overflow     => Err(overflow()),
         ErrorCode::InvalidDigit => Err(invalid_digit(result.error.index)),
         ErrorCode::Empty        => Err(empty()),
-        _                       => unimplemented!(),
+        code                    => Err(grammar(code, result.error.index)),
     }
 }
 
@@ -44,10 +55,26 @@ pub trait FromLexical: Sized {
     /// Error-checking deserialize from byte slice with radix.
     #[cfg(feature = "radix")]
     fn try_from_lexical_radix(bytes: &[u8], radix: u8) -> Result<Self, Error>;
+
+    /// Deserialize from byte slice with custom parsing options.
+    fn from_lexical_with_options(bytes: &[u8], options: &ParseOptions) -> Self;
+
+    /// Error-checking deserialize from byte slice with custom parsing options.
+    fn try_from_lexical_with_options(bytes: &[u8], options: &ParseOptions) -> Result<Self, Error>;
+
+    /// Deserialize a leading number from byte slice, returning the value and bytes processed.
+    fn from_lexical_partial(bytes: &[u8]) -> Result<(Self, usize), Error>;
+
+    /// Deserialize a leading number from byte slice with radix, returning the value and bytes processed.
+    #[cfg(feature = "radix")]
+    fn from_lexical_partial_radix(bytes: &[u8], radix: u8) -> Result<(Self, usize), Error>;
+
+    /// Deserialize a leading number from byte slice with custom parsing options, returning the value and bytes processed.
+    fn from_lexical_partial_with_options(bytes: &[u8], options: &ParseOptions) -> Result<(Self, usize), Error>;
 }
 
 macro_rules! from_lexical {
-    ($t:ty, $decimal_cb:ident, $radix_cb:ident, $try_decimal_cb:ident, $try_radix_cb:ident) => (
+    ($t:ty, $decimal_cb:ident, $radix_cb:ident, $try_decimal_cb:ident, $try_radix_cb:ident, $options_cb:ident, $try_options_cb:ident, $partial_decimal_cb:ident, $partial_radix_cb:ident, $partial_options_cb:ident) => (
         impl FromLexical for $t {
             #[inline]
             fn from_lexical(bytes: &[u8]) -> $t
@@ -74,28 +101,64 @@ macro_rules! from_lexical {
             {
                 convert_result(lexical_core::$try_radix_cb(radix, bytes))
             }
+
+            #[inline]
+            fn from_lexical_with_options(bytes: &[u8], options: &ParseOptions) -> $t
+            {
+                lexical_core::$options_cb(bytes, options)
+            }
+
+            #[inline]
+            fn try_from_lexical_with_options(bytes: &[u8], options: &ParseOptions) -> Result<$t, Error>
+            {
+                convert_result(lexical_core::$try_options_cb(bytes, options))
+            }
+
+            #[inline]
+            fn from_lexical_partial(bytes: &[u8]) -> Result<($t, usize), Error>
+            {
+                convert_result(lexical_core::$partial_decimal_cb(bytes))
+            }
+
+            #[cfg(feature = "radix")]
+            #[inline]
+            fn from_lexical_partial_radix(bytes: &[u8], radix: u8) -> Result<($t, usize), Error>
+            {
+                convert_result(lexical_core::$partial_radix_cb(radix, bytes))
+            }
+
+            #[inline]
+            fn from_lexical_partial_with_options(bytes: &[u8], options: &ParseOptions) -> Result<($t, usize), Error>
+            {
+                convert_result(lexical_core::$partial_options_cb(bytes, options))
+            }
         }
     )
 }
 
-from_lexical!(u8, atou8_slice, atou8_radix_slice, try_atou8_slice, try_atou8_radix_slice);
-from_lexical!(u16, atou16_slice, atou16_radix_slice, try_atou16_slice, try_atou16_radix_slice);
-from_lexical!(u32, atou32_slice, atou32_radix_slice, try_atou32_slice, try_atou32_radix_slice);
-from_lexical!(u64, atou64_slice, atou64_radix_slice, try_atou64_slice, try_atou64_radix_slice);
-from_lexical!(usize, atousize_slice, atousize_radix_slice, try_atousize_slice, try_atousize_radix_slice);
-from_lexical!(i8, atoi8_slice, atoi8_radix_slice, try_atoi8_slice, try_atoi8_radix_slice);
-from_lexical!(i16, atoi16_slice, atoi16_radix_slice, try_atoi16_slice, try_atoi16_radix_slice);
-from_lexical!(i32, atoi32_slice, atoi32_radix_slice, try_atoi32_slice, try_atoi32_radix_slice);
-from_lexical!(i64, atoi64_slice, atoi64_radix_slice, try_atoi64_slice, try_atoi64_radix_slice);
-from_lexical!(isize, atoisize_slice, atoisize_radix_slice, try_atoisize_slice, try_atoisize_radix_slice);
-from_lexical!(f32, atof32_slice, atof32_radix_slice, try_atof32_slice, try_atof32_radix_slice);
-from_lexical!(f64, atof64_slice, atof64_radix_slice, try_atof64_slice, try_atof64_radix_slice);
+from_lexical!(u8, atou8_slice, atou8_radix_slice, try_atou8_slice, try_atou8_radix_slice, atou8_slice_with_options, try_atou8_slice_with_options, atou8_partial_slice, atou8_partial_radix_slice, atou8_partial_slice_with_options);
+from_lexical!(u16, atou16_slice, atou16_radix_slice, try_atou16_slice, try_atou16_radix_slice, atou16_slice_with_options, try_atou16_slice_with_options, atou16_partial_slice, atou16_partial_radix_slice, atou16_partial_slice_with_options);
+from_lexical!(u32, atou32_slice, atou32_radix_slice, try_atou32_slice, try_atou32_radix_slice, atou32_slice_with_options, try_atou32_slice_with_options, atou32_partial_slice, atou32_partial_radix_slice, atou32_partial_slice_with_options);
+from_lexical!(u64, atou64_slice, atou64_radix_slice, try_atou64_slice, try_atou64_radix_slice, atou64_slice_with_options, try_atou64_slice_with_options, atou64_partial_slice, atou64_partial_radix_slice, atou64_partial_slice_with_options);
+from_lexical!(usize, atousize_slice, atousize_radix_slice, try_atousize_slice, try_atousize_radix_slice, atousize_slice_with_options, try_atousize_slice_with_options, atousize_partial_slice, atousize_partial_radix_slice, atousize_partial_slice_with_options);
+from_lexical!(i8, atoi8_slice, atoi8_radix_slice, try_atoi8_slice, try_atoi8_radix_slice, atoi8_slice_with_options, try_atoi8_slice_with_options, atoi8_partial_slice, atoi8_partial_radix_slice, atoi8_partial_slice_with_options);
+from_lexical!(i16, atoi16_slice, atoi16_radix_slice, try_atoi16_slice, try_atoi16_radix_slice, atoi16_slice_with_options, try_atoi16_slice_with_options, atoi16_partial_slice, atoi16_partial_radix_slice, atoi16_partial_slice_with_options);
+from_lexical!(i32, atoi32_slice, atoi32_radix_slice, try_atoi32_slice, try_atoi32_radix_slice, atoi32_slice_with_options, try_atoi32_slice_with_options, atoi32_partial_slice, atoi32_partial_radix_slice, atoi32_partial_slice_with_options);
+from_lexical!(i64, atoi64_slice, atoi64_radix_slice, try_atoi64_slice, try_atoi64_radix_slice, atoi64_slice_with_options, try_atoi64_slice_with_options, atoi64_partial_slice, atoi64_partial_radix_slice, atoi64_partial_slice_with_options);
+from_lexical!(isize, atoisize_slice, atoisize_radix_slice, try_atoisize_slice, try_atoisize_radix_slice, atoisize_slice_with_options, try_atoisize_slice_with_options, atoisize_partial_slice, atoisize_partial_radix_slice, atoisize_partial_slice_with_options);
+from_lexical!(f32, atof32_slice, atof32_radix_slice, try_atof32_slice, try_atof32_radix_slice, atof32_slice_with_options, try_atof32_slice_with_options, atof32_partial_slice, atof32_partial_radix_slice, atof32_partial_slice_with_options);
+from_lexical!(f64, atof64_slice, atof64_radix_slice, try_atof64_slice, try_atof64_radix_slice, atof64_slice_with_options, try_atof64_slice_with_options, atof64_partial_slice, atof64_partial_radix_slice, atof64_partial_slice_with_options);
+from_lexical!(f16, atof16_slice, atof16_radix_slice, try_atof16_slice, try_atof16_radix_slice, atof16_slice_with_options, try_atof16_slice_with_options, atof16_partial_slice, atof16_partial_radix_slice, atof16_partial_slice_with_options);
+from_lexical!(bf16, atobf16_slice, atobf16_radix_slice, try_atobf16_slice, try_atobf16_radix_slice, atobf16_slice_with_options, try_atobf16_slice_with_options, atobf16_partial_slice, atobf16_partial_radix_slice, atobf16_partial_slice_with_options);
 
 #[cfg(has_i128)]
-from_lexical!(u128, atou128_slice, atou128_radix_slice, try_atou128_slice, try_atou128_radix_slice);
+from_lexical!(u128, atou128_slice, atou128_radix_slice, try_atou128_slice, try_atou128_radix_slice, atou128_slice_with_options, try_atou128_slice_with_options, atou128_partial_slice, atou128_partial_radix_slice, atou128_partial_slice_with_options);
 
 #[cfg(has_i128)]
-from_lexical!(i128, atoi128_slice, atoi128_radix_slice, try_atoi128_slice, try_atoi128_radix_slice);
+from_lexical!(i128, atoi128_slice, atoi128_radix_slice, try_atoi128_slice, try_atoi128_radix_slice, atoi128_slice_with_options, try_atoi128_slice_with_options, atoi128_partial_slice, atoi128_partial_radix_slice, atoi128_partial_slice_with_options);
+
+#[cfg(all(has_i128, feature = "correct"))]
+from_lexical!(f128, atof128_slice, atof128_radix_slice, try_atof128_slice, try_atof128_radix_slice, atof128_slice_with_options, try_atof128_slice_with_options, atof128_partial_slice, atof128_partial_radix_slice, atof128_partial_slice_with_options);
 
 // FROM BYTES LOSSY
 
@@ -114,10 +177,16 @@ pub trait FromLexicalLossy: FromLexical {
     /// Error-checking deserialize from byte slice with radix.
     #[cfg(feature = "radix")]
     fn try_from_lexical_lossy_radix(bytes: &[u8], radix: u8) -> Result<Self, Error>;
+
+    /// Deserialize from byte slice with custom parsing options.
+    fn from_lexical_lossy_with_options(bytes: &[u8], options: &ParseOptions) -> Self;
+
+    /// Error-checking deserialize from byte slice with custom parsing options.
+    fn try_from_lexical_lossy_with_options(bytes: &[u8], options: &ParseOptions) -> Result<Self, Error>;
 }
 
 macro_rules! from_lexical_lossy {
-    ($t:ty, $decimal_cb:ident, $radix_cb:ident, $try_decimal_cb:ident, $try_radix_cb:ident) => (
+    ($t:ty, $decimal_cb:ident, $radix_cb:ident, $try_decimal_cb:ident, $try_radix_cb:ident, $options_cb:ident, $try_options_cb:ident) => (
         impl FromLexicalLossy for $t {
             #[inline]
             fn from_lexical_lossy(bytes: &[u8]) -> $t
@@ -144,12 +213,49 @@ macro_rules! from_lexical_lossy {
             {
                 convert_result(lexical_core::$try_radix_cb(radix, bytes))
             }
+
+            #[inline]
+            fn from_lexical_lossy_with_options(bytes: &[u8], options: &ParseOptions) -> $t
+            {
+                lexical_core::$options_cb(bytes, options)
+            }
+
+            #[inline]
+            fn try_from_lexical_lossy_with_options(bytes: &[u8], options: &ParseOptions) -> Result<$t, Error>
+            {
+                convert_result(lexical_core::$try_options_cb(bytes, options))
+            }
         }
     )
 }
 
-from_lexical_lossy!(f32, atof32_lossy_slice, atof32_lossy_radix_slice, try_atof32_lossy_slice, try_atof32_lossy_radix_slice);
-from_lexical_lossy!(f64, atof64_lossy_slice, atof64_lossy_radix_slice, try_atof64_lossy_slice, try_atof64_lossy_radix_slice);
+from_lexical_lossy!(f32, atof32_lossy_slice, atof32_lossy_radix_slice, try_atof32_lossy_slice, try_atof32_lossy_radix_slice, atof32_lossy_slice_with_options, try_atof32_lossy_slice_with_options);
+from_lexical_lossy!(f64, atof64_lossy_slice, atof64_lossy_radix_slice, try_atof64_lossy_slice, try_atof64_lossy_radix_slice, atof64_lossy_slice_with_options, try_atof64_lossy_slice_with_options);
+from_lexical_lossy!(f16, atof16_lossy_slice, atof16_lossy_radix_slice, try_atof16_lossy_slice, try_atof16_lossy_radix_slice, atof16_lossy_slice_with_options, try_atof16_lossy_slice_with_options);
+from_lexical_lossy!(bf16, atobf16_lossy_slice, atobf16_lossy_radix_slice, try_atobf16_lossy_slice, try_atobf16_lossy_radix_slice, atobf16_lossy_slice_with_options, try_atobf16_lossy_slice_with_options);
+
+// APPEND BYTES
+
+/// Trait for growable buffers that serialized numbers can be appended to.
+pub trait AppendBytes {
+    /// Append serialized bytes, which are always valid UTF-8.
+    #[doc(hidden)]
+    fn append_bytes(&mut self, bytes: &[u8]);
+}
+
+impl AppendBytes for Vec<u8> {
+    #[inline]
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+impl AppendBytes for String {
+    #[inline]
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.push_str(str::from_utf8(bytes).expect("Options must only write valid UTF-8."));
+    }
+}
 
 // TO BYTES
 
@@ -161,60 +267,114 @@ pub trait ToLexical: Sized {
     /// Serialize to string with radix.
     #[cfg(feature = "radix")]
     fn to_lexical_radix(&self, radix: u8) -> Vec<u8>;
+
+    /// Serialize to string with custom formatting options.
+    fn to_lexical_with_options(&self, options: &WriteOptions) -> Vec<u8>;
+
+    /// Serialize to a stack buffer, and call the callback with the bytes.
+    #[doc(hidden)]
+    fn to_lexical_buffer<R, Cb>(&self, cb: Cb) -> R
+        where Cb: FnOnce(&[u8]) -> R;
+
+    /// Serialize to a stack buffer with radix, and call the callback with the bytes.
+    #[cfg(feature = "radix")]
+    #[doc(hidden)]
+    fn to_lexical_radix_buffer<R, Cb>(&self, radix: u8, cb: Cb) -> R
+        where Cb: FnOnce(&[u8]) -> R;
+
+    /// Serialize to a stack buffer with custom formatting options, and call the callback with the bytes.
+    #[doc(hidden)]
+    fn to_lexical_with_options_buffer<R, Cb>(&self, options: &WriteOptions, cb: Cb) -> R
+        where Cb: FnOnce(&[u8]) -> R;
 }
 
 macro_rules! to_lexical {
-    ($t:ty, $decimal_cb:ident, $radix_cb:ident, $capacity:ident) => (
+    ($t:ty, $decimal_cb:ident, $radix_cb:ident, $options_cb:ident, $size_cb:ident, $capacity:ident) => (
         impl ToLexical for $t {
             #[inline]
             fn to_lexical(&self) -> Vec<u8> {
-                unsafe {
-                    let mut buf = Vec::<u8>::with_capacity(lexical_core::$capacity);
-                    let len = lexical_core::$decimal_cb(*self, vector_as_slice(&mut buf)).len();
-                    buf.set_len(len);
-                    buf
-                }
+                self.to_lexical_buffer(|bytes| bytes.to_vec())
             }
 
             #[cfg(feature = "radix")]
             #[inline]
             fn to_lexical_radix(&self, radix: u8) -> Vec<u8> {
-                unsafe {
-                    let mut buf = Vec::<u8>::with_capacity(lexical_core::$capacity);
-                    let len = lexical_core::$radix_cb(*self, radix, vector_as_slice(&mut buf)).len();
-                    buf.set_len(len);
-                    buf
+                self.to_lexical_radix_buffer(radix, |bytes| bytes.to_vec())
+            }
+
+            #[inline]
+            fn to_lexical_with_options(&self, options: &WriteOptions) -> Vec<u8> {
+                self.to_lexical_with_options_buffer(options, |bytes| bytes.to_vec())
+            }
+
+            #[inline]
+            fn to_lexical_buffer<R, Cb>(&self, cb: Cb) -> R
+                where Cb: FnOnce(&[u8]) -> R
+            {
+                let mut buf = [b'0'; lexical_core::$capacity];
+                cb(lexical_core::$decimal_cb(*self, &mut buf))
+            }
+
+            #[cfg(feature = "radix")]
+            #[inline]
+            fn to_lexical_radix_buffer<R, Cb>(&self, radix: u8, cb: Cb) -> R
+                where Cb: FnOnce(&[u8]) -> R
+            {
+                let mut buf = [b'0'; lexical_core::$capacity];
+                cb(lexical_core::$radix_cb(*self, radix, &mut buf))
+            }
+
+            #[inline]
+            fn to_lexical_with_options_buffer<R, Cb>(&self, options: &WriteOptions, cb: Cb) -> R
+                where Cb: FnOnce(&[u8]) -> R
+            {
+                // Digit grouping and custom float layouts may write more
+                // than the default maximum size, so only use a stack buffer
+                // if it is always large enough.
+                let size = lexical_core::$size_cb(options);
+                if size <= lexical_core::$capacity {
+                    let mut buf = [b'0'; lexical_core::$capacity];
+                    cb(lexical_core::$options_cb(*self, &mut buf, options))
+                } else {
+                    let mut buf = Vec::with_capacity(size);
+                    buf.resize(size, b'0');
+                    cb(lexical_core::$options_cb(*self, &mut buf, options))
                 }
             }
         }
     )
 }
 
-to_lexical!(u8, u8toa_slice, u8toa_radix_slice, MAX_U8_SIZE);
-to_lexical!(u16, u16toa_slice, u16toa_radix_slice, MAX_U16_SIZE);
-to_lexical!(u32, u32toa_slice, u32toa_radix_slice, MAX_U32_SIZE);
-to_lexical!(u64, u64toa_slice, u64toa_radix_slice, MAX_U64_SIZE);
-to_lexical!(usize, usizetoa_slice, usizetoa_radix_slice, MAX_USIZE_SIZE);
-to_lexical!(i8, i8toa_slice, i8toa_radix_slice, MAX_I8_SIZE);
-to_lexical!(i16, i16toa_slice, i16toa_radix_slice, MAX_I16_SIZE);
-to_lexical!(i32, i32toa_slice, i32toa_radix_slice, MAX_I32_SIZE);
-to_lexical!(i64, i64toa_slice, i64toa_radix_slice, MAX_I64_SIZE);
-to_lexical!(isize, isizetoa_slice, isizetoa_radix_slice, MAX_ISIZE_SIZE);
-to_lexical!(f32, f32toa_slice, f32toa_radix_slice, MAX_F32_SIZE);
-to_lexical!(f64, f64toa_slice, f64toa_radix_slice, MAX_F64_SIZE);
+to_lexical!(u8, u8toa_slice, u8toa_radix_slice, u8toa_slice_with_options, max_u8_size_with_options, MAX_U8_SIZE);
+to_lexical!(u16, u16toa_slice, u16toa_radix_slice, u16toa_slice_with_options, max_u16_size_with_options, MAX_U16_SIZE);
+to_lexical!(u32, u32toa_slice, u32toa_radix_slice, u32toa_slice_with_options, max_u32_size_with_options, MAX_U32_SIZE);
+to_lexical!(u64, u64toa_slice, u64toa_radix_slice, u64toa_slice_with_options, max_u64_size_with_options, MAX_U64_SIZE);
+to_lexical!(usize, usizetoa_slice, usizetoa_radix_slice, usizetoa_slice_with_options, max_usize_size_with_options, MAX_USIZE_SIZE);
+to_lexical!(i8, i8toa_slice, i8toa_radix_slice, i8toa_slice_with_options, max_i8_size_with_options, MAX_I8_SIZE);
+to_lexical!(i16, i16toa_slice, i16toa_radix_slice, i16toa_slice_with_options, max_i16_size_with_options, MAX_I16_SIZE);
+to_lexical!(i32, i32toa_slice, i32toa_radix_slice, i32toa_slice_with_options, max_i32_size_with_options, MAX_I32_SIZE);
+to_lexical!(i64, i64toa_slice, i64toa_radix_slice, i64toa_slice_with_options, max_i64_size_with_options, MAX_I64_SIZE);
+to_lexical!(isize, isizetoa_slice, isizetoa_radix_slice, isizetoa_slice_with_options, max_isize_size_with_options, MAX_ISIZE_SIZE);
+to_lexical!(f32, f32toa_slice, f32toa_radix_slice, f32toa_slice_with_options, max_f32_size_with_options, MAX_F32_SIZE);
+to_lexical!(f64, f64toa_slice, f64toa_radix_slice, f64toa_slice_with_options, max_f64_size_with_options, MAX_F64_SIZE);
+to_lexical!(f16, f16toa_slice, f16toa_radix_slice, f16toa_slice_with_options, max_f16_size_with_options, MAX_F16_SIZE);
+to_lexical!(bf16, bf16toa_slice, bf16toa_radix_slice, bf16toa_slice_with_options, max_bf16_size_with_options, MAX_BF16_SIZE);
 
 #[cfg(has_i128)]
-to_lexical!(u128, u128toa_slice, u128toa_radix_slice, MAX_U128_SIZE);
+to_lexical!(u128, u128toa_slice, u128toa_radix_slice, u128toa_slice_with_options, max_u128_size_with_options, MAX_U128_SIZE);
 
 #[cfg(has_i128)]
-to_lexical!(i128, i128toa_slice, i128toa_radix_slice, MAX_I128_SIZE);
+to_lexical!(i128, i128toa_slice, i128toa_radix_slice, i128toa_slice_with_options, max_i128_size_with_options, MAX_I128_SIZE);
+
+#[cfg(all(has_i128, feature = "correct"))]
+to_lexical!(f128, f128toa_slice, f128toa_radix_slice, f128toa_slice_with_options, max_f128_size_with_options, MAX_F128_SIZE);
 
 // TESTS
 // -----
 
 #[cfg(test)]
 mod tests {
-    use error::invalid_digit;
+    use error::{invalid_digit, ErrorKind};
     use super::*;
 
     macro_rules! deserialize_int {
@@ -223,6 +383,8 @@ mod tests {
             assert_eq!($t::try_from_lexical(b"0"), Ok(0));
             assert_eq!($t::try_from_lexical(b""), Err(empty()));
             assert_eq!($t::try_from_lexical(b"1a"), Err(invalid_digit(1)));
+            assert_eq!($t::from_lexical_partial(b"1a"), Ok((1, 1)));
+            assert_eq!($t::from_lexical_partial(b""), Err(empty()));
 
             #[cfg(feature = "radix")]
             assert_eq!($t::from_lexical_radix(b"0", 10), 0);
@@ -239,7 +401,10 @@ mod tests {
             assert_eq!($t::try_from_lexical(b"0.0"), Ok(0.0));
             assert_eq!($t::try_from_lexical(b"0.0a"), Err(invalid_digit(3)));
             assert_eq!($t::try_from_lexical(b""), Err(empty()));
+            assert_eq!($t::try_from_lexical(b"1e"), Err(ErrorKind::InvalidDigit(1).into()));
             assert_eq!($t::try_from_lexical_lossy(b"0.0"), Ok(0.0));
+            assert_eq!($t::from_lexical_partial(b"1.5a"), Ok((1.5, 3)));
+            assert_eq!($t::from_lexical_partial(b"a"), Err(invalid_digit(0)));
 
             #[cfg(feature = "radix")]
             assert_eq!($t::from_lexical_radix(b"0.0", 10), 0.0);
@@ -298,4 +463,92 @@ mod tests {
         serialize_int! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
         serialize_float! { f32 f64 }
     }
+
+    #[test]
+    fn to_lexical_with_options_test() {
+        let mut options = WriteOptions::new();
+        options.set_locale(lexical_core::Locale::EN_US);
+        assert_eq!(u64::max_value().to_lexical_with_options(&options), b"18,446,744,073,709,551,615".to_vec());
+
+        options.set_locale(lexical_core::Locale::FR_FR);
+        let expected = "-9\u{a0}223\u{a0}372\u{a0}036\u{a0}854\u{a0}775\u{a0}808";
+        assert_eq!(i64::min_value().to_lexical_with_options(&options), expected.as_bytes().to_vec());
+    }
+
+    #[test]
+    fn to_lexical_with_notation_test() {
+        let mut options = WriteOptions::new();
+        options.set_trim_floats(false);
+        options.set_notation(lexical_core::Notation::Positional);
+        assert_eq!(1e300.to_lexical_with_options(&options).len(), 303);
+        assert_eq!(5e-324.to_lexical_with_options(&options).len(), 326);
+
+        options.set_notation(lexical_core::Notation::Auto);
+        options.set_positional_exponents(-400, 400);
+        assert_eq!(1e300.to_lexical_with_options(&options).len(), 303);
+    }
+
+    #[cfg(feature = "correct")]
+    #[test]
+    fn to_lexical_with_precision_test() {
+        let mut options = WriteOptions::new();
+        options.set_precision(lexical_core::Precision::Fixed(2));
+        let bytes = 1e300.to_lexical_with_options(&options);
+        assert_eq!(bytes.len(), 304);
+        assert!(bytes.starts_with(b"1000000000000000052504760255204420248704468581108159154915854115511802457988908195786371375080447864043704443832883878176942523235360430575644792184786706982848387200926575803737830233794788090059368953234970799945081119038967640880074652742780142494579258788820056842838115669472196386865459400540160"));
+        assert!(bytes.ends_with(b".00"));
+
+        options.set_precision(lexical_core::Precision::Scientific(200));
+        assert_eq!(f32::MAX.to_lexical_with_options(&options).len(), 206);
+    }
+
+    #[test]
+    fn append_bytes_test() {
+        let mut vec = b"1,".to_vec();
+        vec.append_bytes(b"2.5");
+        assert_eq!(vec, b"1,2.5".to_vec());
+
+        let mut string = String::from("1,");
+        string.append_bytes(b"2.5");
+        assert_eq!(string, "1,2.5");
+    }
+
+    #[cfg(has_maybe_uninit)]
+    #[test]
+    fn copy_to_uninit_test() {
+        let mut buf = [mem::MaybeUninit::<u8>::uninit(); 4];
+        assert_eq!(copy_to_uninit(b"-1.5", &mut buf), b"-1.5");
+        assert_eq!(copy_to_uninit(b"", &mut buf), b"");
+    }
+
+    #[cfg(has_maybe_uninit)]
+    #[test]
+    #[should_panic]
+    fn copy_to_uninit_overflow_test() {
+        let mut buf = [mem::MaybeUninit::<u8>::uninit(); 2];
+        copy_to_uninit(b"-1.5", &mut buf);
+    }
+
+    #[test]
+    fn half_lexical_test() {
+        assert_eq!(f16::from_lexical(b"1.5"), f16::from_f32(1.5));
+        assert_eq!(bf16::try_from_lexical(b"1.5"), Ok(bf16::from_f32(1.5)));
+        assert_eq!(f16::try_from_lexical(b"1.5a"), Err(invalid_digit(3)));
+        assert_eq!(f16::from_lexical_lossy(b"2049"), f16::from_f32(2048.0));
+        assert_eq!(f16::from_f32(-1.5).to_lexical().to_vec(), b"-1.5".to_vec());
+
+        // Only the correct algorithm writes the shortest digits.
+        #[cfg(feature = "correct")]
+        assert_eq!(bf16::from_f32(0.1).to_lexical().to_vec(), b"0.1".to_vec());
+    }
+
+    #[cfg(all(has_i128, feature = "correct"))]
+    #[test]
+    fn f128_lexical_test() {
+        assert_eq!(f128::from_lexical(b"1.5"), f128::from_f64(1.5));
+        assert_eq!(f128::from_lexical(b"0.1").to_bits(), 0x3FFB999999999999999999999999999A);
+        assert_eq!(f128::try_from_lexical(b"1.5a"), Err(invalid_digit(3)));
+        assert_eq!(f128::from_f64(-1.5).to_lexical().to_vec(), b"-1.5".to_vec());
+        assert_eq!(f128::from_lexical(b"0.1").to_lexical().to_vec(), b"0.1".to_vec());
+    }
 }